@@ -0,0 +1,144 @@
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::error::{Result, SkilError};
+use crate::install::sanitize_name;
+use crate::skills::parse_frontmatter;
+
+/// A plain markdown file that can be converted into a skill.
+#[derive(Debug, Clone)]
+pub struct MarkdownDoc {
+    pub path: PathBuf,
+    /// Name inferred from the first heading, or the file stem.
+    pub name: String,
+    /// Description inferred from the first paragraph line.
+    pub description: String,
+    pub content: String,
+}
+
+/// Finds markdown files without frontmatter, skipping `SKILL.md` and `README.md`.
+pub fn scan_markdown(dir: &Path) -> Result<Vec<MarkdownDoc>> {
+    let mut docs = Vec::new();
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry?;
+        let path = entry.path();
+        let is_markdown = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("md"));
+        if !entry.file_type().is_file() || !is_markdown {
+            continue;
+        }
+        let file_name = entry.file_name().to_string_lossy().to_lowercase();
+        if file_name == "skill.md" || file_name == "readme.md" {
+            continue;
+        }
+        let content = std::fs::read_to_string(path)?;
+        if parse_frontmatter(&content).ok().flatten().is_some() {
+            continue;
+        }
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let (name, description) = infer_metadata(&content, &stem);
+        docs.push(MarkdownDoc {
+            path: path.to_path_buf(),
+            name,
+            description,
+            content,
+        });
+    }
+    Ok(docs)
+}
+
+/// Infers a name from the first heading and a description from the first paragraph line.
+fn infer_metadata(content: &str, stem: &str) -> (String, String) {
+    let mut name = None;
+    let mut description = None;
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(heading) = line.strip_prefix('#') {
+            if name.is_none() {
+                name = Some(heading.trim_start_matches('#').trim().to_string());
+            }
+            continue;
+        }
+        description = Some(line.to_string());
+        break;
+    }
+    let name = name
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| stem.to_string());
+    let description = description.unwrap_or_else(|| format!("Instructions from {stem}.md"));
+    (name, description)
+}
+
+/// Writes `content` as `<root>/<name>/SKILL.md` with generated frontmatter.
+pub fn write_skill(root: &Path, name: &str, description: &str, content: &str) -> Result<PathBuf> {
+    let dir = root.join(sanitize_name(name));
+    std::fs::create_dir_all(&dir)?;
+    let frontmatter = format!(
+        "---\nname: {}\ndescription: {}\n---\n\n",
+        yaml_scalar(name)?,
+        yaml_scalar(description)?
+    );
+    let path = dir.join("SKILL.md");
+    std::fs::write(&path, frontmatter + content)?;
+    Ok(path)
+}
+
+/// Serializes a string as a single-line YAML scalar, quoting when needed.
+fn yaml_scalar(value: &str) -> Result<String> {
+    let value = value.replace(['\r', '\n'], " ");
+    let yaml = serde_yaml::to_string(&value).map_err(|err| SkilError::Message(err.to_string()))?;
+    Ok(yaml.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skills::parse_skill_md;
+
+    #[test]
+    fn adopts_plain_markdown_as_skills() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let prompts = dir.path().join("prompts");
+        std::fs::create_dir_all(&prompts).expect("create");
+        std::fs::write(
+            prompts.join("review.md"),
+            "# Code Review: Rust\n\nReview changes for safety.\n\n- Check unwraps\n",
+        )
+        .expect("write");
+        std::fs::write(prompts.join("plain.md"), "Just text.\n").expect("write");
+        std::fs::write(prompts.join("README.md"), "# Readme\n").expect("write");
+        std::fs::write(
+            prompts.join("existing.md"),
+            "---\nname: done\ndescription: d\n---\n",
+        )
+        .expect("write");
+
+        let docs = scan_markdown(&prompts).expect("scan");
+        let inferred: Vec<(&str, &str)> = docs
+            .iter()
+            .map(|doc| (doc.name.as_str(), doc.description.as_str()))
+            .collect();
+        assert_eq!(
+            inferred,
+            vec![
+                ("plain", "Just text."),
+                ("Code Review: Rust", "Review changes for safety."),
+            ]
+        );
+
+        let out = dir.path().join("out");
+        let path = write_skill(&out, &docs[1].name, &docs[1].description, &docs[1].content)
+            .expect("write skill");
+        let skill = parse_skill_md(&path).expect("parse").expect("skill");
+        assert_eq!(skill.name, "Code Review: Rust");
+        assert_eq!(skill.description, "Review changes for safety.");
+        assert_eq!(path, out.join("code-review-rust").join("SKILL.md"));
+    }
+}
@@ -1,5 +1,7 @@
 use std::path::PathBuf;
 
+use crate::paths::config_home;
+
 /// Configuration for a supported agent and its skills directories.
 #[derive(Debug, Clone)]
 pub struct AgentConfig {
@@ -12,9 +14,7 @@ pub struct AgentConfig {
 /// Returns the full list of known agents with resolved paths.
 pub fn agent_configs() -> Vec<AgentConfig> {
     let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    let config_home = std::env::var("XDG_CONFIG_HOME")
-        .map(PathBuf::from)
-        .unwrap_or_else(|_| home.join(".config"));
+    let config_home = config_home();
     let codex_home = std::env::var("CODEX_HOME")
         .map(PathBuf::from)
         .unwrap_or_else(|_| home.join(".codex"));
@@ -91,7 +91,10 @@ pub fn resolve_agents(requested: &[String]) -> Vec<AgentConfig> {
     let all_agents = agent_configs();
 
     if requested.is_empty() {
-        return detect_default_agents(&all_agents);
+        return match default_agent_names() {
+            Some(names) => resolve_agents(&names),
+            None => detect_default_agents(&all_agents),
+        };
     }
 
     if requested.len() == 1 && requested[0] == "*" {
@@ -108,13 +111,23 @@ pub fn resolve_agents(requested: &[String]) -> Vec<AgentConfig> {
     selected
 }
 
+/// Agents named in `SKIL_DEFAULT_AGENTS` (comma-separated), if set.
+pub fn default_agent_names() -> Option<Vec<String>> {
+    let value = std::env::var("SKIL_DEFAULT_AGENTS").ok()?;
+    let names: Vec<String> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+    (!names.is_empty()).then_some(names)
+}
+
 /// Detects a reasonable default set of agents based on local config folders.
 fn detect_default_agents(all_agents: &[AgentConfig]) -> Vec<AgentConfig> {
     let mut detected = Vec::new();
     let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    let config_home = std::env::var("XDG_CONFIG_HOME")
-        .map(PathBuf::from)
-        .unwrap_or_else(|_| home.join(".config"));
+    let config_home = config_home();
     let codex_home = std::env::var("CODEX_HOME")
         .map(PathBuf::from)
         .unwrap_or_else(|_| home.join(".codex"));
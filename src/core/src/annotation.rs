@@ -0,0 +1,263 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::{Value, json};
+
+/// How check commands report problems.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Lines for people reading a terminal.
+    #[default]
+    Text,
+    /// GitHub Actions workflow commands, which annotate files in pull requests.
+    Github,
+    /// A SARIF 2.1.0 log, for code scanning dashboards.
+    Sarif,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "text" => Ok(OutputFormat::Text),
+            "github" => Ok(OutputFormat::Github),
+            "sarif" => Ok(OutputFormat::Sarif),
+            other => Err(format!(
+                "unknown output {other}: expected text, github, or sarif"
+            )),
+        }
+    }
+}
+
+/// Level of an [`Annotation`], named after its workflow command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationLevel {
+    Error,
+    Warning,
+    Notice,
+}
+
+impl AnnotationLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AnnotationLevel::Error => "error",
+            AnnotationLevel::Warning => "warning",
+            AnnotationLevel::Notice => "notice",
+        }
+    }
+
+    /// The SARIF `level` of a result.
+    fn sarif_level(self) -> &'static str {
+        match self {
+            AnnotationLevel::Error => "error",
+            AnnotationLevel::Warning => "warning",
+            AnnotationLevel::Notice => "note",
+        }
+    }
+}
+
+/// A problem found by a check, written as a GitHub Actions workflow command,
+/// e.g. `::error file=.skil.toml,line=5,col=10::message`, or a SARIF result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    level: AnnotationLevel,
+    message: String,
+    file: Option<String>,
+    line: Option<usize>,
+    column: Option<usize>,
+    title: Option<String>,
+    rule: Option<String>,
+}
+
+impl Annotation {
+    pub fn new(level: AnnotationLevel, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            message: message.into(),
+            file: None,
+            line: None,
+            column: None,
+            title: None,
+            rule: None,
+        }
+    }
+
+    /// File the annotation is shown on, relative to the repository root.
+    pub fn file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// 1-based line in [`Annotation::file`].
+    pub fn line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// 1-based column in [`Annotation::line`].
+    pub fn column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Stable identifier of the check that found the problem, e.g.
+    /// `broken-link`; SARIF results are grouped by it.
+    pub fn rule(mut self, rule: impl Into<String>) -> Self {
+        self.rule = Some(rule.into());
+        self
+    }
+
+    fn sarif_result(&self) -> Value {
+        let mut result = json!({
+            "ruleId": self.rule.as_deref().unwrap_or("skil"),
+            "level": self.level.sarif_level(),
+            "message": { "text": self.message },
+        });
+        if let Some(file) = &self.file {
+            let mut location = json!({ "artifactLocation": { "uri": file } });
+            if let Some(line) = self.line {
+                let mut region = json!({ "startLine": line });
+                if let Some(column) = self.column {
+                    region["startColumn"] = json!(column);
+                }
+                location["region"] = region;
+            }
+            result["locations"] = json!([{ "physicalLocation": location }]);
+        }
+        result
+    }
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut properties = Vec::new();
+        if let Some(file) = &self.file {
+            properties.push(format!("file={}", escape_property(file)));
+        }
+        if let Some(line) = self.line {
+            properties.push(format!("line={line}"));
+        }
+        if let Some(column) = self.column {
+            properties.push(format!("col={column}"));
+        }
+        if let Some(title) = &self.title {
+            properties.push(format!("title={}", escape_property(title)));
+        }
+        write!(f, "::{}", self.level.as_str())?;
+        if !properties.is_empty() {
+            write!(f, " {}", properties.join(","))?;
+        }
+        write!(f, "::{}", escape_data(&self.message))
+    }
+}
+
+/// `annotations` as workflow command lines, or as a SARIF log even when
+/// there are none; empty for text output, which callers write themselves.
+pub fn format_annotations(output: OutputFormat, annotations: &[Annotation]) -> String {
+    match output {
+        OutputFormat::Text => String::new(),
+        OutputFormat::Github => annotations
+            .iter()
+            .map(|annotation| format!("{annotation}\n"))
+            .collect(),
+        OutputFormat::Sarif => format!("{:#}\n", sarif_log(annotations)),
+    }
+}
+
+/// A SARIF 2.1.0 log with one run of skil reporting `annotations`.
+pub fn sarif_log(annotations: &[Annotation]) -> Value {
+    let rules: BTreeSet<&str> = annotations
+        .iter()
+        .map(|annotation| annotation.rule.as_deref().unwrap_or("skil"))
+        .collect();
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "skil",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/matoous/skil",
+                    "rules": rules.iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+                }
+            },
+            "results": annotations.iter().map(Annotation::sarif_result).collect::<Vec<_>>(),
+        }]
+    })
+}
+
+/// Escapes a message so that it stays on one workflow command line.
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a property value, which also ends at `,` and `:`.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_escaped_workflow_commands() {
+        let annotation = Annotation::new(AnnotationLevel::Error, "must be 100%\nfix it")
+            .file("skills/a,b/SKILL.md")
+            .line(3)
+            .column(7)
+            .title("skil: claude-code");
+        assert_eq!(
+            annotation.to_string(),
+            "::error file=skills/a%2Cb/SKILL.md,line=3,col=7,title=skil%3A claude-code::must be 100%25%0Afix it"
+        );
+        assert_eq!(
+            Annotation::new(AnnotationLevel::Notice, "update available").to_string(),
+            "::notice::update available"
+        );
+        assert_eq!("github".parse(), Ok(OutputFormat::Github));
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn writes_sarif_results_with_locations() {
+        let log = sarif_log(&[
+            Annotation::new(AnnotationLevel::Warning, "too long")
+                .file("skills/pdf/SKILL.md")
+                .line(3)
+                .rule("agent-truncates"),
+            Annotation::new(AnnotationLevel::Notice, "update available"),
+        ]);
+        let run = &log["runs"][0];
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(
+            run["tool"]["driver"]["rules"],
+            json!([{ "id": "agent-truncates" }, { "id": "skil" }])
+        );
+        assert_eq!(
+            run["results"][0],
+            json!({
+                "ruleId": "agent-truncates",
+                "level": "warning",
+                "message": { "text": "too long" },
+                "locations": [{ "physicalLocation": {
+                    "artifactLocation": { "uri": "skills/pdf/SKILL.md" },
+                    "region": { "startLine": 3 }
+                }}]
+            })
+        );
+        assert_eq!(run["results"][1]["level"], "note");
+        assert!(run["results"][1].get("locations").is_none());
+    }
+}
@@ -0,0 +1,183 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::SkilConfig;
+use crate::error::{ErrorCode, Result, SkilError};
+use crate::remote::{http_client, send};
+use crate::source::normalize_source;
+
+/// A list of advisories, as served by the registry or kept in a local file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AdvisoryDb {
+    #[serde(default)]
+    pub advisories: Vec<Advisory>,
+}
+
+/// A warning about a source or some of its skills.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Advisory {
+    /// Stable identifier, e.g. `SKIL-2026-0001`.
+    pub id: String,
+    pub kind: AdvisoryKind,
+    /// Source the advisory is about: `owner/repo` or a git URL.
+    pub source: String,
+    /// Affected skills; every skill of the source when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    /// Affected installed checksums; every revision when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checksums: Vec<String>,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// How serious an [`Advisory`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdvisoryKind {
+    /// Known to be harmful; should be removed.
+    Malicious,
+    /// No longer maintained or replaced by something else.
+    Deprecated,
+}
+
+impl AdvisoryKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AdvisoryKind::Malicious => "malicious",
+            AdvisoryKind::Deprecated => "deprecated",
+        }
+    }
+}
+
+/// An advisory that applies to an installed source.
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub advisory: Advisory,
+    /// Config key of the affected source.
+    pub source_key: String,
+    /// Installed skills the advisory covers.
+    pub skills: Vec<String>,
+}
+
+/// Loads advisories from a local JSON file or an `http(s)` URL.
+pub fn load_advisories(location: &str) -> Result<AdvisoryDb> {
+    if location.starts_with("https://") || location.starts_with("http://") {
+        return send(http_client()?.get(location))?
+            .json()
+            .map_err(|err| advisory_error(location, err));
+    }
+    let content = std::fs::read_to_string(Path::new(location)).map_err(|err| {
+        SkilError::new(
+            ErrorCode::SourceNotFound,
+            format!("Cannot read advisories from {location}: {err}"),
+        )
+    })?;
+    serde_json::from_str(&content).map_err(|err| advisory_error(location, err))
+}
+
+fn advisory_error(location: &str, err: impl std::fmt::Display) -> SkilError {
+    SkilError::new(
+        ErrorCode::Parse,
+        format!("Invalid advisory database {location}: {err}"),
+    )
+}
+
+/// Returns the advisories that apply to sources tracked in `config`.
+pub fn audit(config: &SkilConfig, db: &AdvisoryDb) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for advisory in &db.advisories {
+        let target = normalize_source(&advisory.source);
+        for (key, source) in &config.sources {
+            if normalize_source(key) != target {
+                continue;
+            }
+            if !advisory.checksums.is_empty()
+                && !source
+                    .checksum
+                    .as_ref()
+                    .is_some_and(|checksum| advisory.checksums.contains(checksum))
+            {
+                continue;
+            }
+            let skills: Vec<String> = source
+                .skills
+                .iter()
+                .filter(|skill| {
+                    advisory.skills.is_empty()
+                        || advisory
+                            .skills
+                            .iter()
+                            .any(|name| name.eq_ignore_ascii_case(skill))
+                })
+                .cloned()
+                .collect();
+            if skills.is_empty() {
+                continue;
+            }
+            findings.push(Finding {
+                advisory: advisory.clone(),
+                source_key: key.clone(),
+                skills,
+            });
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_advisories_to_tracked_sources() {
+        let config: SkilConfig = toml::from_str(
+            r#"
+[source."https://github.com/Evil/Skills.git"]
+checksum = "bad"
+skills = ["exfiltrate", "harmless"]
+
+[source."https://github.com/old/skills.git"]
+checksum = "good"
+skills = ["legacy"]
+
+[source."https://github.com/fine/skills.git"]
+skills = ["fine"]
+"#,
+        )
+        .expect("config");
+        let db: AdvisoryDb = serde_json::from_str(
+            r#"{"advisories": [
+                {"id": "A-1", "kind": "malicious", "source": "evil/skills",
+                 "skills": ["exfiltrate"], "title": "Uploads ~/.ssh"},
+                {"id": "A-2", "kind": "deprecated", "source": "old/skills",
+                 "checksums": ["older"], "title": "Old revision"},
+                {"id": "A-3", "kind": "deprecated", "source": "https://github.com/old/skills",
+                 "title": "Moved to new/skills"}
+            ]}"#,
+        )
+        .expect("db");
+
+        let found: Vec<(String, String, Vec<String>)> = audit(&config, &db)
+            .into_iter()
+            .map(|finding| (finding.advisory.id, finding.source_key, finding.skills))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "A-1".to_string(),
+                    "https://github.com/Evil/Skills.git".to_string(),
+                    vec!["exfiltrate".to_string()]
+                ),
+                (
+                    "A-3".to_string(),
+                    "https://github.com/old/skills.git".to_string(),
+                    vec!["legacy".to_string()]
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::agent::AgentConfig;
+use crate::config::{AgentBudget, agent_budgets};
+use crate::error::Result;
+use crate::install::agent_skills_base;
+
+/// Size of one skill installed into an agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillSize {
+    pub name: String,
+    pub bytes: u64,
+    /// Estimated with [`approx_tokens`] over the skill's text files.
+    pub tokens: u64,
+}
+
+/// An agent whose installed skills exceed its budget.
+#[derive(Debug, Clone)]
+pub struct BudgetOverrun {
+    pub agent: AgentConfig,
+    pub budget: AgentBudget,
+    pub bytes: u64,
+    pub tokens: u64,
+    /// Installed skills, largest first.
+    pub skills: Vec<SkillSize>,
+}
+
+/// Approximates how many tokens a BPE tokenizer splits `text` into.
+///
+/// Letter runs count one token per eight characters, digit runs one per three,
+/// and every other non-whitespace character one token.
+pub fn approx_tokens(text: &str) -> u64 {
+    let mut tokens = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch.is_whitespace() {
+            continue;
+        }
+        let per_token = if ch.is_ascii_digit() {
+            3
+        } else if ch.is_ascii_alphabetic() {
+            8
+        } else {
+            tokens += 1;
+            continue;
+        };
+        let mut len: u64 = 1;
+        while chars
+            .next_if(|next| {
+                next.is_ascii_digit() == ch.is_ascii_digit() && next.is_ascii_alphanumeric()
+            })
+            .is_some()
+        {
+            len += 1;
+        }
+        tokens += len.div_ceil(per_token);
+    }
+    tokens
+}
+
+/// Returns agents whose installed skills exceed their configured budget.
+pub fn check_budgets(agents: &[AgentConfig], global: bool) -> Result<Vec<BudgetOverrun>> {
+    let budgets = agent_budgets(global)?;
+    let mut overruns = Vec::new();
+    for agent in agents {
+        let Some(budget) = budgets.get(agent.name) else {
+            continue;
+        };
+        let skills = skill_sizes(&agent_skills_base(agent, global)?)?;
+        let bytes = skills.iter().map(|skill| skill.bytes).sum();
+        let tokens = skills.iter().map(|skill| skill.tokens).sum();
+        if exceeds(budget, bytes, tokens) {
+            overruns.push(BudgetOverrun {
+                agent: agent.clone(),
+                budget: *budget,
+                bytes,
+                tokens,
+                skills,
+            });
+        }
+    }
+    Ok(overruns)
+}
+
+/// Returns whether `bytes` or `tokens` is over the matching limit of `budget`.
+fn exceeds(budget: &AgentBudget, bytes: u64, tokens: u64) -> bool {
+    budget.max_bytes.is_some_and(|max| bytes > max)
+        || budget.max_tokens.is_some_and(|max| tokens > max)
+}
+
+/// Sizes of the skill directories in `base`, following symlinks, largest first.
+pub fn skill_sizes(base: &Path) -> Result<Vec<SkillSize>> {
+    let mut sizes = Vec::new();
+    let Ok(entries) = std::fs::read_dir(base) else {
+        return Ok(sizes);
+    };
+    for entry in entries {
+        let path = entry?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        sizes.push(measure_dir(name, &path));
+    }
+    sizes.sort_by(|a, b| b.tokens.cmp(&a.tokens).then_with(|| a.name.cmp(&b.name)));
+    Ok(sizes)
+}
+
+/// Skills taking more disk space than this are flagged as unexpectedly large.
+pub const LARGE_SKILL_BYTES: u64 = 1024 * 1024;
+
+/// Disk space one skill takes in a scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskUsage {
+    pub name: String,
+    /// Bytes of the canonical store folder.
+    pub store_bytes: u64,
+    /// Bytes per agent that holds a copy rather than a symlink.
+    pub copies: Vec<(String, u64)>,
+}
+
+impl DiskUsage {
+    /// Bytes of the store folder and every copy.
+    pub fn total(&self) -> u64 {
+        self.store_bytes + self.copies.iter().map(|(_, bytes)| bytes).sum::<u64>()
+    }
+}
+
+/// Disk usage of each skill in `store` and its copies in the named agent
+/// directories, largest first. Symlinks take no space and are not counted.
+pub fn disk_usage(store: &Path, agent_bases: &[(String, PathBuf)]) -> Result<Vec<DiskUsage>> {
+    let mut usage = Vec::new();
+    let Ok(entries) = std::fs::read_dir(store) else {
+        return Ok(usage);
+    };
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let mut copies = Vec::new();
+        for (agent, base) in agent_bases {
+            let path = base.join(&name);
+            if base.as_path() == store
+                || !std::fs::symlink_metadata(&path).is_ok_and(|meta| meta.is_dir())
+            {
+                continue;
+            }
+            copies.push((agent.clone(), dir_size(&path)));
+        }
+        usage.push(DiskUsage {
+            store_bytes: dir_size(&entry.path()),
+            name,
+            copies,
+        });
+    }
+    usage.sort_by(|a, b| b.total().cmp(&a.total()).then_with(|| a.name.cmp(&b.name)));
+    Ok(usage)
+}
+
+/// Total size of the files under `path`, without following symlinks.
+pub(crate) fn dir_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Total bytes of all files under `dir` and estimated tokens of its text files.
+pub fn measure_dir(name: String, dir: &Path) -> SkillSize {
+    let mut size = SkillSize {
+        name,
+        bytes: 0,
+        tokens: 0,
+    };
+    for entry in WalkDir::new(dir)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+    {
+        let Ok(data) = std::fs::read(entry.path()) else {
+            continue;
+        };
+        size.bytes += data.len() as u64;
+        if let Ok(text) = std::str::from_utf8(&data) {
+            size.tokens += approx_tokens(text);
+        }
+    }
+    size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approximates_tokens() {
+        assert_eq!(approx_tokens(""), 0);
+        assert_eq!(approx_tokens("Hello, world!"), 4);
+        assert_eq!(approx_tokens("internationalization"), 3);
+        assert_eq!(approx_tokens("v12345 = 7;"), 6);
+        assert_eq!(approx_tokens("## Use `cargo test`\n"), 7);
+    }
+
+    #[test]
+    fn counts_store_folders_and_copies_but_not_symlinks() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = dir.path().join("store");
+        for name in ["big", "small"] {
+            std::fs::create_dir_all(store.join(name)).expect("create");
+        }
+        std::fs::write(store.join("big/asset.bin"), vec![0; 1000]).expect("write");
+        std::fs::write(store.join("small/SKILL.md"), "tiny").expect("write");
+        let copied = dir.path().join("copied");
+        std::fs::create_dir_all(copied.join("big")).expect("create");
+        std::fs::write(copied.join("big/asset.bin"), vec![0; 1000]).expect("write");
+        let linked = dir.path().join("linked");
+        std::fs::create_dir_all(&linked).expect("create");
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(store.join("big"), linked.join("big")).expect("symlink");
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_dir(store.join("big"), linked.join("big")).expect("symlink");
+
+        let usage = disk_usage(
+            &store,
+            &[
+                ("copied".to_string(), copied),
+                ("linked".to_string(), linked),
+                ("store".to_string(), store.clone()),
+            ],
+        )
+        .expect("usage");
+        let found: Vec<(&str, u64, u64)> = usage
+            .iter()
+            .map(|skill| (skill.name.as_str(), skill.store_bytes, skill.total()))
+            .collect();
+        assert_eq!(found, vec![("big", 1000, 2000), ("small", 4, 4)]);
+        assert_eq!(usage[0].copies, vec![("copied".to_string(), 1000)]);
+    }
+
+    #[test]
+    fn measures_skills_through_symlinks_and_checks_limits() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = dir.path().join("store/big");
+        std::fs::create_dir_all(&store).expect("create store");
+        std::fs::write(store.join("SKILL.md"), "word ".repeat(80)).expect("write");
+        let base = dir.path().join("agent");
+        std::fs::create_dir_all(base.join("small")).expect("create small");
+        std::fs::write(base.join("small/SKILL.md"), "tiny").expect("write");
+        std::fs::write(base.join("small/logo.bin"), [0xff, 0xfe]).expect("write");
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&store, base.join("big")).expect("symlink");
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_dir(&store, base.join("big")).expect("symlink");
+
+        let sizes = skill_sizes(&base).expect("sizes");
+        let measured: Vec<(&str, u64, u64)> = sizes
+            .iter()
+            .map(|s| (s.name.as_str(), s.bytes, s.tokens))
+            .collect();
+        assert_eq!(measured, vec![("big", 400, 80), ("small", 6, 1)]);
+
+        let budget = |max_bytes, max_tokens| AgentBudget {
+            max_bytes,
+            max_tokens,
+        };
+        assert!(exceeds(&budget(Some(405), None), 406, 81));
+        assert!(!exceeds(&budget(Some(406), None), 406, 81));
+        assert!(exceeds(&budget(None, Some(80)), 406, 81));
+        assert!(!exceeds(&budget(None, Some(81)), 406, 81));
+        assert!(!exceeds(&budget(None, None), 406, 81));
+        assert!(
+            skill_sizes(&dir.path().join("missing"))
+                .expect("sizes")
+                .is_empty()
+        );
+    }
+}
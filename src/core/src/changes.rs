@@ -0,0 +1,123 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+use crate::git::numstat;
+use crate::merge::relative_files;
+
+/// How a file of a skill changed between two installs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl ChangeKind {
+    /// One-letter marker, as `git status --short` prints it.
+    pub fn marker(self) -> &'static str {
+        match self {
+            ChangeKind::Added => "A",
+            ChangeKind::Removed => "D",
+            ChangeKind::Modified => "M",
+        }
+    }
+}
+
+/// One changed file of a skill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub kind: ChangeKind,
+    /// Lines added and removed; `None` for binary files or when git is unavailable.
+    pub lines: Option<(u64, u64)>,
+}
+
+/// Files that differ between two installs of a skill.
+#[derive(Debug, Clone)]
+pub struct SkillChanges {
+    pub skill: String,
+    pub files: Vec<FileChange>,
+}
+
+impl SkillChanges {
+    /// Total lines added and removed across files with known counts.
+    pub fn lines(&self) -> (u64, u64) {
+        self.files
+            .iter()
+            .filter_map(|file| file.lines)
+            .fold((0, 0), |(added, removed), (a, r)| (added + a, removed + r))
+    }
+}
+
+/// Compares two copies of a skill folder, listing changed files by path.
+pub fn compare_dirs(before: &Path, after: &Path) -> Result<Vec<FileChange>> {
+    let mut files = BTreeSet::new();
+    files.extend(relative_files(before)?);
+    files.extend(relative_files(after)?);
+    let mut changes = Vec::new();
+    for path in files {
+        let (old, new) = (before.join(&path), after.join(&path));
+        let (kind, lines) = match (old.is_file(), new.is_file()) {
+            (true, true) => {
+                if std::fs::read(&old)? == std::fs::read(&new)? {
+                    continue;
+                }
+                (ChangeKind::Modified, numstat(&old, &new).ok().flatten())
+            }
+            (false, true) => (ChangeKind::Added, line_count(&new)?.map(|n| (n, 0))),
+            (true, false) => (ChangeKind::Removed, line_count(&old)?.map(|n| (0, n))),
+            (false, false) => continue,
+        };
+        changes.push(FileChange { path, kind, lines });
+    }
+    Ok(changes)
+}
+
+/// Lines in a text file, counting a last line without a newline; `None` for binary files.
+fn line_count(path: &Path) -> Result<Option<u64>> {
+    let content = std::fs::read(path)?;
+    if content.contains(&0) {
+        return Ok(None);
+    }
+    let newlines = content.iter().filter(|byte| **byte == b'\n').count() as u64;
+    let unterminated = !content.is_empty() && !content.ends_with(b"\n");
+    Ok(Some(newlines + u64::from(unterminated)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_added_removed_and_modified_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let (before, after) = (dir.path().join("before"), dir.path().join("after"));
+        for (root, files) in [
+            (&before, &[("SKILL.md", "a\nb\n"), ("old.md", "x\ny")][..]),
+            (&after, &[("SKILL.md", "a\nc\nd\n"), ("new.bin", "\0")][..]),
+        ] {
+            std::fs::create_dir_all(root).expect("mkdir");
+            for (name, content) in files {
+                std::fs::write(root.join(name), content).expect("write");
+            }
+        }
+
+        let changes = compare_dirs(&before, &after).expect("compare");
+        let summary: Vec<(&str, ChangeKind)> = changes
+            .iter()
+            .map(|change| (change.path.to_str().expect("utf-8"), change.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("SKILL.md", ChangeKind::Modified),
+                ("new.bin", ChangeKind::Added),
+                ("old.md", ChangeKind::Removed),
+            ]
+        );
+        assert_eq!(changes[1].lines, None);
+        assert_eq!(changes[2].lines, Some((0, 2)));
+        assert!(compare_dirs(&before, &before).expect("same").is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::agent::AgentConfig;
+use crate::budget::dir_size;
+use crate::config::{config_location, read_config};
+use crate::error::Result;
+use crate::install::{agent_skills_base, canonical_skills_dir, remove_path, sanitize_name};
+use crate::paths::cache_dir;
+
+/// Prefixes of the scratch directories skil creates in [`cache_dir`].
+pub const SCRATCH_PREFIXES: &[&str] = &["clone-", "download-", "adopt-"];
+
+/// Scratch directories younger than this may belong to a running command.
+const STALE_AFTER: Duration = Duration::from_secs(60 * 60);
+
+/// What a [`Leftover`] was left behind by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeftoverKind {
+    /// A clone, download, or adopt directory from an interrupted command.
+    Scratch,
+    /// A canonical skill directory that no source records and no agent uses.
+    OrphanedSkill,
+}
+
+/// A skil-owned path that is safe to remove.
+#[derive(Debug, Clone)]
+pub struct Leftover {
+    pub kind: LeftoverKind,
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// Finds stale scratch directories and orphaned skills in the given scope.
+pub fn find_leftovers(agents: &[AgentConfig], global: bool) -> Result<Vec<Leftover>> {
+    let mut leftovers = stale_scratch_dirs(&cache_dir(), SystemTime::now())?;
+    let store = canonical_skills_dir(global)?;
+    let config = read_config(&config_location(global)?.path)?;
+    let recorded: HashSet<String> = config
+        .sources
+        .values()
+        .flat_map(|source| source.skills.iter().map(|skill| sanitize_name(skill)))
+        .collect();
+    let mut agent_bases = Vec::new();
+    for agent in agents {
+        let base = agent_skills_base(agent, global)?;
+        if base != store {
+            agent_bases.push(base);
+        }
+    }
+    for path in orphaned_skills(&store, &recorded, &agent_bases)? {
+        leftovers.push(Leftover {
+            kind: LeftoverKind::OrphanedSkill,
+            bytes: dir_size(&path),
+            path,
+        });
+    }
+    Ok(leftovers)
+}
+
+/// Removes every leftover, returning the number of bytes freed.
+pub fn remove_leftovers(leftovers: &[Leftover]) -> Result<u64> {
+    let mut freed = 0;
+    for leftover in leftovers {
+        if remove_path(&leftover.path)? {
+            freed += leftover.bytes;
+        }
+    }
+    Ok(freed)
+}
+
+/// Scratch directories in `cache` last modified before [`STALE_AFTER`].
+fn stale_scratch_dirs(cache: &Path, now: SystemTime) -> Result<Vec<Leftover>> {
+    let mut leftovers = Vec::new();
+    let Ok(entries) = std::fs::read_dir(cache) else {
+        return Ok(leftovers);
+    };
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !SCRATCH_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+        {
+            continue;
+        }
+        let modified = entry.metadata()?.modified()?;
+        if now.duration_since(modified).unwrap_or_default() < STALE_AFTER {
+            continue;
+        }
+        let path = entry.path();
+        leftovers.push(Leftover {
+            kind: LeftoverKind::Scratch,
+            bytes: dir_size(&path),
+            path,
+        });
+    }
+    Ok(leftovers)
+}
+
+/// Skill directories in `store` that are not `recorded` and not present in
+/// any of `agent_bases`, either linked or copied.
+fn orphaned_skills(
+    store: &Path,
+    recorded: &HashSet<String>,
+    agent_bases: &[PathBuf],
+) -> Result<Vec<PathBuf>> {
+    let mut orphans = Vec::new();
+    let Ok(entries) = std::fs::read_dir(store) else {
+        return Ok(orphans);
+    };
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if recorded.contains(&name) {
+            continue;
+        }
+        let used = agent_bases
+            .iter()
+            .any(|base| std::fs::symlink_metadata(base.join(&name)).is_ok());
+        if !used {
+            orphans.push(entry.path());
+        }
+    }
+    orphans.sort();
+    Ok(orphans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_stale_scratch_dirs() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for name in ["clone-abc", "download-def", "adopt-ghi", "other"] {
+            std::fs::create_dir_all(dir.path().join(name)).expect("create");
+        }
+        std::fs::write(dir.path().join("clone-abc/SKILL.md"), "skill").expect("write");
+
+        let now = SystemTime::now();
+        assert!(
+            stale_scratch_dirs(dir.path(), now)
+                .expect("scan")
+                .is_empty()
+        );
+
+        let later = now + STALE_AFTER + Duration::from_secs(1);
+        let mut found: Vec<_> = stale_scratch_dirs(dir.path(), later)
+            .expect("scan")
+            .into_iter()
+            .map(|leftover| {
+                (
+                    leftover.path.file_name().unwrap().to_owned(),
+                    leftover.bytes,
+                )
+            })
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("adopt-ghi".into(), 0),
+                ("clone-abc".into(), 5),
+                ("download-def".into(), 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_skills_no_source_or_agent_uses() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = dir.path().join("store");
+        let agent = dir.path().join("agent");
+        for name in ["recorded", "linked", "partial"] {
+            std::fs::create_dir_all(store.join(name)).expect("create");
+        }
+        std::fs::create_dir_all(agent.join("linked")).expect("create");
+        let recorded = HashSet::from(["recorded".to_string()]);
+
+        let orphans = orphaned_skills(&store, &recorded, &[agent]).expect("scan");
+        assert_eq!(orphans, vec![store.join("partial")]);
+        assert!(
+            orphaned_skills(&dir.path().join("missing"), &recorded, &[])
+                .expect("scan")
+                .is_empty()
+        );
+    }
+}
@@ -2,31 +2,341 @@ use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 
-use crate::error::{Result, SkilError};
+use crate::error::{ErrorCode, Result, SkilError};
+use crate::lock::lock_config;
+use crate::paths::{config_home, env_path};
+use crate::schema::error_issue;
+use crate::skills::{Skill, parse_frontmatter};
+use crate::source::{SourceType, normalize_source};
 
 const CONFIG_DIR: &str = "skil";
 const CONFIG_FILE: &str = "config.toml";
 const LOCAL_CONFIG_FILE: &str = ".skil.toml";
+/// Personal project settings layered over `.skil.toml`, kept out of git.
+const LOCAL_OVERRIDES_FILE: &str = ".skil.local.toml";
+/// Project config name used by early releases, renamed on first use.
+const LEGACY_LOCAL_CONFIG_FILE: &str = ".skillz.toml";
+
+/// Format version written to every config file.
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Upgrades a config table by one version; entry `i` turns version `i` into `i + 1`.
+type Migration = fn(&mut toml::Table);
+
+/// Version 0 configs had no `version` key and need no other change.
+const MIGRATIONS: [Migration; CONFIG_VERSION as usize] = [|_| {}];
+/// Number of previous revisions kept per source for `skil rollback`.
+pub const HISTORY_LIMIT: usize = 5;
 
 /// Persistent configuration for installed sources and skills.
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct SkilConfig {
+    /// Format version, see [`CONFIG_VERSION`]; missing in configs older than version 1.
+    #[serde(default)]
+    pub version: u32,
+    /// Opt-in anonymous install ping for registry sources; off unless set to `true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<bool>,
+    /// Global canonical store; defaults to `$XDG_DATA_HOME/skil/skills`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store_dir: Option<PathBuf>,
     #[serde(rename = "source", default)]
     pub sources: BTreeMap<String, SkilSource>,
+    /// Restrictions on which agents skills are installed into.
+    #[serde(rename = "rule", default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<AgentRule>,
+    /// Size limits for installed skills, keyed by agent name.
+    #[serde(rename = "budget", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub budgets: BTreeMap<String, AgentBudget>,
+    /// `.gitignore`-style patterns for skill files that are never installed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+    /// Folders of a source, relative to its root, whose subfolders hold skills,
+    /// searched besides conventional ones such as `skills/`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skill_dirs: Vec<String>,
+    /// Names a skill folder's instructions file may have, most preferred first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skill_files: Vec<String>,
+    /// Also take each markdown file with a `name` and `description` in its
+    /// frontmatter as a skill by itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_skills: Option<bool>,
+    /// Skills larger than this need confirmation before they are installed;
+    /// `0` disables the check. Defaults to [`DEFAULT_MAX_SKILL_BYTES`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_skill_bytes: Option<u64>,
+    /// Agents to install into instead of asking, usually set in `.skil.local.toml`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agents: Vec<String>,
+    /// Copy skills into agents instead of symlinking them, without asking.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub copy: Option<bool>,
+    /// Answers to the `add` prompts, keyed by normalized source; only kept in
+    /// the global config.
+    #[serde(
+        rename = "answers",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub answers: BTreeMap<String, PromptAnswers>,
 }
 
+/// Limit on the size of a newly installed skill when config sets none.
+pub const DEFAULT_MAX_SKILL_BYTES: u64 = 10 * 1024 * 1024;
+
 /// A source entry tracked in config.toml.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SkilSource {
+    /// Hosting provider; inferred from the source key when missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_type: Option<SourceType>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub branch: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subpath: Option<String>,
+    /// Folders searched for skills when added with several `--path` flags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subpaths: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub checksum: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
+    /// RFC 3339 time of the last install or update of this source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
     pub skills: Vec<String>,
+    /// Installed skill folders keyed by skill name, used for skill-level update checks.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub folders: BTreeMap<String, SkillFolder>,
+    /// Previously installed revisions, most recent first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<SkilRevision>,
+    /// Skills edited locally; `update` and `install` leave their files alone.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modified: Vec<String>,
+    /// Every skill the source offered at the last install or update, so
+    /// updates can point out new ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub available: Vec<String>,
+}
+
+impl SkilSource {
+    /// Folders to search for this source's skills: `subpaths`, or else `subpath`.
+    pub fn search_paths(&self) -> Vec<PathBuf> {
+        if self.subpaths.is_empty() {
+            self.subpath.iter().map(PathBuf::from).collect()
+        } else {
+            self.subpaths.iter().map(PathBuf::from).collect()
+        }
+    }
+
+    /// Returns whether `skill` was marked as locally modified.
+    pub fn is_modified(&self, skill: &str) -> bool {
+        self.modified
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(skill))
+    }
+
+    /// Tracked skills that updates may overwrite.
+    pub fn updatable_skills(&self) -> Vec<String> {
+        self.skills
+            .iter()
+            .filter(|skill| !self.is_modified(skill))
+            .cloned()
+            .collect()
+    }
+}
+
+/// A previously installed revision of a source.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SkilRevision {
+    pub checksum: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Location of a skill inside its repository and its git tree hash when installed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SkillFolder {
+    pub path: String,
+    pub hash: String,
+    /// Content hash of the store copy right after install, used to detect local edits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed: Option<String>,
+}
+
+/// Choices made at the `add` prompts for a source, reused next time instead
+/// of asking again. Unset fields were never asked.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct PromptAnswers {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agents: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub copy: Option<bool>,
+}
+
+impl PromptAnswers {
+    pub fn is_empty(&self) -> bool {
+        self.agents.is_empty() && self.global.is_none() && self.copy.is_none()
+    }
+}
+
+/// Limits which agents matching skills are installed into.
+///
+/// A rule matches skills by name or frontmatter tag, or every skill when both
+/// lists are empty.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct AgentRule {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// When set, matching skills go only to these agents.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub only_agents: Vec<String>,
+    /// Agents matching skills are never installed into.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_agents: Vec<String>,
+}
+
+impl AgentRule {
+    /// Returns whether the rule applies to a skill with the given name and tags.
+    pub fn matches(&self, name: &str, tags: &[String]) -> bool {
+        if self.skills.is_empty() && self.tags.is_empty() {
+            return true;
+        }
+        self.skills.iter().any(|s| s.eq_ignore_ascii_case(name))
+            || self
+                .tags
+                .iter()
+                .any(|tag| tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+    }
+
+    /// Returns whether the rule lets a matching skill into `agent`.
+    pub fn permits(&self, agent: &str) -> bool {
+        !self.exclude_agents.iter().any(|a| a == agent)
+            && (self.only_agents.is_empty() || self.only_agents.iter().any(|a| a == agent))
+    }
+}
+
+/// Limits on the total size of skills installed into an agent.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AgentBudget {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+    /// Estimated with [`crate::budget::approx_tokens`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u64>,
+}
+
+/// Returns whether every matching rule permits installing `skill` into `agent`.
+pub fn rules_allow(rules: &[AgentRule], skill: &Skill, agent: &str) -> bool {
+    let tags = parse_frontmatter(&skill.raw_content)
+        .ok()
+        .flatten()
+        .map(|frontmatter| frontmatter.tags)
+        .unwrap_or_default();
+    rules
+        .iter()
+        .filter(|rule| rule.matches(&skill.name, &tags))
+        .all(|rule| rule.permits(agent))
+}
+
+/// Configs that settings are read from, lowest precedence first: the global
+/// config, then for project installs `.skil.toml` and `.skil.local.toml`.
+fn settings_layers(global: bool) -> Result<Vec<SkilConfig>> {
+    let mut layers = vec![read_config(&config_location(true)?.path)?];
+    if !global {
+        layers.push(read_config(&config_location(false)?.path)?);
+        layers.push(read_config(&local_overrides_path()?)?);
+    }
+    Ok(layers)
+}
+
+/// Rules from the global config, plus the project configs for project installs.
+pub fn agent_rules(global: bool) -> Result<Vec<AgentRule>> {
+    Ok(settings_layers(global)?
+        .into_iter()
+        .flat_map(|layer| layer.rules)
+        .collect())
+}
+
+/// Budgets from the global config, overridden per agent by the project configs
+/// for project installs.
+pub fn agent_budgets(global: bool) -> Result<BTreeMap<String, AgentBudget>> {
+    let mut budgets = BTreeMap::new();
+    for layer in settings_layers(global)? {
+        budgets.extend(layer.budgets);
+    }
+    Ok(budgets)
+}
+
+/// Ignore patterns from the global config, plus the project configs for
+/// project installs.
+pub fn ignore_patterns(global: bool) -> Result<Vec<String>> {
+    Ok(settings_layers(global)?
+        .into_iter()
+        .flat_map(|layer| layer.ignore)
+        .collect())
+}
+
+/// Extra skill folders from the global config, plus the project configs for
+/// project installs.
+pub fn skill_dirs(global: bool) -> Result<Vec<PathBuf>> {
+    Ok(settings_layers(global)?
+        .into_iter()
+        .flat_map(|layer| layer.skill_dirs)
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// Skill file names set by the most specific config that names any; empty
+/// when none do.
+pub fn skill_files(global: bool) -> Result<Vec<String>> {
+    Ok(settings_layers(global)?
+        .into_iter()
+        .map(|layer| layer.skill_files)
+        .rfind(|names| !names.is_empty())
+        .unwrap_or_default())
+}
+
+/// Whether the most specific config that sets `file_skills` turns it on.
+pub fn file_skills(global: bool) -> Result<bool> {
+    Ok(settings_layers(global)?
+        .into_iter()
+        .filter_map(|layer| layer.file_skills)
+        .next_back()
+        .unwrap_or(false))
+}
+
+/// Size limit for newly installed skills; the project configs override the
+/// global config for project installs. `0` means no limit.
+pub fn max_skill_bytes(global: bool) -> Result<u64> {
+    let limit = settings_layers(global)?
+        .into_iter()
+        .filter_map(|layer| layer.max_skill_bytes)
+        .next_back();
+    Ok(limit.unwrap_or(DEFAULT_MAX_SKILL_BYTES))
+}
+
+/// Agents set by the most specific config that names any; empty when none do.
+pub fn preferred_agents(global: bool) -> Result<Vec<String>> {
+    Ok(settings_layers(global)?
+        .into_iter()
+        .map(|layer| layer.agents)
+        .rfind(|agents| !agents.is_empty())
+        .unwrap_or_default())
+}
+
+/// Install method set by the most specific config: `Some(true)` to copy.
+pub fn preferred_copy(global: bool) -> Result<Option<bool>> {
+    Ok(settings_layers(global)?
+        .into_iter()
+        .filter_map(|layer| layer.copy)
+        .next_back())
 }
 
 /// Resolved config location and whether it is global.
@@ -36,25 +346,48 @@ pub struct ConfigLocation {
 }
 
 /// Returns the config location for local or global installs.
+///
+/// `SKIL_CONFIG` overrides the global config file and `SKIL_LOCK_FILE` the
+/// project `.skil.toml`.
 pub fn config_location(global: bool) -> Result<ConfigLocation> {
     if global {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-        let config_home = std::env::var("XDG_CONFIG_HOME")
-            .map(PathBuf::from)
-            .unwrap_or_else(|_| home.join(".config"));
+        let path = env_path("SKIL_CONFIG")
+            .unwrap_or_else(|| config_home().join(CONFIG_DIR).join(CONFIG_FILE));
         return Ok(ConfigLocation {
-            path: config_home.join(CONFIG_DIR).join(CONFIG_FILE),
+            path,
             is_global: true,
         });
     }
 
-    let cwd = std::env::current_dir()?;
+    let path = project_config_path()?;
+    let legacy = legacy_project_config_path()?;
+    if env_path("SKIL_LOCK_FILE").is_none() && !path.exists() && legacy.is_file() {
+        std::fs::rename(&legacy, &path)?;
+    }
     Ok(ConfigLocation {
-        path: cwd.join(LOCAL_CONFIG_FILE),
+        path,
         is_global: false,
     })
 }
 
+/// Path of the project config, without renaming a legacy one into place.
+pub fn project_config_path() -> Result<PathBuf> {
+    match env_path("SKIL_LOCK_FILE") {
+        Some(path) => Ok(path),
+        None => Ok(std::env::current_dir()?.join(LOCAL_CONFIG_FILE)),
+    }
+}
+
+/// Path of `.skil.local.toml`, next to the project config.
+pub fn local_overrides_path() -> Result<PathBuf> {
+    Ok(project_config_path()?.with_file_name(LOCAL_OVERRIDES_FILE))
+}
+
+/// Path of the project config used by early releases.
+pub fn legacy_project_config_path() -> Result<PathBuf> {
+    Ok(std::env::current_dir()?.join(LEGACY_LOCAL_CONFIG_FILE))
+}
+
 /// Uses the local config if present, otherwise falls back to global.
 pub fn config_location_auto() -> Result<ConfigLocation> {
     let local = config_location(false)?;
@@ -65,14 +398,64 @@ pub fn config_location_auto() -> Result<ConfigLocation> {
 }
 
 /// Reads config from disk, returning an empty config if missing.
+///
+/// Configs written by older releases are migrated in memory; the file is
+/// upgraded the next time it is written. Configs from newer releases are
+/// rejected so that writing them back cannot drop settings this release does
+/// not know about.
 pub fn read_config(path: &Path) -> Result<SkilConfig> {
     if !path.exists() {
         return Ok(SkilConfig::default());
     }
     let content = std::fs::read_to_string(path)?;
-    let config: SkilConfig =
-        toml::from_str(&content).map_err(|err| SkilError::Message(err.to_string()))?;
-    Ok(config)
+    let invalid = |err: toml::de::Error| {
+        SkilError::new(
+            ErrorCode::Config,
+            format!(
+                "Invalid config {}:{}; run `skil config validate` for details",
+                path.display(),
+                error_issue(&content, &err)
+            ),
+        )
+    };
+    let mut table: toml::Table = toml::from_str(&content).map_err(invalid)?;
+    let version = match table.get("version") {
+        None => 0,
+        Some(value) => value
+            .as_integer()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| {
+                SkilError::new(
+                    ErrorCode::Config,
+                    format!(
+                        "Invalid config {}: version must be a number",
+                        path.display()
+                    ),
+                )
+            })?,
+    };
+    if version > CONFIG_VERSION {
+        return Err(SkilError::new(
+            ErrorCode::Config,
+            format!(
+                "{} uses config version {version}, but this skil supports up to {CONFIG_VERSION}; upgrade skil with `skil upgrade-self`",
+                path.display()
+            ),
+        ));
+    }
+    if version == CONFIG_VERSION {
+        return toml::from_str(&content).map_err(invalid);
+    }
+    migrate_config(&mut table, version);
+    toml::Value::Table(table).try_into().map_err(invalid)
+}
+
+/// Applies every migration from `version` up to [`CONFIG_VERSION`].
+fn migrate_config(table: &mut toml::Table, version: u32) {
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(table);
+    }
+    table.insert("version".to_string(), CONFIG_VERSION.into());
 }
 
 /// Writes config to disk, creating parent directories as needed.
@@ -80,38 +463,328 @@ pub fn write_config(path: &Path, config: &SkilConfig) -> Result<()> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
     }
+    let config = SkilConfig {
+        version: CONFIG_VERSION,
+        ..config.clone()
+    };
     let content =
-        toml::to_string_pretty(config).map_err(|err| SkilError::Message(err.to_string()))?;
+        toml::to_string_pretty(&config).map_err(|err| SkilError::Message(err.to_string()))?;
     std::fs::write(path, content)?;
     Ok(())
 }
 
-/// Updates a config entry with skills and optional checksum/version.
+/// Parses an on/off style toggle used by settings and environment overrides.
+pub fn parse_toggle(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "on" | "true" | "yes" => Some(true),
+        "0" | "off" | "false" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Returns whether the install ping is enabled.
+///
+/// `SKIL_TELEMETRY` overrides the global config and `DO_NOT_TRACK` always disables it.
+pub fn telemetry_enabled() -> Result<bool> {
+    if std::env::var("DO_NOT_TRACK").is_ok_and(|value| parse_toggle(&value) == Some(true)) {
+        return Ok(false);
+    }
+    if let Ok(value) = std::env::var("SKIL_TELEMETRY")
+        && let Some(enabled) = parse_toggle(&value)
+    {
+        return Ok(enabled);
+    }
+    let global = config_location(true)?;
+    Ok(read_config(&global.path)?.telemetry.unwrap_or(false))
+}
+
+/// Updates a config entry with skills, their folders, and optional checksum/version.
 pub fn update_config(
     path: &Path,
     source_key: &str,
-    source: SkilSource,
+    mut source: SkilSource,
     skills: &[String],
     checksum: Option<String>,
     version: Option<String>,
 ) -> Result<()> {
+    let _lock = lock_config(path)?;
     let mut config = read_config(path)?;
+    let folders = std::mem::take(&mut source.folders);
+    let available = std::mem::take(&mut source.available);
+    let branch = source.branch.clone();
     let entry = config
         .sources
         .entry(source_key.to_string())
         .or_insert(source);
+    entry.folders.extend(folders);
+    if !available.is_empty() {
+        entry.available = available;
+    }
+    if branch.is_some() {
+        entry.branch = branch;
+    }
     let mut combined: BTreeSet<String> = entry.skills.iter().cloned().collect();
     combined.extend(skills.iter().cloned());
     entry.skills = combined.into_iter().collect();
+    if let (Some(new), Some(old)) = (&checksum, &entry.checksum)
+        && new != old
+    {
+        let previous = SkilRevision {
+            checksum: old.clone(),
+            version: entry.version.clone(),
+        };
+        entry.history.insert(0, previous);
+        entry.history.truncate(HISTORY_LIMIT);
+    }
     entry.checksum = checksum.or(entry.checksum.clone());
     entry.version = version.or(entry.version.clone());
+    entry.updated_at = Some(jiff::Timestamp::now().to_string());
     write_config(path, &config)?;
     Ok(())
 }
 
+/// Stops tracking `skills` under `source_key`, dropping the source once it
+/// tracks nothing.
+pub fn untrack_skills(path: &Path, source_key: &str, skills: &[String]) -> Result<()> {
+    let _lock = lock_config(path)?;
+    let mut config = read_config(path)?;
+    if let Some(entry) = config.sources.get_mut(source_key) {
+        let untracked = |name: &String| skills.iter().any(|s| s.eq_ignore_ascii_case(name));
+        entry.skills.retain(|name| !untracked(name));
+        entry.folders.retain(|name, _| !untracked(name));
+        entry.modified.retain(|name| !untracked(name));
+        if entry.skills.is_empty() {
+            config.sources.remove(source_key);
+        }
+    }
+    write_config(path, &config)
+}
+
+/// Marks `skill` of `source_key` as locally modified, so updates keep its files.
+pub fn mark_modified(path: &Path, source_key: &str, skill: &str) -> Result<()> {
+    let _lock = lock_config(path)?;
+    let mut config = read_config(path)?;
+    if let Some(entry) = config.sources.get_mut(source_key)
+        && !entry.is_modified(skill)
+    {
+        entry.modified.push(skill.to_string());
+        write_config(path, &config)?;
+    }
+    Ok(())
+}
+
+/// Answers remembered for `source` in `config`, matched by [`normalize_source`].
+pub fn remembered_answers<'a>(config: &'a SkilConfig, source: &str) -> Option<&'a PromptAnswers> {
+    config.answers.get(&normalize_source(source))
+}
+
+/// Records `answers` for `source`, keeping earlier answers to questions not
+/// asked this time.
+pub fn remember_answers(path: &Path, source: &str, answers: PromptAnswers) -> Result<()> {
+    if answers.is_empty() {
+        return Ok(());
+    }
+    let _lock = lock_config(path)?;
+    let mut config = read_config(path)?;
+    let entry = config.answers.entry(normalize_source(source)).or_default();
+    if !answers.agents.is_empty() {
+        entry.agents = answers.agents;
+    }
+    entry.global = answers.global.or(entry.global);
+    entry.copy = answers.copy.or(entry.copy);
+    write_config(path, &config)
+}
+
+/// Returns the key of another source in `config` that tracks a skill named `name`.
+pub fn tracking_source<'a>(config: &'a SkilConfig, name: &str, except: &str) -> Option<&'a str> {
+    config
+        .sources
+        .iter()
+        .find(|(key, source)| {
+            key.as_str() != except && source.skills.iter().any(|s| s.eq_ignore_ascii_case(name))
+        })
+        .map(|(key, _)| key.as_str())
+}
+
+/// Returns the key of the source in `config` that `source` names, given as
+/// tracked or in any form [`normalize_source`] accepts, such as `owner/repo`.
+pub fn find_source<'a>(config: &'a SkilConfig, source: &str) -> Option<&'a str> {
+    if let Some((key, _)) = config.sources.get_key_value(source) {
+        return Some(key.as_str());
+    }
+    let target = normalize_source(source);
+    config
+        .sources
+        .keys()
+        .find(|key| normalize_source(key) == target)
+        .map(String::as_str)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::paths::with_env;
+
+    #[test]
+    fn telemetry_setting_roundtrips_and_defaults_off() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("config.toml");
+
+        write_config(&path, &SkilConfig::default()).expect("write");
+        let content = std::fs::read_to_string(&path).expect("read");
+        assert!(!content.contains("telemetry"));
+        assert_eq!(read_config(&path).expect("read").telemetry, None);
+
+        let mut config = SkilConfig {
+            telemetry: Some(true),
+            ..SkilConfig::default()
+        };
+        config.sources.insert(
+            "repo".to_string(),
+            SkilSource {
+                source_type: None,
+                branch: None,
+                subpath: None,
+                subpaths: Vec::new(),
+                checksum: None,
+                version: None,
+                updated_at: None,
+                skills: vec!["one".to_string()],
+                folders: BTreeMap::new(),
+                history: Vec::new(),
+                modified: Vec::new(),
+                available: Vec::new(),
+            },
+        );
+        write_config(&path, &config).expect("write");
+        assert_eq!(read_config(&path).expect("read").telemetry, Some(true));
+    }
+
+    #[test]
+    fn migrates_old_configs_and_rejects_newer_ones() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "telemetry = true\n").expect("write");
+        let config = read_config(&path).expect("read");
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.telemetry, Some(true));
+
+        write_config(&path, &SkilConfig::default()).expect("write");
+        let content = std::fs::read_to_string(&path).expect("read");
+        assert!(content.starts_with(&format!("version = {CONFIG_VERSION}\n")));
+
+        std::fs::write(&path, format!("version = {}\n", CONFIG_VERSION + 1)).expect("write");
+        let err = read_config(&path).expect_err("newer config");
+        assert_eq!(err.code(), ErrorCode::Config);
+        assert!(err.to_string().contains("upgrade skil"), "{err}");
+    }
+
+    #[test]
+    fn finds_and_untracks_skills_of_other_sources() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(".skil.toml");
+        std::fs::write(
+            &path,
+            "[source.\"a/one\"]\nskills = [\"Review\", \"lint\"]\n\n[source.\"b/two\"]\nskills = [\"docs\"]\n",
+        )
+        .expect("write");
+        let config = read_config(&path).expect("read");
+        assert_eq!(tracking_source(&config, "review", "b/two"), Some("a/one"));
+        assert_eq!(tracking_source(&config, "review", "a/one"), None);
+        assert_eq!(tracking_source(&config, "missing", "b/two"), None);
+
+        untrack_skills(&path, "a/one", &["review".to_string()]).expect("untrack");
+        untrack_skills(&path, "b/two", &["docs".to_string()]).expect("untrack");
+        let config = read_config(&path).expect("read");
+        assert_eq!(config.sources["a/one"].skills, vec!["lint"]);
+        assert!(!config.sources.contains_key("b/two"));
+    }
+
+    #[test]
+    fn finds_sources_by_any_form() {
+        let config: SkilConfig = toml::from_str(
+            "[source.\"https://github.com/Acme/Skills.git\"]\nskills = [\"pdf\"]\n\n[source.\"./local\"]\nskills = [\"notes\"]\n",
+        )
+        .expect("parse");
+        let key = Some("https://github.com/Acme/Skills.git");
+        assert_eq!(find_source(&config, "acme/skills"), key);
+        assert_eq!(find_source(&config, "https://github.com/acme/skills/"), key);
+        assert_eq!(find_source(&config, "./local"), Some("./local"));
+        assert_eq!(find_source(&config, "acme/other"), None);
+    }
+
+    #[test]
+    fn modified_skills_are_not_updatable() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(".skil.toml");
+        std::fs::write(
+            &path,
+            "[source.\"a/one\"]\nskills = [\"review\", \"lint\"]\n",
+        )
+        .expect("write");
+
+        mark_modified(&path, "a/one", "Review").expect("mark");
+        mark_modified(&path, "a/one", "review").expect("mark again");
+        let source = &read_config(&path).expect("read").sources["a/one"];
+        assert_eq!(source.modified, vec!["Review"]);
+        assert!(source.is_modified("review"));
+        assert_eq!(source.updatable_skills(), vec!["lint"]);
+
+        untrack_skills(&path, "a/one", &["review".to_string()]).expect("untrack");
+        let source = &read_config(&path).expect("read").sources["a/one"];
+        assert!(source.modified.is_empty());
+    }
+
+    #[test]
+    fn agent_rules_filter_by_name_and_tag() {
+        let config: SkilConfig = toml::from_str(
+            r#"
+[[rule]]
+tags = ["experimental"]
+exclude_agents = ["codex"]
+
+[[rule]]
+skills = ["Big-Skill"]
+only_agents = ["claude-code"]
+"#,
+        )
+        .expect("parse rules");
+        let skill = |name: &str, tags: &str| Skill {
+            name: name.to_string(),
+            description: "d".to_string(),
+            path: PathBuf::new(),
+            raw_content: format!("---\nname: {name}\ndescription: d\ntags: [{tags}]\n---\n"),
+            resources: Vec::new(),
+            found_by: String::new(),
+            file: None,
+        };
+
+        let experimental = skill("new", "experimental");
+        assert!(!rules_allow(&config.rules, &experimental, "codex"));
+        assert!(rules_allow(&config.rules, &experimental, "cursor"));
+
+        let big = skill("big-skill", "");
+        assert!(rules_allow(&config.rules, &big, "claude-code"));
+        assert!(!rules_allow(&config.rules, &big, "cursor"));
+        assert!(rules_allow(&config.rules, &skill("other", ""), "codex"));
+
+        let written = toml::to_string_pretty(&config).expect("serialize");
+        assert_eq!(
+            toml::from_str::<SkilConfig>(&written)
+                .expect("reparse")
+                .rules,
+            config.rules
+        );
+    }
+
+    #[test]
+    fn parse_toggle_accepts_common_spellings() {
+        assert_eq!(parse_toggle("on"), Some(true));
+        assert_eq!(parse_toggle("TRUE"), Some(true));
+        assert_eq!(parse_toggle("0"), Some(false));
+        assert_eq!(parse_toggle("off"), Some(false));
+        assert_eq!(parse_toggle("maybe"), None);
+    }
 
     #[test]
     fn read_config_returns_default_when_missing() {
@@ -131,11 +804,18 @@ mod tests {
         config.sources.insert(
             "repo".to_string(),
             SkilSource {
+                source_type: None,
                 branch: Some("main".to_string()),
                 subpath: Some("skills".to_string()),
+                subpaths: Vec::new(),
                 checksum: Some("abc123".to_string()),
                 version: Some("v1.2.3".to_string()),
+                updated_at: None,
                 skills: vec!["one".to_string()],
+                folders: BTreeMap::new(),
+                history: Vec::new(),
+                modified: Vec::new(),
+                available: Vec::new(),
             },
         );
 
@@ -155,12 +835,24 @@ mod tests {
         let dir = tempfile::tempdir().expect("tempdir");
         let path = dir.path().join("config.toml");
         let source_key = "https://github.com/example/repo.git";
-        let source = SkilSource {
+        let folder = |path: &str, hash: &str| SkillFolder {
+            path: path.to_string(),
+            hash: hash.to_string(),
+            installed: None,
+        };
+        let mut source = SkilSource {
+            source_type: None,
             branch: Some("main".to_string()),
             subpath: None,
+            subpaths: Vec::new(),
             checksum: Some("rev-1".to_string()),
             version: Some("v1.0.0".to_string()),
+            updated_at: None,
             skills: vec!["alpha".to_string()],
+            folders: BTreeMap::from([("alpha".to_string(), folder("skills/alpha", "t1"))]),
+            history: Vec::new(),
+            modified: Vec::new(),
+            available: Vec::new(),
         };
 
         update_config(
@@ -173,6 +865,7 @@ mod tests {
         )
         .expect("first update");
 
+        source.folders = BTreeMap::from([("gamma".to_string(), folder("skills/gamma", "t2"))]);
         update_config(
             &path,
             source_key,
@@ -190,13 +883,125 @@ mod tests {
         assert_eq!(entry.checksum.as_deref(), Some("rev-2"));
         assert_eq!(entry.version.as_deref(), Some("v1.1.0"));
         assert_eq!(entry.branch.as_deref(), Some("main"));
+        assert_eq!(entry.folders["alpha"], folder("skills/alpha", "t1"));
+        assert_eq!(entry.folders["gamma"], folder("skills/gamma", "t2"));
+        assert_eq!(
+            entry.history,
+            vec![SkilRevision {
+                checksum: "rev-1".to_string(),
+                version: Some("v1.0.0".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn remembers_answers_per_normalized_source() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("config.toml");
+        let answers = PromptAnswers {
+            agents: vec!["claude-code".to_string()],
+            global: Some(true),
+            copy: Some(false),
+        };
+        remember_answers(&path, "https://github.com/Owner/Repo.git", answers.clone())
+            .expect("remember");
+        let config = read_config(&path).expect("read");
+        assert_eq!(remembered_answers(&config, "owner/repo"), Some(&answers));
+        assert_eq!(remembered_answers(&config, "other/repo"), None);
+
+        let copy = PromptAnswers {
+            copy: Some(true),
+            ..PromptAnswers::default()
+        };
+        remember_answers(&path, "owner/repo", copy).expect("remember");
+        let config = read_config(&path).expect("read");
+        let merged = remembered_answers(&config, "owner/repo").expect("answers");
+        assert_eq!(merged.agents, vec!["claude-code".to_string()]);
+        assert_eq!(merged.global, Some(true));
+        assert_eq!(merged.copy, Some(true));
+        assert!(
+            crate::schema::validate_config(&std::fs::read_to_string(&path).expect("read"))
+                .is_empty()
+        );
     }
 
     #[test]
     fn local_config_location_uses_current_directory() {
-        let cwd = std::env::current_dir().expect("cwd");
-        let location = config_location(false).expect("location");
-        assert!(!location.is_global);
-        assert_eq!(location.path, cwd.join(".skil.toml"));
+        with_env(&[("SKIL_LOCK_FILE", None)], || {
+            let cwd = std::env::current_dir().expect("cwd");
+            let location = config_location(false).expect("location");
+            assert!(!location.is_global);
+            assert_eq!(location.path, cwd.join(".skil.toml"));
+            assert_eq!(
+                local_overrides_path().expect("path"),
+                cwd.join(".skil.local.toml")
+            );
+        });
+    }
+
+    #[test]
+    fn local_overrides_win_over_project_and_global_settings() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let global = dir.path().join("config.toml");
+        let project = dir.path().join(".skil.toml");
+        let local = dir.path().join(".skil.local.toml");
+        std::fs::write(
+            &global,
+            "agents = [\"codex\"]\ncopy = false\nmax_skill_bytes = 1\n",
+        )
+        .expect("write");
+        std::fs::write(
+            &project,
+            "agents = [\"cursor\"]\ncopy = true\nmax_skill_bytes = 2\n",
+        )
+        .expect("write");
+
+        let vars = [
+            ("SKIL_CONFIG", Some(global.as_path())),
+            ("SKIL_LOCK_FILE", Some(project.as_path())),
+        ];
+        with_env(&vars, || {
+            assert_eq!(preferred_agents(true).expect("agents"), ["codex"]);
+            assert_eq!(preferred_copy(true).expect("copy"), Some(false));
+            assert_eq!(max_skill_bytes(true).expect("limit"), 1);
+
+            assert_eq!(preferred_agents(false).expect("agents"), ["cursor"]);
+            assert_eq!(preferred_copy(false).expect("copy"), Some(true));
+            assert_eq!(max_skill_bytes(false).expect("limit"), 2);
+
+            std::fs::write(
+                &local,
+                "agents = [\"claude-code\"]\ncopy = false\nmax_skill_bytes = 3\n",
+            )
+            .expect("write");
+            assert_eq!(preferred_agents(false).expect("agents"), ["claude-code"]);
+            assert_eq!(preferred_copy(false).expect("copy"), Some(false));
+            assert_eq!(max_skill_bytes(false).expect("limit"), 3);
+            // The global scope never reads the project files.
+            assert_eq!(preferred_agents(true).expect("agents"), ["codex"]);
+        });
+    }
+
+    #[test]
+    fn sources_in_local_overrides_are_ignored() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let project = dir.path().join(".skil.toml");
+        std::fs::write(
+            dir.path().join(".skil.local.toml"),
+            "agents = [\"cursor\"]\n\n[source.\"github.com/acme/skills\"]\nskills = [\"pdf\"]\n",
+        )
+        .expect("write");
+
+        with_env(&[("SKIL_LOCK_FILE", Some(project.as_path()))], || {
+            let location = config_location(false).expect("location");
+            assert_eq!(location.path, project);
+            assert!(
+                read_config(&location.path)
+                    .expect("read")
+                    .sources
+                    .is_empty()
+            );
+            assert_eq!(preferred_agents(false).expect("agents"), ["cursor"]);
+        });
     }
 }
@@ -0,0 +1,343 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::download::download_source;
+use crate::error::{Result, SkilError};
+use crate::git::{
+    checkout_revision, clone_repo, head_revision, init_submodules, latest_tag, pull_lfs,
+};
+use crate::install::lfs_pointers;
+use crate::observer::{CloneStage, NoopObserver, Observer};
+use crate::paths::scratch_dir;
+use crate::skills::{DiscoverOptions, DuplicateSkill, Skill, discover_skills};
+use crate::source::{Source, parse_source};
+
+/// Which revision of a git source to check out.
+#[derive(Debug, Clone, Default)]
+pub enum Revision {
+    /// The latest tag, or the default branch when the repository has no tags.
+    /// The tip of the branch when one is set with [`Discovery::branch`].
+    #[default]
+    Latest,
+    /// The default branch as cloned.
+    Head,
+    /// A specific commit, tag, or branch.
+    Pinned(String),
+}
+
+/// Resolves a source and discovers the skills it contains.
+///
+/// Git LFS content of discovered git skills is pulled when `git lfs` is
+/// available; files left as pointers are listed in
+/// [`DiscoveredSource::lfs_pointers`].
+///
+/// Git sources are cloned, and gist or raw file sources downloaded, into a
+/// temporary directory that lives as long as the returned [`DiscoveredSource`].
+pub struct Discovery<'a> {
+    source: String,
+    subpaths: Vec<PathBuf>,
+    branch: Option<String>,
+    revision: Revision,
+    options: DiscoverOptions,
+    observer: &'a dyn Observer,
+}
+
+/// A resolved source together with its discovered skills.
+pub struct DiscoveredSource {
+    pub source: Source,
+    pub root: PathBuf,
+    /// Sorted by path.
+    pub skills: Vec<Skill>,
+    /// Skill names declared by several folders within a searched folder;
+    /// only the first of each is in `skills`.
+    pub duplicates: Vec<DuplicateSkill>,
+    pub checksum: Option<String>,
+    pub version: Option<String>,
+    /// Skill files, relative to `root`, still Git LFS pointers after trying
+    /// `git lfs pull`.
+    pub lfs_pointers: Vec<PathBuf>,
+    _temp: Option<tempfile::TempDir>,
+}
+
+impl<'a> Discovery<'a> {
+    /// Creates a discovery for a local path, `owner/repo`, git URL, gist, or raw
+    /// file URL.
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            subpaths: Vec::new(),
+            branch: None,
+            revision: Revision::default(),
+            options: DiscoverOptions::default(),
+            observer: &NoopObserver,
+        }
+    }
+
+    /// Overrides the subpath parsed from the source.
+    pub fn subpath(mut self, subpath: Option<PathBuf>) -> Self {
+        self.subpaths = subpath.into_iter().collect();
+        self
+    }
+
+    /// Searches each of these folders instead of the subpath parsed from the
+    /// source; skills are merged by name, first folder wins, without being
+    /// reported as duplicates.
+    pub fn subpaths(mut self, subpaths: Vec<PathBuf>) -> Self {
+        self.subpaths = subpaths;
+        self
+    }
+
+    /// Follows this branch instead of the latest tag; defaults to the branch of
+    /// a tree URL. Ignored for [`Revision::Pinned`].
+    pub fn branch(mut self, branch: Option<String>) -> Self {
+        self.branch = branch;
+        self
+    }
+
+    /// Sets the revision to check out for git sources.
+    pub fn revision(mut self, revision: Revision) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    /// Keeps searching nested directories even when the root has a SKILL.md.
+    pub fn full_depth(mut self, full_depth: bool) -> Self {
+        self.options.full_depth = full_depth;
+        self
+    }
+
+    /// Also searches folders left out by `.gitignore` and `.ignore` files.
+    pub fn no_ignore(mut self, no_ignore: bool) -> Self {
+        self.options.no_ignore = no_ignore;
+        self
+    }
+
+    /// Searches this many levels of folders when the usual places hold no
+    /// skills; defaults to
+    /// [`DEFAULT_MAX_DEPTH`](crate::skills::DEFAULT_MAX_DEPTH).
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.options.max_depth = max_depth;
+        self
+    }
+
+    /// Also searches the subfolders of these folders, relative to the root.
+    pub fn skill_dirs(mut self, skill_dirs: Vec<PathBuf>) -> Self {
+        self.options.skill_dirs = skill_dirs;
+        self
+    }
+
+    /// Also takes markdown files with skill frontmatter as skills by themselves.
+    pub fn file_skills(mut self, file_skills: bool) -> Self {
+        self.options.file_skills = file_skills;
+        self
+    }
+
+    /// Replaces all options of the skill search.
+    pub fn options(mut self, options: DiscoverOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the observer notified about clone progress.
+    pub fn observer(mut self, observer: &'a dyn Observer) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    /// Resolves the source and discovers its skills.
+    pub fn run(self) -> Result<DiscoveredSource> {
+        let source = parse_source(&self.source)?;
+        let mut downloaded = None;
+        let (root, temp) = match &source {
+            Source::Local { path } => (path.clone(), None),
+            Source::Download { url, kind } => {
+                if matches!(self.revision, Revision::Pinned(_)) {
+                    return Err(SkilError::Message(format!(
+                        "Cannot pin a revision of downloaded source {url}"
+                    )));
+                }
+                let temp = scratch_dir("download-")?;
+                downloaded = Some(download_source(url, kind, temp.path())?);
+                (temp.path().to_path_buf(), Some(temp))
+            }
+            Source::Git { url, .. } => {
+                let temp = scratch_dir("clone-")?;
+                self.observer.on_clone_progress(url, CloneStage::Started);
+                if let Err(err) = clone_repo(url, temp.path()) {
+                    self.observer.on_clone_progress(url, CloneStage::Failed);
+                    return Err(err);
+                }
+                self.observer.on_clone_progress(url, CloneStage::Finished);
+                (temp.path().to_path_buf(), Some(temp))
+            }
+        };
+
+        let branch = self.branch.or_else(|| match &source {
+            Source::Local { .. } | Source::Download { .. } => None,
+            Source::Git { info, .. } => info.github_branch.clone(),
+        });
+        let (checksum, version) = match (&source, &self.revision, &branch) {
+            (Source::Local { .. }, _, _) => (None, None),
+            (Source::Download { .. }, _, _) => (downloaded, None),
+            (Source::Git { .. }, Revision::Pinned(revision), _) => {
+                checkout_revision(&root, revision)?;
+                (head_revision(&root).ok(), None)
+            }
+            (Source::Git { .. }, _, Some(branch)) => {
+                checkout_revision(&root, &format!("origin/{branch}"))
+                    .or_else(|_| checkout_revision(&root, branch))?;
+                (head_revision(&root).ok(), None)
+            }
+            (Source::Git { url, .. }, Revision::Latest, None) => {
+                let tag = latest_tag(url)?;
+                if let Some(tag) = tag.as_deref() {
+                    checkout_revision(&root, tag)?;
+                }
+                (head_revision(&root).ok(), tag)
+            }
+            (Source::Git { .. }, Revision::Head, None) => (head_revision(&root).ok(), None),
+        };
+
+        let subpaths = match &source {
+            Source::Git { subpath, .. } if self.subpaths.is_empty() => {
+                subpath.iter().cloned().collect()
+            }
+            _ => self.subpaths,
+        };
+        if matches!(source, Source::Git { .. }) {
+            init_submodules(&root, &subpaths)?;
+        }
+        let (skills, duplicates) = if subpaths.is_empty() {
+            let discovered = discover_skills(&root, None, &self.options)?;
+            (discovered.skills, discovered.duplicates)
+        } else {
+            let mut skills = Vec::new();
+            let mut duplicates = Vec::new();
+            let mut seen = HashSet::new();
+            for subpath in &subpaths {
+                let discovered = discover_skills(&root, Some(subpath), &self.options)?;
+                for skill in discovered.skills {
+                    if seen.insert(skill.name.clone()) {
+                        skills.push(skill);
+                    }
+                }
+                duplicates.extend(discovered.duplicates);
+            }
+            skills.sort_by(|a, b| a.path.cmp(&b.path));
+            (skills, duplicates)
+        };
+        for duplicate in &duplicates {
+            self.observer.on_duplicate_skill(duplicate);
+        }
+
+        let find_pointers = || -> Vec<PathBuf> {
+            skills
+                .iter()
+                .flat_map(|skill| lfs_pointers(&skill.path))
+                .filter_map(|path| path.strip_prefix(&root).ok().map(PathBuf::from))
+                .collect()
+        };
+        let mut pointers = find_pointers();
+        if !pointers.is_empty()
+            && matches!(source, Source::Git { .. })
+            && pull_lfs(&root, &pointers).is_ok()
+        {
+            pointers = find_pointers();
+        }
+
+        Ok(DiscoveredSource {
+            source,
+            root,
+            skills,
+            duplicates,
+            checksum,
+            version,
+            lfs_pointers: pointers,
+            _temp: temp,
+        })
+    }
+}
+
+impl DiscoveredSource {
+    /// Key under which this source is tracked in config.
+    pub fn key(&self) -> String {
+        source_key(&self.source)
+    }
+}
+
+/// Returns the config key for a source: the local path or the git URL.
+pub fn source_key(source: &Source) -> String {
+    match source {
+        Source::Local { path } => path.to_string_lossy().to_string(),
+        Source::Git { url, .. } | Source::Download { url, .. } => url.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovers_local_skills_and_reports_no_revision() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let skill_dir = dir.path().join("skills").join("one");
+        std::fs::create_dir_all(&skill_dir).expect("mkdir");
+        std::fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: one\ndescription: First\n---\n",
+        )
+        .expect("write");
+
+        let discovered = Discovery::new(dir.path().to_string_lossy())
+            .run()
+            .expect("discover");
+        assert_eq!(discovered.skills.len(), 1);
+        assert_eq!(discovered.skills[0].name, "one");
+        assert!(discovered.checksum.is_none());
+        assert_eq!(
+            discovered.key(),
+            std::fs::canonicalize(dir.path())
+                .expect("canonicalize")
+                .to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn merges_skills_from_several_subpaths() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for (folder, name) in [
+            ("packages/frontend", "design"),
+            ("packages/testing", "unit"),
+            ("packages/testing/extra", "design"),
+            ("packages/other", "ignored"),
+        ] {
+            let skill_dir = dir.path().join(folder).join(name);
+            std::fs::create_dir_all(&skill_dir).expect("mkdir");
+            std::fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {name}\ndescription: {folder}\n---\n"),
+            )
+            .expect("write");
+        }
+
+        let discovered = Discovery::new(dir.path().to_string_lossy())
+            .subpaths(vec![
+                PathBuf::from("packages/frontend"),
+                PathBuf::from("packages/testing"),
+            ])
+            .run()
+            .expect("discover");
+        let found: Vec<(&str, &str)> = discovered
+            .skills
+            .iter()
+            .map(|skill| (skill.name.as_str(), skill.description.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("design", "packages/frontend"),
+                ("unit", "packages/testing")
+            ]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::{ErrorCode, Result, SkilError};
+use crate::git::blob_hash;
+use crate::remote::{github_get, http_client, send};
+use crate::skills::parse_frontmatter;
+use crate::source::DownloadKind;
+
+#[derive(Deserialize)]
+struct Gist {
+    files: BTreeMap<String, GistFile>,
+}
+
+#[derive(Deserialize)]
+struct GistFile {
+    content: Option<String>,
+    #[serde(default)]
+    truncated: bool,
+    raw_url: String,
+}
+
+/// Downloads a gist or raw file into `dest` as one skill directory and returns
+/// a checksum of the downloaded files.
+pub fn download_source(url: &str, kind: &DownloadKind, dest: &Path) -> Result<String> {
+    let files = match kind {
+        DownloadKind::Raw => vec![("SKILL.md".to_string(), fetch_text(url)?)],
+        DownloadKind::Gist(id) => gist_files(id)?,
+    };
+    std::fs::create_dir_all(dest)?;
+    let mut hashed = String::new();
+    for (name, content) in &files {
+        std::fs::write(dest.join(name), content)?;
+        hashed.push_str(&format!("{name}\n{}\n", blob_hash(content.as_bytes())?));
+    }
+    blob_hash(hashed.as_bytes())
+}
+
+/// Returns the checksum [`download_source`] would record for the source now.
+pub fn latest_checksum(url: &str, kind: &DownloadKind) -> Result<String> {
+    let temp = tempfile::tempdir()?;
+    download_source(url, kind, temp.path())
+}
+
+/// Fetches the files of a gist, naming the skill file `SKILL.md`.
+fn gist_files(id: &str) -> Result<Vec<(String, String)>> {
+    let gist: Gist = send(github_get(&format!("/gists/{id}"))?)?.json()?;
+    let mut files = Vec::new();
+    for (name, file) in gist.files {
+        if name.contains(['/', '\\']) || name.starts_with('.') {
+            continue;
+        }
+        let content = match file.content {
+            Some(content) if !file.truncated => content,
+            _ => fetch_text(&file.raw_url)?,
+        };
+        files.push((name, content));
+    }
+    name_skill_file(&mut files).ok_or_else(|| {
+        SkilError::new(
+            ErrorCode::SourceNotFound,
+            format!("Gist {id} has no SKILL.md or markdown file with frontmatter"),
+        )
+    })?;
+    Ok(files)
+}
+
+/// Renames the gist's skill file to `SKILL.md`.
+///
+/// Keeps an existing `SKILL.md`; otherwise picks the only markdown file with
+/// skill frontmatter.
+fn name_skill_file(files: &mut [(String, String)]) -> Option<()> {
+    if files
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case("SKILL.md"))
+    {
+        return Some(());
+    }
+    let mut candidates = files.iter_mut().filter(|(name, content)| {
+        name.to_lowercase().ends_with(".md") && parse_frontmatter(content).ok().flatten().is_some()
+    });
+    let skill = candidates.next()?;
+    if candidates.next().is_some() {
+        return None;
+    }
+    skill.0 = "SKILL.md".to_string();
+    Some(())
+}
+
+fn fetch_text(url: &str) -> Result<String> {
+    Ok(send(http_client()?.get(url))?.text()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, content: &str) -> (String, String) {
+        (name.to_string(), content.to_string())
+    }
+
+    #[test]
+    fn names_the_gist_skill_file() {
+        let skill = "---\nname: review\ndescription: Review code\n---\n";
+        let mut files = vec![file("notes.md", "# Notes\n"), file("review.md", skill)];
+        assert!(name_skill_file(&mut files).is_some());
+        assert_eq!(files[1].0, "SKILL.md");
+
+        let mut files = vec![file("SKILL.md", skill), file("extra.md", skill)];
+        assert!(name_skill_file(&mut files).is_some());
+        assert_eq!(files[1].0, "extra.md");
+
+        let mut files = vec![file("a.md", skill), file("b.md", skill)];
+        assert!(name_skill_file(&mut files).is_none());
+        assert!(name_skill_file(&mut [file("notes.md", "# Notes\n")]).is_none());
+    }
+}
@@ -5,6 +5,8 @@ use thiserror::Error;
 pub enum SkilError {
     #[error("{0}")]
     Message(String),
+    #[error("{message}")]
+    Coded { code: ErrorCode, message: String },
     #[error(transparent)]
     Io(#[from] std::io::Error),
     #[error(transparent)]
@@ -23,5 +25,132 @@ pub enum SkilError {
     Walkdir(#[from] walkdir::Error),
 }
 
+/// Machine-readable failure category, mapped to a distinct process exit status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    Generic,
+    SourceNotFound,
+    InvalidSource,
+    NoSkills,
+    NoMatchingSkills,
+    NoAgents,
+    CloneFailed,
+    GitFailed,
+    Network,
+    Io,
+    Parse,
+    Config,
+    Locked,
+    Unsupported,
+}
+
+impl ErrorCode {
+    /// Every code, in exit-status order.
+    pub const ALL: [ErrorCode; 14] = [
+        ErrorCode::Generic,
+        ErrorCode::SourceNotFound,
+        ErrorCode::InvalidSource,
+        ErrorCode::NoSkills,
+        ErrorCode::NoMatchingSkills,
+        ErrorCode::NoAgents,
+        ErrorCode::CloneFailed,
+        ErrorCode::GitFailed,
+        ErrorCode::Network,
+        ErrorCode::Io,
+        ErrorCode::Parse,
+        ErrorCode::Config,
+        ErrorCode::Locked,
+        ErrorCode::Unsupported,
+    ];
+
+    /// Stable identifier, e.g. `E_NO_SKILLS`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::Generic => "E_GENERIC",
+            ErrorCode::SourceNotFound => "E_SOURCE_NOT_FOUND",
+            ErrorCode::InvalidSource => "E_INVALID_SOURCE",
+            ErrorCode::NoSkills => "E_NO_SKILLS",
+            ErrorCode::NoMatchingSkills => "E_NO_MATCHING_SKILLS",
+            ErrorCode::NoAgents => "E_NO_AGENTS",
+            ErrorCode::CloneFailed => "E_CLONE_FAILED",
+            ErrorCode::GitFailed => "E_GIT_FAILED",
+            ErrorCode::Network => "E_NETWORK",
+            ErrorCode::Io => "E_IO",
+            ErrorCode::Parse => "E_PARSE",
+            ErrorCode::Config => "E_CONFIG",
+            ErrorCode::Locked => "E_LOCKED",
+            ErrorCode::Unsupported => "E_UNSUPPORTED",
+        }
+    }
+
+    /// Process exit status. `2` is left to clap for usage errors.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorCode::Generic => 1,
+            ErrorCode::SourceNotFound => 3,
+            ErrorCode::InvalidSource => 4,
+            ErrorCode::NoSkills => 5,
+            ErrorCode::NoMatchingSkills => 6,
+            ErrorCode::NoAgents => 7,
+            ErrorCode::CloneFailed => 8,
+            ErrorCode::GitFailed => 9,
+            ErrorCode::Network => 10,
+            ErrorCode::Io => 11,
+            ErrorCode::Parse => 12,
+            ErrorCode::Config => 13,
+            ErrorCode::Locked => 14,
+            ErrorCode::Unsupported => 15,
+        }
+    }
+}
+
+impl SkilError {
+    /// Creates an error with an explicit code.
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        SkilError::Coded {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the machine-readable category of this error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            SkilError::Message(_) => ErrorCode::Generic,
+            SkilError::Coded { code, .. } => *code,
+            SkilError::Io(_) | SkilError::Walkdir(_) => ErrorCode::Io,
+            SkilError::SerdeJson(_) | SkilError::SerdeYaml(_) => ErrorCode::Parse,
+            SkilError::Reqwest(_) => ErrorCode::Network,
+            SkilError::GixClone(_) | SkilError::GixFetch(_) | SkilError::GixCheckout(_) => {
+                ErrorCode::CloneFailed
+            }
+        }
+    }
+}
+
 /// Convenient result type for skil APIs.
 pub type Result<T> = std::result::Result<T, SkilError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_codes_have_unique_exit_statuses() {
+        let mut statuses: Vec<i32> = ErrorCode::ALL.iter().map(|c| c.exit_code()).collect();
+        statuses.sort();
+        statuses.dedup();
+        assert_eq!(statuses.len(), ErrorCode::ALL.len());
+        assert!(!statuses.contains(&0) && !statuses.contains(&2));
+    }
+
+    #[test]
+    fn code_is_derived_from_variant() {
+        let err = SkilError::new(ErrorCode::NoSkills, "No skills found in source");
+        assert_eq!(err.code(), ErrorCode::NoSkills);
+        assert_eq!(err.to_string(), "No skills found in source");
+        assert_eq!(SkilError::Message("x".into()).code(), ErrorCode::Generic);
+        let io = SkilError::from(std::io::Error::other("disk"));
+        assert_eq!(io.code().as_str(), "E_IO");
+    }
+}
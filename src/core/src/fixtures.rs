@@ -0,0 +1,47 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::paths::with_env;
+
+/// Runs git in `repo` as a fixed committer.
+pub(crate) fn git(repo: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["-c", "user.name=Tester", "-c", "user.email=t@example.com"])
+        .args(args)
+        .status()
+        .expect("run git");
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// Writes `skills/<name>/SKILL.md` with `body` under its frontmatter.
+pub(crate) fn write_skill(repo: &Path, name: &str, body: &str) {
+    let dir = repo.join("skills").join(name);
+    std::fs::create_dir_all(&dir).expect("create");
+    std::fs::write(
+        dir.join("SKILL.md"),
+        format!("---\nname: {name}\ndescription: Test skill\n---\n{body}\n"),
+    )
+    .expect("write");
+}
+
+/// Runs `f` with the home folder, global config, store, and cache in `dir`.
+pub(crate) fn isolated<T>(dir: &Path, f: impl FnOnce() -> T) -> T {
+    let home = dir.join("home");
+    let vars = [
+        ("HOME", Some(home.clone())),
+        ("XDG_CONFIG_HOME", Some(home.join(".config"))),
+        ("XDG_DATA_HOME", Some(home.join(".local/share"))),
+        ("SKIL_CONFIG", Some(dir.join("config.toml"))),
+        ("SKIL_STORE_DIR", Some(dir.join("store"))),
+        ("SKIL_CACHE_DIR", Some(dir.join("cache"))),
+        ("SKIL_HISTORY_FILE", Some(dir.join("history.jsonl"))),
+        ("CLAUDE_CONFIG_DIR", None),
+    ];
+    let vars: Vec<_> = vars
+        .iter()
+        .map(|(var, value)| (*var, value.as_deref()))
+        .collect();
+    with_env(&vars, f)
+}
@@ -1,11 +1,48 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::error::{Result, SkilError};
+use crate::error::{ErrorCode, Result, SkilError};
+use crate::profile::span;
+
+static SYSTEM_GIT: AtomicBool = AtomicBool::new(false);
+
+/// Makes [`clone_repo`] always clone with the `git` CLI.
+pub fn use_system_git() {
+    SYSTEM_GIT.store(true, Ordering::Relaxed);
+}
 
 /// Clones a git repository URL into the destination directory.
+///
+/// Clones that fail with the built-in client are retried with the `git` CLI,
+/// which honors ssh config, `GIT_SSH_COMMAND`, proxies, and credential helpers.
+/// After [`use_system_git`], or for SSH sources while `GIT_SSH_COMMAND` is set,
+/// the `git` CLI is used directly.
 pub fn clone_repo(url: &str, dest: &Path) -> Result<()> {
+    let _span = span("git clone");
+    if SYSTEM_GIT.load(Ordering::Relaxed)
+        || (is_ssh_url(url) && std::env::var_os("GIT_SSH_COMMAND").is_some())
+    {
+        return clone_with_git(url, dest);
+    }
+    let Err(err) = clone_with_gix(url, dest) else {
+        return Ok(());
+    };
+    if !system_git_available() {
+        let detail = error_chain(&err);
+        if is_ssh_url(url) || is_auth_failure(&detail) {
+            return Err(auth_error(url, &detail));
+        }
+        return Err(err);
+    }
+    if dest.exists() {
+        std::fs::remove_dir_all(dest)?;
+    }
+    std::fs::create_dir_all(dest)?;
+    clone_with_git(url, dest)
+}
+
+fn clone_with_gix(url: &str, dest: &Path) -> Result<()> {
     let mut prepare = gix::prepare_clone(url, dest)?;
     let (mut checkout, _) =
         prepare.fetch_then_checkout(gix::progress::Discard, &AtomicBool::new(false))?;
@@ -13,30 +50,325 @@ pub fn clone_repo(url: &str, dest: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Clones with the `git` CLI.
+fn clone_with_git(url: &str, dest: &Path) -> Result<()> {
+    let output = Command::new("git")
+        .args(["clone", "--quiet", "--", url])
+        .arg(dest)
+        .output()?;
+    if !output.status.success() {
+        return Err(git_failure(
+            url,
+            ErrorCode::CloneFailed,
+            &format!("git clone failed for {url}"),
+            &output.stderr,
+        ));
+    }
+    Ok(())
+}
+
+/// Returns whether a `git` executable can be run.
+fn system_git_available() -> bool {
+    Command::new("git")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Returns whether `url` is reached over SSH (`ssh://` or `user@host:path`).
+fn is_ssh_url(url: &str) -> bool {
+    if url.starts_with("ssh://") {
+        return true;
+    }
+    !url.contains("://")
+        && url
+            .split_once(':')
+            .is_some_and(|(host, _)| host.contains('@') && !host.contains('/'))
+}
+
+/// Returns whether git or ssh output describes an authentication failure.
+fn is_auth_failure(message: &str) -> bool {
+    let message = message.to_lowercase();
+    [
+        "permission denied",
+        "publickey",
+        "authentication failed",
+        "host key verification failed",
+        "could not read username",
+        "could not read password",
+        "terminal prompts disabled",
+    ]
+    .iter()
+    .any(|needle| message.contains(needle))
+}
+
+/// Builds the error for a failed git command, explaining authentication failures.
+fn git_failure(url: &str, code: ErrorCode, message: &str, stderr: &[u8]) -> SkilError {
+    let stderr = String::from_utf8_lossy(stderr);
+    if let Some(line) = stderr.lines().find(|line| is_auth_failure(line)) {
+        return auth_error(url, line.trim());
+    }
+    let mut lines = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    let detail = lines
+        .clone()
+        .find(|line| line.starts_with("fatal:") || line.starts_with("error:"))
+        .or_else(|| lines.next());
+    match detail {
+        Some(line) => SkilError::new(code, format!("{message}: {line}")),
+        None => SkilError::new(code, message),
+    }
+}
+
+/// Authentication error with guidance for the URL's transport.
+fn auth_error(url: &str, detail: &str) -> SkilError {
+    let help = if is_ssh_url(url) {
+        "Check that an SSH key for this host is loaded (ssh-add -l) or configured in ~/.ssh/config. Set GIT_SSH_COMMAND to choose a key, e.g. GIT_SSH_COMMAND=\"ssh -i ~/.ssh/id_ed25519\", or use the https URL for public repositories."
+    } else {
+        "Private repositories need git credentials: configure a credential helper (git config --global credential.helper) or use an SSH URL. Set GIT_TERMINAL_PROMPT=1 to be asked for a password."
+    };
+    SkilError::new(
+        ErrorCode::CloneFailed,
+        format!("Authentication failed for {url}: {detail}\n{help}"),
+    )
+}
+
+/// Joins an error with its sources, since gix reports transport causes there.
+fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        message.push_str(&format!(": {err}"));
+        source = err.source();
+    }
+    message
+}
+
 /// Checks out a specific revision in a cloned repository.
 pub fn checkout_revision(repo_path: &Path, revision: &str) -> Result<()> {
+    let _span = span("git checkout");
     let output = Command::new("git")
         .args(["-C"])
         .arg(repo_path)
         .args(["checkout", "--detach", revision])
         .output()?;
     if !output.status.success() {
-        return Err(crate::error::SkilError::Message(format!(
-            "git checkout failed for revision {}",
-            revision
-        )));
+        return Err(SkilError::new(
+            ErrorCode::GitFailed,
+            format!("git checkout failed for revision {}", revision),
+        ));
+    }
+    Ok(())
+}
+
+/// Initializes the submodules of a cloned repository that overlap `within`,
+/// or all of them when `within` is empty.
+pub fn init_submodules(repo_path: &Path, within: &[PathBuf]) -> Result<()> {
+    let _span = span("git submodules");
+    if !repo_path.join(".gitmodules").is_file() {
+        return Ok(());
+    }
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args([
+            "config",
+            "-f",
+            ".gitmodules",
+            "--get-regexp",
+            r"^submodule\..*\.path$",
+        ])
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let paths = stdout
+        .lines()
+        .filter_map(|line| line.split_once(' ').map(|(_, path)| PathBuf::from(path)));
+    let paths: Vec<PathBuf> = submodules_within(paths, within);
+    if paths.is_empty() {
+        return Ok(());
+    }
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["submodule", "update", "--init", "--recursive", "--"])
+        .args(&paths)
+        .output()?;
+    if !output.status.success() {
+        let names: Vec<String> = paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        return Err(git_failure(
+            &names.join(", "),
+            ErrorCode::CloneFailed,
+            &format!(
+                "Skills are in git submodules ({}) that could not be fetched",
+                names.join(", ")
+            ),
+            &output.stderr,
+        ));
     }
     Ok(())
 }
 
+/// Downloads Git LFS content for `files`, given relative to the repository root.
+pub fn pull_lfs(repo_path: &Path, files: &[PathBuf]) -> Result<()> {
+    let _span = span("git lfs");
+    let include: Vec<String> = files
+        .iter()
+        .map(|file| file.to_string_lossy().replace('\\', "/"))
+        .collect();
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["lfs", "pull", "--include"])
+        .arg(include.join(","))
+        .output()?;
+    if !output.status.success() {
+        return Err(git_failure(
+            &repo_path.display().to_string(),
+            ErrorCode::GitFailed,
+            "git lfs pull failed",
+            &output.stderr,
+        ));
+    }
+    Ok(())
+}
+
+/// Applies a unified diff to the files of `dir` with `git apply`.
+///
+/// Repository discovery stops at `dir`, so a store inside a project checkout
+/// is patched as a plain folder.
+pub fn apply_patch(dir: &Path, patch: &Path) -> Result<()> {
+    let mut command = Command::new("git");
+    command.current_dir(dir).arg("apply").arg(patch);
+    if let Some(parent) = dir.parent() {
+        command.env("GIT_CEILING_DIRECTORIES", parent);
+    }
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(git_failure(
+            &patch.display().to_string(),
+            ErrorCode::GitFailed,
+            &format!("{} no longer applies", patch.display()),
+            &output.stderr,
+        ));
+    }
+    Ok(())
+}
+
+/// Unified diff from folder `a` to folder `b`, as `git diff --no-index` prints it.
+///
+/// Folders sharing a parent are shown by name rather than full path.
+pub fn diff_dirs(a: &Path, b: &Path) -> Result<String> {
+    let mut command = Command::new("git");
+    command.args(["diff", "--no-index", "--no-color", "--"]);
+    match (a.parent(), a.file_name(), b.file_name()) {
+        (Some(parent), Some(a_name), Some(b_name)) if b.parent() == Some(parent) => {
+            command.current_dir(parent).arg(a_name).arg(b_name);
+        }
+        _ => {
+            command.arg(a).arg(b);
+        }
+    }
+    let output = command.output()?;
+    // Status 1 only means the folders differ.
+    if output.status.code().is_none_or(|code| code > 1) {
+        return Err(git_failure(
+            &a.display().to_string(),
+            ErrorCode::GitFailed,
+            "git diff failed",
+            &output.stderr,
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Three-way merges one file with `git merge-file`.
+///
+/// Returns the merged content and whether it contains conflict markers.
+pub fn merge_file(local: &Path, base: &Path, upstream: &Path) -> Result<(Vec<u8>, bool)> {
+    let output = Command::new("git")
+        .args([
+            "merge-file",
+            "-p",
+            "-L",
+            "local",
+            "-L",
+            "base",
+            "-L",
+            "upstream",
+        ])
+        .arg(local)
+        .arg(base)
+        .arg(upstream)
+        .output()?;
+    // The status is the number of conflicts; errors, such as binary files, exit above 127.
+    match output.status.code() {
+        Some(conflicts @ 0..=127) => Ok((output.stdout, conflicts > 0)),
+        _ => Err(git_failure(
+            &local.display().to_string(),
+            ErrorCode::GitFailed,
+            &format!("git merge-file failed for {}", local.display()),
+            &output.stderr,
+        )),
+    }
+}
+
+/// Lines added and removed from file `a` to file `b`, as `git diff --numstat`
+/// counts them; `None` for binary files.
+pub fn numstat(a: &Path, b: &Path) -> Result<Option<(u64, u64)>> {
+    let output = Command::new("git")
+        .args(["diff", "--no-index", "--numstat", "--"])
+        .arg(a)
+        .arg(b)
+        .output()?;
+    if output.status.code().is_none_or(|code| code > 1) {
+        return Err(git_failure(
+            &a.display().to_string(),
+            ErrorCode::GitFailed,
+            "git diff failed",
+            &output.stderr,
+        ));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut counts = stdout.split('\t');
+    let added = counts.next().and_then(|count| count.trim().parse().ok());
+    let removed = counts.next().and_then(|count| count.trim().parse().ok());
+    Ok(match (added, removed) {
+        (Some(added), Some(removed)) => Some((added, removed)),
+        // Identical files print nothing.
+        _ if stdout.trim().is_empty() => Some((0, 0)),
+        _ => None,
+    })
+}
+
+/// Keeps submodule paths inside, or containing, one of the `within` folders.
+fn submodules_within(paths: impl Iterator<Item = PathBuf>, within: &[PathBuf]) -> Vec<PathBuf> {
+    paths
+        .filter(|path| {
+            within.is_empty()
+                || within
+                    .iter()
+                    .any(|folder| path.starts_with(folder) || folder.starts_with(path))
+        })
+        .collect()
+}
+
 /// Returns the latest remote tag name if any tags are available.
 pub fn latest_tag(url: &str) -> Result<Option<String>> {
+    let _span = span("git ls-remote");
     let output = Command::new("git")
         .args(["ls-remote", "--tags", "--refs", "--sort=-v:refname", url])
         .output()?;
     if !output.status.success() {
-        return Err(crate::error::SkilError::Message(
-            "git ls-remote --tags failed".to_string(),
+        return Err(git_failure(
+            url,
+            ErrorCode::GitFailed,
+            "git ls-remote --tags failed",
+            &output.stderr,
         ));
     }
 
@@ -62,23 +394,163 @@ pub fn head_revision(repo_path: &Path) -> Result<String> {
     Ok(head.to_string())
 }
 
+/// Returns the git tree hash of a folder at HEAD in a cloned repository.
+pub fn tree_hash(repo_path: &Path, folder: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["-C"])
+        .arg(repo_path)
+        .args(["rev-parse", &format!("HEAD:{folder}")])
+        .output()?;
+    if !output.status.success() {
+        return Err(SkilError::new(
+            ErrorCode::GitFailed,
+            format!("Could not resolve tree for {folder}"),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Returns the hooks folder of the repository containing `dir`, honoring
+/// `core.hooksPath`.
+pub fn hooks_dir(dir: &Path) -> Result<PathBuf> {
+    let output = Command::new("git")
+        .args(["-C"])
+        .arg(dir)
+        .args(["rev-parse", "--path-format=absolute", "--git-path", "hooks"])
+        .output()?;
+    if !output.status.success() {
+        return Err(SkilError::new(
+            ErrorCode::GitFailed,
+            format!("{} is not in a git repository", dir.display()),
+        ));
+    }
+    Ok(PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim(),
+    ))
+}
+
+/// Computes the git blob hash of the given content, as `git hash-object` would.
+pub fn blob_hash(data: &[u8]) -> Result<String> {
+    let id = gix::objs::compute_hash(gix::hash::Kind::Sha1, gix::objs::Kind::Blob, data)
+        .map_err(|err| SkilError::Message(err.to_string()))?;
+    Ok(id.to_string())
+}
+
 /// Returns the latest revision for a remote URL and optional branch.
 pub fn remote_revision(url: &str, branch: Option<&str>) -> Result<String> {
+    let _span = span("git ls-remote");
     let target = branch.unwrap_or("HEAD");
     let output = Command::new("git")
         .args(["ls-remote", url, target])
         .output()?;
     if !output.status.success() {
-        return Err(crate::error::SkilError::Message(
-            "git ls-remote failed".to_string(),
+        return Err(git_failure(
+            url,
+            ErrorCode::GitFailed,
+            "git ls-remote failed",
+            &output.stderr,
         ));
     }
     let stdout = String::from_utf8_lossy(&output.stdout);
     let rev = stdout.split_whitespace().next().unwrap_or("").to_string();
     if rev.is_empty() {
-        return Err(crate::error::SkilError::Message(
-            "Could not resolve remote revision".to_string(),
+        return Err(SkilError::new(
+            ErrorCode::GitFailed,
+            "Could not resolve remote revision",
         ));
     }
     Ok(rev)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blob_hash_matches_git_hash_object() {
+        // `printf 'hello\n' | git hash-object --stdin`
+        let hash = blob_hash(b"hello\n").expect("hash");
+        assert_eq!(hash, "ce013625030ba8dba906f756967f9e9ca394464a");
+    }
+
+    #[test]
+    fn clones_with_system_git() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let origin = dir.path().join("origin");
+        std::fs::create_dir_all(origin.join("skills/one")).expect("create");
+        std::fs::write(origin.join("skills/one/SKILL.md"), "one").expect("write");
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(&origin)
+                .args(["-c", "user.name=Tester", "-c", "user.email=t@example.com"])
+                .args(args)
+                .status()
+                .expect("run git");
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q"]);
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", "Add one"]);
+
+        let dest = dir.path().join("clone");
+        std::fs::create_dir_all(&dest).expect("create dest");
+        clone_with_git(&origin.to_string_lossy(), &dest).expect("clone");
+        assert!(dest.join("skills/one/SKILL.md").is_file());
+        assert_eq!(
+            head_revision(&dest).expect("head"),
+            head_revision(&origin).expect("origin head")
+        );
+
+        let missing = dir.path().join("missing");
+        let err = clone_with_git(&missing.to_string_lossy(), &dir.path().join("other"))
+            .expect_err("missing origin");
+        assert_eq!(err.code(), ErrorCode::CloneFailed);
+    }
+
+    #[test]
+    fn selects_submodules_overlapping_requested_folders() {
+        let paths = || {
+            ["skills", "vendor/tools", "docs/skills/web"]
+                .into_iter()
+                .map(PathBuf::from)
+        };
+        assert_eq!(submodules_within(paths(), &[]).len(), 3);
+        assert_eq!(
+            submodules_within(paths(), &[PathBuf::from("skills/frontend")]),
+            vec![PathBuf::from("skills")]
+        );
+        assert_eq!(
+            submodules_within(paths(), &[PathBuf::from("docs")]),
+            vec![PathBuf::from("docs/skills/web")]
+        );
+        assert!(submodules_within(paths(), &[PathBuf::from("src")]).is_empty());
+    }
+
+    #[test]
+    fn recognizes_ssh_urls_and_auth_failures() {
+        assert!(is_ssh_url("git@github.com:owner/repo.git"));
+        assert!(is_ssh_url("ssh://git@example.com:2222/repo.git"));
+        assert!(!is_ssh_url("https://user@github.com/owner/repo.git"));
+        assert!(!is_ssh_url("C:/skills"));
+
+        assert!(is_auth_failure(
+            "git@github.com: Permission denied (publickey).\nfatal: Could not read from remote repository."
+        ));
+        assert!(is_auth_failure(
+            "fatal: could not read Username for 'https://github.com': terminal prompts disabled"
+        ));
+        assert!(!is_auth_failure("fatal: repository not found"));
+
+        let err = git_failure(
+            "git@github.com:owner/repo.git",
+            ErrorCode::GitFailed,
+            "git ls-remote failed",
+            b"git@github.com: Permission denied (publickey).\n",
+        );
+        assert_eq!(err.code(), ErrorCode::CloneFailed);
+        assert!(err.to_string().contains("GIT_SSH_COMMAND"));
+        let err = git_failure("x", ErrorCode::GitFailed, "git ls-remote failed", b"");
+        assert_eq!(err.to_string(), "git ls-remote failed");
+    }
+}
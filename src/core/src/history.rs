@@ -0,0 +1,152 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::config_location;
+use crate::error::Result;
+use crate::paths::{data_home, env_path};
+
+/// One operation that changed installed skills, as kept in the history file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// When the operation finished, as an RFC 3339 timestamp.
+    pub at: String,
+    /// Command that ran, such as `add` or `update`.
+    pub command: String,
+    /// `project` or `global`.
+    pub scope: String,
+    /// Config of the scope the operation changed.
+    pub config: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    /// Revision of the source before the operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// Revision of the source after the operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+}
+
+impl HistoryEntry {
+    /// Starts an entry for `command` run now against the given scope.
+    pub fn new(command: &str, global: bool) -> Result<Self> {
+        Ok(Self {
+            at: jiff::Timestamp::now().to_string(),
+            command: command.to_string(),
+            scope: if global { "global" } else { "project" }.to_string(),
+            config: std::path::absolute(config_location(global)?.path)?,
+            source: None,
+            skills: Vec::new(),
+            from: None,
+            to: None,
+        })
+    }
+
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    pub fn skills(mut self, skills: Vec<String>) -> Self {
+        self.skills = skills;
+        self
+    }
+
+    /// Sets the revisions of the source before and after the operation.
+    pub fn revisions(mut self, from: Option<String>, to: Option<String>) -> Self {
+        self.from = from;
+        self.to = to;
+        self
+    }
+
+    /// Parsed [`HistoryEntry::at`]; unreadable timestamps sort as oldest.
+    pub fn time(&self) -> jiff::Timestamp {
+        self.at.parse().unwrap_or(jiff::Timestamp::UNIX_EPOCH)
+    }
+}
+
+/// History file: `SKIL_HISTORY_FILE`, else `$XDG_DATA_HOME/skil/history.jsonl`.
+pub fn history_path() -> PathBuf {
+    env_path("SKIL_HISTORY_FILE").unwrap_or_else(|| data_home().join("skil").join("history.jsonl"))
+}
+
+/// Appends `entry` to the history file.
+pub fn record(entry: &HistoryEntry) -> Result<()> {
+    append(&history_path(), entry)
+}
+
+/// Every recorded operation, oldest first.
+pub fn read_history() -> Result<Vec<HistoryEntry>> {
+    read(&history_path())
+}
+
+fn append(path: &Path, entry: &HistoryEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    // A single write keeps lines from concurrent runs whole.
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())?;
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<Vec<HistoryEntry>> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Ok(Vec::new());
+    };
+    // A line cut short by a crash should not hide the rest of the history.
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_and_reads_entries_skipping_broken_lines() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("history.jsonl");
+        let entry = |command: &str| HistoryEntry {
+            at: "2026-10-13T09:00:00Z".to_string(),
+            command: command.to_string(),
+            scope: "project".to_string(),
+            config: dir.path().join(".skil.toml"),
+            source: None,
+            skills: Vec::new(),
+            from: None,
+            to: None,
+        };
+        append(
+            &path,
+            &entry("add")
+                .source("owner/repo")
+                .skills(vec!["pdf".to_string()])
+                .revisions(None, Some("v1.0.0".to_string())),
+        )
+        .expect("append");
+        std::fs::write(
+            &path,
+            std::fs::read_to_string(&path).expect("read") + "{\"at\":\n",
+        )
+        .expect("write");
+        append(&path, &entry("remove")).expect("append");
+
+        let history = read(&path).expect("read");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].source.as_deref(), Some("owner/repo"));
+        assert_eq!(history[0].to.as_deref(), Some("v1.0.0"));
+        assert_eq!(history[1].command, "remove");
+        assert_eq!(history[1].time().to_string(), "2026-10-13T09:00:00Z");
+    }
+}
@@ -0,0 +1,140 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+
+const BEGIN: &str = "# >>> skil hook >>>";
+const END: &str = "# <<< skil hook <<<";
+
+/// Commands the hook runs, from the repository root.
+const CHECKS: &str = r#"if command -v skil >/dev/null 2>&1; then
+  skil config validate || exit 1
+  if [ -f .skil.toml ]; then
+    skil check --drift || exit 1
+  fi
+  if [ -f .skil-policy.toml ]; then
+    skil check --policy || exit 1
+  fi
+else
+  echo "skil not found; skipping skill checks" >&2
+fi"#;
+
+/// What [`install_hook`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookOutcome {
+    Created,
+    /// The checks were added to the end of an existing hook.
+    Appended,
+    AlreadyInstalled,
+}
+
+/// Path of the hook `name` in `hooks_dir`. With husky, whose hooks path is
+/// `.husky/_`, hooks are the scripts in `.husky` that it calls.
+pub fn hook_path(hooks_dir: &Path, name: &str) -> PathBuf {
+    match hooks_dir.parent() {
+        Some(husky)
+            if hooks_dir.file_name().is_some_and(|dir| dir == "_")
+                && husky.file_name().is_some_and(|dir| dir == ".husky") =>
+        {
+            husky.join(name)
+        }
+        _ => hooks_dir.join(name),
+    }
+}
+
+/// Adds the skil checks to the hook script at `path`, creating an executable
+/// script when there is none.
+pub fn install_hook(path: &Path) -> Result<HookOutcome> {
+    let block = format!("{BEGIN}\n{CHECKS}\n{END}\n");
+    let (content, outcome) = match std::fs::read_to_string(path) {
+        Ok(existing) if existing.contains(BEGIN) => return Ok(HookOutcome::AlreadyInstalled),
+        Ok(existing) if existing.is_empty() || existing.ends_with('\n') => {
+            (format!("{existing}\n{block}"), HookOutcome::Appended)
+        }
+        Ok(existing) => (format!("{existing}\n\n{block}"), HookOutcome::Appended),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            (format!("#!/bin/sh\n\n{block}"), HookOutcome::Created)
+        }
+        Err(err) => return Err(err.into()),
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut permissions = std::fs::metadata(path)?.permissions();
+        permissions.set_mode(permissions.mode() | 0o755);
+        std::fs::set_permissions(path, permissions)?;
+    }
+    Ok(outcome)
+}
+
+/// Removes the skil checks from the hook at `path`, deleting a hook left with
+/// nothing else to run. Returns whether the checks were there.
+pub fn uninstall_hook(path: &Path) -> Result<bool> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Ok(false);
+    };
+    let (Some(start), Some(end)) = (content.find(BEGIN), content.find(END)) else {
+        return Ok(false);
+    };
+    let rest = format!(
+        "{}{}",
+        content[..start].trim_end(),
+        &content[end + END.len()..]
+    );
+    let rest = rest.trim_end();
+    if rest.is_empty() || rest == "#!/bin/sh" {
+        std::fs::remove_file(path)?;
+    } else {
+        std::fs::write(path, format!("{rest}\n"))?;
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_husky_scripts_and_plain_hooks() {
+        assert_eq!(
+            hook_path(Path::new("/repo/.husky/_"), "pre-commit"),
+            PathBuf::from("/repo/.husky/pre-commit")
+        );
+        assert_eq!(
+            hook_path(Path::new("/repo/.git/hooks"), "pre-commit"),
+            PathBuf::from("/repo/.git/hooks/pre-commit")
+        );
+    }
+
+    #[test]
+    fn installs_once_and_uninstalls_cleanly() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let fresh = dir.path().join("hooks").join("pre-commit");
+        assert_eq!(install_hook(&fresh).expect("install"), HookOutcome::Created);
+        assert_eq!(
+            install_hook(&fresh).expect("install"),
+            HookOutcome::AlreadyInstalled
+        );
+        let script = std::fs::read_to_string(&fresh).expect("read");
+        assert!(script.contains("skil config validate"));
+        assert!(script.contains("skil check --drift"));
+        assert!(uninstall_hook(&fresh).expect("uninstall"));
+        assert!(!fresh.exists());
+
+        let existing = dir.path().join("pre-push");
+        std::fs::write(&existing, "#!/bin/sh\nnpm test").expect("write");
+        assert_eq!(
+            install_hook(&existing).expect("install"),
+            HookOutcome::Appended
+        );
+        assert!(uninstall_hook(&existing).expect("uninstall"));
+        assert_eq!(
+            std::fs::read_to_string(&existing).expect("read"),
+            "#!/bin/sh\nnpm test\n"
+        );
+        assert!(!uninstall_hook(&existing).expect("uninstall"));
+    }
+}
@@ -0,0 +1,420 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::adopt::write_skill;
+use crate::error::{ErrorCode, Result, SkilError};
+use crate::install::{copy_dir, remove_path, sanitize_name};
+use crate::skills::{parse_frontmatter, parse_skill_md, split_frontmatter};
+
+/// Agent-specific configuration formats that can be converted into skills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// Claude Code plugins: `skills/`, `commands/`, and `agents/` next to `.claude-plugin/plugin.json`.
+    ClaudePlugins,
+    /// Cursor rules: `.cursor/rules/*.mdc` and a legacy `.cursorrules` file.
+    CursorRules,
+    /// An `AGENTS.md` file, split into one skill per `##` section.
+    AgentsMd,
+}
+
+/// A skill written to the output directory.
+#[derive(Debug, Clone)]
+pub struct ImportedSkill {
+    pub name: String,
+    /// Directory name under the output directory.
+    pub dir: String,
+    /// File or directory the skill was converted from.
+    pub origin: PathBuf,
+}
+
+/// Outcome of an import.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub imported: Vec<ImportedSkill>,
+    /// Skills not written because the output directory already had them.
+    pub skipped: Vec<ImportedSkill>,
+}
+
+/// A converted skill before it is written.
+enum Converted {
+    Doc {
+        name: String,
+        description: String,
+        content: String,
+        origin: PathBuf,
+    },
+    Dir {
+        name: String,
+        origin: PathBuf,
+    },
+}
+
+/// Converts `path` from `format` into skills under `out`.
+///
+/// Existing skills in `out` are kept unless `overwrite` is set.
+pub fn import_skills(
+    format: ImportFormat,
+    path: &Path,
+    out: &Path,
+    overwrite: bool,
+) -> Result<ImportReport> {
+    if !path.exists() {
+        return Err(SkilError::new(
+            ErrorCode::SourceNotFound,
+            format!("Path does not exist: {}", path.display()),
+        ));
+    }
+    let converted = match format {
+        ImportFormat::ClaudePlugins => claude_plugins(path)?,
+        ImportFormat::CursorRules => cursor_rules(path)?,
+        ImportFormat::AgentsMd => agents_md(path)?,
+    };
+    if converted.is_empty() {
+        return Err(SkilError::new(
+            ErrorCode::NoSkills,
+            format!("Nothing to import in {}", path.display()),
+        ));
+    }
+
+    let mut report = ImportReport::default();
+    let mut seen = HashSet::new();
+    for item in converted {
+        let (name, origin) = match &item {
+            Converted::Doc { name, origin, .. } | Converted::Dir { name, origin } => {
+                (name.clone(), origin.clone())
+            }
+        };
+        let dir = sanitize_name(&name);
+        let imported = ImportedSkill {
+            name,
+            dir: dir.clone(),
+            origin,
+        };
+        let target = out.join(&dir);
+        if !seen.insert(dir) || (target.exists() && !overwrite) {
+            report.skipped.push(imported);
+            continue;
+        }
+        remove_path(&target)?;
+        match item {
+            Converted::Doc {
+                name,
+                description,
+                content,
+                ..
+            } => {
+                write_skill(out, &name, &description, &content)?;
+            }
+            Converted::Dir { origin, .. } => copy_dir(&origin, &target)?,
+        }
+        report.imported.push(imported);
+    }
+    Ok(report)
+}
+
+/// Converts every plugin found at or below `path`.
+fn claude_plugins(path: &Path) -> Result<Vec<Converted>> {
+    let mut converted = Vec::new();
+    for entry in WalkDir::new(path)
+        .max_depth(4)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+    {
+        if entry.file_name() != "plugin.json"
+            || entry.path().parent().and_then(Path::file_name) != Some(".claude-plugin".as_ref())
+        {
+            continue;
+        }
+        let Some(root) = entry.path().parent().and_then(Path::parent) else {
+            continue;
+        };
+        converted.extend(plugin_skills(root)?);
+        for kind in ["commands", "agents"] {
+            for file in markdown_files(&root.join(kind), &["md"]) {
+                converted.push(convert_doc(&file)?);
+            }
+        }
+    }
+    Ok(converted)
+}
+
+/// Skills a plugin already ships as `skills/<name>/SKILL.md`.
+fn plugin_skills(root: &Path) -> Result<Vec<Converted>> {
+    let mut converted = Vec::new();
+    let skills_dir = root.join("skills");
+    if !skills_dir.is_dir() {
+        return Ok(converted);
+    }
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(&skills_dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+    dirs.sort();
+    for dir in dirs {
+        let skill_md = dir.join("SKILL.md");
+        if skill_md.is_file()
+            && let Some(skill) = parse_skill_md(&skill_md)?
+        {
+            converted.push(Converted::Dir {
+                name: skill.name,
+                origin: dir,
+            });
+        }
+    }
+    Ok(converted)
+}
+
+/// Converts Cursor rule files from `.cursor/rules`, or `.mdc` files directly in `path`.
+fn cursor_rules(path: &Path) -> Result<Vec<Converted>> {
+    if path.is_file() {
+        return Ok(vec![convert_doc(path)?]);
+    }
+    let rules = path.join(".cursor").join("rules");
+    let files = if rules.is_dir() {
+        markdown_files(&rules, &["mdc", "md"])
+    } else {
+        markdown_files(path, &["mdc"])
+    };
+    let mut converted = Vec::new();
+    for file in files {
+        converted.push(convert_doc(&file)?);
+    }
+    let legacy = path.join(".cursorrules");
+    if legacy.is_file() {
+        let content = std::fs::read_to_string(&legacy)?;
+        converted.push(Converted::Doc {
+            name: "cursorrules".to_string(),
+            description: first_line(&content)
+                .unwrap_or_else(|| "Project rules from .cursorrules".to_string()),
+            content,
+            origin: legacy,
+        });
+    }
+    Ok(converted)
+}
+
+/// Splits an `AGENTS.md` file into one skill per `##` section.
+///
+/// Text before the first section becomes a skill named after the `#` title.
+fn agents_md(path: &Path) -> Result<Vec<Converted>> {
+    let file = if path.is_dir() {
+        path.join("AGENTS.md")
+    } else {
+        path.to_path_buf()
+    };
+    let content = std::fs::read_to_string(&file)?;
+    let stem = file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_else(|| "agents".to_string());
+
+    let mut title = None;
+    let mut sections: Vec<(Option<String>, String)> = vec![(None, String::new())];
+    let mut in_fence = false;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if !in_fence {
+            if let Some(heading) = trimmed.strip_prefix("## ") {
+                sections.push((Some(heading.trim().to_string()), String::new()));
+                continue;
+            }
+            if title.is_none()
+                && let Some(heading) = trimmed.strip_prefix("# ")
+            {
+                title = Some(heading.trim().to_string());
+                continue;
+            }
+        }
+        let body = &mut sections.last_mut().expect("at least one section").1;
+        body.push_str(line);
+        body.push('\n');
+    }
+
+    let mut converted = Vec::new();
+    for (heading, body) in sections {
+        let body = body.trim();
+        if body.is_empty() {
+            continue;
+        }
+        let name = heading
+            .clone()
+            .or_else(|| title.clone())
+            .unwrap_or_else(|| stem.clone());
+        converted.push(Converted::Doc {
+            description: first_line(body).unwrap_or_else(|| name.clone()),
+            content: format!("# {name}\n\n{body}\n"),
+            name,
+            origin: file.clone(),
+        });
+    }
+    Ok(converted)
+}
+
+/// Markdown-like files directly inside `dir` with one of `extensions`, sorted.
+fn markdown_files(dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| extensions.contains(&ext))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Converts a file with optional frontmatter, keeping only its body.
+fn convert_doc(path: &Path) -> Result<Converted> {
+    let content = std::fs::read_to_string(path)?;
+    let (yaml, body) = split_frontmatter(&content);
+    let (name, description) = yaml.map(frontmatter_fields).unwrap_or_default();
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = name.unwrap_or(stem);
+    let description = description
+        .or_else(|| first_line(body))
+        .unwrap_or_else(|| name.clone());
+    Ok(Converted::Doc {
+        name,
+        description,
+        content: body.trim_start().to_string(),
+        origin: path.to_path_buf(),
+    })
+}
+
+/// Reads `name` and `description` from frontmatter.
+///
+/// Falls back to a line scan because rule files often hold values such as
+/// `globs: *.ts` that are not valid YAML.
+fn frontmatter_fields(yaml: &str) -> (Option<String>, Option<String>) {
+    let wrapped = format!("---\n{yaml}---\n");
+    if let Ok(Some(frontmatter)) = parse_frontmatter(&wrapped) {
+        return (frontmatter.name, frontmatter.description);
+    }
+    let field = |key: &str| {
+        yaml.lines().find_map(|line| {
+            let value = line.strip_prefix(key)?.strip_prefix(':')?.trim();
+            let value = value.trim_matches(|c| c == '"' || c == '\'');
+            (!value.is_empty()).then(|| value.to_string())
+        })
+    };
+    (field("name"), field("description"))
+}
+
+/// First non-heading, non-empty line of markdown.
+fn first_line(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with("```"))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_skill(out: &Path, dir: &str) -> crate::skills::Skill {
+        parse_skill_md(&out.join(dir).join("SKILL.md"))
+            .expect("parse")
+            .expect("skill")
+    }
+
+    #[test]
+    fn splits_agents_md_into_sections() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            dir.path().join("AGENTS.md"),
+            "# Repo\n\nGeneral notes.\n\n## Testing\n\nRun cargo test.\n\n```md\n## not a section\n```\n\n## Style\n\nUse rustfmt.\n",
+        )
+        .expect("write");
+        let out = dir.path().join("out");
+
+        let report =
+            import_skills(ImportFormat::AgentsMd, dir.path(), &out, false).expect("import");
+        let dirs: Vec<&str> = report.imported.iter().map(|s| s.dir.as_str()).collect();
+        assert_eq!(dirs, vec!["repo", "testing", "style"]);
+        let testing = read_skill(&out, "testing");
+        assert_eq!(testing.description, "Run cargo test.");
+        assert!(testing.raw_content.contains("## not a section"));
+    }
+
+    #[test]
+    fn converts_cursor_rules_with_loose_frontmatter() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let rules = dir.path().join(".cursor/rules");
+        std::fs::create_dir_all(&rules).expect("create");
+        std::fs::write(
+            rules.join("react.mdc"),
+            "---\ndescription: React conventions\nglobs: *.tsx\nalwaysApply: false\n---\nUse hooks.\n",
+        )
+        .expect("write");
+        std::fs::write(dir.path().join(".cursorrules"), "Be concise.\n").expect("write");
+        let out = dir.path().join("out");
+
+        let report =
+            import_skills(ImportFormat::CursorRules, dir.path(), &out, false).expect("import");
+        assert_eq!(report.imported.len(), 2);
+        let react = read_skill(&out, "react");
+        assert_eq!(react.description, "React conventions");
+        assert!(react.raw_content.ends_with("Use hooks.\n"));
+        assert!(!react.raw_content.contains("globs"));
+        assert_eq!(read_skill(&out, "cursorrules").description, "Be concise.");
+
+        let again =
+            import_skills(ImportFormat::CursorRules, dir.path(), &out, false).expect("import");
+        assert!(again.imported.is_empty());
+        assert_eq!(again.skipped.len(), 2);
+    }
+
+    #[test]
+    fn converts_claude_plugin_commands_agents_and_skills() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let plugin = dir.path().join("plugins/review");
+        for sub in [".claude-plugin", "commands", "agents", "skills/lint"] {
+            std::fs::create_dir_all(plugin.join(sub)).expect("create");
+        }
+        std::fs::write(plugin.join(".claude-plugin/plugin.json"), "{}").expect("write");
+        std::fs::write(
+            plugin.join("commands/review-pr.md"),
+            "---\ndescription: Review a pull request\nallowed-tools: Bash(gh:*)\n---\n\nReview $ARGUMENTS.\n",
+        )
+        .expect("write");
+        std::fs::write(
+            plugin.join("agents/critic.md"),
+            "---\nname: critic\ndescription: Harsh reviewer\ntools: Read\n---\nBe critical.\n",
+        )
+        .expect("write");
+        std::fs::write(
+            plugin.join("skills/lint/SKILL.md"),
+            "---\nname: lint\ndescription: Lint code\n---\n",
+        )
+        .expect("write");
+        std::fs::write(plugin.join("skills/lint/rules.txt"), "no tabs").expect("write");
+        let out = dir.path().join("out");
+
+        let report =
+            import_skills(ImportFormat::ClaudePlugins, dir.path(), &out, false).expect("import");
+        let dirs: Vec<&str> = report.imported.iter().map(|s| s.dir.as_str()).collect();
+        assert_eq!(dirs, vec!["lint", "review-pr", "critic"]);
+        assert!(out.join("lint/rules.txt").is_file());
+        assert_eq!(
+            read_skill(&out, "review-pr").description,
+            "Review a pull request"
+        );
+        assert_eq!(read_skill(&out, "critic").description, "Harsh reviewer");
+    }
+}
@@ -1,10 +1,19 @@
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
+use gix::bstr::ByteSlice;
+use gix::glob::wildmatch;
+use gix::glob::wildmatch::Mode;
 use walkdir::WalkDir;
 
 use crate::agent::AgentConfig;
-use crate::error::Result;
+use crate::config::ignore_patterns;
+use crate::error::{Result, SkilError};
+use crate::git::blob_hash;
+use crate::overrides::{apply_overrides, overrides_dir};
+use crate::paths::global_store_dir;
+use crate::profile::span;
 use crate::skills::Skill;
+use crate::trash::trash_skill;
 
 /// Installation strategy for agent skill directories.
 #[derive(Clone, Copy)]
@@ -13,15 +22,117 @@ pub enum InstallMode {
     Copy,
 }
 
+/// How a skill in an agent directory relates to its canonical store copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallState {
+    /// Symlink resolving to the canonical store.
+    Linked,
+    /// Copy whose content matches the canonical store.
+    Copy,
+    /// Copy or foreign symlink whose content differs from the canonical store.
+    Drifted,
+    /// No canonical store entry to compare against.
+    Unmanaged,
+}
+
+impl InstallState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Linked => "symlink",
+            Self::Copy => "copy",
+            Self::Drifted => "drifted",
+            Self::Unmanaged => "unmanaged",
+        }
+    }
+}
+
+/// File in a source root or skill folder listing extra paths to leave out.
+pub const IGNORE_FILE: &str = ".skilignore";
+
+/// `.gitignore`-style patterns for files left out when installing a skill.
+///
+/// Patterns without a slash match a file or folder name anywhere in the skill;
+/// patterns with one match the path from the skill folder. Negation with `!`
+/// is not supported.
+#[derive(Debug, Clone, Default)]
+pub struct IgnorePatterns {
+    patterns: Vec<String>,
+}
+
+impl IgnorePatterns {
+    /// Patterns from config plus the [`IGNORE_FILE`] at the source root.
+    pub fn load(source_root: &Path, global: bool) -> Result<Self> {
+        Ok(Self::new(ignore_patterns(global)?).with_file(&source_root.join(IGNORE_FILE)))
+    }
+
+    pub fn new(patterns: impl IntoIterator<Item = String>) -> Self {
+        let patterns = patterns
+            .into_iter()
+            .map(|pattern| pattern.trim().trim_end_matches('/').to_string())
+            .filter(|pattern| !pattern.is_empty() && !pattern.starts_with('#'))
+            .collect();
+        Self { patterns }
+    }
+
+    /// Adds the patterns listed in `path`, one per line, when it exists.
+    pub fn with_file(mut self, path: &Path) -> Self {
+        if let Ok(content) = std::fs::read_to_string(path) {
+            let extra = Self::new(content.lines().map(str::to_string));
+            self.patterns.extend(extra.patterns);
+        }
+        self
+    }
+
+    /// Returns whether `rel`, relative to the skill folder, or one of its
+    /// parent folders matches a pattern.
+    pub fn is_ignored(&self, rel: &Path) -> bool {
+        if self.patterns.is_empty() {
+            return false;
+        }
+        let mut prefix = String::new();
+        for component in rel.components() {
+            let name = component.as_os_str().to_string_lossy();
+            if !prefix.is_empty() {
+                prefix.push('/');
+            }
+            prefix.push_str(&name);
+            let matched = self.patterns.iter().any(|pattern| {
+                let anchored = pattern.trim_start_matches('/');
+                if anchored.len() == pattern.len() && !pattern.contains('/') {
+                    wildmatch(
+                        pattern.as_bytes().as_bstr(),
+                        name.as_bytes().as_bstr(),
+                        Mode::empty(),
+                    )
+                } else {
+                    wildmatch(
+                        anchored.as_bytes().as_bstr(),
+                        prefix.as_bytes().as_bstr(),
+                        Mode::NO_MATCH_SLASH_LITERAL,
+                    )
+                }
+            });
+            if matched {
+                return true;
+            }
+        }
+        false
+    }
+}
+
 const AGENTS_DIR: &str = ".agents";
 const SKILLS_SUBDIR: &str = "skills";
 
 /// Installs a skill into the canonical store and agent directories.
+///
+/// Files matching `ignore` or the skill's own [`IGNORE_FILE`] are left out,
+/// and the scope's overrides for the skill are applied to the store copy.
 pub fn install_skill(
     skill: &Skill,
     agent: &AgentConfig,
     global: bool,
     mode: InstallMode,
+    ignore: &IgnorePatterns,
 ) -> Result<()> {
     let raw_name = if skill.name.is_empty() {
         "unnamed".to_string()
@@ -31,13 +142,78 @@ pub fn install_skill(
     let skill_name = sanitize_name(&raw_name);
 
     let canonical_dir = canonical_skills_dir(global)?.join(&skill_name);
-    let agent_dir = agent_skills_base(agent, global)?.join(&skill_name);
-
     if canonical_dir.exists() {
         std::fs::remove_dir_all(&canonical_dir)?;
     }
     std::fs::create_dir_all(&canonical_dir)?;
-    copy_dir(&skill.path, &canonical_dir)?;
+    copy_skill(skill, &canonical_dir, global, ignore)?;
+
+    link_skill(&skill_name, agent, global, mode)
+}
+
+/// Copies a skill's files into `dest` the way [`install_skill`] places them
+/// in the store: ignored files left out, the instructions named `SKILL.md`,
+/// and the scope's overrides applied.
+pub(crate) fn copy_skill(
+    skill: &Skill,
+    dest: &Path,
+    global: bool,
+    ignore: &IgnorePatterns,
+) -> Result<()> {
+    match &skill.file {
+        Some(file) if *file == skill.path => {
+            std::fs::create_dir_all(dest)?;
+            copy_file(file, &dest.join("SKILL.md"))?;
+        }
+        file => {
+            let ignore = ignore.clone().with_file(&skill.path.join(IGNORE_FILE));
+            copy_dir_filtered(&skill.path, dest, &ignore)?;
+            if let Some(rel) = file
+                .as_ref()
+                .and_then(|file| file.strip_prefix(&skill.path).ok())
+            {
+                std::fs::rename(dest.join(rel), dest.join("SKILL.md"))?;
+            }
+        }
+    }
+    let name = sanitize_name(if skill.name.is_empty() {
+        "unnamed"
+    } else {
+        &skill.name
+    });
+    apply_overrides(&overrides_dir(global)?, &name, dest)?;
+    Ok(())
+}
+
+/// Bytes [`install_skill`] would copy for a skill, leaving out the same files.
+pub fn install_size(skill: &Skill, ignore: &IgnorePatterns) -> Result<u64> {
+    let ignore = ignore.clone().with_file(&skill.path.join(IGNORE_FILE));
+    let mut bytes = 0;
+    for entry in WalkDir::new(&skill.path) {
+        let entry = entry?;
+        let rel = entry
+            .path()
+            .strip_prefix(&skill.path)
+            .unwrap_or(entry.path());
+        if should_skip_path(&skill.path, entry.path()) || ignore.is_ignored(rel) {
+            continue;
+        }
+        if entry.file_type().is_file() {
+            bytes += entry.metadata()?.len();
+        }
+    }
+    Ok(bytes)
+}
+
+/// Places a skill already in the canonical store into an agent directory.
+pub fn link_skill(
+    skill_name: &str,
+    agent: &AgentConfig,
+    global: bool,
+    mode: InstallMode,
+) -> Result<()> {
+    let canonical_dir = canonical_skills_dir(global)?.join(skill_name);
+    let agent_dir = agent_skills_base(agent, global)?.join(skill_name);
 
     match mode {
         InstallMode::Symlink => {
@@ -61,11 +237,95 @@ pub fn install_skill(
     Ok(())
 }
 
+/// Removes a skill from the given agents and the canonical store, moving the
+/// folders to the trash.
+///
+/// Returns true when anything was removed.
+pub fn uninstall_skill(name: &str, agents: &[AgentConfig], global: bool) -> Result<bool> {
+    let skill_name = sanitize_name(name);
+    let mut paths = Vec::new();
+    for agent in agents {
+        paths.push(agent_skills_base(agent, global)?.join(&skill_name));
+    }
+    paths.push(canonical_skills_dir(global)?.join(&skill_name));
+    // Agents sharing a skills directory would move the same folder twice.
+    paths.sort();
+    paths.dedup();
+    trash_skill(name, &paths)
+}
+
+/// Removes a directory, or just the link when the path is a symlink.
+pub(crate) fn remove_path(path: &Path) -> Result<bool> {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return Ok(false);
+    };
+    if metadata.is_dir() {
+        std::fs::remove_dir_all(path)?;
+    } else {
+        std::fs::remove_file(path)?;
+    }
+    Ok(true)
+}
+
+/// Compares an agent skill directory with its canonical store directory.
+///
+/// Returns `None` when the agent directory does not exist.
+pub fn install_state(agent_dir: &Path, canonical_dir: &Path) -> Result<Option<InstallState>> {
+    let Ok(metadata) = std::fs::symlink_metadata(agent_dir) else {
+        return Ok(None);
+    };
+    let Ok(resolved) = agent_dir.canonicalize() else {
+        return Ok(Some(InstallState::Drifted));
+    };
+    let Ok(canonical) = canonical_dir.canonicalize() else {
+        return Ok(Some(InstallState::Unmanaged));
+    };
+    if resolved == canonical {
+        return Ok(Some(InstallState::Linked));
+    }
+    let state = if dir_hash(&resolved)? == dir_hash(&canonical)? {
+        if metadata.file_type().is_symlink() {
+            InstallState::Linked
+        } else {
+            InstallState::Copy
+        }
+    } else {
+        InstallState::Drifted
+    };
+    Ok(Some(state))
+}
+
+/// Removes an agent skill directory only when it links to or matches the canonical copy.
+///
+/// Returns true when the directory was removed.
+pub fn remove_managed(agent_dir: &Path, canonical_dir: &Path) -> Result<bool> {
+    match install_state(agent_dir, canonical_dir)? {
+        Some(InstallState::Linked | InstallState::Copy) => remove_path(agent_dir),
+        _ => Ok(false),
+    }
+}
+
+/// Hashes the relative paths and contents of every file copied by [`copy_dir`].
+pub(crate) fn dir_hash(dir: &Path) -> Result<String> {
+    let mut listing = String::new();
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() || should_skip_path(dir, entry.path()) {
+            continue;
+        }
+        let rel = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+        let hash = blob_hash(&std::fs::read(entry.path())?)?;
+        listing.push_str(&format!("{hash} {}\n", rel.to_string_lossy()));
+    }
+    blob_hash(listing.as_bytes())
+}
+
 /// Returns the canonical skill storage directory for a scope.
+///
+/// The global store follows the XDG spec; see [`crate::paths::global_store_dir`].
 pub fn canonical_skills_dir(global: bool) -> Result<PathBuf> {
     if global {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-        Ok(home.join(AGENTS_DIR).join(SKILLS_SUBDIR))
+        global_store_dir()
     } else {
         let cwd = std::env::current_dir()?;
         Ok(cwd.join(AGENTS_DIR).join(SKILLS_SUBDIR))
@@ -83,13 +343,26 @@ pub fn agent_skills_base(agent: &AgentConfig, global: bool) -> Result<PathBuf> {
 }
 
 /// Recursively copies a directory, skipping ignored folders.
-fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+///
+/// File permissions, including executable bits, and modification times are kept.
+/// Relative symlinks that stay inside `from` are re-created; links that leave it
+/// or dangle are rejected.
+pub(crate) fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    copy_dir_filtered(from, to, &IgnorePatterns::default())
+}
+
+/// [`copy_dir`] that also leaves out paths matching `ignore`.
+fn copy_dir_filtered(from: &Path, to: &Path, ignore: &IgnorePatterns) -> Result<()> {
+    let _span = span("copy");
     for entry in WalkDir::new(from) {
         let entry = entry?;
         if should_skip_path(from, entry.path()) {
             continue;
         }
         let rel = entry.path().strip_prefix(from).unwrap_or(entry.path());
+        if ignore.is_ignored(rel) {
+            continue;
+        }
         let dest = to.join(rel);
         if entry.file_type().is_dir() {
             std::fs::create_dir_all(&dest)?;
@@ -97,12 +370,125 @@ fn copy_dir(from: &Path, to: &Path) -> Result<()> {
             if let Some(parent) = dest.parent() {
                 std::fs::create_dir_all(parent)?;
             }
-            std::fs::copy(entry.path(), &dest)?;
+            copy_file(entry.path(), &dest)?;
+            // Best effort: Windows cannot set times through a read-only handle.
+            let _ = copy_mtime(entry.path(), &dest);
+        } else if entry.file_type().is_symlink() {
+            copy_link(from, entry.path(), &dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// Re-creates a symlink found while copying `root`, or copies what it points
+/// to where symlinks cannot be created.
+fn copy_link(root: &Path, link: &Path, dest: &Path) -> Result<()> {
+    let target = std::fs::read_link(link)?;
+    let rel = link.strip_prefix(root).unwrap_or(link);
+    if !link_stays_within(rel, &target) || !link_resolves_within(root, link, &target)? {
+        return Err(SkilError::Message(format!(
+            "Symlink {} points outside the skill folder to {}; replace it with the file itself",
+            link.display(),
+            target.display()
+        )));
+    }
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    #[cfg(unix)]
+    let linked = std::os::unix::fs::symlink(&target, dest);
+    #[cfg(windows)]
+    let linked = if link.is_dir() {
+        std::os::windows::fs::symlink_dir(&target, dest)
+    } else {
+        std::os::windows::fs::symlink_file(&target, dest)
+    };
+    if linked.is_err() {
+        if link.is_dir() {
+            copy_dir(link, dest)?;
+        } else if link.is_file() {
+            copy_file(link, dest)?;
         }
     }
     Ok(())
 }
 
+/// Returns whether a relative `target` of the link at `rel` (relative to the
+/// copied root) resolves inside that root.
+fn link_stays_within(rel: &Path, target: &Path) -> bool {
+    let mut depth = rel.parent().map_or(0, |parent| parent.components().count());
+    for component in target.components() {
+        match component {
+            Component::CurDir => {}
+            Component::Normal(_) => depth += 1,
+            Component::ParentDir if depth > 0 => depth -= 1,
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Returns whether the link at `link` resolves inside `root` once the links it
+/// passes through are followed, which [`link_stays_within`] cannot see: with
+/// `a -> .`, the link `e -> a/..` leaves the root.
+///
+/// Dangling links are treated as leaving it, since what they resolve to is
+/// unknown.
+fn link_resolves_within(root: &Path, link: &Path, target: &Path) -> Result<bool> {
+    let root = root.canonicalize()?;
+    let resolved = link.parent().unwrap_or(link).join(target).canonicalize();
+    Ok(resolved.is_ok_and(|resolved| resolved.starts_with(root)))
+}
+
+/// Sets the modification time of `to` to that of `from`.
+fn copy_mtime(from: &Path, to: &Path) -> std::io::Result<()> {
+    let modified = std::fs::metadata(from)?.modified()?;
+    std::fs::File::open(to)?.set_modified(modified)
+}
+
+/// Copies a file as a reflink sharing the source's blocks when the filesystem
+/// supports it (btrfs, XFS), falling back to a regular copy.
+///
+/// On macOS `std::fs::copy` already clones files on APFS.
+fn copy_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    #[cfg(target_os = "linux")]
+    if reflink(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to).map(|_| ())
+}
+
+#[cfg(target_os = "linux")]
+fn reflink(from: &Path, to: &Path) -> std::io::Result<()> {
+    let source = std::fs::File::open(from)?;
+    let dest = std::fs::File::create(to)?;
+    if let Err(err) = rustix::fs::ioctl_ficlone(&dest, &source) {
+        drop(dest);
+        let _ = std::fs::remove_file(to);
+        return Err(err.into());
+    }
+    dest.set_permissions(source.metadata()?.permissions())
+}
+
+/// Returns whether `data` is a Git LFS pointer rather than the file's content.
+pub fn is_lfs_pointer(data: &[u8]) -> bool {
+    data.len() < 1024 && data.starts_with(b"version https://git-lfs.github.com/spec/")
+}
+
+/// Files under `dir` that are Git LFS pointers, skipping ignored folders.
+pub fn lfs_pointers(dir: &Path) -> Vec<PathBuf> {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file() && !should_skip_path(dir, entry.path()))
+        .filter(|entry| {
+            entry.metadata().is_ok_and(|meta| meta.len() < 1024)
+                && std::fs::read(entry.path()).is_ok_and(|data| is_lfs_pointer(&data))
+        })
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
 /// Checks whether a path should be skipped during copy.
 fn should_skip_path(root: &Path, path: &Path) -> bool {
     let rel = path.strip_prefix(root).unwrap_or(path);
@@ -132,7 +518,7 @@ fn should_skip_component(component: &str) -> bool {
 }
 
 /// Creates a directory symlink, replacing any existing path.
-fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+pub(crate) fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
     if link.exists() {
         if link.is_dir() {
             std::fs::remove_dir_all(link)?;
@@ -181,7 +567,209 @@ pub fn sanitize_name(name: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::sanitize_name;
+    use super::*;
+
+    #[test]
+    fn install_state_detects_links_copies_and_drift() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let canonical = dir.path().join("store/skill");
+        std::fs::create_dir_all(&canonical).expect("create store");
+        std::fs::write(canonical.join("SKILL.md"), "one").expect("write");
+
+        let linked = dir.path().join("linked");
+        create_symlink(&canonical, &linked).expect("symlink");
+        let copied = dir.path().join("copied");
+        copy_dir(&canonical, &copied).expect("copy");
+
+        let state = |path: &Path| install_state(path, &canonical).expect("state");
+        assert_eq!(state(&linked), Some(InstallState::Linked));
+        assert_eq!(state(&copied), Some(InstallState::Copy));
+        assert_eq!(state(&dir.path().join("missing")), None);
+
+        std::fs::write(copied.join("SKILL.md"), "two").expect("edit");
+        assert_eq!(state(&copied), Some(InstallState::Drifted));
+        let missing_store = dir.path().join("store/other");
+        assert_eq!(
+            install_state(&copied, &missing_store).expect("state"),
+            Some(InstallState::Unmanaged)
+        );
+    }
+
+    #[test]
+    fn copies_directories_file_by_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let from = dir.path().join("from");
+        std::fs::create_dir_all(from.join("scripts")).expect("create");
+        std::fs::create_dir_all(from.join(".git")).expect("create");
+        std::fs::write(from.join("SKILL.md"), "skill").expect("write");
+        std::fs::write(from.join("scripts/run.sh"), "echo hi").expect("write");
+        std::fs::write(from.join(".git/HEAD"), "ref").expect("write");
+
+        let to = dir.path().join("to");
+        copy_dir(&from, &to).expect("copy");
+        assert_eq!(
+            std::fs::read_to_string(to.join("scripts/run.sh")).expect("read"),
+            "echo hi"
+        );
+        assert!(to.join("SKILL.md").is_file());
+        assert!(!to.join(".git").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_dir_keeps_executable_bits_and_mtimes() {
+        use std::os::unix::fs::PermissionsExt;
+        use std::time::{Duration, SystemTime};
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let from = dir.path().join("from");
+        std::fs::create_dir_all(from.join("scripts")).expect("create");
+        let script = from.join("scripts/run.sh");
+        std::fs::write(&script, "#!/bin/sh\n").expect("write");
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        std::fs::File::open(&script)
+            .expect("open")
+            .set_modified(modified)
+            .expect("set mtime");
+
+        let to = dir.path().join("to");
+        copy_dir(&from, &to).expect("copy");
+        let metadata = std::fs::metadata(to.join("scripts/run.sh")).expect("metadata");
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o755);
+        assert_eq!(metadata.modified().expect("mtime"), modified);
+    }
+
+    #[test]
+    fn checks_symlink_targets_stay_inside_the_skill() {
+        assert!(link_stays_within(
+            Path::new("link.md"),
+            Path::new("docs/guide.md")
+        ));
+        assert!(link_stays_within(
+            Path::new("refs/link.md"),
+            Path::new("../docs/./guide.md")
+        ));
+        assert!(!link_stays_within(
+            Path::new("link.md"),
+            Path::new("../other/SKILL.md")
+        ));
+        assert!(!link_stays_within(
+            Path::new("refs/link.md"),
+            Path::new("../../secret")
+        ));
+        assert!(!link_stays_within(
+            Path::new("link"),
+            Path::new("/etc/passwd")
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_dir_recreates_inner_symlinks_and_rejects_escaping_ones() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let from = dir.path().join("from");
+        std::fs::create_dir_all(from.join("docs")).expect("create");
+        std::fs::write(from.join("docs/guide.md"), "guide").expect("write");
+        std::os::unix::fs::symlink("docs/guide.md", from.join("GUIDE.md")).expect("symlink");
+        std::os::unix::fs::symlink("docs", from.join("reference")).expect("symlink");
+
+        let to = dir.path().join("to");
+        copy_dir(&from, &to).expect("copy");
+        assert_eq!(
+            std::fs::read_link(to.join("GUIDE.md")).expect("link"),
+            PathBuf::from("docs/guide.md")
+        );
+        assert_eq!(
+            std::fs::read_to_string(to.join("reference/guide.md")).expect("read"),
+            "guide"
+        );
+
+        std::os::unix::fs::symlink("../outside", from.join("escape")).expect("symlink");
+        let err = copy_dir(&from, &dir.path().join("again")).expect_err("escaping link");
+        assert!(err.to_string().contains("points outside the skill folder"));
+        std::fs::remove_file(from.join("escape")).expect("remove");
+
+        std::os::unix::fs::symlink(".", from.join("a")).expect("symlink");
+        std::os::unix::fs::symlink("a/..", from.join("e")).expect("symlink");
+        let err = copy_dir(&from, &dir.path().join("chained")).expect_err("chained link");
+        assert!(err.to_string().contains("points outside the skill folder"));
+    }
+
+    #[test]
+    fn ignore_patterns_match_names_and_paths() {
+        let ignore = IgnorePatterns::new(
+            ["fixtures/", "*.psd", "/docs/drafts", "# comment", ""].map(String::from),
+        );
+        assert!(ignore.is_ignored(Path::new("fixtures")));
+        assert!(ignore.is_ignored(Path::new("tests/fixtures/data.json")));
+        assert!(ignore.is_ignored(Path::new("assets/logo.psd")));
+        assert!(ignore.is_ignored(Path::new("docs/drafts/wip.md")));
+        assert!(!ignore.is_ignored(Path::new("more/docs/drafts/wip.md")));
+        assert!(!ignore.is_ignored(Path::new("SKILL.md")));
+        assert!(!IgnorePatterns::default().is_ignored(Path::new("fixtures")));
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(dir.path().join("from/fixtures")).expect("create");
+        std::fs::write(dir.path().join("from/SKILL.md"), "skill").expect("write");
+        std::fs::write(dir.path().join("from/fixtures/a.json"), "{}").expect("write");
+        std::fs::write(dir.path().join(".skilignore"), "# test data\nfixtures\n").expect("write");
+        let ignore = IgnorePatterns::default().with_file(&dir.path().join(".skilignore"));
+        copy_dir_filtered(&dir.path().join("from"), &dir.path().join("to"), &ignore).expect("copy");
+        assert!(dir.path().join("to/SKILL.md").is_file());
+        assert!(!dir.path().join("to/fixtures").exists());
+    }
+
+    #[test]
+    fn install_size_leaves_out_ignored_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for folder in ["fixtures", "node_modules", "scripts"] {
+            std::fs::create_dir_all(dir.path().join(folder)).expect("create");
+        }
+        std::fs::write(dir.path().join("SKILL.md"), "12345").expect("write");
+        std::fs::write(dir.path().join("scripts/run.sh"), "123").expect("write");
+        std::fs::write(dir.path().join("fixtures/big.bin"), [0u8; 4096]).expect("write");
+        std::fs::write(dir.path().join("node_modules/dep.js"), [0u8; 4096]).expect("write");
+        std::fs::write(dir.path().join(IGNORE_FILE), "fixtures\n").expect("write");
+        let skill = Skill {
+            name: "sized".to_string(),
+            description: String::new(),
+            path: dir.path().to_path_buf(),
+            raw_content: String::new(),
+            resources: Vec::new(),
+            found_by: String::new(),
+            file: None,
+        };
+
+        let ignore_file = "fixtures\n".len() as u64;
+        assert_eq!(
+            install_size(&skill, &IgnorePatterns::default()).expect("size"),
+            5 + 3 + ignore_file
+        );
+        let ignore = IgnorePatterns::new(["*.sh".to_string()]);
+        assert_eq!(
+            install_size(&skill, &ignore).expect("size"),
+            5 + ignore_file
+        );
+    }
+
+    #[test]
+    fn finds_lfs_pointer_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let pointer = "version https://git-lfs.github.com/spec/v1\noid sha256:4d7a\nsize 12345\n";
+        std::fs::create_dir_all(dir.path().join("assets")).expect("create");
+        std::fs::create_dir_all(dir.path().join("node_modules")).expect("create");
+        std::fs::write(dir.path().join("SKILL.md"), "skill").expect("write");
+        std::fs::write(dir.path().join("assets/logo.png"), pointer).expect("write");
+        std::fs::write(dir.path().join("node_modules/x.png"), pointer).expect("write");
+
+        assert!(is_lfs_pointer(pointer.as_bytes()));
+        assert!(!is_lfs_pointer(b"version 1"));
+        assert_eq!(
+            lfs_pointers(dir.path()),
+            vec![dir.path().join("assets/logo.png")]
+        );
+    }
 
     #[test]
     fn sanitizes_names() {
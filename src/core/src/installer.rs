@@ -0,0 +1,673 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use tempfile::TempDir;
+
+use crate::agent::AgentConfig;
+use crate::changes::{SkillChanges, compare_dirs};
+use crate::config::{
+    SkilSource, SkillFolder, agent_rules, config_location, mark_modified, max_skill_bytes,
+    read_config, rules_allow, tracking_source, untrack_skills, update_config,
+};
+use crate::discovery::{DiscoveredSource, Discovery, Revision};
+use crate::error::{ErrorCode, Result, SkilError};
+use crate::git::tree_hash;
+use crate::install::{
+    IgnorePatterns, InstallMode, agent_skills_base, canonical_skills_dir, copy_dir, copy_skill,
+    dir_hash, install_size, install_skill, link_skill, remove_managed, sanitize_name,
+};
+use crate::lock::lock_scope;
+use crate::merge::merge_dirs;
+use crate::observer::{
+    Answer, Conflict, ConflictResolution, Drift, DriftResolution, Homonym, LargeSkill,
+    NoopObserver, Observer, Prompt,
+};
+use crate::skills::{
+    DiscoverOptions, Skill, missing_skills, parse_frontmatter, parse_skill_md, select_skills,
+};
+use crate::source::Source;
+
+/// Non-interactive equivalent of `skil add`.
+///
+/// ```no_run
+/// use skil_core::agent::resolve_agents;
+/// use skil_core::installer::Installer;
+/// use skil_core::observer::NoopObserver;
+///
+/// let report = Installer::new("vercel-labs/agent-skills")
+///     .skills(["web-design-guidelines"])
+///     .agents(resolve_agents(&["cursor".to_string()]))
+///     .observer(&NoopObserver)
+///     .install()?;
+/// println!("installed {:?}", report.skills);
+/// # Ok::<(), skil_core::SkilError>(())
+/// ```
+pub struct Installer<'a> {
+    source: String,
+    subpaths: Vec<PathBuf>,
+    branch: Option<String>,
+    skills: Vec<String>,
+    agents: Vec<AgentConfig>,
+    global: bool,
+    mode: InstallMode,
+    options: DiscoverOptions,
+    revision: Revision,
+    track: bool,
+    missing_ok: bool,
+    replacing: Option<String>,
+    observer: &'a dyn Observer,
+}
+
+/// Outcome of a successful install.
+#[derive(Debug)]
+pub struct InstallReport {
+    pub source: Source,
+    pub source_key: String,
+    pub skills: Vec<String>,
+    pub agents: Vec<String>,
+    pub checksum: Option<String>,
+    pub version: Option<String>,
+    /// Requested skills the source no longer contains.
+    pub missing: Vec<String>,
+    /// Every skill directory written, one per skill and agent.
+    pub installed: Vec<InstalledSkill>,
+    /// Skill and agent pairs skipped because a config rule forbids them.
+    pub excluded: Vec<InstalledSkill>,
+    /// Files of the installed skills that are Git LFS pointers instead of
+    /// content, relative to the source root.
+    pub lfs_pointers: Vec<PathBuf>,
+    /// Locally edited skills left as they were, now marked as modified.
+    pub kept_local: Vec<String>,
+    /// Files of merged skills left with conflict markers or kept as the
+    /// local version, under the canonical store.
+    pub conflicts: Vec<PathBuf>,
+    /// Files changed in skills that were already installed, per skill.
+    pub changes: Vec<SkillChanges>,
+    /// Skills the source offers that it did not at the last install or update.
+    pub new_skills: Vec<String>,
+}
+
+/// A skill installed into one agent's directory.
+#[derive(Debug, Clone)]
+pub struct InstalledSkill {
+    pub skill: String,
+    pub agent: String,
+    pub path: PathBuf,
+}
+
+impl<'a> Installer<'a> {
+    /// Creates an installer for a local path, `owner/repo`, or git URL.
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            subpaths: Vec::new(),
+            branch: None,
+            skills: Vec::new(),
+            agents: Vec::new(),
+            global: false,
+            mode: InstallMode::Symlink,
+            options: DiscoverOptions::default(),
+            revision: Revision::default(),
+            track: true,
+            missing_ok: false,
+            replacing: None,
+            observer: &NoopObserver,
+        }
+    }
+
+    /// Names of skills to install; when empty the observer is prompted, and
+    /// every skill is installed if it answers with the default.
+    pub fn skills<I, S>(mut self, skills: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.skills = skills.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Folders of the source to search for skills; see [`Discovery::subpaths`].
+    pub fn subpaths(mut self, subpaths: Vec<PathBuf>) -> Self {
+        self.subpaths = subpaths;
+        self
+    }
+
+    /// Branch to follow and record in config; see [`Discovery::branch`].
+    pub fn branch(mut self, branch: Option<String>) -> Self {
+        self.branch = branch;
+        self
+    }
+
+    /// Agents to install into.
+    pub fn agents(mut self, agents: Vec<AgentConfig>) -> Self {
+        self.agents = agents;
+        self
+    }
+
+    /// Installs into global agent directories instead of the current project.
+    pub fn global(mut self, global: bool) -> Self {
+        self.global = global;
+        self
+    }
+
+    /// Sets how skills are placed into agent directories.
+    pub fn mode(mut self, mode: InstallMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Keeps searching nested directories even when the root has a SKILL.md.
+    pub fn full_depth(mut self, full_depth: bool) -> Self {
+        self.options.full_depth = full_depth;
+        self
+    }
+
+    /// Also searches folders left out by `.gitignore` and `.ignore` files.
+    pub fn no_ignore(mut self, no_ignore: bool) -> Self {
+        self.options.no_ignore = no_ignore;
+        self
+    }
+
+    /// Searches this many levels of folders when the usual places hold no
+    /// skills; defaults to
+    /// [`DEFAULT_MAX_DEPTH`](crate::skills::DEFAULT_MAX_DEPTH).
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.options.max_depth = max_depth;
+        self
+    }
+
+    /// Also searches the subfolders of these folders, relative to the root,
+    /// besides the `skill_dirs` set in config.
+    pub fn skill_dirs(mut self, skill_dirs: Vec<PathBuf>) -> Self {
+        self.options.skill_dirs = skill_dirs;
+        self
+    }
+
+    /// Also takes markdown files with skill frontmatter as skills by
+    /// themselves, as does `file_skills` in config.
+    pub fn file_skills(mut self, file_skills: bool) -> Self {
+        self.options.file_skills = file_skills;
+        self
+    }
+
+    /// Sets the revision to check out for git sources.
+    pub fn revision(mut self, revision: Revision) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    /// Records the install in config (default `true`).
+    pub fn track(mut self, track: bool) -> Self {
+        self.track = track;
+        self
+    }
+
+    /// Reports requested skills missing from the source in
+    /// [`InstallReport::missing`] instead of failing when none match.
+    pub fn missing_ok(mut self, missing_ok: bool) -> Self {
+        self.missing_ok = missing_ok;
+        self
+    }
+
+    /// Replaces skills installed from this source key without asking the
+    /// observer about them, as when a deprecated skill's successor takes over.
+    pub fn replacing(mut self, source_key: Option<String>) -> Self {
+        self.replacing = source_key;
+        self
+    }
+
+    /// Sets the observer receiving progress, conflicts, and prompts.
+    pub fn observer(mut self, observer: &'a dyn Observer) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    /// Discovers, selects, and installs skills, then records them in config.
+    ///
+    /// Skill and agent pairs forbidden by config rules are skipped, and copies
+    /// skil placed there earlier are removed. Skills over the configured
+    /// `max_skill_bytes` are passed to [`Observer::on_large_skill`] first.
+    pub fn install(self) -> Result<InstallReport> {
+        if self.agents.is_empty() {
+            return Err(SkilError::new(ErrorCode::NoAgents, "No agents selected"));
+        }
+
+        let observer = self.observer;
+        let options = self.options.clone().with_config(self.global)?;
+        let discovered = Discovery::new(self.source)
+            .subpaths(self.subpaths.clone())
+            .branch(self.branch.clone())
+            .revision(self.revision)
+            .options(options.clone())
+            .observer(observer)
+            .run()?;
+        if discovered.skills.is_empty() {
+            return Err(SkilError::new(
+                ErrorCode::NoSkills,
+                "No skills found in source",
+            ));
+        }
+
+        let requested = if self.skills.is_empty() {
+            let prompt = Prompt::SelectSkills {
+                skills: &discovered.skills,
+            };
+            match observer.on_prompt(&prompt)? {
+                Answer::Skills(skills) => skills,
+                Answer::Default => Vec::new(),
+            }
+        } else {
+            self.skills
+        };
+        let selected = select_skills(&discovered.skills, &requested);
+        let missing = missing_skills(&discovered.skills, &requested);
+        if selected.is_empty() && (!self.missing_ok || missing.is_empty()) {
+            return Err(SkilError::new(
+                ErrorCode::NoMatchingSkills,
+                "No matching skills selected",
+            ));
+        }
+        check_skil_version(&selected)?;
+
+        let _lock = lock_scope(self.global)?;
+        let location = config_location(self.global)?;
+        let canonical = canonical_skills_dir(self.global)?;
+        let source_key = discovered.key();
+        let tracked = read_config(&location.path)?;
+        let mut replaced: Vec<(String, String)> = Vec::new();
+        let mut kept = Vec::new();
+        for skill in selected {
+            if let Some(source) = tracking_source(&tracked, &skill.name, &source_key) {
+                if self.replacing.as_deref() != Some(source) {
+                    let skill_md = canonical.join(sanitize_name(&skill.name)).join("SKILL.md");
+                    let homonym = Homonym {
+                        skill: &skill,
+                        source,
+                        description: parse_skill_md(&skill_md)
+                            .ok()
+                            .flatten()
+                            .map(|installed| installed.description),
+                    };
+                    if observer.on_homonym(&homonym)? == ConflictResolution::Skip {
+                        continue;
+                    }
+                }
+                replaced.push((source.to_string(), skill.name.clone()));
+            }
+            kept.push(skill);
+        }
+        let ignore = IgnorePatterns::load(&discovered.root, self.global)?;
+        let limit = max_skill_bytes(self.global)?;
+        if limit > 0 {
+            let mut within = Vec::new();
+            for skill in kept {
+                let bytes = install_size(&skill, &ignore)?;
+                if bytes > limit {
+                    let large = LargeSkill {
+                        skill: &skill,
+                        bytes,
+                        limit,
+                    };
+                    if observer.on_large_skill(&large)? == ConflictResolution::Skip {
+                        replaced.retain(|(_, name)| *name != skill.name);
+                        continue;
+                    }
+                }
+                within.push(skill);
+            }
+            kept = within;
+        }
+
+        // Store copies whose content no longer matches what was installed have local edits.
+        let previous = tracked.sources.get(&source_key);
+        let mut kept_local = Vec::new();
+        let mut merges: BTreeMap<String, TempDir> = BTreeMap::new();
+        let mut unedited = Vec::new();
+        for skill in kept {
+            let store = canonical.join(sanitize_name(&skill.name));
+            let recorded = previous
+                .and_then(|source| source.folders.get(&skill.name))
+                .and_then(|folder| folder.installed.as_deref());
+            if let Some(recorded) = recorded
+                && store.is_dir()
+                && dir_hash(&store)? != recorded
+            {
+                let work = tempfile::tempdir()?;
+                let (local, upstream) = (work.path().join("local"), work.path().join("upstream"));
+                copy_dir(&store, &local)?;
+                copy_skill(&skill, &upstream, self.global, &ignore)?;
+                let drift = Drift {
+                    skill: &skill,
+                    source: &source_key,
+                    local: &local,
+                    upstream: &upstream,
+                };
+                match observer.on_drift(&drift)? {
+                    DriftResolution::KeepLocal => {
+                        replaced.retain(|(_, name)| *name != skill.name);
+                        kept_local.push(skill.name.clone());
+                        continue;
+                    }
+                    DriftResolution::Merge => {
+                        merges.insert(skill.name.clone(), work);
+                    }
+                    DriftResolution::TakeUpstream => {}
+                }
+            }
+            unedited.push(skill);
+        }
+        // Merges need the files as installed before, from the recorded revision.
+        let base = match previous.and_then(|source| source.checksum.clone()) {
+            _ if merges.is_empty() => None,
+            Some(checksum) => Some(
+                Discovery::new(source_key.clone())
+                    .subpaths(self.subpaths.clone())
+                    .branch(self.branch.clone())
+                    .revision(Revision::Pinned(checksum))
+                    .options(options)
+                    .observer(observer)
+                    .run()?,
+            ),
+            None => {
+                return Err(SkilError::Message(format!(
+                    "No installed revision of {source_key} recorded to merge local edits against"
+                )));
+            }
+        };
+        let base_ignore = match &base {
+            Some(base) => IgnorePatterns::load(&base.root, self.global)?,
+            None => IgnorePatterns::default(),
+        };
+
+        let selected = unedited;
+        // Skills the source did not offer at the last install or update.
+        let new_skills: Vec<String> = match previous {
+            Some(previous) if !previous.available.is_empty() => discovered
+                .skills
+                .iter()
+                .map(|skill| skill.name.clone())
+                .filter(|name| {
+                    !previous
+                        .available
+                        .iter()
+                        .chain(&previous.skills)
+                        .any(|known| known.eq_ignore_ascii_case(name))
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        if selected.is_empty() && kept_local.is_empty() && !self.missing_ok {
+            return Err(SkilError::new(
+                ErrorCode::NoMatchingSkills,
+                "No skills left to install",
+            ));
+        }
+
+        let rules = agent_rules(self.global)?;
+        let mut installed = Vec::new();
+        let mut excluded = Vec::new();
+        let mut hashes = BTreeMap::new();
+        let mut conflicts = Vec::new();
+        let mut changes = Vec::new();
+        observer.on_install_started(selected.len(), self.agents.len());
+        for skill in &selected {
+            let dir_name = sanitize_name(&skill.name);
+            let store = canonical.join(&dir_name);
+            // Reinstalls report what changed in the store copy.
+            let before = match previous {
+                Some(_) if store.is_dir() => {
+                    let before = tempfile::tempdir()?;
+                    copy_dir(&store, before.path())?;
+                    Some(before)
+                }
+                _ => None,
+            };
+            let mut placed = Vec::new();
+            for agent in &self.agents {
+                let target = agent_skills_base(agent, self.global)?.join(&dir_name);
+                if !rules_allow(&rules, skill, agent.name) {
+                    remove_managed(&target, &canonical.join(&dir_name))?;
+                    excluded.push(InstalledSkill {
+                        skill: skill.name.clone(),
+                        agent: agent.name.to_string(),
+                        path: target,
+                    });
+                    continue;
+                }
+                if target.exists() {
+                    let conflict = Conflict {
+                        skill,
+                        agent,
+                        path: &target,
+                    };
+                    if observer.on_conflict(&conflict)? == ConflictResolution::Skip {
+                        continue;
+                    }
+                }
+                install_skill(skill, agent, self.global, self.mode, &ignore)?;
+                observer.on_skill_installed(skill, agent);
+                placed.push(agent);
+                installed.push(InstalledSkill {
+                    skill: skill.name.clone(),
+                    agent: agent.name.to_string(),
+                    path: target,
+                });
+            }
+            if placed.is_empty() {
+                continue;
+            }
+            hashes.insert(skill.name.clone(), dir_hash(&store)?);
+            if let Some(work) = merges.get(&skill.name) {
+                let base_dir = work.path().join("base");
+                let base_skill = base
+                    .iter()
+                    .flat_map(|base| &base.skills)
+                    .find(|old| old.name == skill.name);
+                if let Some(base_skill) = base_skill {
+                    copy_skill(base_skill, &base_dir, self.global, &base_ignore)?;
+                }
+                let local = work.path().join("local");
+                for file in merge_dirs(&base_dir, &local, &store)? {
+                    conflicts.push(store.join(file));
+                }
+                // Agents holding copies need the merged files too.
+                for agent in placed {
+                    link_skill(&dir_name, agent, self.global, self.mode)?;
+                }
+            }
+            if let Some(before) = before {
+                let files = compare_dirs(before.path(), &store)?;
+                if !files.is_empty() {
+                    changes.push(SkillChanges {
+                        skill: skill.name.clone(),
+                        files,
+                    });
+                }
+            }
+        }
+        observer.on_install_finished(selected.len(), self.agents.len());
+
+        let lfs_pointers = discovered
+            .lfs_pointers
+            .iter()
+            .filter(|path| {
+                let path = discovered.root.join(path);
+                selected.iter().any(|skill| path.starts_with(&skill.path))
+            })
+            .cloned()
+            .collect();
+        let skill_names: Vec<String> = selected.iter().map(|skill| skill.name.clone()).collect();
+        for (source, skill) in &replaced {
+            untrack_skills(&location.path, source, std::slice::from_ref(skill))?;
+        }
+        if self.track {
+            let mut entry = config_entry(&discovered.source);
+            if self.branch.is_some() {
+                entry.branch = self.branch.clone();
+            }
+            if self.subpaths.len() > 1 {
+                entry.subpath = None;
+                entry.subpaths = self.subpaths.iter().map(|p| path_string(p)).collect();
+            } else if let Some(subpath) = self.subpaths.first() {
+                entry.subpath = Some(path_string(subpath));
+            }
+            entry.folders = skill_folders(&discovered, &selected);
+            entry.available = discovered
+                .skills
+                .iter()
+                .map(|skill| skill.name.clone())
+                .collect();
+            for (name, folder) in entry.folders.iter_mut() {
+                folder.installed = hashes.get(name).cloned();
+            }
+            update_config(
+                &location.path,
+                &source_key,
+                entry,
+                &skill_names,
+                discovered.checksum.clone(),
+                discovered.version.clone(),
+            )?;
+            for skill in &kept_local {
+                mark_modified(&location.path, &source_key, skill)?;
+            }
+        }
+
+        Ok(InstallReport {
+            source_key,
+            skills: skill_names,
+            agents: self.agents.iter().map(|a| a.name.to_string()).collect(),
+            checksum: discovered.checksum.clone(),
+            version: discovered.version.clone(),
+            source: discovered.source.clone(),
+            missing,
+            installed,
+            excluded,
+            lfs_pointers,
+            kept_local,
+            conflicts,
+            changes,
+            new_skills,
+        })
+    }
+}
+
+/// Fails when a selected skill declares a `min-skil-version` newer than this
+/// build, before anything is installed.
+pub fn check_skil_version(skills: &[Skill]) -> Result<()> {
+    let unsupported: Vec<String> = skills
+        .iter()
+        .filter_map(|skill| {
+            let frontmatter = parse_frontmatter(&skill.raw_content).ok().flatten()?;
+            let required = frontmatter.unsupported_version()?;
+            Some(format!("{} requires skil {required}", skill.name))
+        })
+        .collect();
+    if unsupported.is_empty() {
+        return Ok(());
+    }
+    Err(SkilError::new(
+        ErrorCode::Unsupported,
+        format!(
+            "{}, but this is skil {}; upgrade with `skil upgrade-self`",
+            unsupported.join(", "),
+            env!("CARGO_PKG_VERSION")
+        ),
+    ))
+}
+
+/// Returns the config entry recorded for a newly added source.
+fn config_entry(source: &Source) -> SkilSource {
+    let (source_type, branch, subpath) = match source {
+        Source::Local { .. } | Source::Download { .. } => (None, None, None),
+        Source::Git { subpath, info, .. } => (
+            Some(info.source_type),
+            info.github_branch.clone(),
+            subpath.as_deref().map(path_string),
+        ),
+    };
+    SkilSource {
+        source_type,
+        branch,
+        subpath,
+        subpaths: Vec::new(),
+        checksum: None,
+        version: None,
+        updated_at: None,
+        skills: vec![],
+        folders: BTreeMap::new(),
+        history: Vec::new(),
+        modified: Vec::new(),
+        available: Vec::new(),
+    }
+}
+
+/// Converts a repository-relative path to the `/`-separated form stored in config.
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Records the repository folder and tree hash of each installed git skill.
+fn skill_folders(discovered: &DiscoveredSource, skills: &[Skill]) -> BTreeMap<String, SkillFolder> {
+    if !matches!(discovered.source, Source::Git { .. }) {
+        return BTreeMap::new();
+    }
+    skills
+        .iter()
+        .filter_map(|skill| {
+            let path = skill.path.strip_prefix(&discovered.root).ok()?;
+            let path = path.to_string_lossy().replace('\\', "/");
+            let hash = tree_hash(&discovered.root, &path).ok()?;
+            let folder = SkillFolder {
+                path,
+                hash,
+                installed: None,
+            };
+            Some((skill.name.clone(), folder))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::resolve_agents;
+    use crate::config::{config_location, read_config};
+    use crate::fixtures::{git, isolated, write_skill};
+    use crate::install::canonical_skills_dir;
+
+    #[test]
+    fn installs_and_records_a_non_default_branch() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let origin = dir.path().join("origin");
+        write_skill(&origin, "pdf", "from main");
+        git(&origin, &["init", "-q", "-b", "main"]);
+        git(&origin, &["add", "-A"]);
+        git(&origin, &["commit", "-q", "-m", "Add pdf"]);
+        git(&origin, &["checkout", "-q", "-b", "next"]);
+        write_skill(&origin, "pdf", "from next");
+        git(&origin, &["commit", "-q", "-am", "Update pdf"]);
+        git(&origin, &["checkout", "-q", "main"]);
+
+        isolated(dir.path(), || {
+            let report = Installer::new(format!("file://{}", origin.display()))
+                .branch(Some("next".to_string()))
+                .agents(resolve_agents(&["claude-code".to_string()]))
+                .global(true)
+                .install()
+                .expect("install");
+
+            let installed = canonical_skills_dir(true)
+                .expect("store")
+                .join("pdf")
+                .join("SKILL.md");
+            let content = std::fs::read_to_string(installed).expect("read");
+            assert!(content.contains("from next"));
+
+            let config =
+                read_config(&config_location(true).expect("location").path).expect("config");
+            let entry = &config.sources[&report.source_key];
+            assert_eq!(entry.branch.as_deref(), Some("next"));
+        });
+    }
+}
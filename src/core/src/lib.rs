@@ -1,11 +1,42 @@
 #![allow(clippy::result_large_err)]
 
+pub mod adopt;
 pub mod agent;
+pub mod annotation;
+pub mod audit;
+pub mod budget;
+pub mod changes;
+pub mod clean;
 pub mod config;
+pub mod discovery;
+pub mod download;
 pub mod error;
+#[cfg(test)]
+mod fixtures;
 pub mod git;
+pub mod history;
+pub mod hook;
+pub mod import;
 pub mod install;
+pub mod installer;
+pub mod lint;
+pub mod lock;
+pub mod merge;
+pub mod migrate;
+pub mod observer;
+pub mod overrides;
+pub mod paths;
+pub mod policy;
+pub mod profile;
+pub mod remote;
+pub mod render;
+pub mod schema;
+pub mod search_cache;
 pub mod skills;
+pub mod snapshot;
 pub mod source;
+pub mod template;
+pub mod trash;
+pub mod updater;
 
-pub use error::{Result, SkilError};
+pub use error::{ErrorCode, Result, SkilError};
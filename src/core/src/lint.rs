@@ -0,0 +1,278 @@
+use std::fmt;
+
+use crate::skills::Skill;
+
+/// What an agent does with a skill that breaks one of its rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The agent does not load the skill.
+    Rejected,
+    /// The agent loads the skill but cuts the value short.
+    Truncated,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Rejected => "rejected",
+            Severity::Truncated => "truncated",
+        }
+    }
+}
+
+/// Frontmatter limits of one agent.
+#[derive(Debug, Clone, Copy)]
+pub struct LintProfile {
+    /// Agent name, or [`SPEC_PROFILE`] for agents that follow the Agent Skills spec.
+    pub agent: &'static str,
+    name_max: usize,
+    /// Names must be lowercase letters, digits, and single inner hyphens.
+    strict_name: bool,
+    /// Words names must not contain.
+    reserved: &'static [&'static str],
+    description_max: usize,
+    description_overflow: Severity,
+    single_line: bool,
+    no_xml: bool,
+}
+
+/// Name of the profile for agents without rules of their own.
+pub const SPEC_PROFILE: &str = "agent-skills";
+
+const SPEC: LintProfile = LintProfile {
+    agent: SPEC_PROFILE,
+    name_max: 64,
+    strict_name: true,
+    reserved: &[],
+    description_max: 1024,
+    description_overflow: Severity::Rejected,
+    single_line: false,
+    no_xml: false,
+};
+
+/// Known profiles; agents not listed follow [`SPEC_PROFILE`].
+pub const PROFILES: &[LintProfile] = &[
+    SPEC,
+    LintProfile {
+        agent: "claude-code",
+        reserved: &["anthropic", "claude"],
+        no_xml: true,
+        ..SPEC
+    },
+    LintProfile {
+        agent: "codex",
+        name_max: 100,
+        strict_name: false,
+        description_max: 500,
+        description_overflow: Severity::Truncated,
+        single_line: true,
+        ..SPEC
+    },
+];
+
+/// Returns the profile an agent is checked against.
+pub fn profile(agent: &str) -> &'static LintProfile {
+    PROFILES
+        .iter()
+        .find(|profile| profile.agent == agent)
+        .unwrap_or(&PROFILES[0])
+}
+
+/// A frontmatter value an agent will reject or truncate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    pub agent: &'static str,
+    /// 1-based line of the offending key in SKILL.md.
+    pub line: usize,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}: {} ({})",
+            self.line,
+            self.agent,
+            self.message,
+            self.severity.as_str()
+        )
+    }
+}
+
+/// Checks a skill's name and description against an agent's limits.
+pub fn lint_skill(skill: &Skill, profile: &LintProfile) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    let mut push = |field: &str, severity: Severity, message: String| {
+        issues.push(LintIssue {
+            agent: profile.agent,
+            line: field_line(&skill.raw_content, field),
+            severity,
+            message,
+        });
+    };
+
+    let name = &skill.name;
+    let length = name.chars().count();
+    if length > profile.name_max {
+        push(
+            "name",
+            Severity::Rejected,
+            format!(
+                "name is {length} characters, over the limit of {}",
+                profile.name_max
+            ),
+        );
+    }
+    if profile.strict_name && !is_strict_name(name) {
+        push(
+            "name",
+            Severity::Rejected,
+            format!("name `{name}` must be lowercase letters, digits, and single hyphens"),
+        );
+    }
+    let lower = name.to_lowercase();
+    for word in profile.reserved {
+        if lower.contains(word) {
+            push(
+                "name",
+                Severity::Rejected,
+                format!("name `{name}` must not contain the reserved word `{word}`"),
+            );
+        }
+    }
+
+    let description = &skill.description;
+    let length = description.chars().count();
+    if length > profile.description_max {
+        push(
+            "description",
+            profile.description_overflow,
+            format!(
+                "description is {length} characters, over the limit of {}",
+                profile.description_max
+            ),
+        );
+    }
+
+    for (field, value) in [("name", name), ("description", description)] {
+        if profile.single_line && value.trim().contains('\n') {
+            push(
+                field,
+                Severity::Rejected,
+                format!("{field} must be a single line"),
+            );
+        }
+        if profile.no_xml && has_xml_tag(value) {
+            push(
+                field,
+                Severity::Rejected,
+                format!("{field} must not contain XML tags"),
+            );
+        }
+    }
+    issues
+}
+
+/// Lowercase ASCII letters and digits joined by single hyphens.
+fn is_strict_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('-').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        })
+}
+
+fn has_xml_tag(value: &str) -> bool {
+    value.split('<').skip(1).any(|rest| {
+        rest.starts_with(|c: char| c.is_ascii_alphabetic() || c == '/') && rest.contains('>')
+    })
+}
+
+/// Line of a frontmatter key, or 1 when it is not found.
+fn field_line(content: &str, field: &str) -> usize {
+    content
+        .lines()
+        .position(|line| {
+            line.strip_prefix(field)
+                .is_some_and(|rest| rest.trim_start().starts_with(':'))
+        })
+        .map_or(1, |index| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn skill(name: &str, description: &str) -> Skill {
+        Skill {
+            name: name.to_string(),
+            description: description.to_string(),
+            path: PathBuf::from(name),
+            raw_content: format!("---\nname: {name}\ndescription: {description}\n---\n"),
+            resources: Vec::new(),
+            found_by: String::new(),
+            file: None,
+        }
+    }
+
+    fn found(skill: &Skill, agent: &str) -> Vec<(usize, Severity, String)> {
+        lint_skill(skill, profile(agent))
+            .into_iter()
+            .map(|issue| (issue.line, issue.severity, issue.message))
+            .collect()
+    }
+
+    #[test]
+    fn accepts_names_and_descriptions_within_limits() {
+        let valid = skill("pdf-tools2", "Fill and merge PDF forms.");
+        for profile in PROFILES {
+            assert_eq!(lint_skill(&valid, profile), Vec::new(), "{}", profile.agent);
+        }
+    }
+
+    #[test]
+    fn applies_each_agents_rules() {
+        let claude = skill("Claude-Helper", "Uses <tool> calls.");
+        assert_eq!(
+            found(&claude, "claude-code"),
+            vec![
+                (
+                    2,
+                    Severity::Rejected,
+                    "name `Claude-Helper` must be lowercase letters, digits, and single hyphens"
+                        .to_string()
+                ),
+                (
+                    2,
+                    Severity::Rejected,
+                    "name `Claude-Helper` must not contain the reserved word `claude`".to_string()
+                ),
+                (
+                    3,
+                    Severity::Rejected,
+                    "description must not contain XML tags".to_string()
+                ),
+            ]
+        );
+        assert_eq!(found(&claude, "codex"), Vec::new());
+
+        let long = skill("notes", &"x".repeat(600));
+        assert_eq!(
+            found(&long, "codex"),
+            vec![(
+                3,
+                Severity::Truncated,
+                "description is 600 characters, over the limit of 500".to_string()
+            )]
+        );
+        assert_eq!(found(&long, "cursor"), Vec::new());
+        assert!(!is_strict_name("double--hyphen"));
+        assert!(!is_strict_name("-leading"));
+    }
+}
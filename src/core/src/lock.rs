@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::thread::ThreadId;
+use std::time::{Duration, Instant};
+
+use crate::config::config_location;
+use crate::error::{ErrorCode, Result, SkilError};
+use crate::git::blob_hash;
+use crate::paths::cache_dir;
+
+/// How long to wait for another skil process by default.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A lock file and the thread holding it.
+type HeldKey = (PathBuf, ThreadId);
+
+/// Lock files held by threads of this process with their hold counts, so
+/// nested acquisitions of the same config on one thread do not wait on
+/// themselves.
+static HELD: LazyLock<Mutex<HashMap<HeldKey, (File, usize)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// An advisory lock on a config file and the store it describes, released on drop.
+#[derive(Debug)]
+pub struct ConfigLock {
+    key: HeldKey,
+}
+
+impl Drop for ConfigLock {
+    fn drop(&mut self) {
+        let mut held = HELD.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some((_, count)) = held.get_mut(&self.key) {
+            *count -= 1;
+            if *count == 0 {
+                held.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// Locks the config of a scope; see [`lock_config`].
+pub fn lock_scope(global: bool) -> Result<ConfigLock> {
+    lock_config(&config_location(global)?.path)
+}
+
+/// Waits until no other skil process changes `config` or its store, then
+/// holds them until the returned lock is dropped.
+///
+/// Gives up after `SKIL_LOCK_TIMEOUT` seconds, 60 by default. Locks are
+/// re-entrant within a thread; other threads wait like other processes do.
+pub fn lock_config(config: &Path) -> Result<ConfigLock> {
+    let lock_path = lock_path(config)?;
+    let key = (lock_path.clone(), std::thread::current().id());
+    if let Some((_, count)) = HELD
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .get_mut(&key)
+    {
+        *count += 1;
+        return Ok(ConfigLock { key });
+    }
+    if let Some(parent) = lock_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)?;
+    let timeout = lock_timeout();
+    let started = Instant::now();
+    loop {
+        match file.try_lock() {
+            Ok(()) => break,
+            Err(TryLockError::WouldBlock) if started.elapsed() < timeout => {
+                std::thread::sleep(POLL_INTERVAL);
+            }
+            Err(TryLockError::WouldBlock) => {
+                return Err(SkilError::new(
+                    ErrorCode::Locked,
+                    format!(
+                        "Another skil process is still changing {} after {}s; \
+                         set SKIL_LOCK_TIMEOUT to wait longer",
+                        config.display(),
+                        timeout.as_secs()
+                    ),
+                ));
+            }
+            Err(TryLockError::Error(err)) => return Err(err.into()),
+        }
+    }
+    HELD.lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(key.clone(), (file, 1));
+    Ok(ConfigLock { key })
+}
+
+/// Lock file for `config` in the cache, so projects are not littered with them.
+///
+/// The config's folder is canonicalized when it exists, so paths through
+/// symlinks or `..` lock the same file.
+fn lock_path(config: &Path) -> Result<PathBuf> {
+    let config = std::path::absolute(config)?;
+    let config = match (config.parent(), config.file_name()) {
+        (Some(parent), Some(name)) => parent
+            .canonicalize()
+            .map_or(config.clone(), |parent| parent.join(name)),
+        _ => config,
+    };
+    let hash = blob_hash(config.to_string_lossy().as_bytes())?;
+    Ok(cache_dir().join("locks").join(format!("{hash}.lock")))
+}
+
+fn lock_timeout() -> Duration {
+    std::env::var("SKIL_LOCK_TIMEOUT")
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_TIMEOUT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paths::env_lock;
+
+    #[test]
+    fn waits_for_other_holders_and_is_reentrant() {
+        let _env = env_lock();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = dir.path().join(".skil.toml");
+        let outer = lock_config(&config).expect("lock");
+        let inner = lock_config(&config).expect("nested lock");
+        drop(inner);
+
+        let path = lock_path(&config).expect("path");
+        let other = File::open(&path).expect("open");
+        assert!(matches!(other.try_lock(), Err(TryLockError::WouldBlock)));
+        drop(outer);
+        other.try_lock().expect("released");
+    }
+
+    #[test]
+    fn other_threads_wait_without_blocking_other_configs() {
+        let _env = env_lock();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let first = dir.path().join("first.toml");
+        let second = dir.path().join("second.toml");
+        let outer = lock_config(&first).expect("lock");
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let waiting = {
+            let first = dir.path().join(".").join("first.toml");
+            let sender = sender.clone();
+            std::thread::spawn(move || {
+                let _lock = lock_config(&first).expect("lock on another thread");
+                sender.send("first").expect("send");
+            })
+        };
+        let other = std::thread::spawn(move || {
+            let _lock = lock_config(&second).expect("lock of another config");
+            sender.send("second").expect("send");
+        });
+
+        let timeout = Duration::from_secs(5);
+        assert_eq!(receiver.recv_timeout(timeout), Ok("second"));
+        assert!(receiver.recv_timeout(POLL_INTERVAL * 3).is_err());
+        drop(outer);
+        assert_eq!(receiver.recv_timeout(timeout), Ok("first"));
+        waiting.join().expect("join");
+        other.join().expect("join");
+    }
+}
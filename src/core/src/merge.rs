@@ -0,0 +1,146 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::error::Result;
+use crate::git::merge_file;
+
+/// Three-way merges a skill folder in place.
+///
+/// `merged` holds the new upstream files and receives the result, `base` the
+/// files as installed before, and `local` the locally edited copy. A file
+/// changed on one side only takes that side; a file changed on both is merged
+/// line by line. Returns the files, relative to `merged`, that were left with
+/// conflict markers or kept as the local version because they could not be
+/// merged.
+pub fn merge_dirs(base: &Path, local: &Path, merged: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = BTreeSet::new();
+    for dir in [base, local, merged] {
+        files.extend(relative_files(dir)?);
+    }
+    let empty = tempfile::NamedTempFile::new()?;
+    let mut conflicts = Vec::new();
+    for rel in files {
+        let read = |dir: &Path| std::fs::read(dir.join(&rel)).ok();
+        let (original, ours, theirs) = (read(base), read(local), read(merged));
+        if ours == original || ours == theirs {
+            continue;
+        }
+        let target = merged.join(&rel);
+        if theirs == original {
+            take_local(local, &rel, &target)?;
+            continue;
+        }
+        // Both sides changed the file; a deletion on either side cannot be merged.
+        if ours.is_none() || theirs.is_none() {
+            take_local(local, &rel, &target)?;
+            conflicts.push(rel);
+            continue;
+        }
+        let base_file = if original.is_some() {
+            base.join(&rel)
+        } else {
+            empty.path().to_path_buf()
+        };
+        match merge_file(&local.join(&rel), &base_file, &target) {
+            Ok((content, conflicted)) => {
+                std::fs::write(&target, content)?;
+                if conflicted {
+                    conflicts.push(rel);
+                }
+            }
+            Err(_) => {
+                take_local(local, &rel, &target)?;
+                conflicts.push(rel);
+            }
+        }
+    }
+    Ok(conflicts)
+}
+
+/// Replaces `target` with the local version of `rel`, or removes it when the
+/// local copy has none.
+fn take_local(local: &Path, rel: &Path, target: &Path) -> Result<()> {
+    let source = local.join(rel);
+    if source.is_file() {
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(&source, target)?;
+    } else if target.is_file() {
+        std::fs::remove_file(target)?;
+    }
+    Ok(())
+}
+
+/// Files under `dir`, relative to it; none when `dir` is missing.
+pub(crate) fn relative_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+    for entry in WalkDir::new(dir) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            let rel = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+            files.push(rel.to_path_buf());
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, files: &[(&str, &str)]) {
+        for (name, content) in files {
+            let path = dir.join(name);
+            std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+            std::fs::write(path, content).expect("write");
+        }
+    }
+
+    #[test]
+    fn merges_local_and_upstream_changes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let (base, local, merged) = (
+            dir.path().join("base"),
+            dir.path().join("local"),
+            dir.path().join("merged"),
+        );
+        write(
+            &base,
+            &[
+                ("SKILL.md", "one\ntwo\nthree\n"),
+                ("notes.md", "old\n"),
+                ("gone.md", "gone\n"),
+            ],
+        );
+        write(
+            &local,
+            &[
+                ("SKILL.md", "ONE\ntwo\nthree\n"),
+                ("notes.md", "mine\n"),
+                ("added.md", "added\n"),
+            ],
+        );
+        write(
+            &merged,
+            &[
+                ("SKILL.md", "one\ntwo\nTHREE\n"),
+                ("notes.md", "theirs\n"),
+                ("gone.md", "gone\n"),
+            ],
+        );
+
+        let conflicts = merge_dirs(&base, &local, &merged).expect("merge");
+        assert_eq!(conflicts, vec![PathBuf::from("notes.md")]);
+        let read = |name: &str| std::fs::read_to_string(merged.join(name)).expect("read");
+        assert_eq!(read("SKILL.md"), "ONE\ntwo\nTHREE\n");
+        assert_eq!(read("added.md"), "added\n");
+        assert!(read("notes.md").contains("<<<<<<< local"));
+        assert!(!merged.join("gone.md").exists());
+    }
+}
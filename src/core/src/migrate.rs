@@ -0,0 +1,185 @@
+use std::collections::btree_map::Entry;
+use std::path::{Path, PathBuf};
+
+use crate::agent::AgentConfig;
+use crate::config::{legacy_project_config_path, project_config_path, read_config, write_config};
+use crate::error::Result;
+use crate::install::{copy_dir, remove_path};
+use crate::lock::{lock_config, lock_scope};
+use crate::paths::{configured_store_dir, legacy_store_dir, relink_dir};
+
+/// What [`migrate_legacy`] moved, or would move on a dry run.
+#[derive(Debug, Clone, Default)]
+pub struct MigrationReport {
+    /// Skills moved from the legacy global store into the current one.
+    pub moved: Vec<String>,
+    /// Legacy skills left in place because the current store has one of the same name.
+    pub kept: Vec<String>,
+    /// Legacy store the skills came from, when one was found.
+    pub legacy_store: Option<PathBuf>,
+    /// Sources copied from a legacy project config into `.skil.toml`.
+    pub sources: Vec<String>,
+    /// Legacy project config merged into `.skil.toml`, when one was found.
+    pub legacy_config: Option<PathBuf>,
+}
+
+impl MigrationReport {
+    /// Returns whether no legacy state was found.
+    pub fn is_empty(&self) -> bool {
+        self.legacy_store.is_none() && self.legacy_config.is_none()
+    }
+}
+
+/// Moves state left by earlier releases into the current layout.
+///
+/// Skills in the legacy `~/.agents/skills` store move into the global store
+/// and agent symlinks are re-pointed; a project `.skillz.toml` is merged into
+/// `.skil.toml`. Nothing is overwritten: skills and sources that already exist
+/// in the current layout win. With `dry_run`, only reports what would change.
+pub fn migrate_legacy(agents: &[AgentConfig], dry_run: bool) -> Result<MigrationReport> {
+    let mut report = MigrationReport::default();
+    {
+        let _lock = lock_scope(true)?;
+        let legacy = legacy_store_dir();
+        let store = configured_store_dir()?;
+        if legacy.is_dir() && legacy != store {
+            migrate_skills(&legacy, &store, dry_run, &mut report)?;
+            if !dry_run {
+                for agent in agents {
+                    relink_dir(Path::new(&agent.global_skills_dir), &legacy, &store)?;
+                }
+            }
+            report.legacy_store = Some(legacy);
+        }
+    }
+
+    let legacy = legacy_project_config_path()?;
+    if legacy.is_file() {
+        let current = project_config_path()?;
+        let _lock = lock_config(&current)?;
+        merge_config(&legacy, &current, dry_run, &mut report)?;
+        report.legacy_config = Some(legacy);
+    }
+    Ok(report)
+}
+
+/// Moves skill folders from `legacy` into `store`, skipping names `store` has.
+fn migrate_skills(
+    legacy: &Path,
+    store: &Path,
+    dry_run: bool,
+    report: &mut MigrationReport,
+) -> Result<()> {
+    let mut entries: Vec<_> = std::fs::read_dir(legacy)?.collect::<std::io::Result<_>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let target = store.join(&name);
+        if target.exists() {
+            report.kept.push(name);
+            continue;
+        }
+        if !dry_run {
+            std::fs::create_dir_all(store)?;
+            if std::fs::rename(entry.path(), &target).is_err() {
+                // Different filesystems; fall back to copy and delete.
+                copy_dir(&entry.path(), &target)?;
+                remove_path(&entry.path())?;
+            }
+        }
+        report.moved.push(name);
+    }
+    if !dry_run && std::fs::read_dir(legacy)?.next().is_none() {
+        std::fs::remove_dir(legacy)?;
+    }
+    Ok(())
+}
+
+/// Adds sources from `legacy` that `current` lacks, then removes `legacy`.
+///
+/// Without a `current` config, `legacy` is simply renamed.
+fn merge_config(
+    legacy: &Path,
+    current: &Path,
+    dry_run: bool,
+    report: &mut MigrationReport,
+) -> Result<()> {
+    let old = read_config(legacy)?;
+    if !current.exists() {
+        report.sources.extend(old.sources.into_keys());
+        if !dry_run {
+            std::fs::rename(legacy, current)?;
+        }
+        return Ok(());
+    }
+    let mut config = read_config(current)?;
+    for (key, source) in old.sources {
+        if let Entry::Vacant(entry) = config.sources.entry(key) {
+            report.sources.push(entry.key().clone());
+            entry.insert(source);
+        }
+    }
+    if !dry_run {
+        write_config(current, &config)?;
+        std::fs::remove_file(legacy)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_legacy_skills_without_overwriting() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let legacy = dir.path().join("legacy");
+        let store = dir.path().join("store");
+        for name in ["old", "both"] {
+            std::fs::create_dir_all(legacy.join(name)).expect("mkdir");
+            std::fs::write(legacy.join(name).join("SKILL.md"), "legacy").expect("write");
+        }
+        std::fs::create_dir_all(store.join("both")).expect("mkdir");
+        std::fs::write(store.join("both").join("SKILL.md"), "current").expect("write");
+
+        let mut report = MigrationReport::default();
+        migrate_skills(&legacy, &store, true, &mut report).expect("dry run");
+        assert_eq!(report.moved, vec!["old"]);
+        assert!(legacy.join("old").is_dir());
+
+        let mut report = MigrationReport::default();
+        migrate_skills(&legacy, &store, false, &mut report).expect("migrate");
+        assert_eq!(report.moved, vec!["old"]);
+        assert_eq!(report.kept, vec!["both"]);
+        assert!(store.join("old").join("SKILL.md").is_file());
+        assert_eq!(
+            std::fs::read_to_string(store.join("both").join("SKILL.md")).expect("read"),
+            "current"
+        );
+        assert!(legacy.join("both").is_dir());
+    }
+
+    #[test]
+    fn merges_legacy_project_sources() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let legacy = dir.path().join(".skillz.toml");
+        let current = dir.path().join(".skil.toml");
+        std::fs::write(
+            &legacy,
+            "[source.\"a/old\"]\nskills = [\"one\"]\n\n[source.\"a/both\"]\nskills = [\"legacy\"]\n",
+        )
+        .expect("write");
+        std::fs::write(&current, "[source.\"a/both\"]\nskills = [\"current\"]\n").expect("write");
+
+        let mut report = MigrationReport::default();
+        merge_config(&legacy, &current, false, &mut report).expect("merge");
+        assert_eq!(report.sources, vec!["a/old"]);
+        assert!(!legacy.exists());
+        let config = read_config(&current).expect("read");
+        assert_eq!(config.sources["a/old"].skills, vec!["one"]);
+        assert_eq!(config.sources["a/both"].skills, vec!["current"]);
+    }
+}
@@ -0,0 +1,141 @@
+use std::path::Path;
+
+use crate::agent::AgentConfig;
+use crate::error::Result;
+use crate::skills::{DuplicateSkill, Skill};
+
+/// Stage of a repository clone reported to [`Observer::on_clone_progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloneStage {
+    Started,
+    Finished,
+    Failed,
+}
+
+/// An install target that already exists.
+#[derive(Debug)]
+pub struct Conflict<'a> {
+    pub skill: &'a Skill,
+    pub agent: &'a AgentConfig,
+    pub path: &'a Path,
+}
+
+/// A selected skill has the name of one already installed from another source.
+#[derive(Debug)]
+pub struct Homonym<'a> {
+    pub skill: &'a Skill,
+    /// Key of the source the installed skill came from.
+    pub source: &'a str,
+    /// Description of the installed skill, when its SKILL.md can be read.
+    pub description: Option<String>,
+}
+
+/// A selected skill is larger than the configured `max_skill_bytes`.
+#[derive(Debug)]
+pub struct LargeSkill<'a> {
+    pub skill: &'a Skill,
+    /// Bytes the install would copy, after ignore patterns.
+    pub bytes: u64,
+    pub limit: u64,
+}
+
+/// A tracked skill whose store copy was edited since it was installed.
+#[derive(Debug)]
+pub struct Drift<'a> {
+    /// The new upstream version of the skill.
+    pub skill: &'a Skill,
+    /// Key of the source the skill is tracked under.
+    pub source: &'a str,
+    /// Copy of the edited store folder.
+    pub local: &'a Path,
+    /// The files the update would install, with ignore patterns and overrides applied.
+    pub upstream: &'a Path,
+}
+
+/// How to handle a [`Drift`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftResolution {
+    /// Skip the skill and mark it as locally modified.
+    KeepLocal,
+    /// Replace the local edits with the upstream version.
+    TakeUpstream,
+    /// Three-way merge the local edits into the upstream version.
+    Merge,
+}
+
+/// How to handle a [`Conflict`], [`Homonym`], or [`LargeSkill`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    Overwrite,
+    Skip,
+}
+
+/// A question core operations need answered.
+#[derive(Debug)]
+pub enum Prompt<'a> {
+    /// No skills were requested; choose which of the discovered skills to install.
+    SelectSkills { skills: &'a [Skill] },
+}
+
+/// Answer to a [`Prompt`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    /// Use the non-interactive default (all skills for `SelectSkills`).
+    Default,
+    /// Names of the selected skills.
+    Skills(Vec<String>),
+}
+
+/// Receives events from [`Discovery`](crate::discovery::Discovery),
+/// [`Installer`](crate::installer::Installer), and [`Updater`](crate::updater::Updater).
+///
+/// Every method has a no-op default, so frontends only implement what they render.
+pub trait Observer {
+    /// A git source started, finished, or failed cloning.
+    fn on_clone_progress(&self, _url: &str, _stage: CloneStage) {}
+
+    /// Several folders of a source declare the same skill name; only the
+    /// first is offered.
+    fn on_duplicate_skill(&self, _duplicate: &DuplicateSkill) {}
+
+    /// Skills are about to be copied into agent directories.
+    fn on_install_started(&self, _skills: usize, _agents: usize) {}
+
+    /// One skill was installed for one agent.
+    fn on_skill_installed(&self, _skill: &Skill, _agent: &AgentConfig) {}
+
+    /// All selected skills were installed.
+    fn on_install_finished(&self, _skills: usize, _agents: usize) {}
+
+    /// An install target already exists. Defaults to overwriting it.
+    fn on_conflict(&self, _conflict: &Conflict<'_>) -> Result<ConflictResolution> {
+        Ok(ConflictResolution::Overwrite)
+    }
+
+    /// A selected skill would replace one installed from another source.
+    /// Defaults to keeping the installed one.
+    fn on_homonym(&self, _homonym: &Homonym<'_>) -> Result<ConflictResolution> {
+        Ok(ConflictResolution::Skip)
+    }
+
+    /// A selected skill exceeds the size limit. Defaults to installing it.
+    fn on_large_skill(&self, _large: &LargeSkill<'_>) -> Result<ConflictResolution> {
+        Ok(ConflictResolution::Overwrite)
+    }
+
+    /// A selected skill has local edits the install would overwrite.
+    /// Defaults to taking the upstream version.
+    fn on_drift(&self, _drift: &Drift<'_>) -> Result<DriftResolution> {
+        Ok(DriftResolution::TakeUpstream)
+    }
+
+    /// Asks the frontend a question. Defaults to [`Answer::Default`].
+    fn on_prompt(&self, _prompt: &Prompt<'_>) -> Result<Answer> {
+        Ok(Answer::Default)
+    }
+}
+
+/// Observer that ignores events and answers every prompt with the default.
+pub struct NoopObserver;
+
+impl Observer for NoopObserver {}
@@ -0,0 +1,234 @@
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::config::config_location;
+use crate::error::{Result, SkilError};
+use crate::git::apply_patch;
+
+/// Folder next to `.skil.toml` holding a project's overrides, one subfolder per skill.
+pub const OVERRIDES_DIR: &str = ".skil-overrides";
+
+/// Returns the overrides folder of a scope: [`OVERRIDES_DIR`] next to
+/// `.skil.toml`, or `overrides` next to the global config.
+pub fn overrides_dir(global: bool) -> Result<PathBuf> {
+    let config = std::path::absolute(config_location(global)?.path)?;
+    let parent = config.parent().unwrap_or(Path::new(""));
+    Ok(parent.join(if global { "overrides" } else { OVERRIDES_DIR }))
+}
+
+/// Override files of one skill, relative to its override folder, in the
+/// order [`apply_overrides`] applies them: markdown sections, then patches.
+pub fn skill_overrides(overrides: &Path, skill_name: &str) -> Result<Vec<PathBuf>> {
+    let root = overrides.join(skill_name);
+    if !root.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut sections = Vec::new();
+    let mut patches = Vec::new();
+    for entry in WalkDir::new(&root).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = entry.path().strip_prefix(&root).unwrap_or(entry.path());
+        match rel.extension().and_then(|ext| ext.to_str()) {
+            Some("md") => sections.push(rel.to_path_buf()),
+            Some("patch") if rel.parent() == Some(Path::new("")) => {
+                patches.push(rel.to_path_buf());
+            }
+            _ => {}
+        }
+    }
+    sections.extend(patches);
+    Ok(sections)
+}
+
+/// Applies the overrides of `skill_name` to its installed folder `dir`.
+///
+/// A markdown file replaces the same-titled sections of the skill file at the
+/// same path, or appends them when the skill lacks one. A `.patch` file at the
+/// top of the override folder is applied as a unified diff. Returns the
+/// applied override files.
+pub fn apply_overrides(overrides: &Path, skill_name: &str, dir: &Path) -> Result<Vec<PathBuf>> {
+    let files = skill_overrides(overrides, skill_name)?;
+    let root = overrides.join(skill_name);
+    for file in &files {
+        let source = root.join(file);
+        if file.extension().is_some_and(|ext| ext == "patch") {
+            apply_patch(dir, &source)?;
+            continue;
+        }
+        let target = dir.join(file);
+        let Ok(content) = std::fs::read_to_string(&target) else {
+            return Err(SkilError::Message(format!(
+                "Override {} has no matching file in {skill_name}",
+                source.display()
+            )));
+        };
+        let overlay = std::fs::read_to_string(&source)?;
+        std::fs::write(&target, replace_sections(&content, &overlay))?;
+    }
+    Ok(files)
+}
+
+/// A markdown section: its heading line and every line up to the next
+/// heading of the same or a higher level.
+struct Section {
+    level: usize,
+    title: String,
+    start: usize,
+    end: usize,
+}
+
+/// Replaces sections of `content` with the same-titled sections of `overlay`.
+///
+/// A section of `overlay` with only a heading removes the matching section.
+/// Text before the first heading of `overlay` is ignored.
+pub fn replace_sections(content: &str, overlay: &str) -> String {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let overlay_lines: Vec<&str> = overlay.lines().collect();
+    let overlay_sections = sections(&overlay_lines);
+    let mut covered = 0;
+    for section in &overlay_sections {
+        // Nested sections were replaced along with their parent.
+        if section.start < covered {
+            continue;
+        }
+        covered = section.end;
+        let mut replacement: Vec<String> = overlay_lines[section.start..section.end]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        let removes = replacement[1..].iter().all(|line| line.trim().is_empty());
+        let existing = sections(&lines)
+            .into_iter()
+            .find(|target| target.level == section.level && target.title == section.title);
+        match existing {
+            Some(target) => {
+                if removes {
+                    replacement.clear();
+                } else if target.end < lines.len() && !ends_blank(&replacement) {
+                    replacement.push(String::new());
+                }
+                lines.splice(target.start..target.end, replacement);
+            }
+            None if removes => {}
+            None => {
+                if !lines.is_empty() && !ends_blank(&lines) {
+                    lines.push(String::new());
+                }
+                lines.extend(replacement);
+            }
+        }
+    }
+    while ends_blank(&lines) {
+        lines.pop();
+    }
+    let mut result = lines.join("\n");
+    result.push('\n');
+    result
+}
+
+fn ends_blank(lines: &[String]) -> bool {
+    lines.last().is_some_and(|line| line.trim().is_empty())
+}
+
+/// ATX headings outside code fences, with the extent of their sections.
+fn sections<S: AsRef<str>>(lines: &[S]) -> Vec<Section> {
+    let mut headings = Vec::new();
+    let mut fence: Option<&str> = None;
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.as_ref().trim_start();
+        for marker in ["```", "~~~"] {
+            if trimmed.starts_with(marker) {
+                fence = match fence {
+                    Some(open) if open == marker => None,
+                    None => Some(marker),
+                    other => other,
+                };
+            }
+        }
+        if fence.is_some() {
+            continue;
+        }
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        let rest = &trimmed[level..];
+        if (1..=6).contains(&level) && (rest.is_empty() || rest.starts_with(' ')) {
+            headings.push((index, level, rest.trim().trim_end_matches('#').trim()));
+        }
+    }
+    headings
+        .iter()
+        .enumerate()
+        .map(|(i, &(start, level, title))| Section {
+            level,
+            title: title.to_string(),
+            start,
+            end: headings[i + 1..]
+                .iter()
+                .find(|(_, next, _)| *next <= level)
+                .map_or(lines.len(), |(index, _, _)| *index),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SKILL: &str = "---\nname: pdf\n---\n# PDF\n\nIntro.\n\n## Forms\n\nUse pdftk.\n\n### Flatten\n\nFlatten after.\n\n## Merge\n\n```sh\n# not a heading\n```\n";
+
+    #[test]
+    fn replaces_removes_and_appends_sections() {
+        let overlay = "Ignored preamble.\n\n## Forms\n\nUse our form filler.\n\n## Merge\n\n## Internal\n\nAsk #pdf-team.\n";
+        assert_eq!(
+            replace_sections(SKILL, overlay),
+            "---\nname: pdf\n---\n# PDF\n\nIntro.\n\n## Forms\n\nUse our form filler.\n\n## Internal\n\nAsk #pdf-team.\n"
+        );
+        // Headings inside code fences are not sections.
+        assert_eq!(
+            replace_sections(SKILL, "## not a heading\n\nx\n"),
+            format!("{SKILL}\n## not a heading\n\nx\n")
+        );
+    }
+
+    #[test]
+    fn applies_sections_then_patches() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let skill = dir.path().join("store/pdf");
+        std::fs::create_dir_all(&skill).expect("mkdir");
+        std::fs::write(skill.join("SKILL.md"), SKILL).expect("write");
+        let overrides = dir.path().join("overrides");
+        std::fs::create_dir_all(overrides.join("pdf")).expect("mkdir");
+        std::fs::write(
+            overrides.join("pdf/SKILL.md"),
+            "## Forms\n\nUse our form filler.\n",
+        )
+        .expect("write");
+        std::fs::write(
+            overrides.join("pdf/intro.patch"),
+            "--- a/SKILL.md\n+++ b/SKILL.md\n@@ -4,4 +4,4 @@\n # PDF\n \n-Intro.\n+Internal intro.\n \n",
+        )
+        .expect("write");
+
+        let applied = apply_overrides(&overrides, "pdf", &skill).expect("apply");
+        assert_eq!(
+            applied,
+            vec![PathBuf::from("SKILL.md"), PathBuf::from("intro.patch")]
+        );
+        let content = std::fs::read_to_string(skill.join("SKILL.md")).expect("read");
+        assert!(content.contains("Internal intro."), "{content}");
+        assert!(content.contains("Use our form filler."), "{content}");
+        assert!(!content.contains("Flatten"), "{content}");
+
+        assert!(
+            apply_overrides(&overrides, "missing", &skill)
+                .expect("none")
+                .is_empty()
+        );
+        std::fs::write(overrides.join("pdf/references.md"), "# X\n").expect("write");
+        let err = apply_overrides(&overrides, "pdf", &skill).expect_err("no target");
+        assert!(err.to_string().contains("no matching file"), "{err}");
+    }
+}
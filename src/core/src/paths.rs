@@ -0,0 +1,265 @@
+use std::path::{Path, PathBuf};
+
+use crate::agent::agent_configs;
+use crate::config::{config_location, read_config};
+use crate::error::Result;
+use crate::install::{copy_dir, create_symlink, remove_path};
+
+fn home_dir() -> PathBuf {
+    dirs::home_dir().unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Reads an XDG base directory variable, ignoring empty and relative values as
+/// the spec requires.
+fn xdg_dir(var: &str, fallback: &str) -> PathBuf {
+    std::env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .unwrap_or_else(|| home_dir().join(fallback))
+}
+
+/// Reads a path override such as `SKIL_STORE_DIR`, ignoring empty values.
+pub fn env_path(var: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// `$XDG_CONFIG_HOME`, defaulting to `~/.config`.
+pub fn config_home() -> PathBuf {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// `$XDG_DATA_HOME`, defaulting to `~/.local/share`.
+pub fn data_home() -> PathBuf {
+    xdg_dir("XDG_DATA_HOME", ".local/share")
+}
+
+/// Scratch directory for clones: `SKIL_CACHE_DIR`, else `$XDG_CACHE_HOME/skil`.
+pub fn cache_dir() -> PathBuf {
+    env_path("SKIL_CACHE_DIR").unwrap_or_else(|| xdg_dir("XDG_CACHE_HOME", ".cache").join("skil"))
+}
+
+/// Creates a scratch directory named `<prefix>...` in [`cache_dir`].
+///
+/// It is removed when dropped; `skil clean` removes ones left behind by
+/// interrupted commands.
+pub fn scratch_dir(prefix: &str) -> Result<tempfile::TempDir> {
+    let cache = cache_dir();
+    std::fs::create_dir_all(&cache)?;
+    Ok(tempfile::Builder::new().prefix(prefix).tempdir_in(&cache)?)
+}
+
+/// Global canonical store used before skil followed the XDG spec.
+pub fn legacy_store_dir() -> PathBuf {
+    home_dir().join(".agents").join("skills")
+}
+
+/// Returns the global canonical store, migrating the legacy store on first use.
+///
+/// `SKIL_STORE_DIR` wins and is used as is; otherwise `store_dir` from the
+/// global config, falling back to `$XDG_DATA_HOME/skil/skills`.
+pub fn global_store_dir() -> Result<PathBuf> {
+    if let Some(store) = env_path("SKIL_STORE_DIR") {
+        return Ok(store);
+    }
+    let store = configured_store_dir()?;
+    let legacy = legacy_store_dir();
+    if !store.exists() && legacy.is_dir() && legacy != store {
+        migrate_store(&legacy, &store)?;
+    }
+    Ok(store)
+}
+
+/// The global store [`global_store_dir`] resolves to, without migrating anything.
+pub fn configured_store_dir() -> Result<PathBuf> {
+    if let Some(store) = env_path("SKIL_STORE_DIR") {
+        return Ok(store);
+    }
+    Ok(read_config(&config_location(true)?.path)?
+        .store_dir
+        .unwrap_or_else(|| data_home().join("skil").join("skills")))
+}
+
+/// Moves the store from `from` to `to` and re-points agent symlinks into it.
+fn migrate_store(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if std::fs::rename(from, to).is_err() {
+        // Different filesystems; fall back to copy and delete.
+        copy_dir(from, to)?;
+        std::fs::remove_dir_all(from)?;
+    }
+
+    for agent in agent_configs() {
+        relink_dir(Path::new(&agent.global_skills_dir), from, to)?;
+    }
+    Ok(())
+}
+
+/// Re-points symlinks in `dir` that target `from` so they target `to` instead.
+pub(crate) fn relink_dir(dir: &Path, from: &Path, to: &Path) -> Result<()> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let link = entry?.path();
+        let Ok(target) = std::fs::read_link(&link) else {
+            continue;
+        };
+        if let Ok(rest) = target.strip_prefix(from) {
+            // The old link dangles now, so it must be removed explicitly.
+            remove_path(&link)?;
+            create_symlink(&to.join(rest), &link)?;
+        }
+    }
+    Ok(())
+}
+
+/// Serializes tests that read or change the environment.
+#[cfg(test)]
+static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Holds the environment for tests that only read it.
+#[cfg(test)]
+pub(crate) fn env_lock() -> std::sync::MutexGuard<'static, ()> {
+    ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Sets (or, for `None`, removes) `vars` for the duration of `f`, restoring
+/// them afterwards even when `f` panics.
+#[cfg(test)]
+pub(crate) fn with_env<T>(vars: &[(&str, Option<&Path>)], f: impl FnOnce() -> T) -> T {
+    struct Restore(Vec<(String, Option<std::ffi::OsString>)>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            for (var, value) in &self.0 {
+                // SAFETY: tests touching the environment hold `ENV_LOCK`.
+                unsafe {
+                    match value {
+                        Some(value) => std::env::set_var(var, value),
+                        None => std::env::remove_var(var),
+                    }
+                }
+            }
+        }
+    }
+
+    let _env = env_lock();
+    let _restore = Restore(
+        vars.iter()
+            .map(|(var, _)| (var.to_string(), std::env::var_os(var)))
+            .collect(),
+    );
+    for (var, value) in vars {
+        // SAFETY: as above.
+        unsafe {
+            match value {
+                Some(value) => std::env::set_var(var, value),
+                None => std::env::remove_var(var),
+            }
+        }
+    }
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::project_config_path;
+
+    #[cfg(unix)]
+    #[test]
+    fn migrating_store_moves_skills_and_relinks_agents() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let legacy = dir.path().join("legacy");
+        let store = dir.path().join("data").join("skil").join("skills");
+        let agent = dir.path().join("agent");
+        std::fs::create_dir_all(legacy.join("one")).expect("mkdir");
+        std::fs::write(legacy.join("one").join("SKILL.md"), "x").expect("write");
+        std::fs::create_dir_all(&agent).expect("mkdir");
+        std::os::unix::fs::symlink(legacy.join("one"), agent.join("one")).expect("symlink");
+
+        migrate_store(&legacy, &store).expect("migrate");
+        relink_dir(&agent, &legacy, &store).expect("relink");
+
+        assert!(!legacy.exists());
+        assert!(store.join("one").join("SKILL.md").is_file());
+        assert_eq!(
+            std::fs::read_link(agent.join("one")).expect("link"),
+            store.join("one")
+        );
+    }
+
+    #[test]
+    fn env_overrides_replace_default_locations() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let home = dir.path().join("home");
+        let lock_file = dir.path().join("team.toml");
+        let store = dir.path().join("store");
+        let cache = dir.path().join("cache");
+        let config = dir.path().join("config.toml");
+        let base = [
+            ("HOME", Some(home.as_path())),
+            ("XDG_DATA_HOME", None),
+            ("XDG_CACHE_HOME", None),
+            ("SKIL_CONFIG", Some(config.as_path())),
+        ];
+
+        let unset = [
+            base.as_slice(),
+            &[
+                ("SKIL_LOCK_FILE", None),
+                ("SKIL_STORE_DIR", None),
+                ("SKIL_CACHE_DIR", None),
+            ],
+        ]
+        .concat();
+        with_env(&unset, || {
+            let cwd = std::env::current_dir().expect("cwd");
+            assert_eq!(project_config_path().expect("path"), cwd.join(".skil.toml"));
+            assert_eq!(
+                configured_store_dir().expect("store"),
+                home.join(".local/share/skil/skills")
+            );
+            assert_eq!(cache_dir(), home.join(".cache/skil"));
+        });
+
+        let set = [
+            base.as_slice(),
+            &[
+                ("SKIL_LOCK_FILE", Some(&lock_file)),
+                ("SKIL_STORE_DIR", Some(&store)),
+                ("SKIL_CACHE_DIR", Some(&cache)),
+            ],
+        ]
+        .concat();
+        with_env(&set, || {
+            assert_eq!(project_config_path().expect("path"), lock_file);
+            assert_eq!(config_location(false).expect("location").path, lock_file);
+            assert_eq!(configured_store_dir().expect("store"), store);
+            assert_eq!(global_store_dir().expect("store"), store);
+            assert_eq!(cache_dir(), cache);
+        });
+
+        // Empty values count as unset.
+        let empty = [
+            base.as_slice(),
+            &[
+                ("SKIL_LOCK_FILE", Some(Path::new(""))),
+                ("SKIL_STORE_DIR", Some(Path::new(""))),
+                ("SKIL_CACHE_DIR", Some(Path::new(""))),
+            ],
+        ]
+        .concat();
+        with_env(&empty, || {
+            assert_eq!(
+                configured_store_dir().expect("store"),
+                home.join(".local/share/skil/skills")
+            );
+            assert_eq!(cache_dir(), home.join(".cache/skil"));
+        });
+    }
+}
@@ -0,0 +1,273 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{SkilConfig, find_source};
+use crate::error::{ErrorCode, Result, SkilError};
+use crate::install::{dir_hash, sanitize_name};
+use crate::skills::is_newer_version;
+
+/// Skills a team requires every checkout to have installed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Policy {
+    #[serde(default)]
+    pub require: Vec<Requirement>,
+}
+
+/// A source that must be installed, with the skills and revisions accepted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Requirement {
+    /// `owner/repo` or a git URL, matched like the config's source keys.
+    pub source: String,
+    /// Skills of the source that must be installed; any one will do when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    /// Oldest acceptable release tag.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_version: Option<String>,
+    /// Accepted installed checksums; a prefix such as a short hash is enough.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checksums: Vec<String>,
+}
+
+/// A problem with the installed skills: a requirement of a [`Policy`] they do
+/// not meet, or a store copy that changed since install.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    pub source: String,
+    /// The skill at fault, or `None` when the whole source is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill: Option<String>,
+    pub problem: String,
+}
+
+/// Reads a policy file.
+pub fn load_policy(path: &Path) -> Result<Policy> {
+    let content = std::fs::read_to_string(path).map_err(|err| {
+        SkilError::new(
+            ErrorCode::SourceNotFound,
+            format!("Cannot read policy {}: {err}", path.display()),
+        )
+    })?;
+    toml::from_str(&content).map_err(|err| {
+        SkilError::new(
+            ErrorCode::Parse,
+            format!("Invalid policy {}: {err}", path.display()),
+        )
+    })
+}
+
+/// Checks the sources tracked in `config` against `policy`; skills count as
+/// installed when their folder exists in the `store`.
+pub fn check_policy(policy: &Policy, config: &SkilConfig, store: &Path) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    for requirement in &policy.require {
+        let violation = |skill: Option<&str>, problem: String| Violation {
+            source: requirement.source.clone(),
+            skill: skill.map(str::to_string),
+            problem,
+        };
+        let Some(key) = find_source(config, &requirement.source) else {
+            violations.push(violation(None, "not installed".to_string()));
+            continue;
+        };
+        let source = &config.sources[key];
+        if let Some(min) = &requirement.min_version {
+            match &source.version {
+                Some(version) if !is_newer_version(min, version) => {}
+                Some(version) => violations.push(violation(
+                    None,
+                    format!("installed at {version}, older than {min}"),
+                )),
+                None => violations.push(violation(
+                    None,
+                    format!("installed at an untagged revision, need {min} or later"),
+                )),
+            }
+        }
+        if !requirement.checksums.is_empty() {
+            let accepted = source.checksum.as_ref().is_some_and(|checksum| {
+                requirement
+                    .checksums
+                    .iter()
+                    .any(|prefix| checksum.starts_with(prefix.as_str()))
+            });
+            if !accepted {
+                let installed = source.checksum.as_deref().unwrap_or("an unknown revision");
+                violations.push(violation(
+                    None,
+                    format!("installed at {installed}, not an accepted revision"),
+                ));
+            }
+        }
+        let installed = |name: &str| {
+            source.skills.iter().any(|s| s.eq_ignore_ascii_case(name))
+                && store.join(sanitize_name(name)).is_dir()
+        };
+        for skill in &requirement.skills {
+            if !installed(skill) {
+                violations.push(violation(Some(skill), "not installed".to_string()));
+            }
+        }
+        if requirement.skills.is_empty() && !source.skills.iter().any(|skill| installed(skill)) {
+            violations.push(violation(None, "no skills installed".to_string()));
+        }
+    }
+    violations
+}
+
+/// Checks that the store copy of each skill tracked in `config` still has the
+/// content hash recorded when it was installed, the comparison `update` uses
+/// to find local edits.
+///
+/// Skills marked modified by `skil edit`, and ones without a recorded hash,
+/// such as skills from local folders, are not checked.
+pub fn check_drift(config: &SkilConfig, store: &Path) -> Result<Vec<Violation>> {
+    let mut violations = Vec::new();
+    for (key, source) in &config.sources {
+        for skill in source.updatable_skills() {
+            let Some(recorded) = source
+                .folders
+                .get(&skill)
+                .and_then(|folder| folder.installed.as_deref())
+            else {
+                continue;
+            };
+            let dir = store.join(sanitize_name(&skill));
+            let problem = if !dir.is_dir() {
+                "missing from the store"
+            } else if dir_hash(&dir)? != recorded {
+                "changed since it was installed"
+            } else {
+                continue;
+            };
+            violations.push(Violation {
+                source: key.clone(),
+                skill: Some(skill),
+                problem: problem.to_string(),
+            });
+        }
+    }
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_missing_sources_skills_and_revisions() {
+        let store = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(store.path().join("review")).expect("mkdir");
+        std::fs::create_dir(store.path().join("pdf")).expect("mkdir");
+        let config: SkilConfig = toml::from_str(
+            r#"
+[source."https://github.com/team/skills.git"]
+checksum = "d99ba7198680e68f"
+version = "v1.1.0"
+skills = ["review", "deploy"]
+
+[source."https://github.com/anthropics/skills.git"]
+checksum = "abc123"
+skills = ["pdf"]
+"#,
+        )
+        .expect("config");
+        let policy: Policy = toml::from_str(
+            r#"
+[[require]]
+source = "team/skills"
+skills = ["review", "deploy"]
+min_version = "v1.2.0"
+
+[[require]]
+source = "anthropics/skills"
+checksums = ["abc"]
+
+[[require]]
+source = "other/skills"
+"#,
+        )
+        .expect("policy");
+
+        let problems: Vec<(String, Option<String>, String)> =
+            check_policy(&policy, &config, store.path())
+                .into_iter()
+                .map(|v| (v.source, v.skill, v.problem))
+                .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (
+                    "team/skills".to_string(),
+                    None,
+                    "installed at v1.1.0, older than v1.2.0".to_string()
+                ),
+                (
+                    "team/skills".to_string(),
+                    Some("deploy".to_string()),
+                    "not installed".to_string()
+                ),
+                (
+                    "other/skills".to_string(),
+                    None,
+                    "not installed".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_store_copies_changed_since_install() {
+        let store = tempfile::tempdir().expect("tempdir");
+        for skill in ["review", "pdf"] {
+            let dir = store.path().join(skill);
+            std::fs::create_dir(&dir).expect("mkdir");
+            std::fs::write(dir.join("SKILL.md"), skill).expect("write");
+        }
+        let hash = |skill: &str| dir_hash(&store.path().join(skill)).expect("hash");
+        let config: SkilConfig = toml::from_str(&format!(
+            r#"
+[source."https://github.com/team/skills.git"]
+skills = ["review", "pdf", "deploy"]
+
+[source."https://github.com/team/skills.git".folders]
+review = {{ path = "skills/review", hash = "t1", installed = "{}" }}
+pdf = {{ path = "skills/pdf", hash = "t2", installed = "{}" }}
+deploy = {{ path = "skills/deploy", hash = "t3", installed = "h3" }}
+"#,
+            hash("review"),
+            hash("pdf")
+        ))
+        .expect("config");
+        let problems = || -> Vec<(Option<String>, String)> {
+            check_drift(&config, store.path())
+                .expect("drift")
+                .into_iter()
+                .map(|v| (v.skill, v.problem))
+                .collect()
+        };
+        assert_eq!(
+            problems(),
+            vec![(
+                Some("deploy".to_string()),
+                "missing from the store".to_string()
+            )]
+        );
+
+        std::fs::write(store.path().join("pdf/SKILL.md"), "edited").expect("write");
+        assert_eq!(
+            problems(),
+            vec![
+                (
+                    Some("pdf".to_string()),
+                    "changed since it was installed".to_string()
+                ),
+                (
+                    Some("deploy".to_string()),
+                    "missing from the store".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static TIMINGS: Mutex<Vec<Timing>> = Mutex::new(Vec::new());
+
+/// Starts recording [`span`]s; until then they measure nothing.
+pub fn enable_profiling() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Time spent in one kind of work, such as cloning or copying.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub phase: &'static str,
+    pub calls: usize,
+    pub total: Duration,
+}
+
+/// Measures the work done until it is dropped.
+#[must_use = "a span measures until it is dropped"]
+pub struct Span {
+    phase: &'static str,
+    start: Option<Instant>,
+}
+
+/// Starts measuring work of `phase`, e.g. `let _span = span("git clone");`.
+pub fn span(phase: &'static str) -> Span {
+    Span {
+        phase,
+        start: ENABLED.load(Ordering::Relaxed).then(Instant::now),
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            record(
+                &mut TIMINGS.lock().unwrap_or_else(|err| err.into_inner()),
+                self.phase,
+                start.elapsed(),
+            );
+        }
+    }
+}
+
+/// Timings recorded so far, in the order their phases first ran.
+pub fn timings() -> Vec<Timing> {
+    TIMINGS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone()
+}
+
+fn record(timings: &mut Vec<Timing>, phase: &'static str, elapsed: Duration) {
+    match timings.iter_mut().find(|timing| timing.phase == phase) {
+        Some(timing) => {
+            timing.calls += 1;
+            timing.total += elapsed;
+        }
+        None => timings.push(Timing {
+            phase,
+            calls: 1,
+            total: elapsed,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_up_calls_per_phase_in_first_run_order() {
+        let mut timings = Vec::new();
+        record(&mut timings, "git clone", Duration::from_millis(300));
+        record(&mut timings, "copy", Duration::from_millis(5));
+        record(&mut timings, "git clone", Duration::from_millis(200));
+        assert_eq!(
+            timings,
+            vec![
+                Timing {
+                    phase: "git clone",
+                    calls: 2,
+                    total: Duration::from_millis(500)
+                },
+                Timing {
+                    phase: "copy",
+                    calls: 1,
+                    total: Duration::from_millis(5)
+                },
+            ]
+        );
+        assert!(span("copy").start.is_none());
+    }
+}
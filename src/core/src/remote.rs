@@ -0,0 +1,251 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::config::SkilSource;
+use crate::error::{ErrorCode, Result, SkilError};
+use crate::git::remote_revision;
+use crate::profile::span;
+use crate::source::{SourceType, parse_hosted_git_url};
+
+const GITHUB_API: &str = "https://api.github.com";
+const GITLAB_API: &str = "https://gitlab.com/api/v4";
+const CODEBERG_API: &str = "https://codeberg.org/api/v1";
+
+/// Returns the provider of a tracked source, inferring it from the key for older configs.
+pub fn source_type(source_key: &str, source: &SkilSource) -> SourceType {
+    source
+        .source_type
+        .unwrap_or_else(|| SourceType::from_url(source_key))
+}
+
+/// Returns the latest commit of a tracked source.
+///
+/// GitHub and GitLab sources ask the provider API; other providers, and API
+/// failures such as rate limits, fall back to `git ls-remote`.
+pub fn latest_revision(source_key: &str, source: &SkilSource) -> Result<String> {
+    let branch = source.branch.as_deref();
+    let owner_repo =
+        parse_hosted_git_url(source_key).and_then(|(_, _, owner_repo, _, _)| owner_repo);
+    let from_api = match (source_type(source_key, source), owner_repo) {
+        (SourceType::Github, Some(owner_repo)) => github_commit(&owner_repo, branch).ok(),
+        (SourceType::Gitlab, Some(owner_repo)) => gitlab_commit(&owner_repo, branch).ok(),
+        _ => None,
+    };
+    match from_api {
+        Some(revision) => Ok(revision),
+        None => remote_revision(source_key, branch),
+    }
+}
+
+/// Returns the git tree hash of a folder in a tracked source, or `None` when the
+/// provider has no tree API or the folder is missing.
+///
+/// `folder` is relative to the repository root, e.g. `skills/web-design`.
+/// `reference` is a branch, tag, or commit; `None` uses the tracked branch.
+pub fn fetch_skill_folder_hash(
+    source_key: &str,
+    source: &SkilSource,
+    folder: &str,
+    reference: Option<&str>,
+) -> Result<Option<String>> {
+    let folder = folder.trim_matches('/');
+    let Some(owner_repo) =
+        parse_hosted_git_url(source_key).and_then(|(_, _, owner_repo, _, _)| owner_repo)
+    else {
+        return Ok(None);
+    };
+    if folder.is_empty() {
+        return Ok(None);
+    }
+    let branch = reference.or(source.branch.as_deref());
+    let parent = folder
+        .rsplit_once('/')
+        .map(|(parent, _)| parent)
+        .unwrap_or("");
+
+    let entries: Vec<TreeEntry> = match source_type(source_key, source) {
+        SourceType::Github => {
+            let path = format!(
+                "/repos/{owner_repo}/git/trees/{}?recursive=1",
+                branch.unwrap_or("HEAD")
+            );
+            send(github_get(&path)?)?.json::<GithubTree>()?.tree
+        }
+        SourceType::Gitlab => {
+            let mut path = format!("/repository/tree?per_page=100&path={parent}");
+            if let Some(branch) = branch {
+                path.push_str(&format!("&ref={branch}"));
+            }
+            send(gitlab_get(&owner_repo, &path)?)?.json()?
+        }
+        SourceType::Codeberg => {
+            let mut url = format!("{CODEBERG_API}/repos/{owner_repo}/contents/{parent}");
+            if let Some(branch) = branch {
+                url.push_str(&format!("?ref={branch}"));
+            }
+            send(http_client()?.get(url))?.json()?
+        }
+        SourceType::Git => return Ok(None),
+    };
+    Ok(find_tree_hash(&entries, folder))
+}
+
+/// Tree listing entry shared by the GitHub, GitLab and Gitea/Forgejo APIs.
+#[derive(Debug, Deserialize)]
+struct TreeEntry {
+    path: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(alias = "id")]
+    sha: String,
+}
+
+#[derive(Deserialize)]
+struct GithubTree {
+    tree: Vec<TreeEntry>,
+}
+
+fn find_tree_hash(entries: &[TreeEntry], folder: &str) -> Option<String> {
+    entries
+        .iter()
+        .find(|entry| entry.path == folder && matches!(entry.kind.as_str(), "tree" | "dir"))
+        .map(|entry| entry.sha.clone())
+}
+
+/// HTTP client for provider APIs.
+pub(crate) fn http_client() -> Result<reqwest::blocking::Client> {
+    Ok(reqwest::blocking::Client::builder()
+        .user_agent(concat!("skil/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(10))
+        .build()?)
+}
+
+/// Sends a request and fails on non-success statuses.
+pub(crate) fn send(
+    request: reqwest::blocking::RequestBuilder,
+) -> Result<reqwest::blocking::Response> {
+    let _span = span("http");
+    let res = request.send()?;
+    if !res.status().is_success() {
+        return Err(SkilError::new(
+            ErrorCode::Network,
+            format!("{} returned {}", res.url(), res.status()),
+        ));
+    }
+    Ok(res)
+}
+
+/// Authenticated GitHub API request, using `GITHUB_TOKEN` when set.
+pub(crate) fn github_get(path: &str) -> Result<reqwest::blocking::RequestBuilder> {
+    let mut request = http_client()?.get(format!("{GITHUB_API}{path}"));
+    if let Ok(token) = std::env::var("GITHUB_TOKEN")
+        && !token.is_empty()
+    {
+        request = request.bearer_auth(token);
+    }
+    Ok(request)
+}
+
+/// GitLab API request for a project, using `GITLAB_TOKEN` when set.
+pub(crate) fn gitlab_get(
+    owner_repo: &str,
+    path: &str,
+) -> Result<reqwest::blocking::RequestBuilder> {
+    let project = owner_repo.replace('/', "%2F");
+    let mut request = http_client()?.get(format!("{GITLAB_API}/projects/{project}{path}"));
+    if let Ok(token) = std::env::var("GITLAB_TOKEN")
+        && !token.is_empty()
+    {
+        request = request.header("PRIVATE-TOKEN", token);
+    }
+    Ok(request)
+}
+
+fn github_commit(owner_repo: &str, branch: Option<&str>) -> Result<String> {
+    let path = format!("/repos/{owner_repo}/commits/{}", branch.unwrap_or("HEAD"));
+    let res = send(github_get(&path)?.header("Accept", "application/vnd.github.sha"))?;
+    Ok(res.text()?.trim().to_string())
+}
+
+#[derive(Deserialize)]
+struct GitlabCommit {
+    id: String,
+}
+
+fn gitlab_commit(owner_repo: &str, branch: Option<&str>) -> Result<String> {
+    let mut path = "/repository/commits?per_page=1".to_string();
+    if let Some(branch) = branch {
+        path.push_str(&format!("&ref_name={branch}"));
+    }
+    let commits: Vec<GitlabCommit> = send(gitlab_get(owner_repo, &path)?)?.json()?;
+    commits
+        .into_iter()
+        .next()
+        .map(|commit| commit.id)
+        .ok_or_else(|| SkilError::new(ErrorCode::GitFailed, "Could not resolve remote revision"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(source_type: Option<SourceType>) -> SkilSource {
+        SkilSource {
+            source_type,
+            branch: None,
+            subpath: None,
+            subpaths: Vec::new(),
+            checksum: None,
+            version: None,
+            updated_at: None,
+            skills: vec![],
+            folders: Default::default(),
+            history: Vec::new(),
+            modified: Vec::new(),
+            available: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn finds_folder_hash_in_provider_listings() {
+        let github: GithubTree = serde_json::from_str(
+            r#"{"tree":[{"path":"skills","type":"tree","sha":"aaa"},{"path":"skills/one","type":"tree","sha":"bbb"},{"path":"skills/one/SKILL.md","type":"blob","sha":"ccc"}]}"#,
+        )
+        .expect("github");
+        assert_eq!(
+            find_tree_hash(&github.tree, "skills/one").as_deref(),
+            Some("bbb")
+        );
+        assert_eq!(find_tree_hash(&github.tree, "skills/one/SKILL.md"), None);
+
+        let gitlab: Vec<TreeEntry> = serde_json::from_str(
+            r#"[{"id":"ddd","name":"one","type":"tree","path":"skills/one","mode":"040000"}]"#,
+        )
+        .expect("gitlab");
+        assert_eq!(
+            find_tree_hash(&gitlab, "skills/one").as_deref(),
+            Some("ddd")
+        );
+
+        let gitea: Vec<TreeEntry> = serde_json::from_str(
+            r#"[{"name":"one","path":"skills/one","sha":"eee","type":"dir","size":0}]"#,
+        )
+        .expect("gitea");
+        assert_eq!(find_tree_hash(&gitea, "skills/one").as_deref(), Some("eee"));
+    }
+
+    #[test]
+    fn source_type_prefers_recorded_value_and_falls_back_to_key() {
+        let key = "https://gitlab.com/example/skills.git";
+        assert_eq!(source_type(key, &entry(None)), SourceType::Gitlab);
+        assert_eq!(
+            source_type(key, &entry(Some(SourceType::Git))),
+            SourceType::Git
+        );
+        assert_eq!(
+            source_type("https://example.com/repo.git", &entry(None)),
+            SourceType::Git
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::skills::{Skill, split_frontmatter};
+
+/// Marker opening the section managed by `skil render`.
+pub const SECTION_BEGIN: &str = "<!-- skil:begin -->";
+/// Marker closing the section managed by `skil render`.
+pub const SECTION_END: &str = "<!-- skil:end -->";
+
+/// Where an installed skill came from, recorded as a comment above it.
+#[derive(Debug, Clone, Default)]
+pub struct Provenance {
+    pub source: Option<String>,
+    pub revision: Option<String>,
+}
+
+/// Renders skills as one marked markdown section with a `###` heading per skill.
+///
+/// Headings inside each skill are demoted so they nest under the skill heading.
+pub fn render_section(skills: &[(Skill, Provenance)]) -> String {
+    let mut out = format!("{SECTION_BEGIN}\n## Skills\n");
+    for (skill, provenance) in skills {
+        let mut comment = format!("skill: {}", skill.name);
+        if let Some(source) = &provenance.source {
+            comment.push_str(&format!(" source: {source}"));
+        }
+        if let Some(revision) = &provenance.revision {
+            comment.push_str(&format!(" revision: {revision}"));
+        }
+        let (_, body) = split_frontmatter(&skill.raw_content);
+        out.push_str(&format!(
+            "\n<!-- {comment} -->\n### {}\n\n{}\n",
+            skill.name, skill.description
+        ));
+        let body = demote_headings(body.trim(), 2);
+        if !body.is_empty() {
+            out.push_str(&format!("\n{body}\n"));
+        }
+    }
+    out.push_str(SECTION_END);
+    out.push('\n');
+    out
+}
+
+/// Replaces the marked section in `existing`, or appends it when missing.
+pub fn replace_section(existing: &str, section: &str) -> String {
+    if let Some(start) = existing.find(SECTION_BEGIN)
+        && let Some(end) = existing[start..].find(SECTION_END)
+    {
+        let end = start + end + SECTION_END.len();
+        let rest = existing[end..]
+            .strip_prefix('\n')
+            .unwrap_or(&existing[end..]);
+        return format!("{}{section}{rest}", &existing[..start]);
+    }
+    if existing.trim().is_empty() {
+        return section.to_string();
+    }
+    format!("{}\n\n{section}", existing.trim_end())
+}
+
+/// Adds `levels` to every ATX heading outside code fences, capped at `######`.
+fn demote_headings(body: &str, levels: usize) -> String {
+    let mut in_fence = false;
+    let lines: Vec<String> = body
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+            }
+            let depth = line.chars().take_while(|c| *c == '#').count();
+            let is_heading = depth > 0 && line[depth..].starts_with(' ');
+            if in_fence || !is_heading {
+                return line.to_string();
+            }
+            format!("{}{}", "#".repeat((depth + levels).min(6)), &line[depth..])
+        })
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn renders_skills_with_provenance_and_demoted_headings() {
+        let skill = Skill {
+            name: "lint".to_string(),
+            description: "Lint code".to_string(),
+            path: PathBuf::from("lint"),
+            raw_content: "---\nname: lint\ndescription: Lint code\n---\n\n# Lint\n\nRun it.\n\n```sh\n# comment\n```\n".to_string(),
+            resources: Vec::new(),
+            found_by: String::new(),
+            file: None,
+        };
+        let provenance = Provenance {
+            source: Some("owner/repo".to_string()),
+            revision: Some("v1".to_string()),
+        };
+        let section = render_section(&[(skill, provenance)]);
+        assert_eq!(
+            section,
+            "<!-- skil:begin -->\n## Skills\n\n<!-- skill: lint source: owner/repo revision: v1 -->\n### lint\n\nLint code\n\n### Lint\n\nRun it.\n\n```sh\n# comment\n```\n<!-- skil:end -->\n"
+        );
+    }
+
+    #[test]
+    fn replaces_existing_section_or_appends() {
+        let section = "<!-- skil:begin -->\nnew\n<!-- skil:end -->\n";
+        assert_eq!(replace_section("", section), section);
+        assert_eq!(
+            replace_section("# Notes\n", section),
+            format!("# Notes\n\n{section}")
+        );
+        let existing = "# Notes\n\n<!-- skil:begin -->\nold\n<!-- skil:end -->\n\nFooter\n";
+        assert_eq!(
+            replace_section(existing, section),
+            format!("# Notes\n\n{section}\nFooter\n")
+        );
+    }
+}
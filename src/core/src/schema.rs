@@ -0,0 +1,402 @@
+use std::fmt;
+use std::ops::Range;
+
+use toml::Spanned;
+use toml::de::{DeTable, DeValue};
+
+use crate::config::SkilConfig;
+
+/// Expected shape of a config value.
+enum Kind {
+    Bool,
+    Integer,
+    Str,
+    /// A string naming one of the listed values.
+    OneOf(&'static [&'static str]),
+    StrList,
+    Table(&'static [Field]),
+    /// A table with arbitrary keys whose values all have one shape.
+    Map(&'static Kind),
+    /// An array of tables, written as `[[name]]`.
+    TableList(&'static [Field]),
+}
+
+type Field = (&'static str, Kind);
+
+const CONFIG: &[Field] = &[
+    ("version", Kind::Integer),
+    ("telemetry", Kind::Bool),
+    ("store_dir", Kind::Str),
+    ("source", Kind::Map(&Kind::Table(SOURCE))),
+    ("rule", Kind::TableList(RULE)),
+    ("budget", Kind::Map(&Kind::Table(BUDGET))),
+    ("ignore", Kind::StrList),
+    ("skill_dirs", Kind::StrList),
+    ("skill_files", Kind::StrList),
+    ("file_skills", Kind::Bool),
+    ("max_skill_bytes", Kind::Integer),
+    ("agents", Kind::StrList),
+    ("copy", Kind::Bool),
+    ("answers", Kind::Map(&Kind::Table(ANSWERS))),
+];
+
+const SOURCE: &[Field] = &[
+    (
+        "source_type",
+        Kind::OneOf(&["github", "gitlab", "codeberg", "git"]),
+    ),
+    ("branch", Kind::Str),
+    ("subpath", Kind::Str),
+    ("subpaths", Kind::StrList),
+    ("checksum", Kind::Str),
+    ("version", Kind::Str),
+    ("updated_at", Kind::Str),
+    ("skills", Kind::StrList),
+    ("folders", Kind::Map(&Kind::Table(FOLDER))),
+    ("history", Kind::TableList(REVISION)),
+    ("modified", Kind::StrList),
+    ("available", Kind::StrList),
+];
+
+const FOLDER: &[Field] = &[
+    ("path", Kind::Str),
+    ("hash", Kind::Str),
+    ("installed", Kind::Str),
+];
+
+const REVISION: &[Field] = &[("checksum", Kind::Str), ("version", Kind::Str)];
+
+const RULE: &[Field] = &[
+    ("skills", Kind::StrList),
+    ("tags", Kind::StrList),
+    ("only_agents", Kind::StrList),
+    ("exclude_agents", Kind::StrList),
+];
+
+const ANSWERS: &[Field] = &[
+    ("agents", Kind::StrList),
+    ("global", Kind::Bool),
+    ("copy", Kind::Bool),
+];
+
+const BUDGET: &[Field] = &[("max_bytes", Kind::Integer), ("max_tokens", Kind::Integer)];
+
+/// A problem found in a config file, located by 1-based line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    pub hint: Option<String>,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+        if let Some(hint) = &self.hint {
+            write!(f, " ({hint})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks config file content for syntax errors, unknown keys, and values of
+/// the wrong type, sorted by position.
+pub fn validate_config(content: &str) -> Vec<ConfigIssue> {
+    let document = match DeTable::parse(content) {
+        Ok(document) => document,
+        Err(err) => return vec![error_issue(content, &err)],
+    };
+    let mut issues = Vec::new();
+    check_table(content, document.get_ref(), CONFIG, "", &mut issues);
+    if issues.is_empty()
+        && let Err(err) = toml::from_str::<SkilConfig>(content)
+    {
+        issues.push(error_issue(content, &err));
+    }
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues
+}
+
+/// Locates a deserialization error in `content`.
+pub fn error_issue(content: &str, err: &toml::de::Error) -> ConfigIssue {
+    issue(
+        content,
+        err.span().unwrap_or(0..0),
+        err.message().trim().to_string(),
+        None,
+    )
+}
+
+fn check_table(
+    content: &str,
+    table: &DeTable<'_>,
+    fields: &[Field],
+    prefix: &str,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    for (key, value) in table.iter() {
+        let name = key.get_ref().as_ref();
+        let path = if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix}.{name}")
+        };
+        match fields.iter().find(|(field, _)| *field == name) {
+            Some((_, kind)) => check_value(content, value, kind, &path, issues),
+            None => {
+                let hint = closest(name, fields.iter().map(|(field, _)| *field))
+                    .map(|field| format!("did you mean `{field}`?"));
+                issues.push(issue(
+                    content,
+                    key.span(),
+                    format!("unknown key `{path}`"),
+                    hint,
+                ));
+            }
+        }
+    }
+}
+
+fn check_value(
+    content: &str,
+    value: &Spanned<DeValue<'_>>,
+    kind: &Kind,
+    path: &str,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    let found = value.get_ref();
+    let expected = match (kind, found) {
+        (Kind::Bool, DeValue::Boolean(_))
+        | (Kind::Integer, DeValue::Integer(_))
+        | (Kind::Str, DeValue::String(_)) => return,
+        (Kind::OneOf(values), DeValue::String(text)) => {
+            if !values.contains(&text.as_ref()) {
+                issues.push(issue(
+                    content,
+                    value.span(),
+                    format!("`{path}` cannot be \"{text}\""),
+                    Some(format!("use one of: {}", values.join(", "))),
+                ));
+            }
+            return;
+        }
+        (Kind::StrList, DeValue::Array(items)) => {
+            for item in items.iter() {
+                check_value(content, item, &Kind::Str, &format!("{path}[]"), issues);
+            }
+            return;
+        }
+        (Kind::Table(fields), DeValue::Table(table)) => {
+            check_table(content, table, fields, path, issues);
+            return;
+        }
+        (Kind::Map(inner), DeValue::Table(table)) => {
+            for (key, item) in table.iter() {
+                let path = format!("{path}.{}", quote_key(key.get_ref()));
+                check_value(content, item, inner, &path, issues);
+            }
+            return;
+        }
+        (Kind::TableList(fields), DeValue::Array(items)) => {
+            for item in items.iter() {
+                match item.get_ref() {
+                    DeValue::Table(table) => check_table(content, table, fields, path, issues),
+                    other => issues.push(issue(
+                        content,
+                        item.span(),
+                        format!(
+                            "`{path}` entries must be tables, found {}",
+                            other.type_str()
+                        ),
+                        None,
+                    )),
+                }
+            }
+            return;
+        }
+        (Kind::Bool, _) => "a boolean (true or false)",
+        (Kind::Integer, _) => "an integer",
+        (Kind::Str | Kind::OneOf(_), _) => "a string",
+        (Kind::StrList, _) => "an array of strings",
+        (Kind::Table(_) | Kind::Map(_), _) => "a table",
+        (Kind::TableList(_), _) => "an array of tables",
+    };
+    let hint = match (kind, found) {
+        (Kind::StrList, DeValue::String(text)) => Some(format!("write [\"{text}\"]")),
+        (Kind::Bool, DeValue::String(text)) if matches!(text.as_ref(), "true" | "false") => {
+            Some(format!("drop the quotes: {text}"))
+        }
+        _ => None,
+    };
+    issues.push(issue(
+        content,
+        value.span(),
+        format!("`{path}` must be {expected}, found {}", found.type_str()),
+        hint,
+    ));
+}
+
+/// Quotes a map key for display when it is not a bare TOML key.
+fn quote_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        key.to_string()
+    } else {
+        format!("\"{key}\"")
+    }
+}
+
+fn issue(content: &str, span: Range<usize>, message: String, hint: Option<String>) -> ConfigIssue {
+    let before = &content[..span.start.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rfind('\n')
+        .map_or(before, |newline| &before[newline + 1..])
+        .chars()
+        .count()
+        + 1;
+    ConfigIssue {
+        line,
+        column,
+        message,
+        hint,
+    }
+}
+
+/// The candidate within two edits of `name`, if any.
+fn closest<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitute.min(row[j] + 1).min(previous + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_a_written_config() {
+        let content = r#"
+telemetry = true
+ignore = ["fixtures"]
+agents = ["claude-code"]
+copy = false
+
+[source."owner/repo"]
+source_type = "github"
+skills = ["review"]
+
+[source."owner/repo".folders.review]
+path = "skills/review"
+hash = "abc"
+
+[[source."owner/repo".history]]
+checksum = "def"
+
+[[rule]]
+tags = ["experimental"]
+exclude_agents = ["codex"]
+
+[budget.claude-code]
+max_tokens = 50000
+"#;
+        assert_eq!(validate_config(content), Vec::new());
+    }
+
+    #[test]
+    fn reports_unknown_keys_and_wrong_types_with_lines() {
+        let content = r#"telemetry = "true"
+stor_dir = "/tmp"
+
+[source."owner/repo"]
+skills = "review"
+source_type = "svn"
+
+[budget.cursor]
+max_tokens = "lots"
+"#;
+        let issues = validate_config(content);
+        let found: Vec<(usize, usize, &str, Option<&str>)> = issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue.line,
+                    issue.column,
+                    issue.message.as_str(),
+                    issue.hint.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    1,
+                    13,
+                    "`telemetry` must be a boolean (true or false), found string",
+                    Some("drop the quotes: true")
+                ),
+                (
+                    2,
+                    1,
+                    "unknown key `stor_dir`",
+                    Some("did you mean `store_dir`?")
+                ),
+                (
+                    5,
+                    10,
+                    "`source.\"owner/repo\".skills` must be an array of strings, found string",
+                    Some("write [\"review\"]")
+                ),
+                (
+                    6,
+                    15,
+                    "`source.\"owner/repo\".source_type` cannot be \"svn\"",
+                    Some("use one of: github, gitlab, codeberg, git")
+                ),
+                (
+                    9,
+                    14,
+                    "`budget.cursor.max_tokens` must be an integer, found string",
+                    None
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_syntax_and_missing_field_errors() {
+        let issues = validate_config("telemetry = \n");
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].line, issues[0].column), (1, 13));
+
+        let issues = validate_config("[source.\"owner/repo\"]\nbranch = \"main\"\n");
+        assert_eq!(issues.len(), 1);
+        assert!(
+            issues[0].message.contains("skills"),
+            "{}",
+            issues[0].message
+        );
+    }
+}
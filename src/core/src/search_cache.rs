@@ -0,0 +1,113 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::error::Result;
+use crate::git::blob_hash;
+use crate::paths::cache_dir;
+
+/// Cached search responses younger than this are used without asking the
+/// registry again.
+pub const SEARCH_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Skill names saved for a source younger than this are completed without
+/// cloning the source again.
+pub const SKILL_NAMES_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A registry response saved by an earlier search.
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub body: String,
+    /// Time since the response was fetched.
+    pub age: Duration,
+}
+
+impl CachedResponse {
+    /// Whether the response is younger than [`SEARCH_CACHE_TTL`].
+    pub fn is_fresh(&self) -> bool {
+        self.age < SEARCH_CACHE_TTL
+    }
+}
+
+/// Folder in [`cache_dir`] holding search responses, one file per URL.
+pub fn search_cache_dir() -> PathBuf {
+    cache_dir().join("search")
+}
+
+/// The saved response for `url`, however old; `None` when there is none.
+pub fn cached_response(url: &str) -> Option<CachedResponse> {
+    read(&search_cache_dir(), url, SystemTime::now())
+}
+
+/// Saves the response body fetched from `url`.
+pub fn cache_response(url: &str, body: &str) -> Result<()> {
+    write(&search_cache_dir(), url, body)
+}
+
+/// Folder in [`cache_dir`] holding the skill names of sources, one file per
+/// source key.
+pub fn skill_names_cache_dir() -> PathBuf {
+    cache_dir().join("skill-names")
+}
+
+/// Skill names saved for `source_key` less than [`SKILL_NAMES_TTL`] ago.
+pub fn cached_skill_names(source_key: &str) -> Option<Vec<String>> {
+    let cached = read(&skill_names_cache_dir(), source_key, SystemTime::now())?;
+    if cached.age >= SKILL_NAMES_TTL {
+        return None;
+    }
+    serde_json::from_str(&cached.body).ok()
+}
+
+/// Saves the names of the skills found in the source `source_key`.
+pub fn cache_skill_names(source_key: &str, names: &[String]) -> Result<()> {
+    write(
+        &skill_names_cache_dir(),
+        source_key,
+        &serde_json::to_string(names)?,
+    )
+}
+
+fn entry_path(dir: &Path, url: &str) -> Result<PathBuf> {
+    Ok(dir.join(format!("{}.json", blob_hash(url.as_bytes())?)))
+}
+
+fn read(dir: &Path, url: &str, now: SystemTime) -> Option<CachedResponse> {
+    let path = entry_path(dir, url).ok()?;
+    let modified = std::fs::metadata(&path).ok()?.modified().ok()?;
+    let body = std::fs::read_to_string(&path).ok()?;
+    Some(CachedResponse {
+        body,
+        age: now.duration_since(modified).unwrap_or_default(),
+    })
+}
+
+fn write(dir: &Path, url: &str, body: &str) -> Result<()> {
+    std::fs::create_dir_all(dir)?;
+    // Written aside and renamed so a concurrent search never reads half a file.
+    let temp = tempfile::NamedTempFile::new_in(dir)?;
+    std::fs::write(temp.path(), body)?;
+    temp.persist(entry_path(dir, url)?)
+        .map_err(|err| err.error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_one_response_per_url_and_reports_age() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let url = "https://skills.sh/api/search?q=pdf&limit=10";
+        assert!(read(dir.path(), url, SystemTime::now()).is_none());
+
+        write(dir.path(), url, "{\"skills\":[]}").expect("write");
+        write(dir.path(), "https://skills.sh/api/search?q=docx", "{}").expect("write");
+        let cached = read(dir.path(), url, SystemTime::now()).expect("cached");
+        assert_eq!(cached.body, "{\"skills\":[]}");
+        assert!(cached.is_fresh());
+
+        let later = SystemTime::now() + SEARCH_CACHE_TTL * 2;
+        assert!(!read(dir.path(), url, later).expect("cached").is_fresh());
+    }
+}
@@ -1,9 +1,12 @@
 use std::path::{Path, PathBuf};
 
+use gix::bstr::ByteSlice;
 use serde::Deserialize;
 use walkdir::WalkDir;
 
+use crate::config;
 use crate::error::Result;
+use crate::profile::span;
 
 /// Parsed skill metadata and file location.
 #[derive(Debug, Clone)]
@@ -12,70 +15,466 @@ pub struct Skill {
     pub description: String,
     pub path: PathBuf,
     pub raw_content: String,
+    /// Files in the skill's [`RESOURCE_DIRS`], relative to `path`.
+    pub resources: Vec<PathBuf>,
+    /// Search pattern that found the skill, such as `skills/*/SKILL.md`,
+    /// relative to the searched folder; empty when not discovered.
+    pub found_by: String,
+    /// The skill's instructions when they are not `SKILL.md` in `path`: a file
+    /// with another of [`DiscoverOptions::skill_files`], or for a skill made
+    /// of one markdown file, `path` itself.
+    pub file: Option<PathBuf>,
 }
 
+/// Conventional folders for files a skill ships next to its SKILL.md.
+pub const RESOURCE_DIRS: &[&str] = &["references", "scripts", "assets"];
+
 /// Frontmatter structure for SKILL.md.
 #[derive(Debug, Deserialize)]
 pub struct Frontmatter {
     pub name: Option<String>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// `true`, or the reason the skill should no longer be used.
+    #[serde(default)]
+    pub deprecated: Option<Deprecated>,
+    /// Skill to use instead: a skill name in the same source, or `<source>@<skill>`.
+    #[serde(default, rename = "superseded-by")]
+    pub superseded_by: Option<String>,
+    /// Oldest skil release the skill works with, such as `0.3.0`.
+    #[serde(default, rename = "min-skil-version")]
+    pub min_skil_version: Option<String>,
+}
+
+/// The `deprecated` frontmatter value.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Deprecated {
+    Flag(bool),
+    Reason(String),
+}
+
+/// Why a skill is deprecated and what replaces it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Deprecation {
+    pub reason: Option<String>,
+    pub successor: Option<Successor>,
+}
+
+/// A skill declared as the replacement of a deprecated one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Successor {
+    /// Source to install from; the deprecated skill's own source when `None`.
+    pub source: Option<String>,
+    pub skill: String,
+}
+
+impl Frontmatter {
+    /// Returns the deprecation, if the skill is deprecated or superseded.
+    pub fn deprecation(&self) -> Option<Deprecation> {
+        let reason = match &self.deprecated {
+            Some(Deprecated::Flag(true)) => None,
+            Some(Deprecated::Reason(reason)) if !reason.trim().is_empty() => {
+                Some(reason.trim().to_string())
+            }
+            _ if self.superseded_by.is_some() => None,
+            _ => return None,
+        };
+        let successor = self
+            .superseded_by
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| match value.rsplit_once('@') {
+                Some((source, skill)) => Successor {
+                    source: Some(source.to_string()),
+                    skill: skill.to_string(),
+                },
+                None => Successor {
+                    source: None,
+                    skill: value.to_string(),
+                },
+            });
+        Some(Deprecation { reason, successor })
+    }
+
+    /// Returns the declared `min-skil-version` when it is newer than this build.
+    pub fn unsupported_version(&self) -> Option<&str> {
+        self.min_skil_version
+            .as_deref()
+            .map(str::trim)
+            .filter(|required| is_newer_version(required, env!("CARGO_PKG_VERSION")))
+    }
+}
+
+/// Returns true when `latest` is a higher `major.minor.patch` than `current`.
+///
+/// Pre-release suffixes are ignored for the comparison, so `1.2.0-rc.1` never
+/// upgrades an installed `1.2.0`.
+pub fn is_newer_version(latest: &str, current: &str) -> bool {
+    fn parts(version: &str) -> Vec<u64> {
+        version
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or("")
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    }
+    parts(latest) > parts(current)
+}
+
+/// How [`discover_skills`] searches a source.
+#[derive(Debug, Clone, Default)]
+pub struct DiscoverOptions {
+    /// Keep searching below a root that is itself a skill.
+    pub full_depth: bool,
+    /// Also search folders left out by `.gitignore` and `.ignore` files.
+    pub no_ignore: bool,
+    /// Levels of folders searched below the root when no skill is found in
+    /// the usual places; defaults to [`DEFAULT_MAX_DEPTH`].
+    pub max_depth: Option<usize>,
+    /// Folders, relative to the root, whose subfolders are searched for skills
+    /// like `skills/`.
+    pub skill_dirs: Vec<PathBuf>,
+    /// Names a skill folder's instructions file may have, most preferred
+    /// first; [`DEFAULT_SKILL_FILES`] when empty.
+    pub skill_files: Vec<String>,
+    /// Also take each markdown file with a `name` and `description` in its
+    /// frontmatter as a skill by itself.
+    pub file_skills: bool,
+}
+
+impl DiscoverOptions {
+    /// Adds the `skill_dirs`, `skill_files`, and `file_skills` settings of the
+    /// global config, plus the project configs unless `global`.
+    pub fn with_config(mut self, global: bool) -> Result<Self> {
+        self.skill_dirs.extend(config::skill_dirs(global)?);
+        if self.skill_files.is_empty() {
+            self.skill_files = config::skill_files(global)?;
+        }
+        self.file_skills |= config::file_skills(global)?;
+        Ok(self)
+    }
+
+    fn skill_files(&self) -> Vec<&str> {
+        if self.skill_files.is_empty() {
+            DEFAULT_SKILL_FILES.to_vec()
+        } else {
+            self.skill_files.iter().map(String::as_str).collect()
+        }
+    }
+}
+
+/// Depth of the discovery walk when [`DiscoverOptions::max_depth`] is unset.
+pub const DEFAULT_MAX_DEPTH: usize = 5;
+
+/// Names of a skill folder's instructions file when config sets none.
+/// Installed skills always get a `SKILL.md`.
+pub const DEFAULT_SKILL_FILES: &[&str] = &["SKILL.md", "skill.md", "SKILL.mdx"];
+
+/// Skills found by [`discover_skills`].
+#[derive(Debug, Clone, Default)]
+pub struct DiscoveredSkills {
+    /// One skill per name, sorted by path.
+    pub skills: Vec<Skill>,
+    /// Names declared by more than one folder, sorted by name.
+    pub duplicates: Vec<DuplicateSkill>,
+}
+
+/// A skill name declared by several folders of a source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateSkill {
+    pub name: String,
+    /// Folders declaring the name, relative to the source root; the first is
+    /// the one kept.
+    pub paths: Vec<PathBuf>,
 }
 
 /// Discovers skills in a repository or directory tree.
+///
+/// When several folders declare the same name, the first one found is kept:
+/// the root, then the conventional skill folders in order, each read in name
+/// order.
 pub fn discover_skills(
     base: &Path,
     subpath: Option<&Path>,
-    full_depth: bool,
-) -> Result<Vec<Skill>> {
+    options: &DiscoverOptions,
+) -> Result<DiscoveredSkills> {
+    let _span = span("skill discovery");
     let search_root = subpath
         .map(|p| base.join(p))
         .unwrap_or_else(|| base.to_path_buf());
 
-    let mut skills = Vec::new();
-    let mut seen = std::collections::HashSet::new();
-
-    if has_skill_md(&search_root)
-        && let Some(skill) = parse_skill_md(&search_root.join("SKILL.md"))?
-    {
-        seen.insert(skill.name.clone());
-        skills.push(skill);
-        if !full_depth {
-            return Ok(skills);
+    let names = options.skill_files();
+    let mut found = Vec::new();
+
+    if let Some(file) = skill_file(&search_root, &names) {
+        let pattern = file_name(&file);
+        found.extend(parse_candidates(&[Candidate {
+            file,
+            pattern,
+            single: false,
+        }])?);
+        if !found.is_empty() && !options.full_depth {
+            return Ok(keep_first_by_name(base, found));
         }
     }
 
-    let priority_dirs = priority_skill_dirs(&search_root);
-    for dir in priority_dirs {
-        if !dir.exists() {
+    let mut ignores = IgnoreFiles::new(base, !options.no_ignore);
+    let mut candidates = Vec::new();
+    let dirs = priority_skill_dirs(&search_root)
+        .into_iter()
+        .chain(options.skill_dirs.iter().map(|dir| search_root.join(dir)));
+    for dir in dirs {
+        if !dir.is_dir() || ignores.is_ignored(&dir, true) {
             continue;
         }
-        for entry in std::fs::read_dir(&dir)? {
-            let entry = entry?;
-            if entry.path().is_dir()
-                && has_skill_md(&entry.path())
-                && let Some(skill) = parse_skill_md(&entry.path().join("SKILL.md"))?
-                && seen.insert(skill.name.clone())
+        let prefix = match dir.strip_prefix(&search_root) {
+            Ok(relative) if !relative.as_os_str().is_empty() => {
+                format!("{}/", relative.display())
+            }
+            _ => String::new(),
+        };
+        let mut paths = std::fs::read_dir(&dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        paths.sort();
+        for path in paths {
+            if let Some(file) = skill_file(&path, &names)
+                && !ignores.is_ignored(&path, true)
+            {
+                let pattern = format!("{prefix}*/{}", file_name(&file));
+                candidates.push(Candidate {
+                    file,
+                    pattern,
+                    single: false,
+                });
+            } else if options.file_skills
+                && is_file_skill_candidate(&path, &names)
+                && !ignores.is_ignored(&path, false)
             {
-                skills.push(skill);
+                candidates.push(Candidate {
+                    file: path,
+                    pattern: format!("{prefix}*.md"),
+                    single: true,
+                });
             }
         }
     }
+    found.extend(parse_candidates(&candidates)?);
 
-    if skills.is_empty() {
-        for entry in WalkDir::new(&search_root)
-            .max_depth(5)
+    if found.is_empty() {
+        let candidates: Vec<Candidate> = WalkDir::new(&search_root)
+            .max_depth(options.max_depth.unwrap_or(DEFAULT_MAX_DEPTH))
+            .sort_by_file_name()
             .into_iter()
+            .filter_entry(|entry| {
+                let is_dir = entry.file_type().is_dir();
+                entry.depth() == 0
+                    || !(is_dir && is_pruned_dir(entry.path(), &names)
+                        || ignores.is_ignored(entry.path(), is_dir))
+            })
             .filter_map(|e| e.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_str()?;
+                if names.contains(&name) {
+                    let pattern = format!("**/{name}");
+                    Some(Candidate {
+                        file: entry.into_path(),
+                        pattern,
+                        single: false,
+                    })
+                } else if options.file_skills && is_file_skill_candidate(entry.path(), &names) {
+                    Some(Candidate {
+                        file: entry.into_path(),
+                        pattern: "**/*.md".to_string(),
+                        single: true,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+        found.extend(parse_candidates(&candidates)?);
+    }
+
+    // Markdown files inside a skill folder, such as its references, belong
+    // to that skill.
+    let folders: Vec<PathBuf> = found
+        .iter()
+        .filter(|skill| skill.file.as_ref() != Some(&skill.path))
+        .map(|skill| skill.path.clone())
+        .collect();
+    found.retain(|skill| {
+        skill.file.as_ref() != Some(&skill.path)
+            || !folders.iter().any(|folder| skill.path.starts_with(folder))
+    });
+
+    Ok(keep_first_by_name(base, found))
+}
+
+/// Keeps the first of `found` skills with each name, recording the folders
+/// of the others as duplicates. A folder found twice, e.g. through
+/// [`DiscoverOptions::skill_dirs`] naming `skills`, is not a duplicate.
+fn keep_first_by_name(base: &Path, found: Vec<Skill>) -> DiscoveredSkills {
+    let relative = |path: &Path| path.strip_prefix(base).unwrap_or(path).to_path_buf();
+    let mut kept: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    let mut skills: Vec<Skill> = Vec::new();
+    let mut duplicates: Vec<DuplicateSkill> = Vec::new();
+    for skill in found {
+        let Some(&index) = kept.get(&skill.name) else {
+            kept.insert(skill.name.clone(), skills.len());
+            skills.push(skill);
+            continue;
+        };
+        let first = &skills[index];
+        if first.path == skill.path {
+            continue;
+        }
+        match duplicates
+            .iter_mut()
+            .find(|duplicate| duplicate.name == skill.name)
         {
-            if entry.file_name() == "SKILL.md"
-                && let Some(skill) = parse_skill_md(entry.path())?
-                && seen.insert(skill.name.clone())
-            {
-                skills.push(skill);
+            Some(duplicate) => duplicate.paths.push(relative(&skill.path)),
+            None => duplicates.push(DuplicateSkill {
+                name: skill.name.clone(),
+                paths: vec![relative(&first.path), relative(&skill.path)],
+            }),
+        }
+    }
+    skills.sort_by(|a, b| a.path.cmp(&b.path));
+    duplicates.sort_by(|a, b| a.name.cmp(&b.name));
+    DiscoveredSkills { skills, duplicates }
+}
+
+/// Folders the discovery walk never enters: version control, dependencies,
+/// and build output, which can hold many files but no skills of the source.
+///
+/// A folder with a skill file of its own, such as `skills/build/SKILL.md`, is
+/// a skill rather than build output and is kept.
+fn is_pruned_dir(dir: &Path, names: &[&str]) -> bool {
+    dir.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| {
+            matches!(
+                name,
+                ".git"
+                    | "node_modules"
+                    | "target"
+                    | "dist"
+                    | "build"
+                    | ".next"
+                    | ".turbo"
+                    | ".cache"
+            )
+        })
+        && skill_file(dir, names).is_none()
+}
+
+/// `.gitignore` and `.ignore` files of a source, matched the way git does.
+///
+/// A folder's files are read the first time a path below it is checked.
+struct IgnoreFiles {
+    root: PathBuf,
+    enabled: bool,
+    search: gix::ignore::Search,
+    loaded: std::collections::HashSet<PathBuf>,
+}
+
+impl IgnoreFiles {
+    /// Ignore files under `root`; none are read unless `enabled`.
+    fn new(root: &Path, enabled: bool) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            enabled,
+            search: Default::default(),
+            loaded: Default::default(),
+        }
+    }
+
+    /// Returns whether `path`, or a folder holding it, is ignored by the
+    /// ignore files of the folders above it.
+    fn is_ignored(&mut self, path: &Path, is_dir: bool) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let Ok(rel) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        let components: Vec<String> = rel
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().to_string())
+            .collect();
+        let mut dir = self.root.clone();
+        for (index, name) in components.iter().enumerate() {
+            self.load(&dir);
+            dir.push(name);
+            let rel = components[..=index].join("/");
+            let matched = self.search.pattern_matching_relative_path(
+                rel.as_bytes().as_bstr(),
+                Some(is_dir || index + 1 < components.len()),
+                gix::glob::pattern::Case::Sensitive,
+            );
+            if matched.is_some_and(|matched| !matched.pattern.is_negative()) {
+                return true;
             }
         }
+        false
     }
 
+    fn load(&mut self, dir: &Path) {
+        if !self.loaded.insert(dir.to_path_buf()) {
+            return;
+        }
+        for name in [".gitignore", ".ignore"] {
+            let file = dir.join(name);
+            if let Ok(bytes) = std::fs::read(&file) {
+                self.search.add_patterns_buffer(
+                    &bytes,
+                    file,
+                    Some(&self.root),
+                    gix::ignore::search::Ignore::default(),
+                );
+            }
+        }
+    }
+}
+
+/// A file [`discover_skills`] found that may hold a skill.
+struct Candidate {
+    file: PathBuf,
+    /// Search pattern that found the file, see [`Skill::found_by`].
+    pattern: String,
+    /// The file is a skill by itself rather than the instructions of its folder.
+    single: bool,
+}
+
+/// Parses `candidates` on all available cores, keeping their order and
+/// leaving out files that are not valid skills.
+fn parse_candidates(candidates: &[Candidate]) -> Result<Vec<Skill>> {
+    let threads = std::thread::available_parallelism().map_or(1, |threads| threads.get());
+    let chunk_size = candidates.len().div_ceil(threads).max(1);
+    let chunks: Vec<Result<Vec<Option<Skill>>>> = std::thread::scope(|scope| {
+        let workers: Vec<_> = candidates
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(parse_candidate).collect()))
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+    let mut skills = Vec::new();
+    for chunk in chunks {
+        skills.extend(chunk?.into_iter().flatten());
+    }
     Ok(skills)
 }
 
@@ -99,6 +498,60 @@ pub fn select_skills(skills: &[Skill], requested: &[String]) -> Vec<Skill> {
     selected
 }
 
+/// Groups skills by the folder holding them, named relative to the deepest
+/// folder all of those share, such as `frontend` for `skills/frontend/button`.
+///
+/// Groups are sorted by name and keep discovery order inside. Skills directly
+/// in the shared folder form a group with an empty name, which is the only
+/// group when every skill sits in the same folder.
+pub fn group_skills(skills: &[Skill]) -> Vec<(String, Vec<&Skill>)> {
+    let parents: Vec<&Path> = skills
+        .iter()
+        .map(|skill| skill.path.parent().unwrap_or(Path::new("")))
+        .collect();
+    let common = parents
+        .iter()
+        .skip(1)
+        .fold(
+            parents.first().map(|p| p.to_path_buf()),
+            |common, parent| {
+                common.map(|common| {
+                    common
+                        .components()
+                        .zip(parent.components())
+                        .take_while(|(a, b)| a == b)
+                        .map(|(a, _)| a)
+                        .collect()
+                })
+            },
+        )
+        .unwrap_or_default();
+    let mut groups: std::collections::BTreeMap<String, Vec<&Skill>> = Default::default();
+    for (skill, parent) in skills.iter().zip(parents) {
+        let name = parent
+            .strip_prefix(&common)
+            .unwrap_or(parent)
+            .to_string_lossy()
+            .replace('\\', "/");
+        groups.entry(name).or_default().push(skill);
+    }
+    groups.into_iter().collect()
+}
+
+/// Returns requested names that match none of the skills (case-insensitive).
+pub fn missing_skills(skills: &[Skill], requested: &[String]) -> Vec<String> {
+    requested
+        .iter()
+        .filter(|name| name.as_str() != "*")
+        .filter(|name| {
+            !skills
+                .iter()
+                .any(|skill| skill.name.eq_ignore_ascii_case(name))
+        })
+        .cloned()
+        .collect()
+}
+
 /// Returns a prioritized list of directories to scan for skills.
 fn priority_skill_dirs(base: &Path) -> Vec<PathBuf> {
     vec![
@@ -131,9 +584,49 @@ fn priority_skill_dirs(base: &Path) -> Vec<PathBuf> {
     ]
 }
 
-/// Checks if a directory contains a SKILL.md file.
-fn has_skill_md(dir: &Path) -> bool {
-    dir.join("SKILL.md").is_file()
+fn parse_candidate(candidate: &Candidate) -> Result<Option<Skill>> {
+    let file = &candidate.file;
+    Ok(parse_skill_md(file)?.map(|skill| {
+        if candidate.single {
+            Skill {
+                path: file.clone(),
+                resources: Vec::new(),
+                found_by: candidate.pattern.clone(),
+                file: Some(file.clone()),
+                ..skill
+            }
+        } else {
+            Skill {
+                found_by: candidate.pattern.clone(),
+                file: (file_name(file) != "SKILL.md").then(|| file.clone()),
+                ..skill
+            }
+        }
+    }))
+}
+
+/// The instructions file of a skill folder: the first of `names` in `dir`.
+fn skill_file(dir: &Path, names: &[&str]) -> Option<PathBuf> {
+    names
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|file| file.is_file())
+}
+
+/// Whether `path` is a markdown file that may be a skill by itself: not a
+/// skill folder's instructions file and not a README.
+fn is_file_skill_candidate(path: &Path, names: &[&str]) -> bool {
+    let name = file_name(path);
+    path.extension().is_some_and(|ext| ext == "md")
+        && !names.contains(&name.as_str())
+        && !name.eq_ignore_ascii_case("README.md")
+        && path.is_file()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
 }
 
 /// Parses a SKILL.md file into a Skill if valid.
@@ -150,14 +643,31 @@ pub fn parse_skill_md(path: &Path) -> Result<Option<Skill>> {
         return Ok(None);
     }
 
+    let dir = path.parent().unwrap_or(Path::new("."));
     Ok(Some(Skill {
         name,
         description,
-        path: path.parent().unwrap_or(Path::new(".")).to_path_buf(),
+        path: dir.to_path_buf(),
         raw_content: content,
+        resources: skill_resources(dir),
+        found_by: String::new(),
+        file: None,
     }))
 }
 
+/// Lists the files in a skill folder's [`RESOURCE_DIRS`], sorted.
+pub fn skill_resources(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = RESOURCE_DIRS
+        .iter()
+        .flat_map(|name| WalkDir::new(dir.join(name)).into_iter())
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.path().strip_prefix(dir).ok().map(PathBuf::from))
+        .collect();
+    files.sort();
+    files
+}
+
 /// Parses YAML frontmatter from SKILL.md content.
 pub fn parse_frontmatter(content: &str) -> Result<Option<Frontmatter>> {
     let mut lines = content.lines();
@@ -183,6 +693,24 @@ pub fn parse_frontmatter(content: &str) -> Result<Option<Frontmatter>> {
     Ok(Some(data))
 }
 
+/// Splits content into the frontmatter block, if any, and the body.
+pub fn split_frontmatter(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, content)
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::Path;
@@ -190,6 +718,13 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    fn full_depth() -> DiscoverOptions {
+        DiscoverOptions {
+            full_depth: true,
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn parses_frontmatter() {
         let content = "---\nname: Test Skill\ndescription: Does stuff\n---\n\n# Test";
@@ -212,6 +747,137 @@ mod tests {
         assert!(!err.to_string().is_empty());
     }
 
+    #[test]
+    fn lists_resource_files() {
+        let dir = tempdir().expect("tempdir");
+        for file in [
+            "references/api.md",
+            "scripts/run.sh",
+            "assets/img/logo.png",
+            "notes/x.md",
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).expect("mkdir");
+            std::fs::write(path, "x").expect("write");
+        }
+        std::fs::write(
+            dir.path().join("SKILL.md"),
+            "---\nname: a\ndescription: b\n---\n",
+        )
+        .expect("write");
+
+        let skill = parse_skill_md(&dir.path().join("SKILL.md"))
+            .expect("ok")
+            .expect("some");
+        assert_eq!(
+            skill.resources,
+            vec![
+                PathBuf::from("assets/img/logo.png"),
+                PathBuf::from("references/api.md"),
+                PathBuf::from("scripts/run.sh"),
+            ]
+        );
+    }
+
+    #[test]
+    fn is_newer_version_compares_numeric_parts() {
+        assert!(is_newer_version("0.10.0", "0.9.3"));
+        assert!(is_newer_version("1.0.0", "0.99.0"));
+        assert!(is_newer_version("v1.0", "0.9.3"));
+        assert!(!is_newer_version("0.9.3", "0.9.3"));
+        assert!(!is_newer_version("0.9.3-rc.1", "0.9.3"));
+        assert!(!is_newer_version("0.9.2", "0.9.3"));
+    }
+
+    #[test]
+    fn flags_min_skil_version_newer_than_this_build() {
+        let unsupported = |version: &str| {
+            parse_frontmatter(&format!(
+                "---\nname: a\ndescription: b\nmin-skil-version: \"{version}\"\n---\n"
+            ))
+            .expect("ok")
+            .expect("some")
+            .unsupported_version()
+            .map(str::to_string)
+        };
+        assert_eq!(unsupported("999.0.0"), Some("999.0.0".to_string()));
+        assert_eq!(unsupported(env!("CARGO_PKG_VERSION")), None);
+        assert_eq!(unsupported("0.0.0"), None);
+    }
+
+    #[test]
+    fn groups_skills_by_folder_below_the_shared_one() {
+        let skill = |path: &str| Skill {
+            name: path.rsplit('/').next().expect("name").to_string(),
+            description: String::new(),
+            path: PathBuf::from(path),
+            raw_content: String::new(),
+            resources: Vec::new(),
+            found_by: String::new(),
+            file: None,
+        };
+        let names = |skills: &[Skill]| -> Vec<(String, Vec<String>)> {
+            group_skills(skills)
+                .into_iter()
+                .map(|(group, skills)| (group, skills.iter().map(|s| s.name.clone()).collect()))
+                .collect()
+        };
+        let nested = [
+            skill("/repo/skills/frontend/button"),
+            skill("/repo/skills/backend/api"),
+            skill("/repo/skills/frontend/forms"),
+            skill("/repo/skills/pdf"),
+        ];
+        assert_eq!(
+            names(&nested),
+            vec![
+                (String::new(), vec!["pdf".to_string()]),
+                ("backend".to_string(), vec!["api".to_string()]),
+                (
+                    "frontend".to_string(),
+                    vec!["button".to_string(), "forms".to_string()]
+                ),
+            ]
+        );
+        let flat = [skill("/repo/skills/a"), skill("/repo/skills/b")];
+        assert_eq!(names(&flat).len(), 1);
+        assert!(group_skills(&[]).is_empty());
+    }
+
+    #[test]
+    fn parses_deprecation() {
+        let deprecation = |yaml: &str| {
+            parse_frontmatter(&format!("---\nname: a\ndescription: b\n{yaml}---\n"))
+                .expect("ok")
+                .expect("some")
+                .deprecation()
+        };
+        assert_eq!(deprecation(""), None);
+        assert_eq!(deprecation("deprecated: false\n"), None);
+        assert_eq!(
+            deprecation("deprecated: true\n"),
+            Some(Deprecation::default())
+        );
+        assert_eq!(
+            deprecation("deprecated: Use pdf-v2\nsuperseded-by: pdf-v2\n"),
+            Some(Deprecation {
+                reason: Some("Use pdf-v2".to_string()),
+                successor: Some(Successor {
+                    source: None,
+                    skill: "pdf-v2".to_string()
+                }),
+            })
+        );
+        assert_eq!(
+            deprecation("superseded-by: git@github.com:owner/repo.git@pdf\n")
+                .and_then(|deprecation| deprecation.successor),
+            Some(Successor {
+                source: Some("git@github.com:owner/repo.git".to_string()),
+                skill: "pdf".to_string()
+            })
+        );
+    }
+
     #[test]
     fn selects_skills_case_insensitively() {
         let skills = vec![
@@ -220,18 +886,28 @@ mod tests {
                 description: "One".to_string(),
                 path: Path::new("one").to_path_buf(),
                 raw_content: String::new(),
+                resources: Vec::new(),
+                found_by: String::new(),
+                file: None,
             },
             Skill {
                 name: "go-style".to_string(),
                 description: "Two".to_string(),
                 path: Path::new("two").to_path_buf(),
                 raw_content: String::new(),
+                resources: Vec::new(),
+                found_by: String::new(),
+                file: None,
             },
         ];
 
         let selected = select_skills(&skills, &[String::from("WEB-DESIGN")]);
         assert_eq!(selected.len(), 1);
         assert_eq!(selected[0].name, "Web-Design");
+
+        let requested = [String::from("web-design"), String::from("renamed")];
+        assert_eq!(missing_skills(&skills, &requested), vec!["renamed"]);
+        assert!(missing_skills(&skills, &[String::from("*")]).is_empty());
     }
 
     #[test]
@@ -245,7 +921,9 @@ mod tests {
         )
         .expect("write skill");
 
-        let discovered = discover_skills(dir.path(), None, true).expect("discover");
+        let discovered = discover_skills(dir.path(), None, &full_depth())
+            .expect("discover")
+            .skills;
         assert_eq!(discovered.len(), 1);
         assert_eq!(discovered[0].name, "MySkill");
         assert_eq!(discovered[0].description, "Desc");
@@ -259,12 +937,18 @@ mod tests {
                 description: "A".to_string(),
                 path: Path::new("a").to_path_buf(),
                 raw_content: String::new(),
+                resources: Vec::new(),
+                found_by: String::new(),
+                file: None,
             },
             Skill {
                 name: "b".to_string(),
                 description: "B".to_string(),
                 path: Path::new("b").to_path_buf(),
                 raw_content: String::new(),
+                resources: Vec::new(),
+                found_by: String::new(),
+                file: None,
             },
         ];
 
@@ -308,9 +992,273 @@ mod tests {
         std::fs::write(root_skill.join("SKILL.md"), content).expect("write root");
         std::fs::write(nested_skill.join("SKILL.md"), content).expect("write nested");
 
-        let discovered = discover_skills(dir.path(), None, true).expect("discover");
+        let discovered = discover_skills(dir.path(), None, &full_depth()).expect("discover");
+        assert_eq!(discovered.skills.len(), 1);
+        assert_eq!(discovered.skills[0].path, root_skill);
+        assert_eq!(
+            discovered.duplicates,
+            vec![DuplicateSkill {
+                name: "SameName".to_string(),
+                paths: vec![
+                    PathBuf::from("root-skill"),
+                    PathBuf::from("skills/nested-skill")
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn discover_skills_returns_skills_sorted_by_path() {
+        let dir = tempdir().expect("tempdir");
+        for folder in [
+            "skills/zeta",
+            "skills/alpha",
+            ".claude/skills/mid",
+            "skills/beta",
+        ] {
+            let skill_dir = dir.path().join(folder);
+            std::fs::create_dir_all(&skill_dir).expect("mkdir");
+            std::fs::write(
+                skill_dir.join("SKILL.md"),
+                format!(
+                    "---\nname: {}\ndescription: Desc\n---\n",
+                    folder.rsplit('/').next().unwrap_or_default()
+                ),
+            )
+            .expect("write skill");
+        }
+
+        let discovered = discover_skills(dir.path(), None, &DiscoverOptions::default())
+            .expect("discover")
+            .skills;
+        let names: Vec<&str> = discovered.iter().map(|skill| skill.name.as_str()).collect();
+        assert_eq!(names, vec!["mid", "alpha", "beta", "zeta"]);
+    }
+
+    #[test]
+    fn discovery_walk_skips_dependency_and_build_folders() {
+        let dir = tempdir().expect("tempdir");
+        for (folder, name) in [
+            ("packages/app/skill", "kept"),
+            ("node_modules/pkg/skill", "dependency"),
+            ("packages/app/target/skill", "build-output"),
+        ] {
+            let skill_dir = dir.path().join(folder);
+            std::fs::create_dir_all(&skill_dir).expect("mkdir");
+            std::fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {name}\ndescription: Desc\n---\n"),
+            )
+            .expect("write skill");
+        }
+
+        let discovered = discover_skills(dir.path(), None, &DiscoverOptions::default())
+            .expect("discover")
+            .skills;
+        let names: Vec<&str> = discovered.iter().map(|skill| skill.name.as_str()).collect();
+        assert_eq!(names, vec!["kept"]);
+        let discovered = discover_skills(
+            dir.path(),
+            Some(Path::new("node_modules")),
+            &DiscoverOptions::default(),
+        )
+        .expect("discover")
+        .skills;
         assert_eq!(discovered.len(), 1);
-        assert_eq!(discovered[0].name, "SameName");
+    }
+
+    #[test]
+    fn discovery_walk_keeps_skills_named_like_build_folders() {
+        let dir = tempdir().expect("tempdir");
+        for (folder, name) in [
+            ("packages/app/skills/build", "build"),
+            ("packages/app/skills/build/target/skill", "build-output"),
+            ("tools/dist", "dist"),
+        ] {
+            let skill_dir = dir.path().join(folder);
+            std::fs::create_dir_all(&skill_dir).expect("mkdir");
+            std::fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {name}\ndescription: Desc\n---\n"),
+            )
+            .expect("write skill");
+        }
+
+        let discovered = discover_skills(dir.path(), None, &DiscoverOptions::default())
+            .expect("discover")
+            .skills;
+        let names: Vec<&str> = discovered.iter().map(|skill| skill.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "dist"]);
+    }
+
+    #[test]
+    fn discovery_searches_configured_depth_and_skill_dirs() {
+        let dir = tempdir().expect("tempdir");
+        for (folder, name) in [
+            ("a/b/c/d/e/deep", "deep"),
+            ("ai/prompts/review", "review"),
+            ("skills/pdf", "pdf"),
+        ] {
+            let skill_dir = dir.path().join(folder);
+            std::fs::create_dir_all(&skill_dir).expect("mkdir");
+            std::fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {name}\ndescription: Desc\n---\n"),
+            )
+            .expect("write skill");
+        }
+
+        let found = |options: &DiscoverOptions| -> Vec<(String, String)> {
+            discover_skills(dir.path(), Some(Path::new("a")), options)
+                .expect("discover")
+                .skills
+                .into_iter()
+                .map(|skill| (skill.name, skill.found_by))
+                .collect()
+        };
+        assert!(found(&DiscoverOptions::default()).is_empty());
+        let deeper = DiscoverOptions {
+            max_depth: Some(6),
+            ..Default::default()
+        };
+        assert_eq!(
+            found(&deeper),
+            vec![("deep".to_string(), "**/SKILL.md".to_string())]
+        );
+
+        let options = DiscoverOptions {
+            skill_dirs: vec![PathBuf::from("ai/prompts")],
+            ..Default::default()
+        };
+        let discovered = discover_skills(dir.path(), None, &options)
+            .expect("discover")
+            .skills;
+        let found: Vec<(&str, &str)> = discovered
+            .iter()
+            .map(|skill| (skill.name.as_str(), skill.found_by.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("review", "ai/prompts/*/SKILL.md"),
+                ("pdf", "skills/*/SKILL.md")
+            ]
+        );
+    }
+
+    #[test]
+    fn discovery_reads_alternative_skill_files_and_file_skills() {
+        let dir = tempdir().expect("tempdir");
+        let skill = |name: &str| format!("---\nname: {name}\ndescription: Desc\n---\n");
+        for (file, name) in [
+            ("skills/lower/skill.md", "lower"),
+            ("skills/mdx/SKILL.mdx", "mdx"),
+            ("skills/mdx/references/forms.md", "forms"),
+            ("skills/review.md", "review"),
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+            std::fs::write(&path, skill(name)).expect("write");
+        }
+        std::fs::write(dir.path().join("skills/README.md"), skill("readme")).expect("write");
+
+        let found = |options: &DiscoverOptions| -> Vec<(String, String, Option<PathBuf>)> {
+            discover_skills(dir.path(), None, options)
+                .expect("discover")
+                .skills
+                .into_iter()
+                .map(|skill| {
+                    let file = skill
+                        .file
+                        .map(|file| file.strip_prefix(dir.path()).expect("inside").to_path_buf());
+                    (skill.name, skill.found_by, file)
+                })
+                .collect()
+        };
+        assert_eq!(
+            found(&DiscoverOptions::default()),
+            vec![
+                (
+                    "lower".to_string(),
+                    "skills/*/skill.md".to_string(),
+                    Some(PathBuf::from("skills/lower/skill.md"))
+                ),
+                (
+                    "mdx".to_string(),
+                    "skills/*/SKILL.mdx".to_string(),
+                    Some(PathBuf::from("skills/mdx/SKILL.mdx"))
+                ),
+            ]
+        );
+
+        let options = DiscoverOptions {
+            skill_files: vec!["skill.md".to_string()],
+            file_skills: true,
+            ..Default::default()
+        };
+        let discovered = discover_skills(dir.path(), None, &options).expect("discover");
+        let names: Vec<&str> = discovered
+            .skills
+            .iter()
+            .map(|skill| skill.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["lower", "review"]);
+        let review = &discovered.skills[1];
+        assert_eq!(review.path, dir.path().join("skills/review.md"));
+        assert_eq!(review.file.as_ref(), Some(&review.path));
+        assert_eq!(review.found_by, "skills/*.md");
+    }
+
+    #[test]
+    fn discovery_respects_ignore_files_unless_told_not_to() {
+        let dir = tempdir().expect("tempdir");
+        for (folder, name) in [
+            ("skills/kept", "kept"),
+            ("skills/archived", "archived"),
+            ("examples/old/skill", "old-example"),
+            ("examples/current/skill", "current-example"),
+            ("third_party/lib/skill", "vendored"),
+        ] {
+            let skill_dir = dir.path().join(folder);
+            std::fs::create_dir_all(&skill_dir).expect("mkdir");
+            std::fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {name}\ndescription: Desc\n---\n"),
+            )
+            .expect("write skill");
+        }
+        std::fs::write(dir.path().join(".gitignore"), "/third_party/\n").expect("write");
+        std::fs::write(dir.path().join("skills/.ignore"), "archived\n").expect("write");
+        std::fs::write(
+            dir.path().join("examples/.gitignore"),
+            "*\n!current/\n!current/**\n",
+        )
+        .expect("write");
+
+        let names = |options: &DiscoverOptions, subpath: Option<&Path>| {
+            let mut names: Vec<String> = discover_skills(dir.path(), subpath, options)
+                .expect("discover")
+                .skills
+                .into_iter()
+                .map(|skill| skill.name)
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(&DiscoverOptions::default(), None), vec!["kept"]);
+        assert_eq!(
+            names(&DiscoverOptions::default(), Some(Path::new("examples"))),
+            vec!["current-example"]
+        );
+        let no_ignore = DiscoverOptions {
+            no_ignore: true,
+            ..Default::default()
+        };
+        assert_eq!(names(&no_ignore, None), vec!["archived", "kept"]);
+        assert_eq!(
+            names(&no_ignore, Some(Path::new("examples"))),
+            vec!["current-example", "old-example"]
+        );
     }
 
     #[test]
@@ -330,7 +1278,9 @@ mod tests {
         )
         .expect("write nested skill");
 
-        let discovered = discover_skills(dir.path(), None, false).expect("discover");
+        let discovered = discover_skills(dir.path(), None, &DiscoverOptions::default())
+            .expect("discover")
+            .skills;
         assert_eq!(discovered.len(), 1);
         assert_eq!(discovered[0].name, "RootSkill");
     }
@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+use crate::install::{copy_dir, create_symlink, remove_path};
+
+/// Saved state of one path.
+enum Saved {
+    Absent,
+    Link(PathBuf),
+    Dir(PathBuf),
+    File(PathBuf),
+}
+
+/// Copy of a set of files and directories that can be restored after a failed
+/// batch of changes.
+pub struct Snapshot {
+    entries: Vec<(PathBuf, Saved)>,
+    _temp: tempfile::TempDir,
+}
+
+impl Snapshot {
+    /// Saves the current state of `paths`; missing paths are restored as missing.
+    pub fn capture<I, P>(paths: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let temp = tempfile::tempdir()?;
+        let mut entries = Vec::new();
+        for (index, path) in paths.into_iter().enumerate() {
+            let path = path.as_ref().to_path_buf();
+            let backup = temp.path().join(index.to_string());
+            let saved = match std::fs::symlink_metadata(&path) {
+                Err(_) => Saved::Absent,
+                Ok(metadata) if metadata.file_type().is_symlink() => {
+                    Saved::Link(std::fs::read_link(&path)?)
+                }
+                Ok(metadata) if metadata.is_dir() => {
+                    std::fs::create_dir_all(&backup)?;
+                    copy_dir(&path, &backup)?;
+                    Saved::Dir(backup)
+                }
+                Ok(_) => {
+                    std::fs::copy(&path, &backup)?;
+                    Saved::File(backup)
+                }
+            };
+            entries.push((path, saved));
+        }
+        Ok(Self {
+            entries,
+            _temp: temp,
+        })
+    }
+
+    /// Puts every captured path back the way it was.
+    pub fn restore(&self) -> Result<()> {
+        for (path, saved) in &self.entries {
+            remove_path(path)?;
+            match saved {
+                Saved::Absent => {}
+                Saved::Link(target) => create_symlink(target, path)?,
+                Saved::Dir(backup) => {
+                    std::fs::create_dir_all(path)?;
+                    copy_dir(backup, path)?;
+                }
+                Saved::File(backup) => {
+                    if let Some(parent) = path.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    std::fs::copy(backup, path)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restores_files_directories_and_missing_paths() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = dir.path().join("config.toml");
+        let skill = dir.path().join("skills").join("one");
+        let added = dir.path().join("skills").join("two");
+        std::fs::write(&file, "old").expect("write");
+        std::fs::create_dir_all(&skill).expect("mkdir");
+        std::fs::write(skill.join("SKILL.md"), "v1").expect("write");
+
+        let snapshot = Snapshot::capture([&file, &skill, &added]).expect("capture");
+        std::fs::write(&file, "new").expect("write");
+        std::fs::write(skill.join("SKILL.md"), "v2").expect("write");
+        std::fs::write(skill.join("extra.md"), "x").expect("write");
+        std::fs::create_dir_all(&added).expect("mkdir");
+
+        snapshot.restore().expect("restore");
+        assert_eq!(std::fs::read_to_string(&file).expect("read"), "old");
+        assert_eq!(
+            std::fs::read_to_string(skill.join("SKILL.md")).expect("read"),
+            "v1"
+        );
+        assert!(!skill.join("extra.md").exists());
+        assert!(!added.exists());
+    }
+}
@@ -1,17 +1,48 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::error::{Result, SkilError};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ErrorCode, Result, SkilError};
+
+/// Hosting provider of a git source, used to pick provider APIs for updates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceType {
+    Github,
+    Gitlab,
+    Codeberg,
+    Git,
+}
+
+impl SourceType {
+    /// Detects the provider of a git URL, falling back to plain git.
+    pub fn from_url(url: &str) -> Self {
+        parse_hosted_git_url(url)
+            .map(|(_, _, _, _, source_type)| source_type)
+            .unwrap_or(SourceType::Git)
+    }
+}
 
 /// Source metadata used for installs and updates.
 #[derive(Debug, Clone)]
 pub struct SourceInfo {
     pub source_id: String,
     pub source_url: String,
+    pub source_type: SourceType,
     pub github_owner_repo: Option<String>,
     pub github_branch: Option<String>,
 }
 
-/// A parsed source, either local or git-based.
+/// What a [`Source::Download`] fetches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadKind {
+    /// Every file of a GitHub gist, by gist id.
+    Gist(String),
+    /// A single raw file, saved as `SKILL.md`.
+    Raw,
+}
+
+/// A parsed source, either local, git-based, or downloaded over HTTP.
 #[derive(Debug, Clone)]
 pub enum Source {
     Local {
@@ -22,20 +53,75 @@ pub enum Source {
         subpath: Option<PathBuf>,
         info: SourceInfo,
     },
+    Download {
+        url: String,
+        kind: DownloadKind,
+    },
+}
+
+/// How to read a source that could be either a local path or `owner/repo`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SourceHint {
+    /// Guess from the string, failing when both readings are plausible.
+    #[default]
+    Auto,
+    /// A local path, searched for in parent directories when missing here.
+    Local,
+    /// A git URL or GitHub `owner/repo`.
+    Git,
 }
 
 /// Parses a user-provided source string into a concrete source.
 pub fn parse_source(source: &str) -> Result<Source> {
-    if is_local_path(source) {
-        let source_path = PathBuf::from(source);
-        if !source_path.exists() {
-            return Err(SkilError::Message(format!(
-                "Local path does not exist: {}",
-                source
-            )));
+    parse_source_as(source, SourceHint::Auto)
+}
+
+/// Parses a source string, reading it as `hint` says.
+///
+/// `file://` URLs are local paths unless they point at a git repository, which
+/// is cloned like any other git URL; a `git+` prefix (`git+https://`,
+/// `git+ssh://`, `git+file://`) is stripped and the rest parsed as a git URL.
+///
+/// With [`SourceHint::Auto`], a relative path that does not exist here but does
+/// in a parent directory is rejected as ambiguous instead of becoming a GitHub
+/// repository.
+pub fn parse_source_as(source: &str, hint: SourceHint) -> Result<Source> {
+    match hint {
+        SourceHint::Auto => {}
+        SourceHint::Local => {
+            let path = source.strip_prefix("file://").map_or(source, file_url_path);
+            if !Path::new(path).exists()
+                && let Some(found) = find_up(&std::env::current_dir()?, path)
+            {
+                return parse_local_path(&found.to_string_lossy());
+            }
+            return parse_local_path(path);
+        }
+        SourceHint::Git => {
+            if let Some(download) = parse_download_url(source) {
+                return Ok(download);
+            }
+            let url = source.strip_prefix("git+").unwrap_or(source);
+            if looks_like_url(url) {
+                return parse_git_url(&normalize_ssh_url(url));
+            }
+            return parse_owner_repo(source);
         }
-        let path = std::fs::canonicalize(source_path)?;
-        return Ok(Source::Local { path });
+    }
+
+    if let Some(path) = source.strip_prefix("file://") {
+        let path = file_url_path(path);
+        if !PathBuf::from(path).join(".git").exists() {
+            return parse_local_path(path);
+        }
+    }
+
+    if let Some(url) = source.strip_prefix("git+") {
+        return parse_git_url(&normalize_ssh_url(url));
+    }
+
+    if is_local_path(source) {
+        return parse_local_path(source);
     }
 
     let source_path = PathBuf::from(source);
@@ -44,13 +130,132 @@ pub fn parse_source(source: &str) -> Result<Source> {
         return Ok(Source::Local { path });
     }
 
+    if let Some(download) = parse_download_url(source) {
+        return Ok(download);
+    }
+
     if looks_like_url(source) {
         return parse_git_url(source);
     }
 
+    if let Some(found) = find_up(&std::env::current_dir()?, source) {
+        return Err(ambiguous_source(source, &found));
+    }
+
     parse_owner_repo(source)
 }
 
+/// Finds `source` as a directory in a parent of `start`, nearest first.
+fn find_up(start: &Path, source: &str) -> Option<PathBuf> {
+    if Path::new(source).is_absolute() {
+        return None;
+    }
+    start
+        .ancestors()
+        .skip(1)
+        .map(|dir| dir.join(source))
+        .find(|path| path.is_dir())
+}
+
+/// Error for a source that reads both as `owner/repo` and as a local path.
+fn ambiguous_source(source: &str, found: &Path) -> SkilError {
+    let repo = match parse_owner_repo(source) {
+        Ok(Source::Git { info, .. }) => format!("GitHub repository {}", info.source_id),
+        _ => format!("GitHub repository {source}"),
+    };
+    SkilError::new(
+        ErrorCode::InvalidSource,
+        format!(
+            "Ambiguous source {source}: there is no such directory here, so it reads as the {repo}, but {} exists. Pass --local to install from that directory or --git to use the repository.",
+            found.display()
+        ),
+    )
+}
+
+/// Resolves an existing local path source.
+fn parse_local_path(source: &str) -> Result<Source> {
+    let source_path = PathBuf::from(source);
+    if !source_path.exists() {
+        return Err(SkilError::new(
+            ErrorCode::SourceNotFound,
+            format!("Local path does not exist: {}", source),
+        ));
+    }
+    let path = std::fs::canonicalize(source_path)?;
+    Ok(Source::Local { path })
+}
+
+/// Returns the path of a `file://` URL with the scheme already removed,
+/// dropping a `localhost` host and the slash before a Windows drive letter.
+fn file_url_path(rest: &str) -> &str {
+    let path = rest.strip_prefix("localhost").unwrap_or(rest);
+    let bytes = path.as_bytes();
+    if bytes.len() >= 3 && bytes[0] == b'/' && bytes[1].is_ascii_alphabetic() && bytes[2] == b':' {
+        return &path[1..];
+    }
+    path
+}
+
+/// Rewrites `ssh://git@host/path` to the `git@host:path` form hosted
+/// providers are recognized by; URLs with a port are kept as-is.
+fn normalize_ssh_url(url: &str) -> String {
+    if let Some(rest) = url.strip_prefix("ssh://git@")
+        && let Some((host, path)) = rest.split_once('/')
+        && !host.contains(':')
+    {
+        return format!("git@{host}:{path}");
+    }
+    url.to_string()
+}
+
+/// Reduces `owner/repo` and the URL forms of a repository to one comparable string.
+pub fn normalize_source(source: &str) -> String {
+    let url = match parse_source_as(source, SourceHint::Git) {
+        Ok(Source::Git { url, .. } | Source::Download { url, .. }) => url,
+        _ => source.to_string(),
+    };
+    let url = url.to_lowercase();
+    let url = url.trim_end_matches('/');
+    url.strip_suffix(".git").unwrap_or(url).to_string()
+}
+
+/// Parses gist and `raw.githubusercontent.com` URLs into a download source.
+///
+/// Gist links resolve to the whole gist; raw file links, including raw gist
+/// files, to that single file.
+pub fn parse_download_url(source: &str) -> Option<Source> {
+    let source = source
+        .split('#')
+        .next()
+        .unwrap_or(source)
+        .trim_end_matches('/');
+    let rest = source
+        .strip_prefix("https://")
+        .or_else(|| source.strip_prefix("http://"))?;
+    let (host, path) = rest.split_once('/')?;
+    match host {
+        "raw.githubusercontent.com" | "gist.githubusercontent.com" => Some(Source::Download {
+            url: format!("https://{rest}"),
+            kind: DownloadKind::Raw,
+        }),
+        "gist.github.com" => {
+            let parts: Vec<&str> = path.split('/').collect();
+            let id = match parts.as_slice() {
+                [id] | [_, id] => id.trim_end_matches(".git"),
+                _ => return None,
+            };
+            if id.is_empty() || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            Some(Source::Download {
+                url: format!("https://gist.github.com/{}", parts.join("/")),
+                kind: DownloadKind::Gist(id.to_string()),
+            })
+        }
+        _ => None,
+    }
+}
+
 /// Heuristic for URL-like sources (http/ssh git).
 fn looks_like_url(source: &str) -> bool {
     source.contains("://") || source.starts_with("git@")
@@ -81,8 +286,9 @@ fn is_local_path(source: &str) -> bool {
 fn parse_owner_repo(source: &str) -> Result<Source> {
     let parts: Vec<&str> = source.split('/').filter(|s| !s.is_empty()).collect();
     if parts.len() < 2 {
-        return Err(SkilError::Message(
-            "Invalid source: expected owner/repo or URL".to_string(),
+        return Err(SkilError::new(
+            ErrorCode::InvalidSource,
+            "Invalid source: expected owner/repo or URL",
         ));
     }
     let owner = parts[0];
@@ -102,6 +308,7 @@ fn parse_owner_repo(source: &str) -> Result<Source> {
         info: SourceInfo {
             source_id,
             source_url: url,
+            source_type: SourceType::Github,
             github_owner_repo: Some(format!("{}/{}", owner, repo)),
             github_branch: None,
         },
@@ -110,13 +317,14 @@ fn parse_owner_repo(source: &str) -> Result<Source> {
 
 /// Parses supported hosted git URLs into a source.
 fn parse_git_url(source: &str) -> Result<Source> {
-    if let Some((url, subpath, owner_repo, branch)) = parse_hosted_git_url(source) {
+    if let Some((url, subpath, owner_repo, branch, source_type)) = parse_hosted_git_url(source) {
         return Ok(Source::Git {
             url: url.clone(),
             subpath,
             info: SourceInfo {
                 source_id: owner_repo.clone().unwrap_or_else(|| url.clone()),
                 source_url: url,
+                source_type,
                 github_owner_repo: owner_repo,
                 github_branch: branch,
             },
@@ -129,6 +337,7 @@ fn parse_git_url(source: &str) -> Result<Source> {
         info: SourceInfo {
             source_id: source.to_string(),
             source_url: source.to_string(),
+            source_type: SourceType::Git,
             github_owner_repo: parse_github_owner_repo(source),
             github_branch: None,
         },
@@ -196,21 +405,27 @@ fn parse_github_owner_repo(source: &str) -> Option<String> {
     None
 }
 
-/// Parsed hosted git tuple: repo URL, subpath, owner/repo, branch.
-type ParsedHostedGitUrl = (String, Option<PathBuf>, Option<String>, Option<String>);
+/// Parsed hosted git tuple: repo URL, subpath, owner/repo, branch, provider.
+type ParsedHostedGitUrl = (
+    String,
+    Option<PathBuf>,
+    Option<String>,
+    Option<String>,
+    SourceType,
+);
 
 /// Parses supported hosted git providers (GitHub, GitLab, Codeberg).
 pub fn parse_hosted_git_url(source: &str) -> Option<ParsedHostedGitUrl> {
     if let Some((url, subpath, owner_repo, branch)) = parse_github_tree_url(source) {
-        return Some((url, subpath, owner_repo, branch));
+        return Some((url, subpath, owner_repo, branch, SourceType::Github));
     }
 
     if let Some((url, subpath, owner_repo, branch)) = parse_gitlab_tree_url(source) {
-        return Some((url, subpath, owner_repo, branch));
+        return Some((url, subpath, owner_repo, branch, SourceType::Gitlab));
     }
 
     if let Some((url, subpath, owner_repo, branch)) = parse_codeberg_tree_url(source) {
-        return Some((url, subpath, owner_repo, branch));
+        return Some((url, subpath, owner_repo, branch, SourceType::Codeberg));
     }
 
     None
@@ -345,7 +560,9 @@ mod tests {
     #[test]
     fn parses_gitlab_tree_url() {
         let url = "https://gitlab.com/example/skills/-/tree/main/skills/web-design";
-        let (repo_url, subpath, owner_repo, branch) = parse_hosted_git_url(url).expect("parsed");
+        let (repo_url, subpath, owner_repo, branch, source_type) =
+            parse_hosted_git_url(url).expect("parsed");
+        assert_eq!(source_type, SourceType::Gitlab);
         assert_eq!(repo_url, "https://gitlab.com/example/skills.git");
         assert_eq!(
             subpath.expect("subpath").to_string_lossy(),
@@ -358,7 +575,9 @@ mod tests {
     #[test]
     fn parses_codeberg_tree_url() {
         let url = "https://codeberg.org/example/skills/src/branch/main/skills/web-design";
-        let (repo_url, subpath, owner_repo, branch) = parse_hosted_git_url(url).expect("parsed");
+        let (repo_url, subpath, owner_repo, branch, source_type) =
+            parse_hosted_git_url(url).expect("parsed");
+        assert_eq!(source_type, SourceType::Codeberg);
         assert_eq!(repo_url, "https://codeberg.org/example/skills.git");
         assert_eq!(
             subpath.expect("subpath").to_string_lossy(),
@@ -384,6 +603,7 @@ mod tests {
             info.github_owner_repo.expect("owner/repo"),
             "vercel-labs/agent-skills"
         );
+        assert_eq!(info.source_type, SourceType::Github);
     }
 
     #[test]
@@ -416,6 +636,7 @@ mod tests {
         assert_eq!(url, "https://example.com/custom/repo.git");
         assert!(subpath.is_none());
         assert_eq!(info.source_id, "https://example.com/custom/repo.git");
+        assert_eq!(info.source_type, SourceType::Git);
     }
 
     #[test]
@@ -444,7 +665,7 @@ mod tests {
     #[test]
     fn parses_gitlab_and_codeberg_ssh_urls() {
         let gitlab = "git@gitlab.com:example/repo.git";
-        let (repo_url, subpath, owner_repo, branch) =
+        let (repo_url, subpath, owner_repo, branch, _) =
             parse_hosted_git_url(gitlab).expect("gitlab parsed");
         assert_eq!(repo_url, gitlab);
         assert!(subpath.is_none());
@@ -452,7 +673,7 @@ mod tests {
         assert!(branch.is_none());
 
         let codeberg = "git@codeberg.org:example/repo.git";
-        let (repo_url, subpath, owner_repo, branch) =
+        let (repo_url, subpath, owner_repo, branch, _) =
             parse_hosted_git_url(codeberg).expect("codeberg parsed");
         assert_eq!(repo_url, codeberg);
         assert!(subpath.is_none());
@@ -460,11 +681,117 @@ mod tests {
         assert!(branch.is_none());
     }
 
+    #[test]
+    fn parses_gist_and_raw_urls_as_downloads() {
+        let download = |url: &str| match parse_source(url).expect("parsed") {
+            Source::Download { url, kind } => (url, kind),
+            other => panic!("expected download source, got {other:?}"),
+        };
+        assert_eq!(
+            download("https://gist.github.com/octo/aa5a315d61ae9438b18d#file-skill-md"),
+            (
+                "https://gist.github.com/octo/aa5a315d61ae9438b18d".to_string(),
+                DownloadKind::Gist("aa5a315d61ae9438b18d".to_string())
+            )
+        );
+        assert_eq!(
+            download("https://gist.github.com/aa5a315d61ae9438b18d.git").1,
+            DownloadKind::Gist("aa5a315d61ae9438b18d".to_string())
+        );
+        assert_eq!(
+            download("https://raw.githubusercontent.com/owner/repo/main/skills/web/SKILL.md"),
+            (
+                "https://raw.githubusercontent.com/owner/repo/main/skills/web/SKILL.md".to_string(),
+                DownloadKind::Raw
+            )
+        );
+        assert!(parse_download_url("https://gist.github.com/octo").is_none());
+        assert!(parse_download_url("https://github.com/owner/repo").is_none());
+    }
+
     #[test]
     fn hosted_git_url_returns_none_for_non_supported_hosts() {
         assert!(parse_hosted_git_url("https://example.com/org/repo.git").is_none());
     }
 
+    #[test]
+    fn parses_git_plus_and_file_url_schemes() {
+        let Source::Git { url, info, .. } =
+            parse_source("git+https://github.com/vercel-labs/agent-skills.git").expect("parsed")
+        else {
+            panic!("expected git source");
+        };
+        assert_eq!(url, "https://github.com/vercel-labs/agent-skills.git");
+        assert_eq!(info.source_type, SourceType::Github);
+
+        let Source::Git { url, info, .. } =
+            parse_source("git+ssh://git@gitlab.com/example/repo.git").expect("parsed")
+        else {
+            panic!("expected git source");
+        };
+        assert_eq!(url, "git@gitlab.com:example/repo.git");
+        assert_eq!(info.source_type, SourceType::Gitlab);
+        assert_eq!(
+            normalize_ssh_url("ssh://git@example.com:2222/repo.git"),
+            "ssh://git@example.com:2222/repo.git"
+        );
+
+        let dir = tempdir().expect("tempdir");
+        let file_url = format!("file://{}", dir.path().display());
+        let Source::Local { path } = parse_source(&file_url).expect("parsed") else {
+            panic!("expected local source");
+        };
+        assert_eq!(
+            path,
+            std::fs::canonicalize(dir.path()).expect("canonical path")
+        );
+        std::fs::create_dir(dir.path().join(".git")).expect("create .git");
+        assert!(matches!(
+            parse_source(&file_url).expect("parsed"),
+            Source::Git { .. }
+        ));
+        assert_eq!(file_url_path("localhost/srv/skills"), "/srv/skills");
+        assert_eq!(file_url_path("/C:/skills"), "C:/skills");
+    }
+
+    #[test]
+    fn finds_relative_sources_in_parent_directories() {
+        let dir = tempdir().expect("tempdir");
+        let nested = dir.path().join("a/b");
+        std::fs::create_dir_all(&nested).expect("create nested");
+        std::fs::create_dir_all(dir.path().join("skills/web")).expect("create skills");
+
+        assert_eq!(
+            find_up(&nested, "skills/web"),
+            Some(dir.path().join("skills/web"))
+        );
+        assert_eq!(find_up(&nested, "skills/missing"), None);
+
+        let err = ambiguous_source("skills/web", &dir.path().join("skills/web"));
+        assert_eq!(err.code(), ErrorCode::InvalidSource);
+        assert!(err.to_string().contains("GitHub repository skills/web"));
+        assert!(err.to_string().contains("--local"));
+    }
+
+    #[test]
+    fn source_hint_forces_git_or_local_reading() {
+        let dir = tempdir().expect("tempdir");
+        let Source::Git { url, subpath, .. } =
+            parse_source_as("skills/web/frontend", SourceHint::Git).expect("parsed")
+        else {
+            panic!("expected git source");
+        };
+        assert_eq!(url, "https://github.com/skills/web.git");
+        assert_eq!(subpath, Some(PathBuf::from("frontend")));
+
+        let path = dir.path().to_str().expect("utf8 path");
+        assert!(matches!(
+            parse_source_as(path, SourceHint::Local).expect("parsed"),
+            Source::Local { .. }
+        ));
+        assert!(parse_source_as("./__skil_missing__", SourceHint::Local).is_err());
+    }
+
     #[test]
     fn parse_source_rejects_missing_explicit_local_path() {
         let missing_path = "./__skil_missing_path_for_test__";
@@ -0,0 +1,202 @@
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::discovery::{DiscoveredSource, Discovery};
+use crate::error::{ErrorCode, Result, SkilError};
+use crate::observer::Observer;
+use crate::skills::parse_skill_md;
+
+/// Folder of a template source that holds one template per subfolder.
+pub const TEMPLATES_DIR: &str = "templates";
+
+/// Template source used when `--template` names none.
+pub const DEFAULT_TEMPLATE_SOURCE: &str = "matoous/skil-templates";
+
+/// Replaced with the new skill's name in template file names and contents.
+pub const NAME_PLACEHOLDER: &str = "{{name}}";
+
+/// A skill template: a folder with a SKILL.md and any scripts or references.
+#[derive(Debug, Clone)]
+pub struct Template {
+    /// Folder name of the template.
+    pub name: String,
+    pub description: String,
+    pub path: PathBuf,
+}
+
+/// Templates of one source, kept on disk while this value lives.
+pub struct TemplateSet {
+    pub templates: Vec<Template>,
+    _source: DiscoveredSource,
+}
+
+impl TemplateSet {
+    /// Returns the template named `name`.
+    pub fn get(&self, name: &str) -> Result<&Template> {
+        self.templates
+            .iter()
+            .find(|template| template.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                let names: Vec<&str> = self
+                    .templates
+                    .iter()
+                    .map(|template| template.name.as_str())
+                    .collect();
+                SkilError::new(
+                    ErrorCode::NoMatchingSkills,
+                    format!("No template named {name}; available: {}", names.join(", ")),
+                )
+            })
+    }
+}
+
+/// Splits `<name>@<source>` into the template name and source, falling back
+/// to [`DEFAULT_TEMPLATE_SOURCE`].
+pub fn parse_template_ref(value: &str) -> (&str, &str) {
+    value
+        .split_once('@')
+        .unwrap_or((value, DEFAULT_TEMPLATE_SOURCE))
+}
+
+/// Fetches the templates under [`TEMPLATES_DIR`] of a source.
+pub fn fetch_templates(source: &str, observer: &dyn Observer) -> Result<TemplateSet> {
+    let discovered = Discovery::new(source)
+        .subpath(Some(PathBuf::from(TEMPLATES_DIR)))
+        .observer(observer)
+        .run()?;
+    // Templates share placeholder names, so list folders instead of
+    // relying on discovery, which keeps one skill per name.
+    let mut templates = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(discovered.root.join(TEMPLATES_DIR)) {
+        for entry in entries {
+            let path = entry?.path();
+            let skill_file = path.join("SKILL.md");
+            if !skill_file.is_file() {
+                continue;
+            }
+            let description = parse_skill_md(&skill_file)?
+                .map(|skill| skill.description)
+                .unwrap_or_default();
+            templates.push(Template {
+                name: path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string(),
+                description,
+                path,
+            });
+        }
+    }
+    if templates.is_empty() {
+        return Err(SkilError::new(
+            ErrorCode::NoSkills,
+            format!("No templates found in {source}/{TEMPLATES_DIR}"),
+        ));
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(TemplateSet {
+        templates,
+        _source: discovered,
+    })
+}
+
+/// Copies a template into `dest`, replacing [`NAME_PLACEHOLDER`] with `name`
+/// in file names and text files.
+///
+/// Fails without writing anything when one of the files already exists.
+/// Returns the created files relative to `dest`.
+pub fn scaffold(template: &Template, dest: &Path, name: &str) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(&template.path)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != ".git")
+    {
+        let entry = entry.map_err(|err| SkilError::Message(err.to_string()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(&template.path)
+            .map_err(|err| SkilError::Message(err.to_string()))?;
+        let target = PathBuf::from(relative.to_string_lossy().replace(NAME_PLACEHOLDER, name));
+        if dest.join(&target).exists() {
+            return Err(SkilError::Message(format!(
+                "{} already exists",
+                dest.join(&target).display()
+            )));
+        }
+        files.push((entry.path().to_path_buf(), target));
+    }
+
+    for (from, target) in &files {
+        let to = dest.join(target);
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let bytes = std::fs::read(from)?;
+        match String::from_utf8(bytes) {
+            Ok(text) => std::fs::write(&to, text.replace(NAME_PLACEHOLDER, name))?,
+            Err(err) => std::fs::write(&to, err.into_bytes())?,
+        }
+        // Keep scripts executable.
+        std::fs::set_permissions(&to, std::fs::metadata(from)?.permissions())?;
+    }
+    Ok(files.into_iter().map(|(_, target)| target).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaffolds_files_with_the_skill_name() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let source = dir.path().join("template");
+        std::fs::create_dir_all(source.join("scripts")).expect("mkdir");
+        std::fs::create_dir_all(source.join("references")).expect("mkdir");
+        std::fs::write(
+            source.join("SKILL.md"),
+            "---\nname: \"{{name}}\"\ndescription: Template\n---\n# {{name}}\n",
+        )
+        .expect("write");
+        std::fs::write(source.join("scripts/run.sh"), "echo {{name}}\n").expect("write");
+        std::fs::write(source.join("references/{{name}}.md"), "ref\n").expect("write");
+        let template = Template {
+            name: "template".to_string(),
+            description: "Template".to_string(),
+            path: source,
+        };
+
+        let dest = dir.path().join("pdf");
+        let files = scaffold(&template, &dest, "pdf").expect("scaffold");
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("SKILL.md"),
+                PathBuf::from("references/pdf.md"),
+                PathBuf::from("scripts/run.sh"),
+            ]
+        );
+        assert_eq!(
+            std::fs::read_to_string(dest.join("SKILL.md")).expect("read"),
+            "---\nname: \"pdf\"\ndescription: Template\n---\n# pdf\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dest.join("scripts/run.sh")).expect("read"),
+            "echo pdf\n"
+        );
+
+        let err = scaffold(&template, &dest, "pdf").expect_err("files exist");
+        assert!(err.to_string().contains("already exists"), "{err}");
+    }
+
+    #[test]
+    fn parses_template_refs() {
+        assert_eq!(parse_template_ref("cli@owner/repo"), ("cli", "owner/repo"));
+        assert_eq!(parse_template_ref("cli"), ("cli", DEFAULT_TEMPLATE_SOURCE));
+    }
+}
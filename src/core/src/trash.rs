@@ -0,0 +1,222 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, SkilError};
+use crate::install::{copy_dir, create_symlink, remove_path, sanitize_name};
+use crate::paths::cache_dir;
+
+/// Removed skills older than this many days are deleted for good.
+pub const TRASH_EXPIRY_DAYS: i64 = 14;
+
+const MANIFEST_FILE: &str = "trash.json";
+
+/// One removal of a skill, kept in the trash until restored or expired.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub skill: String,
+    /// When the skill was removed, as an RFC 3339 timestamp.
+    pub removed_at: String,
+    /// Removed folders and agent links, in the order they were moved.
+    pub paths: Vec<TrashedPath>,
+    #[serde(skip)]
+    pub dir: PathBuf,
+}
+
+impl TrashEntry {
+    /// Parsed [`TrashEntry::removed_at`]; unreadable timestamps sort as oldest.
+    pub fn removed_time(&self) -> jiff::Timestamp {
+        self.removed_at
+            .parse()
+            .unwrap_or(jiff::Timestamp::UNIX_EPOCH)
+    }
+}
+
+/// A removed path and where to put it back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedPath {
+    pub original: PathBuf,
+    /// Target of the removed path when it was a symlink.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<PathBuf>,
+}
+
+/// Folder in [`cache_dir`] holding removed skills, one subfolder per removal.
+pub fn trash_dir() -> PathBuf {
+    cache_dir().join("trash")
+}
+
+/// Moves the existing `paths` of a skill into the trash.
+///
+/// Expired entries are deleted first. Returns false when none of `paths`
+/// exists.
+pub fn trash_skill(skill: &str, paths: &[PathBuf]) -> Result<bool> {
+    let trash = trash_dir();
+    let now = jiff::Timestamp::now();
+    expire(&trash, now)?;
+    trash_into(&trash, skill, paths, now)
+}
+
+/// Entries in the trash, newest first.
+pub fn trash_entries() -> Result<Vec<TrashEntry>> {
+    entries(&trash_dir())
+}
+
+/// Puts back the most recently removed copy of `skill` and drops it from the
+/// trash.
+pub fn restore_skill(skill: &str) -> Result<TrashEntry> {
+    restore_from(&trash_dir(), skill)
+}
+
+fn trash_into(trash: &Path, skill: &str, paths: &[PathBuf], now: jiff::Timestamp) -> Result<bool> {
+    let existing: Vec<&PathBuf> = paths
+        .iter()
+        .filter(|path| std::fs::symlink_metadata(path).is_ok())
+        .collect();
+    if existing.is_empty() {
+        return Ok(false);
+    }
+    let stamp = now.strftime("%Y%m%dT%H%M%S%.3f").to_string();
+    let mut dir = trash.join(format!("{stamp}-{}", sanitize_name(skill)));
+    for attempt in 1.. {
+        if !dir.exists() {
+            break;
+        }
+        dir = trash.join(format!("{stamp}-{}-{attempt}", sanitize_name(skill)));
+    }
+    std::fs::create_dir_all(&dir)?;
+
+    let mut entry = TrashEntry {
+        skill: skill.to_string(),
+        removed_at: now.to_string(),
+        paths: Vec::new(),
+        dir: dir.clone(),
+    };
+    for (index, path) in existing.into_iter().enumerate() {
+        let original = std::path::absolute(path)?;
+        let metadata = std::fs::symlink_metadata(path)?;
+        let link = if metadata.file_type().is_symlink() {
+            let target = std::fs::read_link(path)?;
+            remove_path(path)?;
+            Some(target)
+        } else {
+            move_path(path, &dir.join(index.to_string()))?;
+            None
+        };
+        entry.paths.push(TrashedPath { original, link });
+        // Written after each move so an interrupted removal can still be restored.
+        let manifest = serde_json::to_string_pretty(&entry)?;
+        std::fs::write(dir.join(MANIFEST_FILE), manifest)?;
+    }
+    Ok(true)
+}
+
+fn entries(trash: &Path) -> Result<Vec<TrashEntry>> {
+    let mut entries = Vec::new();
+    let Ok(dirs) = std::fs::read_dir(trash) else {
+        return Ok(entries);
+    };
+    for dir in dirs {
+        let dir = dir?.path();
+        let Ok(manifest) = std::fs::read_to_string(dir.join(MANIFEST_FILE)) else {
+            continue;
+        };
+        let mut entry: TrashEntry = serde_json::from_str(&manifest)?;
+        entry.dir = dir;
+        entries.push(entry);
+    }
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.removed_time()));
+    Ok(entries)
+}
+
+fn restore_from(trash: &Path, skill: &str) -> Result<TrashEntry> {
+    let Some(entry) = entries(trash)?
+        .into_iter()
+        .find(|entry| entry.skill.eq_ignore_ascii_case(skill))
+    else {
+        return Err(SkilError::Message(format!(
+            "No removed copy of {skill} in the trash"
+        )));
+    };
+    if let Some(taken) = entry
+        .paths
+        .iter()
+        .find(|path| std::fs::symlink_metadata(&path.original).is_ok())
+    {
+        return Err(SkilError::Message(format!(
+            "{} already exists; remove it before restoring {skill}",
+            taken.original.display()
+        )));
+    }
+    for (index, path) in entry.paths.iter().enumerate() {
+        if let Some(parent) = path.original.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        match &path.link {
+            Some(target) => create_symlink(target, &path.original)?,
+            None => move_path(&entry.dir.join(index.to_string()), &path.original)?,
+        }
+    }
+    std::fs::remove_dir_all(&entry.dir)?;
+    Ok(entry)
+}
+
+/// Deletes entries removed more than [`TRASH_EXPIRY_DAYS`] before `now`.
+fn expire(trash: &Path, now: jiff::Timestamp) -> Result<()> {
+    let cutoff = now - jiff::SignedDuration::from_hours(TRASH_EXPIRY_DAYS * 24);
+    for entry in entries(trash)? {
+        if entry.removed_time() < cutoff {
+            std::fs::remove_dir_all(&entry.dir)?;
+        }
+    }
+    Ok(())
+}
+
+/// Renames a folder, copying it when `to` is on another filesystem.
+fn move_path(from: &Path, to: &Path) -> Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::create_dir_all(to)?;
+    copy_dir(from, to)?;
+    std::fs::remove_dir_all(from)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn restores_folders_and_links_and_expires_old_entries() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let trash = dir.path().join("trash");
+        let store = dir.path().join("store").join("pdf");
+        let link = dir.path().join("agent").join("pdf");
+        std::fs::create_dir_all(&store).expect("mkdir");
+        std::fs::write(store.join("SKILL.md"), "x").expect("write");
+        std::fs::create_dir_all(link.parent().expect("parent")).expect("mkdir");
+        std::os::unix::fs::symlink(&store, &link).expect("symlink");
+
+        let now = jiff::Timestamp::now();
+        let paths = [link.clone(), store.clone(), dir.path().join("missing")];
+        assert!(trash_into(&trash, "pdf", &paths, now).expect("trash"));
+        assert!(!store.exists() && std::fs::symlink_metadata(&link).is_err());
+        assert!(!trash_into(&trash, "pdf", &paths, now).expect("nothing left"));
+
+        let entry = restore_from(&trash, "PDF").expect("restore");
+        assert_eq!(entry.paths.len(), 2);
+        assert_eq!(
+            std::fs::read_to_string(link.join("SKILL.md")).expect("read"),
+            "x"
+        );
+        assert!(entries(&trash).expect("entries").is_empty());
+        assert!(restore_from(&trash, "pdf").is_err());
+
+        let old = now - jiff::SignedDuration::from_hours((TRASH_EXPIRY_DAYS + 1) * 24);
+        trash_into(&trash, "pdf", std::slice::from_ref(&store), old).expect("trash");
+        expire(&trash, now).expect("expire");
+        assert!(entries(&trash).expect("entries").is_empty());
+    }
+}
@@ -0,0 +1,619 @@
+use std::path::PathBuf;
+
+use crate::agent::{AgentConfig, agent_configs, resolve_agents};
+use crate::config::{
+    ConfigLocation, SkilConfig, SkilSource, SkillFolder, config_location, config_location_auto,
+    find_source, preferred_agents, preferred_copy, read_config, remembered_answers, untrack_skills,
+    write_config,
+};
+use crate::discovery::Revision;
+use crate::download::latest_checksum;
+use crate::error::{Result, SkilError};
+use crate::git::{checkout_revision, clone_repo, latest_tag, tree_hash};
+use crate::install::{
+    InstallMode, agent_skills_base, canonical_skills_dir, sanitize_name, uninstall_skill,
+};
+use crate::installer::{InstallReport, Installer};
+use crate::lock::lock_config;
+use crate::observer::{NoopObserver, Observer};
+use crate::paths::scratch_dir;
+use crate::remote::{fetch_skill_folder_hash, latest_revision};
+use crate::skills::{Deprecation, parse_frontmatter};
+use crate::snapshot::Snapshot;
+use crate::source::{Source, parse_source};
+
+/// One tracked source with a newer checksum or version available.
+#[derive(Debug, Clone)]
+pub struct AvailableUpdate {
+    pub source_key: String,
+    pub source: SkilSource,
+    pub latest_checksum: Option<String>,
+    pub latest_version: Option<String>,
+}
+
+impl AvailableUpdate {
+    /// The newer version, or checksum for untagged repositories.
+    pub fn latest(&self) -> &str {
+        self.latest_version
+            .as_deref()
+            .or(self.latest_checksum.as_deref())
+            .unwrap_or("unknown")
+    }
+}
+
+/// A tracked skill whose installed SKILL.md marks it deprecated.
+#[derive(Debug, Clone)]
+pub struct DeprecatedSkill {
+    pub source_key: String,
+    pub skill: String,
+    pub deprecation: Deprecation,
+}
+
+/// Non-interactive equivalent of `skil check` and `skil update`.
+pub struct Updater<'a> {
+    config_path: PathBuf,
+    global: bool,
+    agents: Option<Vec<AgentConfig>>,
+    observer: &'a dyn Observer,
+}
+
+impl<'a> Updater<'a> {
+    /// Creates an updater for the project config, or the global config with `global`.
+    pub fn new(global: bool) -> Result<Self> {
+        Ok(Self::from_location(config_location(global)?))
+    }
+
+    /// Creates an updater for the project config when present, otherwise the global one.
+    pub fn auto() -> Result<Self> {
+        Ok(Self::from_location(config_location_auto()?))
+    }
+
+    /// Creates an updater for an explicit config location.
+    pub fn from_location(location: ConfigLocation) -> Self {
+        Self {
+            config_path: location.path,
+            global: location.is_global,
+            agents: None,
+            observer: &NoopObserver,
+        }
+    }
+
+    /// Agents to reinstall updated skills into; defaults to detected agents.
+    pub fn agents(mut self, agents: Vec<AgentConfig>) -> Self {
+        self.agents = Some(agents);
+        self
+    }
+
+    /// Sets the observer used while reinstalling sources.
+    pub fn observer(mut self, observer: &'a dyn Observer) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    /// Whether this updater works on the global config.
+    pub fn is_global(&self) -> bool {
+        self.global
+    }
+
+    /// Reads the tracked config.
+    pub fn config(&self) -> Result<SkilConfig> {
+        read_config(&self.config_path)
+    }
+
+    /// Returns tracked remote sources that have a newer tag or revision.
+    ///
+    /// Tagged repositories compare by latest tag unless a branch is tracked; others
+    /// by the remote revision checksum of the tracked branch,
+    /// resolved through the provider API for GitHub and GitLab sources. When the
+    /// installed skill folders are known, a new revision only counts as an update
+    /// if one of their tree hashes changed; hosts without a tree API are cloned
+    /// to compare them. Gist and raw file sources are
+    /// downloaded again and compared by content checksum.
+    pub fn check(&self) -> Result<Vec<AvailableUpdate>> {
+        let config = self.config()?;
+        let mut updates = Vec::new();
+        for (source_key, source) in &config.sources {
+            if !is_remote_source_key(source_key) || source.updatable_skills().is_empty() {
+                continue;
+            }
+
+            if let Ok(Source::Download { url, kind }) = parse_source(source_key) {
+                let latest = latest_checksum(&url, &kind)?;
+                if source.checksum.as_deref() != Some(latest.as_str()) {
+                    updates.push(AvailableUpdate {
+                        source_key: source_key.clone(),
+                        source: source.clone(),
+                        latest_checksum: Some(latest),
+                        latest_version: None,
+                    });
+                }
+                continue;
+            }
+
+            if source.branch.is_none()
+                && let Some(tag) = latest_tag(source_key)?
+            {
+                let current = source.version.clone().unwrap_or_default();
+                if current != tag && skills_changed(source_key, source, &tag) {
+                    updates.push(AvailableUpdate {
+                        source_key: source_key.clone(),
+                        source: source.clone(),
+                        latest_checksum: None,
+                        latest_version: Some(tag),
+                    });
+                }
+                continue;
+            }
+
+            let latest = latest_revision(source_key, source)?;
+            let current = source.checksum.clone().unwrap_or_default();
+            if current.is_empty()
+                || (current != latest && skills_changed(source_key, source, &latest))
+            {
+                updates.push(AvailableUpdate {
+                    source_key: source_key.clone(),
+                    source: source.clone(),
+                    latest_checksum: Some(latest),
+                    latest_version: None,
+                });
+            }
+        }
+        Ok(updates)
+    }
+
+    /// Returns tracked skills whose installed copies declare `deprecated` or
+    /// `superseded-by` in their frontmatter.
+    pub fn deprecated(&self) -> Result<Vec<DeprecatedSkill>> {
+        let config = self.config()?;
+        let store = canonical_skills_dir(self.global)?;
+        let mut deprecated = Vec::new();
+        for (source_key, source) in &config.sources {
+            for skill in &source.skills {
+                let path = store.join(sanitize_name(skill)).join("SKILL.md");
+                let Ok(content) = std::fs::read_to_string(path) else {
+                    continue;
+                };
+                let Some(deprecation) = parse_frontmatter(&content)
+                    .ok()
+                    .flatten()
+                    .and_then(|frontmatter| frontmatter.deprecation())
+                else {
+                    continue;
+                };
+                deprecated.push(DeprecatedSkill {
+                    source_key: source_key.clone(),
+                    skill: skill.clone(),
+                    deprecation,
+                });
+            }
+        }
+        Ok(deprecated)
+    }
+
+    /// Installs the declared successor of a deprecated skill, then removes the
+    /// deprecated skill.
+    ///
+    /// A successor without a source comes from the deprecated skill's source.
+    pub fn replace_deprecated(&self, deprecated: &DeprecatedSkill) -> Result<InstallReport> {
+        let Some(successor) = &deprecated.deprecation.successor else {
+            return Err(SkilError::Message(format!(
+                "{} declares no successor",
+                deprecated.skill
+            )));
+        };
+        let _lock = lock_config(&self.config_path)?;
+        let installer = match &successor.source {
+            Some(source) => Installer::new(source.clone()),
+            None => {
+                let config = self.config()?;
+                let Some(source) = config.sources.get(&deprecated.source_key) else {
+                    return Err(SkilError::Message(format!(
+                        "Source not tracked: {}",
+                        deprecated.source_key
+                    )));
+                };
+                Installer::new(deprecated.source_key.clone())
+                    .subpaths(source.search_paths())
+                    .branch(source.branch.clone())
+            }
+        };
+        let agents = self.agents_with(&deprecated.skill)?;
+        let report = installer
+            .replacing(Some(deprecated.source_key.clone()))
+            .skills(vec![successor.skill.clone()])
+            .agents(agents.clone())
+            .global(self.global)
+            .observer(self.observer)
+            .install()?;
+        // A successor of the same name already took over the installed folders.
+        if sanitize_name(&successor.skill) != sanitize_name(&deprecated.skill) {
+            uninstall_skill(&deprecated.skill, &agents, self.global)?;
+            untrack_skills(
+                &self.config_path,
+                &deprecated.source_key,
+                std::slice::from_ref(&deprecated.skill),
+            )?;
+        }
+        Ok(report)
+    }
+
+    /// Reinstalls the tracked skills of one source at its latest revision.
+    ///
+    /// Skills removed or renamed upstream are listed in [`InstallReport::missing`].
+    /// Locally modified skills are left as they are. Agents and install method
+    /// default to the answers remembered when the source was added.
+    pub fn update(&self, update: &AvailableUpdate) -> Result<InstallReport> {
+        let (agents, mode) = self.install_target(&update.source_key)?;
+        Installer::new(update.source_key.clone())
+            .subpaths(update.source.search_paths())
+            .branch(update.source.branch.clone())
+            .skills(update.source.updatable_skills())
+            .agents(agents)
+            .mode(mode)
+            .global(self.global)
+            .missing_ok(true)
+            .observer(self.observer)
+            .install()
+    }
+
+    /// Captures the config and installed directories `updates` may touch, so a
+    /// failed batch can be undone with [`Snapshot::restore`].
+    pub fn snapshot(&self, updates: &[AvailableUpdate]) -> Result<Snapshot> {
+        let canonical = canonical_skills_dir(self.global)?;
+        let mut paths = vec![self.config_path.clone()];
+        for update in updates {
+            let (agents, _) = self.install_target(&update.source_key)?;
+            for name in &update.source.skills {
+                let name = sanitize_name(name);
+                paths.push(canonical.join(&name));
+                for agent in &agents {
+                    paths.push(agent_skills_base(agent, self.global)?.join(&name));
+                }
+            }
+        }
+        Snapshot::capture(paths)
+    }
+
+    /// Removes skills of a source from agents, the canonical store, and config.
+    ///
+    /// The source entry is dropped once it tracks no skills.
+    pub fn prune(&self, source_key: &str, skills: &[String]) -> Result<()> {
+        let _lock = lock_config(&self.config_path)?;
+        let agents = self.resolved_agents();
+        for name in skills {
+            uninstall_skill(name, &agents, self.global)?;
+        }
+        untrack_skills(&self.config_path, source_key, skills)
+    }
+
+    /// Removes every skill of a tracked source from all agents and the
+    /// canonical store, and drops the source from config.
+    ///
+    /// `source` is the config key or any form [`find_source`] accepts.
+    /// Returns the key and the removed skills.
+    pub fn remove_source(&self, source: &str) -> Result<(String, Vec<String>)> {
+        let _lock = lock_config(&self.config_path)?;
+        let config = self.config()?;
+        let Some(key) = find_source(&config, source) else {
+            return Err(SkilError::Message(format!("Source not tracked: {source}")));
+        };
+        let skills = config.sources[key].skills.clone();
+        let agents = self.agents.clone().unwrap_or_else(agent_configs);
+        for name in &skills {
+            uninstall_skill(name, &agents, self.global)?;
+        }
+        let mut config = self.config()?;
+        config.sources.remove(key);
+        write_config(&self.config_path, &config)?;
+        Ok((key.to_string(), skills))
+    }
+
+    /// Reinstalls the source tracking `skill` at its previous revision.
+    ///
+    /// Revisions are tracked per source, so every skill of that source is
+    /// rolled back together, except locally modified ones. Repeated rollbacks
+    /// walk further back in history.
+    pub fn rollback(&self, skill: &str) -> Result<InstallReport> {
+        let _lock = lock_config(&self.config_path)?;
+        let config = self.config()?;
+        let Some((source_key, source)) = config.sources.iter().find(|(_, source)| {
+            source
+                .skills
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(skill))
+        }) else {
+            return Err(SkilError::Message(format!("Skill not tracked: {skill}")));
+        };
+        let Some((previous, older)) = source.history.split_first() else {
+            return Err(SkilError::Message(format!(
+                "No previous revision recorded for {source_key}"
+            )));
+        };
+        let skills = source.updatable_skills();
+        if skills.is_empty() {
+            return Err(SkilError::Message(format!(
+                "Every skill of {source_key} is locally modified"
+            )));
+        }
+
+        let report = Installer::new(source_key.clone())
+            .subpaths(source.search_paths())
+            .branch(source.branch.clone())
+            .skills(skills)
+            .agents(self.resolved_agents())
+            .global(self.global)
+            .revision(Revision::Pinned(previous.checksum.clone()))
+            .missing_ok(true)
+            .observer(self.observer)
+            .install()?;
+
+        let mut config = self.config()?;
+        if let Some(entry) = config.sources.get_mut(source_key) {
+            entry.checksum = Some(previous.checksum.clone());
+            entry.version = previous.version.clone();
+            entry.history = older.to_vec();
+        }
+        write_config(&self.config_path, &config)?;
+        Ok(report)
+    }
+
+    /// Agents that have `skill` installed, or the resolved agents when none do.
+    fn agents_with(&self, skill: &str) -> Result<Vec<AgentConfig>> {
+        let name = sanitize_name(skill);
+        let mut agents = Vec::new();
+        for agent in self.agents.clone().unwrap_or_else(agent_configs) {
+            let path = agent_skills_base(&agent, self.global)?.join(&name);
+            if std::fs::symlink_metadata(path).is_ok() {
+                agents.push(agent);
+            }
+        }
+        if agents.is_empty() {
+            agents = self.resolved_agents();
+        }
+        Ok(agents)
+    }
+
+    fn resolved_agents(&self) -> Vec<AgentConfig> {
+        match &self.agents {
+            Some(agents) => agents.clone(),
+            None => resolve_agents(&[]),
+        }
+    }
+
+    /// Agents and install method for reinstalling `source_key`: the agents set
+    /// on the updater, otherwise those set in config, otherwise those
+    /// remembered from `skil add`, otherwise detected ones.
+    fn install_target(&self, source_key: &str) -> Result<(Vec<AgentConfig>, InstallMode)> {
+        let global = read_config(&config_location(true)?.path)?;
+        let answers = remembered_answers(&global, source_key)
+            .cloned()
+            .unwrap_or_default();
+        let agents = match &self.agents {
+            Some(agents) => agents.clone(),
+            None => {
+                let preferred = preferred_agents(self.global)?;
+                if preferred.is_empty() {
+                    resolve_agents(&answers.agents)
+                } else {
+                    resolve_agents(&preferred)
+                }
+            }
+        };
+        let copy = preferred_copy(self.global)?.or(answers.copy);
+        let mode = if copy == Some(true) {
+            InstallMode::Copy
+        } else {
+            InstallMode::Symlink
+        };
+        Ok((agents, mode))
+    }
+}
+
+/// Compares stored skill folder hashes against the remote at `reference`.
+///
+/// Hashes the provider API cannot give, as for hosts without a tree API, are
+/// compared in a clone of the source. Returns true when a folder is unknown or
+/// the clone fails.
+fn skills_changed(source_key: &str, source: &SkilSource, reference: &str) -> bool {
+    let skills = source.updatable_skills();
+    if skills.is_empty() {
+        return true;
+    }
+    let mut unresolved = Vec::new();
+    for skill in &skills {
+        let Some(folder) = source.folders.get(skill) else {
+            return true;
+        };
+        match fetch_skill_folder_hash(source_key, source, &folder.path, Some(reference)) {
+            Ok(Some(hash)) if hash != folder.hash => return true,
+            Ok(Some(_)) => {}
+            _ => unresolved.push(folder),
+        }
+    }
+    !unresolved.is_empty()
+        && cloned_folders_changed(source_key, reference, &unresolved).unwrap_or(true)
+}
+
+/// Clones the source at `reference` and compares the tree hashes of `folders`.
+fn cloned_folders_changed(
+    source_key: &str,
+    reference: &str,
+    folders: &[&SkillFolder],
+) -> Result<bool> {
+    let Source::Git { url, .. } = parse_source(source_key)? else {
+        return Ok(true);
+    };
+    let temp = scratch_dir("check-")?;
+    clone_repo(&url, temp.path())?;
+    checkout_revision(temp.path(), reference)?;
+    Ok(folders.iter().any(|folder| {
+        tree_hash(temp.path(), &folder.path).ok().as_deref() != Some(folder.hash.as_str())
+    }))
+}
+
+/// Returns true when a source key looks like a remote git reference.
+pub fn is_remote_source_key(source_key: &str) -> bool {
+    source_key.contains("://") || source_key.starts_with("git@")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::fixtures::{git, isolated, write_skill};
+    use crate::git::head_revision;
+    use crate::paths::env_lock;
+
+    #[test]
+    fn clones_hosts_without_a_tree_api_to_compare_skill_folders() {
+        let _env = env_lock();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let origin = dir.path().join("origin");
+        std::fs::create_dir_all(origin.join("skills/pdf")).expect("create");
+        std::fs::write(origin.join("skills/pdf/SKILL.md"), "pdf").expect("write");
+        git(&origin, &["init", "-q"]);
+        git(&origin, &["add", "-A"]);
+        git(&origin, &["commit", "-q", "-m", "Add pdf"]);
+
+        let source_key = format!("file://{}", origin.display());
+        let folder = SkillFolder {
+            path: "skills/pdf".to_string(),
+            hash: tree_hash(&origin, "skills/pdf").expect("tree hash"),
+            installed: None,
+        };
+        let source = SkilSource {
+            source_type: None,
+            branch: None,
+            subpath: None,
+            subpaths: Vec::new(),
+            checksum: Some(head_revision(&origin).expect("head")),
+            version: None,
+            updated_at: None,
+            skills: vec!["pdf".to_string()],
+            folders: BTreeMap::from([("pdf".to_string(), folder)]),
+            history: Vec::new(),
+            modified: Vec::new(),
+            available: Vec::new(),
+        };
+
+        std::fs::write(origin.join("README.md"), "readme").expect("write");
+        git(&origin, &["add", "-A"]);
+        git(&origin, &["commit", "-q", "-m", "Add readme"]);
+        let unrelated = head_revision(&origin).expect("head");
+        assert!(!skills_changed(&source_key, &source, &unrelated));
+
+        std::fs::write(origin.join("skills/pdf/SKILL.md"), "pdf v2").expect("write");
+        git(&origin, &["add", "-A"]);
+        git(&origin, &["commit", "-q", "-m", "Update pdf"]);
+        let changed = head_revision(&origin).expect("head");
+        assert!(skills_changed(&source_key, &source, &changed));
+    }
+
+    #[test]
+    fn prune_removes_skills_gone_upstream() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let origin = dir.path().join("origin");
+        write_skill(&origin, "pdf", "pdf");
+        write_skill(&origin, "docx", "docx");
+        git(&origin, &["init", "-q"]);
+        git(&origin, &["add", "-A"]);
+        git(&origin, &["commit", "-q", "-m", "Add skills"]);
+
+        isolated(dir.path(), || {
+            let agents = resolve_agents(&["claude-code".to_string()]);
+            let report = Installer::new(format!("file://{}", origin.display()))
+                .agents(agents.clone())
+                .global(true)
+                .install()
+                .expect("install");
+            let source_key = report.source_key;
+
+            std::fs::remove_dir_all(origin.join("skills/docx")).expect("remove");
+            git(&origin, &["add", "-A"]);
+            git(&origin, &["commit", "-q", "-m", "Drop docx"]);
+
+            let updater = Updater::new(true).expect("updater").agents(agents.clone());
+            let updates = updater.check().expect("check");
+            assert_eq!(updates.len(), 1);
+            let report = updater.update(&updates[0]).expect("update");
+            assert_eq!(report.missing, ["docx"]);
+
+            let store = canonical_skills_dir(true).expect("store");
+            let linked = agent_skills_base(&agents[0], true)
+                .expect("agent dir")
+                .join("docx");
+            let tracked = |name: &str| {
+                updater.config().expect("config").sources[&source_key]
+                    .skills
+                    .iter()
+                    .any(|skill| skill == name)
+            };
+            // Without `--prune` the vanished skill stays installed and tracked.
+            assert!(store.join("docx").is_dir());
+            assert!(std::fs::symlink_metadata(&linked).is_ok());
+            assert!(tracked("docx"));
+
+            updater.prune(&source_key, &report.missing).expect("prune");
+            assert!(!store.join("docx").exists());
+            assert!(std::fs::symlink_metadata(&linked).is_err());
+            assert!(!tracked("docx"));
+            assert!(tracked("pdf"));
+            assert!(store.join("pdf").is_dir());
+        });
+    }
+
+    #[test]
+    fn rollback_restores_the_previous_revision() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let origin = dir.path().join("origin");
+        write_skill(&origin, "pdf", "first");
+        git(&origin, &["init", "-q"]);
+        git(&origin, &["add", "-A"]);
+        git(&origin, &["commit", "-q", "-m", "Add pdf"]);
+        let first = head_revision(&origin).expect("head");
+
+        isolated(dir.path(), || {
+            let agents = resolve_agents(&["claude-code".to_string()]);
+            let source_key = Installer::new(format!("file://{}", origin.display()))
+                .agents(agents.clone())
+                .global(true)
+                .install()
+                .expect("install")
+                .source_key;
+
+            write_skill(&origin, "pdf", "second");
+            git(&origin, &["add", "-A"]);
+            git(&origin, &["commit", "-q", "-m", "Update pdf"]);
+            let second = head_revision(&origin).expect("head");
+
+            let updater = Updater::new(true).expect("updater").agents(agents);
+            let updates = updater.check().expect("check");
+            assert_eq!(updates.len(), 1);
+            updater.update(&updates[0]).expect("update");
+
+            let installed = canonical_skills_dir(true)
+                .expect("store")
+                .join("pdf")
+                .join("SKILL.md");
+            let source =
+                |updater: &Updater| updater.config().expect("config").sources[&source_key].clone();
+            assert_eq!(source(&updater).checksum.as_deref(), Some(second.as_str()));
+            assert!(
+                std::fs::read_to_string(&installed)
+                    .expect("read")
+                    .contains("second")
+            );
+
+            updater.rollback("pdf").expect("rollback");
+            let source = source(&updater);
+            assert_eq!(source.checksum.as_deref(), Some(first.as_str()));
+            assert!(source.history.is_empty());
+            assert!(
+                std::fs::read_to_string(&installed)
+                    .expect("read")
+                    .contains("first")
+            );
+        });
+    }
+}
@@ -0,0 +1,40 @@
+(function () {
+  var storageKey = "skil-docs-theme";
+  var root = document.documentElement;
+
+  function stored() {
+    try {
+      return localStorage.getItem(storageKey);
+    } catch (_) {
+      return null;
+    }
+  }
+
+  function current() {
+    var explicit = root.getAttribute("data-theme");
+    if (explicit) {
+      return explicit;
+    }
+    return window.matchMedia("(prefers-color-scheme: dark)").matches ? "dark" : "light";
+  }
+
+  var initial = stored();
+  if (initial === "light" || initial === "dark") {
+    root.setAttribute("data-theme", initial);
+  }
+
+  document.addEventListener("DOMContentLoaded", function () {
+    var toggle = document.querySelector(".theme-toggle");
+    if (!toggle) {
+      return;
+    }
+    toggle.hidden = false;
+    toggle.addEventListener("click", function () {
+      var next = current() === "dark" ? "light" : "dark";
+      root.setAttribute("data-theme", next);
+      try {
+        localStorage.setItem(storageKey, next);
+      } catch (_) {}
+    });
+  });
+})();
@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use skil_core::Result;
+use skil_core::git::blob_hash;
+
+/// File name of the build cache stored inside the output directory.
+pub(crate) const CACHE_FILE: &str = ".skil-docs-cache.json";
+
+/// Content keys from the previous build, used to skip unchanged output.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub(crate) struct BuildCache {
+    pub version: String,
+    pub assets: String,
+    pub pages: BTreeMap<String, String>,
+}
+
+impl BuildCache {
+    /// Reads the cache from an output directory, returning `None` when it is
+    /// missing, unreadable, or written by a different skil version.
+    pub fn read(output: &Path) -> Option<Self> {
+        let content = fs::read_to_string(output.join(CACHE_FILE)).ok()?;
+        let cache: Self = serde_json::from_str(&content).ok()?;
+        if cache.version != env!("CARGO_PKG_VERSION") {
+            return None;
+        }
+        Some(cache)
+    }
+
+    /// Writes the cache into the output directory.
+    pub fn write(&self, output: &Path) -> Result<()> {
+        fs::write(output.join(CACHE_FILE), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Returns true when the cached key for `slug` matches and the page still exists.
+    pub fn is_fresh(&self, output: &Path, slug: &str, key: &str) -> bool {
+        self.pages.get(slug).is_some_and(|cached| cached == key)
+            && output
+                .join("skills")
+                .join(slug)
+                .join("index.html")
+                .is_file()
+    }
+}
+
+/// Hashes a list of parts into a single cache key.
+pub(crate) fn cache_key(parts: &[&[u8]]) -> Result<String> {
+    let mut data = Vec::new();
+    for part in parts {
+        data.extend_from_slice(&(part.len() as u64).to_le_bytes());
+        data.extend_from_slice(part);
+    }
+    blob_hash(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_key_separates_parts() {
+        let joined = cache_key(&[b"ab", b"c"]).expect("key");
+        let split = cache_key(&[b"a", b"bc"]).expect("key");
+        assert_ne!(joined, split);
+    }
+
+    #[test]
+    fn read_ignores_cache_from_other_versions() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let cache = BuildCache {
+            version: "0.0.0-other".to_string(),
+            ..BuildCache::default()
+        };
+        cache.write(dir.path()).expect("write");
+        assert!(BuildCache::read(dir.path()).is_none());
+
+        let cache = BuildCache {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            ..BuildCache::default()
+        };
+        cache.write(dir.path()).expect("write");
+        assert!(BuildCache::read(dir.path()).is_some());
+    }
+}
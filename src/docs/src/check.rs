@@ -0,0 +1,272 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use pulldown_cmark::{Event, Options, Parser, Tag};
+
+use skil_core::annotation::{Annotation, AnnotationLevel};
+use skil_core::lint::{LintIssue, LintProfile, Severity, lint_skill};
+
+use crate::source::DocPage;
+
+/// Kind of reference found in a SKILL.md body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReferenceKind {
+    Link,
+    Image,
+}
+
+impl ReferenceKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ReferenceKind::Link => "link",
+            ReferenceKind::Image => "image",
+        }
+    }
+}
+
+/// A reference that could not be resolved.
+#[derive(Debug, PartialEq)]
+pub(crate) struct BrokenReference {
+    pub file: PathBuf,
+    pub line: usize,
+    pub kind: ReferenceKind,
+    pub target: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for BrokenReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: broken {} `{}` ({})",
+            self.file.display(),
+            self.line,
+            self.kind.as_str(),
+            self.target,
+            self.reason
+        )
+    }
+}
+
+/// Validates links and image references in every page's SKILL.md and the
+/// markdown files among its resources.
+pub(crate) fn check_pages(pages: &[DocPage]) -> Vec<BrokenReference> {
+    let slugs: HashSet<&str> = pages.iter().map(|page| page.slug.as_str()).collect();
+    let mut broken = Vec::new();
+    for page in pages {
+        let location = PathBuf::from(page.location());
+        broken.extend(check_markdown(
+            &location.join("SKILL.md"),
+            &page.skill.path,
+            &page.skill.raw_content,
+            &slugs,
+        ));
+        for resource in &page.skill.resources {
+            if resource.extension().is_none_or(|ext| ext != "md") {
+                continue;
+            }
+            let path = page.skill.path.join(resource);
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            broken.extend(check_markdown(
+                &location.join(resource),
+                path.parent().unwrap_or(&page.skill.path),
+                &content,
+                &slugs,
+            ));
+        }
+    }
+    broken
+}
+
+/// Checks every page's frontmatter against each agent profile.
+pub(crate) fn lint_pages(
+    pages: &[DocPage],
+    profiles: &[&LintProfile],
+) -> Vec<(PathBuf, LintIssue)> {
+    let mut issues = Vec::new();
+    for page in pages {
+        let file = Path::new(&page.location()).join("SKILL.md");
+        for profile in profiles {
+            for issue in lint_skill(page.skill, profile) {
+                issues.push((file.clone(), issue));
+            }
+        }
+    }
+    issues
+}
+
+/// GitHub Actions annotations for broken references and lint issues; values
+/// agents reject are errors, truncated ones warnings.
+pub(crate) fn annotations(
+    broken: &[BrokenReference],
+    issues: &[(PathBuf, LintIssue)],
+) -> Vec<Annotation> {
+    let references = broken.iter().map(|reference| {
+        Annotation::new(
+            AnnotationLevel::Error,
+            format!(
+                "broken {} `{}` ({})",
+                reference.kind.as_str(),
+                reference.target,
+                reference.reason
+            ),
+        )
+        .file(reference.file.to_string_lossy())
+        .line(reference.line)
+        .title("skil docs check")
+        .rule(format!("broken-{}", reference.kind.as_str()))
+    });
+    let lints = issues.iter().map(|(file, issue)| {
+        let (level, rule) = match issue.severity {
+            Severity::Rejected => (AnnotationLevel::Error, "agent-rejects"),
+            Severity::Truncated => (AnnotationLevel::Warning, "agent-truncates"),
+        };
+        Annotation::new(
+            level,
+            format!("{} ({})", issue.message, issue.severity.as_str()),
+        )
+        .file(file.to_string_lossy())
+        .line(issue.line)
+        .title(format!("skil docs check: {}", issue.agent))
+        .rule(rule)
+    });
+    references.chain(lints).collect()
+}
+
+/// Validates references in one markdown document.
+///
+/// Relative targets are resolved against `base`; site-absolute `/skills/<slug>/`
+/// links must point at a known skill slug. External URLs and in-page anchors are skipped.
+pub(crate) fn check_markdown(
+    file: &Path,
+    base: &Path,
+    content: &str,
+    slugs: &HashSet<&str>,
+) -> Vec<BrokenReference> {
+    let mut broken = Vec::new();
+    let parser = Parser::new_ext(content, Options::all()).into_offset_iter();
+    for (event, range) in parser {
+        let (kind, target) = match event {
+            Event::Start(Tag::Link { dest_url, .. }) => (ReferenceKind::Link, dest_url),
+            Event::Start(Tag::Image { dest_url, .. }) => (ReferenceKind::Image, dest_url),
+            _ => continue,
+        };
+        if let Some(reason) = check_target(base, &target, slugs) {
+            broken.push(BrokenReference {
+                file: file.to_path_buf(),
+                line: line_of(content, range.start),
+                kind,
+                target: target.to_string(),
+                reason,
+            });
+        }
+    }
+    broken
+}
+
+/// Returns why a target is broken, or `None` when it resolves (or is not checked).
+fn check_target(base: &Path, target: &str, slugs: &HashSet<&str>) -> Option<String> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Some("empty target".to_string());
+    }
+    if target.starts_with('#') || is_external(target) {
+        return None;
+    }
+
+    let path = target.split(['#', '?']).next().unwrap_or("");
+    if let Some(site_path) = path.strip_prefix('/') {
+        let mut parts = site_path.split('/').filter(|part| !part.is_empty());
+        return match (parts.next(), parts.next()) {
+            (None, _) => None,
+            (Some("skills"), Some(slug)) if slugs.contains(slug) => None,
+            (Some("skills"), Some(slug)) => Some(format!("unknown skill `{slug}`")),
+            _ => None,
+        };
+    }
+
+    let decoded = percent_decode(path);
+    if base.join(&decoded).exists() {
+        None
+    } else {
+        Some("file not found".to_string())
+    }
+}
+
+fn is_external(target: &str) -> bool {
+    target.starts_with("//")
+        || target
+            .split_once(':')
+            .is_some_and(|(scheme, _)| !scheme.is_empty() && !scheme.contains('/'))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0usize;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && i + 2 < bytes.len()
+            && let Some(byte) = std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            out.push(byte);
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())]
+        .bytes()
+        .filter(|b| *b == b'\n')
+        .count()
+        + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_markdown_reports_broken_references_with_lines() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(dir.path().join("references")).expect("mkdir");
+        std::fs::write(dir.path().join("references/api.md"), "api").expect("write");
+
+        let content = "---\nname: a\n---\n\
+            [ok](references/api.md)\n\
+            [missing](references/nope.md#section)\n\
+            ![logo](assets/logo%20dark.png)\n\
+            [web](https://example.com) [mail](mailto:a@b.c) [anchor](#top)\n\
+            [other](/skills/known/) [gone](/skills/unknown/)\n";
+        let slugs: HashSet<&str> = ["known"].into_iter().collect();
+
+        let broken = check_markdown(Path::new("SKILL.md"), dir.path(), content, &slugs);
+        let summary: Vec<(usize, ReferenceKind, &str)> = broken
+            .iter()
+            .map(|b| (b.line, b.kind, b.target.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (5, ReferenceKind::Link, "references/nope.md#section"),
+                (6, ReferenceKind::Image, "assets/logo%20dark.png"),
+                (8, ReferenceKind::Link, "/skills/unknown/"),
+            ]
+        );
+        assert!(broken[2].to_string().contains("unknown skill `unknown`"));
+        assert!(
+            annotations(&broken, &[])[0]
+                .to_string()
+                .starts_with("::error file=SKILL.md,line=5,title=skil docs check::broken link")
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::io::Write;
+
+use gix::features::zlib::stream::deflate;
+
+/// Bodies smaller than this are sent uncompressed; the framing overhead is not worth it.
+const MIN_COMPRESS_BYTES: usize = 256;
+
+/// Content encodings the docs server can produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Encoding {
+    Gzip,
+    Deflate,
+}
+
+impl Encoding {
+    /// Value used in the `Content-Encoding` header.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+        }
+    }
+}
+
+/// Picks the preferred supported encoding from an `Accept-Encoding` header.
+///
+/// Codings with `q=0` are treated as refused, and `*` only stands for codings
+/// the header does not name, so it never brings back a refused one. Among the
+/// rest the highest q-value wins, preferring gzip on ties.
+pub(crate) fn negotiate(accept_encoding: Option<&str>) -> Option<Encoding> {
+    let header = accept_encoding?;
+    let mut offered = Vec::new();
+    let mut wildcard = None;
+    for item in header.split(',') {
+        let mut parts = item.split(';');
+        let coding = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+        let quality = parts
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        match coding.as_str() {
+            "gzip" | "x-gzip" => offered.push((Encoding::Gzip, quality)),
+            "deflate" => offered.push((Encoding::Deflate, quality)),
+            "*" => wildcard = Some(quality),
+            _ => {}
+        }
+    }
+    if let Some(quality) = wildcard {
+        for encoding in [Encoding::Gzip, Encoding::Deflate] {
+            if !offered.iter().any(|(named, _)| *named == encoding) {
+                offered.push((encoding, quality));
+            }
+        }
+    }
+
+    let mut best: Option<(Encoding, f32)> = None;
+    for (encoding, quality) in offered {
+        if quality <= 0.0 {
+            continue;
+        }
+        let better = match best {
+            None => true,
+            Some((current, current_q)) => {
+                quality > current_q || (quality == current_q && current != Encoding::Gzip)
+            }
+        };
+        if better {
+            best = Some((encoding, quality));
+        }
+    }
+    best.map(|(encoding, _)| encoding)
+}
+
+/// Returns true for content types that benefit from compression.
+pub(crate) fn is_compressible(content_type: &str) -> bool {
+    content_type.starts_with("text/")
+        || content_type.starts_with("application/json")
+        || content_type.starts_with("application/javascript")
+        || content_type.starts_with("image/svg+xml")
+}
+
+/// Compresses `body` with the given encoding, or returns `None` when it is too small.
+pub(crate) fn encode(body: &[u8], encoding: Encoding) -> std::io::Result<Option<Vec<u8>>> {
+    if body.len() < MIN_COMPRESS_BYTES {
+        return Ok(None);
+    }
+    let zlib = zlib_compress(body)?;
+    let encoded = match encoding {
+        Encoding::Deflate => zlib,
+        Encoding::Gzip => zlib_to_gzip(&zlib, body),
+    };
+    Ok(Some(encoded))
+}
+
+fn zlib_compress(body: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut writer = deflate::Write::new(Vec::new());
+    writer.write_all(body)?;
+    writer.flush()?;
+    Ok(writer.into_inner())
+}
+
+/// Re-wraps a zlib stream as gzip: the raw deflate data is the same, only the
+/// header and trailer differ.
+fn zlib_to_gzip(zlib: &[u8], original: &[u8]) -> Vec<u8> {
+    let raw = &zlib[2..zlib.len().saturating_sub(4)];
+    let mut out = Vec::with_capacity(raw.len() + 18);
+    out.extend_from_slice(&[0x1f, 0x8b, 0x08, 0, 0, 0, 0, 0, 0, 0xff]);
+    out.extend_from_slice(raw);
+    out.extend_from_slice(&crc32fast::hash(original).to_le_bytes());
+    out.extend_from_slice(&(original.len() as u32).to_le_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiate_prefers_gzip_and_respects_q_values() {
+        assert_eq!(negotiate(Some("gzip, deflate, br")), Some(Encoding::Gzip));
+        assert_eq!(negotiate(Some("deflate, gzip")), Some(Encoding::Gzip));
+        assert_eq!(
+            negotiate(Some("gzip;q=0.5, deflate")),
+            Some(Encoding::Deflate)
+        );
+        assert_eq!(negotiate(Some("gzip;q=0, deflate;q=0")), None);
+        assert_eq!(negotiate(Some("br")), None);
+        assert_eq!(negotiate(None), None);
+    }
+
+    #[test]
+    fn wildcard_never_picks_a_refused_coding() {
+        assert_eq!(negotiate(Some("*")), Some(Encoding::Gzip));
+        assert_eq!(negotiate(Some("gzip;q=0, *")), Some(Encoding::Deflate));
+        assert_eq!(negotiate(Some("*, x-gzip;q=0")), Some(Encoding::Deflate));
+        assert_eq!(negotiate(Some("gzip;q=0, deflate;q=0, *")), None);
+        assert_eq!(
+            negotiate(Some("deflate;q=0.5, *;q=0.8")),
+            Some(Encoding::Gzip)
+        );
+        assert_eq!(negotiate(Some("br, *;q=0")), None);
+    }
+
+    #[test]
+    fn gzip_output_has_valid_framing() {
+        let body = "hello docs ".repeat(100);
+        let encoded = encode(body.as_bytes(), Encoding::Gzip)
+            .expect("encode")
+            .expect("compressed");
+        assert_eq!(&encoded[..3], &[0x1f, 0x8b, 0x08]);
+        assert!(encoded.len() < body.len());
+
+        let len = encoded.len();
+        let crc = u32::from_le_bytes(encoded[len - 8..len - 4].try_into().expect("crc"));
+        let size = u32::from_le_bytes(encoded[len - 4..].try_into().expect("size"));
+        assert_eq!(crc, crc32fast::hash(body.as_bytes()));
+        assert_eq!(size as usize, body.len());
+    }
+
+    #[test]
+    fn encode_skips_small_bodies() {
+        assert!(encode(b"tiny", Encoding::Gzip).expect("encode").is_none());
+    }
+}
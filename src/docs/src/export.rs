@@ -0,0 +1,147 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use skil_core::Result;
+
+use crate::manifest::ManifestSkill;
+use crate::manifest_entry;
+use crate::markdown::strip_frontmatter;
+use crate::source::DocPage;
+
+/// Output format of `skil docs export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+}
+
+#[derive(Serialize)]
+struct ExportedCatalog {
+    skills: Vec<ExportedSkill>,
+}
+
+/// A manifest entry together with the SKILL.md body.
+#[derive(Serialize)]
+struct ExportedSkill {
+    #[serde(flatten)]
+    entry: ManifestSkill,
+    content: String,
+}
+
+/// Renders every skill as one Markdown document, with headings shifted so
+/// each skill nests under its own `##` section.
+pub(crate) fn export_markdown(pages: &[DocPage]) -> Result<String> {
+    let mut out = String::from("# Skill Catalog\n\n");
+    for page in pages {
+        out.push_str(&format!(
+            "- [{}](#{}): {}\n",
+            page.skill.name,
+            page.slug,
+            page.skill.description.replace('\n', " ")
+        ));
+    }
+
+    for page in pages {
+        let entry = manifest_entry(page)?;
+        out.push_str(&format!("\n## {}\n\n", entry.name));
+        out.push_str(&format!("{}\n\n", entry.description.trim()));
+        out.push_str(&format!("- Source: `{}`\n", entry.source));
+        out.push_str(&format!("- Path: `{}`\n", entry.path));
+        if !entry.tags.is_empty() {
+            out.push_str(&format!("- Tags: {}\n", entry.tags.join(", ")));
+        }
+        out.push_str(&format!("- Install: `{}`\n\n", entry.install));
+        out.push_str(&demote_headings(
+            strip_frontmatter(&page.skill.raw_content).trim(),
+        ));
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Renders every skill as a JSON document with manifest fields and the SKILL.md body.
+pub(crate) fn export_json(pages: &[DocPage]) -> Result<String> {
+    let catalog = ExportedCatalog {
+        skills: pages
+            .iter()
+            .map(|page| {
+                Ok(ExportedSkill {
+                    entry: manifest_entry(page)?,
+                    content: strip_frontmatter(&page.skill.raw_content)
+                        .trim()
+                        .to_string(),
+                })
+            })
+            .collect::<Result<_>>()?,
+    };
+    let mut out = serde_json::to_string_pretty(&catalog)?;
+    out.push('\n');
+    Ok(out)
+}
+
+/// Pushes ATX headings two levels down, leaving fenced code blocks untouched.
+fn demote_headings(markdown: &str) -> String {
+    let mut out = String::with_capacity(markdown.len());
+    let mut fence: Option<&str> = None;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+        } else if trimmed.starts_with("```") {
+            fence = Some("```");
+        } else if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
+        } else if trimmed.starts_with('#') {
+            let level = trimmed.chars().take_while(|c| *c == '#').count();
+            let rest = &trimmed[level..];
+            if level <= 6 && (rest.is_empty() || rest.starts_with(' ')) {
+                out.push_str(&"#".repeat((level + 2).min(6)));
+                out.push_str(rest);
+                out.push('\n');
+                continue;
+            }
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::{collect_pages, load_sources};
+
+    #[test]
+    fn demote_headings_skips_code_blocks() {
+        let out = demote_headings("# Title\n```sh\n# comment\n```\n### Deep\n#tag");
+        assert_eq!(out, "### Title\n```sh\n# comment\n```\n##### Deep\n#tag\n");
+    }
+
+    #[test]
+    fn exports_markdown_and_json_catalogs() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            dir.path().join("SKILL.md"),
+            "---\nname: Alpha\ndescription: First skill\ntags: [ci]\n---\n# Usage\nRun it.\n",
+        )
+        .expect("write");
+        let source = dir.path().display().to_string();
+        let sources = load_sources(&[source], false).expect("sources");
+        let pages = collect_pages(&sources);
+
+        let markdown = export_markdown(&pages).expect("markdown");
+        assert!(markdown.starts_with("# Skill Catalog\n\n- [Alpha](#alpha): First skill\n"));
+        assert!(markdown.contains("\n## Alpha\n\nFirst skill\n"));
+        assert!(markdown.contains("- Tags: ci\n"));
+        assert!(markdown.contains("### Usage\nRun it.\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&export_json(&pages).expect("json")).expect("parse");
+        let skill = &json["skills"][0];
+        assert_eq!(skill["name"], "Alpha");
+        assert_eq!(skill["tags"][0], "ci");
+        assert_eq!(skill["content"], "# Usage\nRun it.");
+    }
+}
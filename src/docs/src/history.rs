@@ -0,0 +1,165 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{hosted_commit_url, normalize_origin_source, repo_origin_url};
+
+/// Maximum number of commits listed in a skill's history section.
+const HISTORY_LIMIT: usize = 20;
+
+/// One commit that touched a skill folder.
+pub(crate) struct HistoryEntry {
+    pub short_id: String,
+    pub date: String,
+    pub author: String,
+    pub subject: String,
+    pub url: Option<String>,
+}
+
+/// Git repository backing a docs source, used to render per-skill history.
+pub(crate) struct RepoHistory {
+    repo: gix::Repository,
+    root: PathBuf,
+    origin: Option<String>,
+}
+
+impl RepoHistory {
+    /// Opens the repository containing `path`, if there is one.
+    pub fn discover(path: &Path) -> Option<Self> {
+        let repo = gix::discover(path).ok()?;
+        let root = repo.workdir().map(Path::to_path_buf)?;
+        let root = fs::canonicalize(root).ok()?;
+        let origin = repo_origin_url(&repo).map(|origin| normalize_origin_source(&origin));
+        Some(Self { repo, root, origin })
+    }
+
+    /// Lists the most recent first-parent commits that changed the given skill folder.
+    pub fn skill_history(&self, skill_dir: &Path) -> Vec<HistoryEntry> {
+        let Ok(rel) = skill_dir.strip_prefix(&self.root) else {
+            return Vec::new();
+        };
+        self.collect(rel).unwrap_or_default()
+    }
+
+    /// Returns the tree id of the skill folder at HEAD, which changes whenever
+    /// its content or its history section would change.
+    pub fn folder_id_at_head(&self, skill_dir: &Path) -> Option<String> {
+        let rel = skill_dir.strip_prefix(&self.root).ok()?;
+        let commit = self.repo.head_commit().ok()?;
+        folder_id(&commit, rel).map(|id| id.to_string())
+    }
+
+    fn collect(&self, rel: &Path) -> Option<Vec<HistoryEntry>> {
+        let head = self.repo.head_id().ok()?;
+        let walk = head.ancestors().first_parent_only().all().ok()?;
+
+        let mut entries = Vec::new();
+        let mut pending: Option<(gix::Commit<'_>, Option<gix::ObjectId>)> = None;
+        for info in walk {
+            let Ok(info) = info else { break };
+            let Ok(commit) = info.object() else { break };
+            let folder_id = folder_id(&commit, rel);
+
+            if let Some((newer, newer_id)) = pending.take()
+                && newer_id.is_some()
+                && newer_id != folder_id
+            {
+                entries.push(self.entry(&newer));
+                if entries.len() >= HISTORY_LIMIT {
+                    return Some(entries);
+                }
+            }
+            pending = Some((commit, folder_id));
+        }
+
+        if let Some((oldest, Some(_))) = pending {
+            entries.push(self.entry(&oldest));
+        }
+        Some(entries)
+    }
+
+    fn entry(&self, commit: &gix::Commit<'_>) -> HistoryEntry {
+        let id = commit.id().to_string();
+        let (author, date) = commit
+            .author()
+            .map(|author| {
+                let date = author
+                    .time()
+                    .map(|time| time.format_or_unix(gix::date::time::format::SHORT))
+                    .unwrap_or_default();
+                (author.name.to_string(), date)
+            })
+            .unwrap_or_default();
+        let subject = commit
+            .message()
+            .map(|message| message.summary().to_string())
+            .unwrap_or_default();
+
+        HistoryEntry {
+            short_id: id.chars().take(7).collect(),
+            url: self
+                .origin
+                .as_deref()
+                .and_then(|origin| hosted_commit_url(origin, &id)),
+            date,
+            author,
+            subject,
+        }
+    }
+}
+
+/// Returns the tree id of the skill folder at the given commit, if it exists there.
+fn folder_id(commit: &gix::Commit<'_>, rel: &Path) -> Option<gix::ObjectId> {
+    let tree = commit.tree().ok()?;
+    if rel.as_os_str().is_empty() {
+        return Some(tree.id);
+    }
+    let entry = tree.lookup_entry_by_path(rel).ok()??;
+    Some(entry.object_id())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=Tester", "-c", "user.email=t@example.com"])
+            .args(args)
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn skill_history_lists_commits_touching_the_folder() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = fs::canonicalize(dir.path()).expect("canonical");
+        let skill = root.join("skills").join("alpha");
+        fs::create_dir_all(&skill).expect("create skill");
+        git(&root, &["init", "-q"]);
+
+        fs::write(skill.join("SKILL.md"), "v1").expect("write");
+        git(&root, &["add", "-A"]);
+        git(&root, &["commit", "-q", "-m", "Add alpha"]);
+
+        fs::write(root.join("README.md"), "readme").expect("write");
+        git(&root, &["add", "-A"]);
+        git(&root, &["commit", "-q", "-m", "Unrelated change"]);
+
+        fs::write(skill.join("SKILL.md"), "v2").expect("write");
+        git(&root, &["add", "-A"]);
+        git(&root, &["commit", "-q", "-m", "Update alpha"]);
+
+        let history = RepoHistory::discover(&root).expect("repo");
+        let entries = history.skill_history(&skill);
+        let subjects: Vec<&str> = entries.iter().map(|e| e.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["Update alpha", "Add alpha"]);
+        assert_eq!(entries[0].author, "Tester");
+        assert_eq!(entries[0].short_id.len(), 7);
+        assert!(entries[0].url.is_none());
+    }
+}
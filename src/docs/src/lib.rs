@@ -1,17 +1,34 @@
 #![allow(clippy::result_large_err)]
 
 use std::fs;
-use std::io::{Read, Write};
-use std::net::{TcpListener, TcpStream};
 use std::path::{Path, PathBuf};
 
 use clap::{Args, Subcommand};
 use gix::bstr::ByteSlice;
 use maud::{DOCTYPE, Markup, PreEscaped, html};
-use skil_core::skills::{Skill, discover_skills};
+use skil_core::agent::agent_configs;
+use skil_core::annotation::{OutputFormat, format_annotations};
+use skil_core::git::blob_hash;
+use skil_core::lint::{LintProfile, PROFILES, SPEC_PROFILE, Severity, profile};
+use skil_core::skills::parse_frontmatter;
 use skil_core::{Result, SkilError};
 
+use crate::cache::{BuildCache, cache_key};
+pub use crate::export::ExportFormat;
+use crate::manifest::{MANIFEST_FILE, ManifestSkill, SkillsManifest};
+use crate::source::{DocPage, DocSource, collect_pages, load_sources};
+use crate::theme::{Theme, render_template};
+
+mod cache;
+mod check;
+mod encoding;
+mod export;
+mod history;
+pub mod manifest;
 mod markdown;
+mod server;
+mod source;
+mod theme;
 
 #[derive(Args, Clone)]
 #[command(about = "Build and serve static docs for discovered skills")]
@@ -24,30 +41,74 @@ pub struct DocsArgs {
 pub enum DocsCommand {
     #[command(about = "Build a static website from repository skills")]
     Build(DocsBuildArgs),
-    #[command(about = "Build and serve skill docs locally")]
+    #[command(
+        about = "Build and serve skill docs locally",
+        long_about = "Build and serve skill docs locally.\n\nText assets are compressed with \
+                      gzip or deflate when the client accepts them; Brotli (br) is not supported."
+    )]
     Serve(DocsServeArgs),
+    #[command(about = "Check skill docs for broken links and file references")]
+    Check(DocsCheckArgs),
+    #[command(about = "Export the skill catalog as a single Markdown or JSON document")]
+    Export(DocsExportArgs),
 }
 
 #[derive(Args, Clone)]
 pub struct DocsBuildArgs {
     #[arg(long = "source", default_value = ".")]
-    pub source: PathBuf,
+    pub source: Vec<String>,
     #[arg(long = "output", default_value = "site")]
     pub output: PathBuf,
     #[arg(long = "full-depth")]
     pub full_depth: bool,
+    #[arg(long = "theme")]
+    pub theme: Option<PathBuf>,
+    #[arg(long = "clean")]
+    pub clean: bool,
 }
 
 #[derive(Args, Clone)]
 pub struct DocsServeArgs {
     #[arg(long = "source", default_value = ".")]
-    pub source: PathBuf,
+    pub source: Vec<String>,
     #[arg(long = "output", default_value = "site")]
     pub output: PathBuf,
     #[arg(long = "host", default_value = "127.0.0.1")]
     pub host: String,
     #[arg(long = "port", default_value_t = 4173)]
     pub port: u16,
+    #[arg(long = "strict-port")]
+    pub strict_port: bool,
+    #[arg(long = "full-depth")]
+    pub full_depth: bool,
+    #[arg(long = "theme")]
+    pub theme: Option<PathBuf>,
+    #[arg(long = "clean")]
+    pub clean: bool,
+}
+
+#[derive(Args, Clone)]
+pub struct DocsCheckArgs {
+    #[arg(long = "source", default_value = ".")]
+    pub source: Vec<String>,
+    #[arg(long = "full-depth")]
+    pub full_depth: bool,
+    /// Check frontmatter only against these agents' limits.
+    #[arg(short = 'a', long = "agent", num_args = 1..)]
+    pub agent: Vec<String>,
+    /// `text`, `github` for GitHub Actions annotations, or `sarif` for code scanning.
+    #[arg(long = "output", default_value = "text")]
+    pub output: OutputFormat,
+}
+
+#[derive(Args, Clone)]
+pub struct DocsExportArgs {
+    #[arg(long = "source", default_value = ".")]
+    pub source: Vec<String>,
+    #[arg(long = "format", value_enum, default_value_t = ExportFormat::Markdown)]
+    pub format: ExportFormat,
+    #[arg(long = "output")]
+    pub output: Option<PathBuf>,
     #[arg(long = "full-depth")]
     pub full_depth: bool,
 }
@@ -56,161 +117,514 @@ pub fn run_docs(args: DocsArgs) -> Result<()> {
     match args.command {
         DocsCommand::Build(args) => run_build(args),
         DocsCommand::Serve(args) => run_serve(args),
+        DocsCommand::Check(args) => run_check(args),
+        DocsCommand::Export(args) => run_export(args),
     }
 }
 
 pub fn run_build(args: DocsBuildArgs) -> Result<()> {
-    let source = fs::canonicalize(&args.source)?;
     let output = args.output;
-    let install_source = install_source_for(&source);
-
-    let mut skills = discover_skills(&source, None, args.full_depth)?;
-    if skills.is_empty() {
-        return Err(SkilError::Message(format!(
-            "No skills found in {}",
-            source.display()
-        )));
-    }
+    let theme = Theme::load(args.theme.as_deref())?;
+    let sources = load_sources(&args.source, args.full_depth)?;
+    let pages = collect_pages(&sources);
 
-    skills.sort_by_key(|a| a.name.to_lowercase());
-
-    if output.exists() {
+    let previous = if args.clean {
+        None
+    } else {
+        BuildCache::read(&output)
+    };
+    if previous.is_none() && output.exists() {
         fs::remove_dir_all(&output)?;
     }
+    let previous = previous.unwrap_or_default();
     fs::create_dir_all(output.join("skills"))?;
 
-    write_styles(&output)?;
-    write_index(&output, &skills)?;
-    for skill in &skills {
-        write_skill_page(&output, &source, &install_source, skill)?;
+    let theme_key = theme.fingerprint()?;
+    let mut cache = BuildCache {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        assets: cache_key(&[
+            STYLES.as_bytes(),
+            COLOR_SCHEME_SCRIPT.as_bytes(),
+            theme_key.as_bytes(),
+        ])?,
+        pages: Default::default(),
+    };
+
+    if cache.assets != previous.assets {
+        write_styles(&output)?;
+        theme.write_assets(&output)?;
+    }
+    write_index(&output, &theme, &sources, &pages)?;
+    write_llms_txt(&output, &pages)?;
+    write_manifest(&output, &pages)?;
+
+    let mut rebuilt = 0usize;
+    for page in &pages {
+        let key = page_cache_key(page, &theme_key)?;
+        if !previous.is_fresh(&output, &page.slug, &key) {
+            write_skill_page(&output, &theme, page)?;
+            rebuilt += 1;
+        }
+        cache.pages.insert(page.slug.clone(), key);
     }
+    for slug in previous.pages.keys() {
+        let dir = output.join("skills").join(slug);
+        if !cache.pages.contains_key(slug) && dir.exists() {
+            fs::remove_dir_all(dir)?;
+        }
+    }
+    cache.write(&output)?;
 
     println!(
-        "Built docs for {} skill(s) in {}",
-        skills.len(),
+        "Built docs for {} skill(s) ({} page(s) updated) in {}",
+        pages.len(),
+        rebuilt,
         output.display()
     );
     Ok(())
 }
 
+/// Key covering everything that influences a rendered skill page.
+fn page_cache_key(page: &DocPage, theme_key: &str) -> Result<String> {
+    let folder_id = page
+        .source
+        .history
+        .as_ref()
+        .and_then(|history| history.folder_id_at_head(&page.skill.path))
+        .unwrap_or_default();
+    let mut resources = Vec::new();
+    for file in &page.skill.resources {
+        resources.extend_from_slice(file.to_string_lossy().as_bytes());
+        resources.extend(fs::read(page.skill.path.join(file))?);
+    }
+    cache_key(&[
+        page.skill.raw_content.as_bytes(),
+        page.slug.as_bytes(),
+        page.location().as_bytes(),
+        page.source.install_source.as_bytes(),
+        folder_id.as_bytes(),
+        theme_key.as_bytes(),
+        &resources,
+    ])
+}
+
 pub fn run_serve(args: DocsServeArgs) -> Result<()> {
     run_build(DocsBuildArgs {
         source: args.source,
         output: args.output.clone(),
         full_depth: args.full_depth,
+        theme: args.theme,
+        clean: args.clean,
     })?;
 
-    let addr = format!("{}:{}", args.host, args.port);
-    let listener = TcpListener::bind(&addr)?;
+    let listener = server::bind(&args.host, args.port, args.strict_port)?;
+    let port = listener.local_addr()?.port();
     let root = fs::canonicalize(&args.output)?;
-    let docs_url = format!("http://{}", addr);
+    let docs_url = format!("http://{}:{}", args.host, port);
 
     println!("Serving docs at {}", docs_url);
     if let Err(err) = open::that(&docs_url) {
         eprintln!("Failed to open docs in browser: {err}");
     }
 
-    for stream in listener.incoming() {
-        let mut stream = match stream {
-            Ok(stream) => stream,
-            Err(err) => {
-                eprintln!("Connection error: {err}");
-                continue;
-            }
-        };
+    server::serve(listener, root)
+}
 
-        if let Err(err) = serve_request(&mut stream, &root) {
-            eprintln!("Request failed: {err}");
+pub fn run_check(args: DocsCheckArgs) -> Result<()> {
+    let profiles = lint_profiles(&args.agent)?;
+    let sources = load_sources(&args.source, args.full_depth)?;
+    let pages = collect_pages(&sources);
+    let broken = check::check_pages(&pages);
+    let issues = check::lint_pages(&pages, &profiles);
+    if args.output != OutputFormat::Text {
+        print!(
+            "{}",
+            format_annotations(args.output, &check::annotations(&broken, &issues))
+        );
+    } else {
+        for reference in &broken {
+            eprintln!("{reference}");
+        }
+        for (file, issue) in &issues {
+            eprintln!("{}:{issue}", file.display());
         }
     }
+    let rejected = issues
+        .iter()
+        .filter(|(_, issue)| issue.severity == Severity::Rejected)
+        .count();
+    if !broken.is_empty() || rejected > 0 {
+        return Err(SkilError::Message(format!(
+            "Found {} broken reference(s) and {} frontmatter value(s) agents reject in {} skill(s)",
+            broken.len(),
+            rejected,
+            pages.len()
+        )));
+    }
+    let summary = format!(
+        "Checked {} skill(s), no broken references or rejected frontmatter",
+        pages.len()
+    );
+    // Stdout holds the SARIF log.
+    if args.output == OutputFormat::Sarif {
+        eprintln!("{summary}");
+    } else {
+        println!("{summary}");
+    }
+    Ok(())
+}
 
+/// Lint profiles for the requested agents, or every profile when none are.
+fn lint_profiles(agents: &[String]) -> Result<Vec<&'static LintProfile>> {
+    if agents.is_empty() {
+        return Ok(PROFILES.iter().collect());
+    }
+    let known = agent_configs();
+    let mut profiles: Vec<&'static LintProfile> = Vec::new();
+    for agent in agents {
+        if agent != SPEC_PROFILE && !known.iter().any(|config| config.name == agent) {
+            return Err(SkilError::Message(format!("Unknown agent: {agent}")));
+        }
+        let profile = profile(agent);
+        if !profiles.iter().any(|seen| seen.agent == profile.agent) {
+            profiles.push(profile);
+        }
+    }
+    Ok(profiles)
+}
+
+pub fn run_export(args: DocsExportArgs) -> Result<()> {
+    let sources = load_sources(&args.source, args.full_depth)?;
+    let pages = collect_pages(&sources);
+    let document = match args.format {
+        ExportFormat::Markdown => export::export_markdown(&pages)?,
+        ExportFormat::Json => export::export_json(&pages)?,
+    };
+    match args.output {
+        Some(path) => {
+            if let Some(parent) = path.parent()
+                && !parent.as_os_str().is_empty()
+            {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, document)?;
+            eprintln!("Exported {} skill(s) to {}", pages.len(), path.display());
+        }
+        None => print!("{document}"),
+    }
     Ok(())
 }
 
 fn write_styles(output: &Path) -> Result<()> {
     fs::write(output.join("styles.css"), STYLES)?;
+    fs::write(output.join("color-scheme.js"), COLOR_SCHEME_SCRIPT)?;
     Ok(())
 }
 
-fn write_index(output: &Path, skills: &[Skill]) -> Result<()> {
-    let page = page_shell(
-        "Skill Docs",
+fn write_index(
+    output: &Path,
+    theme: &Theme,
+    sources: &[DocSource],
+    pages: &[DocPage],
+) -> Result<()> {
+    let title = "Skill Docs";
+    let content = if sources.len() > 1 {
         html! {
             h1 { "Skill Docs" }
-            p class="lead" { "Discovered skills in this repository." }
-            ul class="skills" {
-                @for skill in skills {
-                    li {
-                        a href={ "/skills/" (slugify(&skill.name)) "/" } { (&skill.name) }
-                        p { (&skill.description) }
-                    }
+            p class="lead" { "Discovered skills across " (sources.len()) " sources." }
+            @for source in sources {
+                section class="source" {
+                    h2 { (&source.label) }
+                    p class="meta" { (&source.install_source) }
+                    (skill_list(pages.iter().filter(|page| std::ptr::eq(page.source, source))))
                 }
             }
-        },
-    );
-    fs::write(output.join("index.html"), page.into_string())?;
+        }
+    } else {
+        html! {
+            h1 { "Skill Docs" }
+            p class="lead" { "Discovered skills in this repository." }
+            (skill_list(pages.iter()))
+        }
+    };
+
+    let page = match &theme.index_template {
+        Some(template) => render_page_template(template, theme, title, content, &[]),
+        None => page_shell(title, theme, content).into_string(),
+    };
+    fs::write(output.join("index.html"), page)?;
     Ok(())
 }
 
-fn write_skill_page(
-    output: &Path,
-    source_root: &Path,
-    install_source: &str,
-    skill: &Skill,
-) -> Result<()> {
-    let slug = slugify(&skill.name);
-    let dir = output.join("skills").join(slug);
+fn skill_list<'a>(pages: impl Iterator<Item = &'a DocPage<'a>>) -> Markup {
+    html! {
+        ul class="skills" {
+            @for page in pages {
+                li {
+                    a href={ "/skills/" (&page.slug) "/" } { (&page.skill.name) }
+                    p { (&page.skill.description) }
+                }
+            }
+        }
+    }
+}
+
+fn write_skill_page(output: &Path, theme: &Theme, page: &DocPage) -> Result<()> {
+    let skill = page.skill;
+    let dir = output.join("skills").join(&page.slug);
     fs::create_dir_all(&dir)?;
 
-    let content = markdown::markdown_to_html(markdown::strip_frontmatter(&skill.raw_content));
-    let location = skill
-        .path
-        .strip_prefix(source_root)
-        .unwrap_or(&skill.path)
-        .display()
-        .to_string();
-    let install_cmd = format!(
-        "skil add {} --skill {}",
-        shell_escape_single_arg(install_source),
-        shell_escape_single_arg(&skill.name)
-    );
+    let body = markdown::markdown_to_html(markdown::strip_frontmatter(&skill.raw_content));
+    let location = page.location();
+    let install_cmd = install_command(&page.source.install_source, &skill.name);
+    let history = page
+        .source
+        .history
+        .as_ref()
+        .map(|history| history.skill_history(&skill.path))
+        .unwrap_or_default();
+
+    let files = resource_list(&skill.resources);
 
     let title = format!("{} | Skill Docs", skill.name);
-    let page = page_shell(
-        &title,
-        html! {
-            p { a href="/" { "← All skills" } }
-            h1 { (&skill.name) }
-            p class="lead" { (&skill.description) }
-            p class="meta" { "Path: " (&location) }
-            h2 { "Install" }
-            pre { code { (&install_cmd) } }
-            article class="content" { (PreEscaped(content)) }
-        },
-    );
+    let content = html! {
+        p { a href="/" { "← All skills" } }
+        h1 { (&skill.name) }
+        p class="lead" { (&skill.description) }
+        p class="meta" { "Path: " (&location) }
+        h2 { "Install" }
+        (install_tabs(&page.source.install_source, &skill.name))
+        article class="content" { (PreEscaped(&body)) }
+        (files)
+        @if !history.is_empty() {
+            section class="history" {
+                h2 { "History" }
+                ul {
+                    @for entry in &history {
+                        li {
+                            span class="meta" { (&entry.date) " · " }
+                            @if let Some(url) = &entry.url {
+                                a href=(url) { code { (&entry.short_id) } }
+                            } @else {
+                                code { (&entry.short_id) }
+                            }
+                            " " (&entry.subject)
+                            span class="meta" { " — " (&entry.author) }
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    let page = match &theme.skill_template {
+        Some(template) => {
+            let name = html! { (&skill.name) }.into_string();
+            let description = html! { (&skill.description) }.into_string();
+            let path = html! { (&location) }.into_string();
+            let install = html! { (&install_cmd) }.into_string();
+            let files = files.into_string();
+            render_page_template(
+                template,
+                theme,
+                &title,
+                content,
+                &[
+                    ("name", &name),
+                    ("description", &description),
+                    ("path", &path),
+                    ("install", &install),
+                    ("body", &body),
+                    ("files", &files),
+                ],
+            )
+        }
+        None => page_shell(&title, theme, content).into_string(),
+    };
+
+    fs::write(dir.join("index.html"), page)?;
+    fs::write(dir.join("SKILL.md"), &skill.raw_content)?;
+    for file in &skill.resources {
+        let target = dir.join(file);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(skill.path.join(file), target)?;
+    }
+    Ok(())
+}
 
-    fs::write(dir.join("index.html"), page.into_string())?;
+/// Links to the files a skill ships in its resource folders, copied next to the page.
+fn resource_list(resources: &[PathBuf]) -> Markup {
+    html! {
+        @if !resources.is_empty() {
+            section class="files" {
+                h2 { "Files" }
+                ul {
+                    @for file in resources {
+                        @let href = file.to_string_lossy().replace('\\', "/");
+                        li { a href=(&href) { code { (&href) } } }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Writes an `llms.txt` index pointing at the raw SKILL.md of every skill.
+fn write_llms_txt(output: &Path, pages: &[DocPage]) -> Result<()> {
+    fs::write(output.join("llms.txt"), llms_txt(pages))?;
     Ok(())
 }
 
-fn page_shell(title: &str, body: Markup) -> Markup {
+/// Writes `skills.json`, a machine-readable index of all skills in the site.
+fn write_manifest(output: &Path, pages: &[DocPage]) -> Result<()> {
+    let manifest = SkillsManifest {
+        skills: pages.iter().map(manifest_entry).collect::<Result<_>>()?,
+    };
+    fs::write(
+        output.join(MANIFEST_FILE),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+    Ok(())
+}
+
+pub(crate) fn manifest_entry(page: &DocPage) -> Result<ManifestSkill> {
+    let skill = page.skill;
+    let tags = parse_frontmatter(&skill.raw_content)?
+        .map(|frontmatter| frontmatter.tags)
+        .unwrap_or_default();
+    Ok(ManifestSkill {
+        name: skill.name.clone(),
+        slug: page.slug.clone(),
+        description: skill.description.clone(),
+        tags,
+        install: install_command(&page.source.install_source, &skill.name),
+        source: page.source.install_source.clone(),
+        path: page.location(),
+        hash: blob_hash(skill.raw_content.as_bytes())?,
+    })
+}
+
+fn llms_txt(pages: &[DocPage]) -> String {
+    let mut out = String::from(
+        "# Skill Docs\n\n> Agent skills discovered in this repository.\n\n## Skills\n\n",
+    );
+    for page in pages {
+        out.push_str(&format!(
+            "- [{}](/skills/{}/SKILL.md): {}\n",
+            page.skill.name,
+            page.slug,
+            page.skill.description.replace('\n', " ")
+        ));
+    }
+    out
+}
+
+fn page_shell(title: &str, theme: &Theme, body: Markup) -> Markup {
     html! {
         (DOCTYPE)
         html {
-            head {
-                meta charset="utf-8";
-                meta name="viewport" content="width=device-width,initial-scale=1";
-                title { (title) }
-                link rel="stylesheet" href="/styles.css";
-            }
+            head { (page_head(title, theme)) }
             body {
+                (page_header(theme))
                 main { (body) }
+                @if let Some(footer) = &theme.footer {
+                    (PreEscaped(footer))
+                }
+            }
+        }
+    }
+}
+
+fn page_head(title: &str, theme: &Theme) -> Markup {
+    html! {
+        meta charset="utf-8";
+        meta name="viewport" content="width=device-width,initial-scale=1";
+        title { (title) }
+        link rel="stylesheet" href="/styles.css";
+        @if theme.styles.is_some() {
+            link rel="stylesheet" href="/theme.css";
+        }
+        script src="/color-scheme.js" {}
+    }
+}
+
+fn page_header(theme: &Theme) -> Markup {
+    html! {
+        button class="theme-toggle" type="button" aria-label="Toggle dark mode" hidden { "Theme" }
+        @if let Some(header) = &theme.header {
+            (PreEscaped(header))
+        } @else if let Some(logo) = theme.logo_href() {
+            header class="site-header" {
+                a href="/" { img class="logo" src=(logo) alt="Logo"; }
+            }
+        }
+    }
+}
+
+/// Renders a full-page theme template, exposing the built-in pieces as placeholders.
+fn render_page_template(
+    template: &str,
+    theme: &Theme,
+    title: &str,
+    content: Markup,
+    extra: &[(&str, &str)],
+) -> String {
+    let head = page_head(title, theme).into_string();
+    let header = page_header(theme).into_string();
+    let footer = theme.footer.clone().unwrap_or_default();
+    let title = html! { (title) }.into_string();
+    let content = content.into_string();
+    let logo = theme.logo_href().unwrap_or_default();
+
+    let mut values: Vec<(&str, &str)> = vec![
+        ("title", &title),
+        ("head", &head),
+        ("header", &header),
+        ("footer", &footer),
+        ("content", &content),
+        ("logo", &logo),
+    ];
+    values.extend_from_slice(extra);
+    render_template(template, &values)
+}
+
+/// Renders tabbed install snippets: a generic command plus one tab per known agent.
+fn install_tabs(install_source: &str, skill_name: &str) -> Markup {
+    let generic = install_command(install_source, skill_name);
+    html! {
+        div class="install-tabs" {
+            input type="radio" name="install-agent" id="install-any" checked;
+            label for="install-any" { "Any agent" }
+            div class="install-panel" {
+                pre { code { (&generic) } }
+            }
+            @for agent in agent_configs() {
+                @let id = format!("install-{}", agent.name);
+                @let project = format!("{generic} --agent {}", agent.name);
+                input type="radio" name="install-agent" id=(&id);
+                label for=(&id) { (agent.display_name) }
+                div class="install-panel" {
+                    p class="meta" { "Project (" code { (&agent.skills_dir) } ")" }
+                    pre { code { (&project) } }
+                    p class="meta" { "Global" }
+                    pre { code { (&project) " --global" } }
+                }
             }
         }
     }
 }
 
-fn slugify(name: &str) -> String {
+fn install_command(install_source: &str, skill_name: &str) -> String {
+    format!(
+        "skil add {} --skill {}",
+        shell_escape_single_arg(install_source),
+        shell_escape_single_arg(skill_name)
+    )
+}
+
+pub(crate) fn slugify(name: &str) -> String {
     let mut out = String::new();
     let mut prev_dash = false;
 
@@ -245,7 +659,7 @@ fn shell_escape_single_arg(value: &str) -> String {
     format!("'{}'", value.replace('\'', "'\\''"))
 }
 
-fn install_source_for(source_root: &Path) -> String {
+pub(crate) fn install_source_for(source_root: &Path) -> String {
     detect_repo_install_source(source_root).unwrap_or_else(|| source_root.display().to_string())
 }
 
@@ -273,7 +687,7 @@ fn detect_repo_install_source(source_root: &Path) -> Option<String> {
     Some(normalized_origin)
 }
 
-fn repo_origin_url(repo: &gix::Repository) -> Option<String> {
+pub(crate) fn repo_origin_url(repo: &gix::Repository) -> Option<String> {
     let remote = repo.find_remote("origin".as_bytes().as_bstr()).ok()?;
     let url = remote.url(gix::remote::Direction::Fetch)?;
     let url = url.to_string();
@@ -304,6 +718,17 @@ fn hosted_tree_url(origin: &str, branch: &str, rel: &str) -> Option<String> {
     }
 }
 
+pub(crate) fn hosted_commit_url(origin: &str, commit: &str) -> Option<String> {
+    let (host, owner, repo) = parse_hosted_origin(origin)?;
+    match host {
+        "github.com" | "codeberg.org" => {
+            Some(format!("https://{host}/{owner}/{repo}/commit/{commit}"))
+        }
+        "gitlab.com" => Some(format!("https://{host}/{owner}/{repo}/-/commit/{commit}")),
+        _ => None,
+    }
+}
+
 fn parse_hosted_origin(origin: &str) -> Option<(&'static str, String, String)> {
     for host in ["github.com", "gitlab.com", "codeberg.org"] {
         if let Some(rest) = origin.strip_prefix(&format!("https://{host}/")) {
@@ -338,111 +763,50 @@ fn parse_owner_repo(host: &'static str, rest: &str) -> Option<(&'static str, Str
     Some((host, owner, repo))
 }
 
-fn normalize_origin_source(origin: &str) -> String {
+pub(crate) fn normalize_origin_source(origin: &str) -> String {
     if let Some((host, owner, repo)) = parse_hosted_origin(origin) {
         return format!("https://{host}/{owner}/{repo}");
     }
     origin.to_string()
 }
 
-fn serve_request(stream: &mut TcpStream, root: &Path) -> Result<()> {
-    let mut buffer = [0u8; 8192];
-    let bytes = stream.read(&mut buffer)?;
-    if bytes == 0 {
-        return Ok(());
+const STYLES: &str = include_str!("../assets/styles.css");
+const COLOR_SCHEME_SCRIPT: &str = include_str!("../assets/color-scheme.js");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn llms_txt_links_raw_skill_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let skill_dir = dir.path().join("skills").join("web-design");
+        fs::create_dir_all(&skill_dir).expect("create skill dir");
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: Web Design\ndescription: Builds sites\n---\n# Web",
+        )
+        .expect("write skill");
+
+        let sources =
+            load_sources(&[dir.path().display().to_string()], false).expect("load sources");
+        let pages = collect_pages(&sources);
+
+        let out = llms_txt(&pages);
+        assert!(out.starts_with("# Skill Docs\n"));
+        assert!(out.contains("- [Web Design](/skills/web-design/SKILL.md): Builds sites\n"));
     }
 
-    let request = String::from_utf8_lossy(&buffer[..bytes]);
-    let mut parts = request.lines().next().unwrap_or("").split_whitespace();
-    let method = parts.next().unwrap_or("");
-    let path = parts.next().unwrap_or("/");
-
-    if method != "GET" && method != "HEAD" {
-        return write_plain(
-            stream,
-            405,
-            "Method Not Allowed",
-            "Method not allowed",
-            method == "HEAD",
+    #[test]
+    fn hosted_commit_url_supports_known_hosts() {
+        assert_eq!(
+            hosted_commit_url("https://github.com/acme/skills", "abc").as_deref(),
+            Some("https://github.com/acme/skills/commit/abc")
         );
+        assert_eq!(
+            hosted_commit_url("git@gitlab.com:acme/skills.git", "abc").as_deref(),
+            Some("https://gitlab.com/acme/skills/-/commit/abc")
+        );
+        assert!(hosted_commit_url("https://example.com/acme/skills", "abc").is_none());
     }
-
-    let mut relative = path.trim_start_matches('/').to_string();
-    if relative.is_empty() {
-        relative.push_str("index.html");
-    }
-    if relative.ends_with('/') {
-        relative.push_str("index.html");
-    }
-
-    let mut requested = root.join(&relative);
-    if requested.is_dir() {
-        requested = requested.join("index.html");
-    }
-
-    if !requested.exists() {
-        return write_plain(stream, 404, "Not Found", "Not found", method == "HEAD");
-    }
-
-    let canonical = fs::canonicalize(&requested)?;
-    if !canonical.starts_with(root) {
-        return write_plain(stream, 403, "Forbidden", "Forbidden", method == "HEAD");
-    }
-
-    let body = fs::read(&canonical)?;
-    let content_type = content_type_for(&canonical);
-    write_response(stream, 200, "OK", content_type, &body, method == "HEAD")
-}
-
-fn content_type_for(path: &Path) -> &'static str {
-    match path.extension().and_then(|ext| ext.to_str()).unwrap_or("") {
-        "html" => "text/html; charset=utf-8",
-        "css" => "text/css; charset=utf-8",
-        "js" => "application/javascript; charset=utf-8",
-        "json" => "application/json; charset=utf-8",
-        "svg" => "image/svg+xml",
-        "png" => "image/png",
-        "jpg" | "jpeg" => "image/jpeg",
-        _ => "application/octet-stream",
-    }
-}
-
-fn write_plain(
-    stream: &mut TcpStream,
-    status: u16,
-    status_text: &str,
-    body: &str,
-    head_only: bool,
-) -> Result<()> {
-    write_response(
-        stream,
-        status,
-        status_text,
-        "text/plain; charset=utf-8",
-        body.as_bytes(),
-        head_only,
-    )
-}
-
-fn write_response(
-    stream: &mut TcpStream,
-    status: u16,
-    status_text: &str,
-    content_type: &str,
-    body: &[u8],
-    head_only: bool,
-) -> Result<()> {
-    let header = format!(
-        "HTTP/1.1 {status} {status_text}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
-        body.len()
-    );
-
-    stream.write_all(header.as_bytes())?;
-    if !head_only {
-        stream.write_all(body)?;
-    }
-    stream.flush()?;
-    Ok(())
 }
-
-const STYLES: &str = include_str!("../assets/styles.css");
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+/// File name of the JSON manifest written at the root of a built docs site.
+pub const MANIFEST_FILE: &str = "skills.json";
+
+/// JSON index of every skill in a built docs site.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SkillsManifest {
+    pub skills: Vec<ManifestSkill>,
+}
+
+/// One skill entry in `skills.json`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ManifestSkill {
+    pub name: String,
+    pub slug: String,
+    pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub install: String,
+    #[serde(default)]
+    pub source: String,
+    pub path: String,
+    pub hash: String,
+}
+
+impl SkillsManifest {
+    /// Returns skills whose name, description, or tags contain the query (case-insensitive).
+    pub fn search(&self, query: &str) -> Vec<&ManifestSkill> {
+        let query = query.to_lowercase();
+        self.skills
+            .iter()
+            .filter(|skill| {
+                skill.name.to_lowercase().contains(&query)
+                    || skill.description.to_lowercase().contains(&query)
+                    || skill
+                        .tags
+                        .iter()
+                        .any(|tag| tag.to_lowercase().contains(&query))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, description: &str, tags: &[&str]) -> ManifestSkill {
+        ManifestSkill {
+            name: name.to_string(),
+            slug: name.to_lowercase(),
+            description: description.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            install: format!("skil add . --skill {name}"),
+            source: ".".to_string(),
+            path: name.to_lowercase(),
+            hash: String::new(),
+        }
+    }
+
+    #[test]
+    fn search_matches_name_description_and_tags() {
+        let manifest = SkillsManifest {
+            skills: vec![
+                entry("go-style", "Go conventions", &["golang"]),
+                entry("web-design", "Frontend layouts", &["css"]),
+                entry("release", "Cut releases", &["GoReleaser"]),
+            ],
+        };
+
+        let names: Vec<&str> = manifest
+            .search("go")
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["go-style", "release"]);
+        assert_eq!(manifest.search("FRONTEND").len(), 1);
+    }
+}
@@ -0,0 +1,562 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use jiff::fmt::rfc2822::{DateTimeParser, DateTimePrinter};
+use signal_hook::consts::TERM_SIGNALS;
+use skil_core::git::blob_hash;
+use skil_core::{Result, SkilError};
+
+use crate::encoding;
+
+/// Idle time after which a keep-alive connection is closed.
+const KEEP_ALIVE_TIMEOUT: Duration = Duration::from_secs(5);
+/// How often the accept loop checks for a shutdown request.
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Number of consecutive ports tried when the requested one is taken.
+const PORT_FALLBACK_ATTEMPTS: u16 = 20;
+/// Upper bound for the request line plus headers.
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+/// Upper bound for a request body we are willing to read and discard.
+const MAX_BODY_BYTES: u64 = 1024 * 1024;
+
+/// A parsed HTTP request head.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Request {
+    pub method: String,
+    pub path: String,
+    pub keep_alive: bool,
+    pub headers: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Returns the first header value with the given (case-insensitive) name.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Binds the docs server, trying the following ports when the requested one is taken.
+pub(crate) fn bind(host: &str, port: u16, strict_port: bool) -> Result<TcpListener> {
+    let attempts = if strict_port {
+        1
+    } else {
+        PORT_FALLBACK_ATTEMPTS
+    };
+    let mut last_err = None;
+    for offset in 0..attempts {
+        let Some(candidate) = port.checked_add(offset) else {
+            break;
+        };
+        match TcpListener::bind((host, candidate)) {
+            Ok(listener) => {
+                if offset > 0 {
+                    eprintln!("Port {port} is in use, using {candidate} instead");
+                }
+                return Ok(listener);
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::AddrInUse => last_err = Some(err),
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    let err = last_err.unwrap_or_else(|| std::io::ErrorKind::AddrInUse.into());
+    if strict_port {
+        return Err(SkilError::Message(format!(
+            "Port {port} is already in use: {err}"
+        )));
+    }
+    Err(SkilError::Message(format!(
+        "No free port found in {port}-{}: {err}",
+        port.saturating_add(attempts - 1)
+    )))
+}
+
+/// Serves files from `root` on one thread per connection until SIGINT/SIGTERM.
+///
+/// On shutdown it stops accepting, lets in-flight responses finish, and closes idle
+/// keep-alive connections before returning.
+pub(crate) fn serve(listener: TcpListener, root: PathBuf) -> Result<()> {
+    let shutdown = Arc::new(AtomicBool::new(false));
+    for signal in TERM_SIGNALS {
+        signal_hook::flag::register(*signal, Arc::clone(&shutdown))?;
+    }
+    listener.set_nonblocking(true)?;
+
+    let root = Arc::new(root);
+    let connections: Arc<Mutex<HashMap<u64, TcpStream>>> = Arc::default();
+    let mut workers = Vec::new();
+    let mut next_id = 0u64;
+
+    while !shutdown.load(Ordering::SeqCst) {
+        let stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(ACCEPT_POLL_INTERVAL);
+                continue;
+            }
+            Err(err) => {
+                eprintln!("Connection error: {err}");
+                continue;
+            }
+        };
+        stream.set_nonblocking(false)?;
+
+        let id = next_id;
+        next_id += 1;
+        if let Ok(clone) = stream.try_clone() {
+            lock(&connections).insert(id, clone);
+        }
+
+        let root = Arc::clone(&root);
+        let connections = Arc::clone(&connections);
+        workers.push(std::thread::spawn(move || {
+            if let Err(err) = handle_connection(stream, &root) {
+                eprintln!("Request failed: {err}");
+            }
+            lock(&connections).remove(&id);
+        }));
+        workers.retain(|worker| !worker.is_finished());
+    }
+
+    println!("Shutting down docs server...");
+    for stream in lock(&connections).values() {
+        // Unblocks idle keep-alive reads; responses being written still complete.
+        let _ = stream.shutdown(Shutdown::Read);
+    }
+    for worker in workers {
+        let _ = worker.join();
+    }
+    Ok(())
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Serves requests on one connection until the client closes it or it goes idle.
+fn handle_connection(stream: TcpStream, root: &Path) -> Result<()> {
+    stream.set_read_timeout(Some(KEEP_ALIVE_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+
+    loop {
+        let request = match read_request(&mut reader) {
+            Ok(Some(request)) => request,
+            Ok(None) => return Ok(()),
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) =>
+            {
+                return Ok(());
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::InvalidData => {
+                let response = Response::plain(400, "Bad Request", "Bad request");
+                write_response(&mut writer, &response, false, false)?;
+                return Ok(());
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let keep_alive = request.keep_alive;
+        serve_request(&mut writer, &request, root)?;
+        if !keep_alive {
+            return Ok(());
+        }
+    }
+}
+
+/// Reads one request head (and discards any body), returning `None` on a clean EOF.
+pub(crate) fn read_request<R: BufRead>(reader: &mut R) -> std::io::Result<Option<Request>> {
+    let mut consumed = 0usize;
+    let mut request_line = String::new();
+    loop {
+        request_line.clear();
+        let read = reader.read_line(&mut request_line)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        consumed += read;
+        // Tolerate stray blank lines between pipelined requests.
+        if !request_line.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(invalid_data("malformed request line"));
+    };
+    let version = parts.next().unwrap_or("HTTP/1.0");
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        let read = reader.read_line(&mut line)?;
+        consumed += read;
+        if consumed > MAX_HEADER_BYTES {
+            return Err(invalid_data("request headers too large"));
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        if read == 0 || line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let connection = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("connection"))
+        .map(|(_, value)| value.to_ascii_lowercase());
+    let keep_alive = match connection.as_deref() {
+        Some(value) if value.contains("close") => false,
+        Some(value) if value.contains("keep-alive") => true,
+        _ => version.eq_ignore_ascii_case("HTTP/1.1"),
+    };
+
+    let body_len = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse::<u64>().ok())
+        .unwrap_or(0);
+    if body_len > MAX_BODY_BYTES {
+        return Err(invalid_data("request body too large"));
+    }
+    std::io::copy(&mut reader.take(body_len), &mut std::io::sink())?;
+
+    Ok(Some(Request {
+        method: method.to_string(),
+        path: decode_path(target),
+        keep_alive,
+        headers,
+    }))
+}
+
+fn invalid_data(message: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Strips the query/fragment from a request target and percent-decodes it.
+fn decode_path(target: &str) -> String {
+    let path = target.split(['?', '#']).next().unwrap_or("/");
+    let bytes = path.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0usize;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && i + 2 < bytes.len()
+            && let Some(byte) = std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            out.push(byte);
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+/// A response ready to be written to the client.
+struct Response {
+    status: u16,
+    status_text: &'static str,
+    content_type: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn plain(status: u16, status_text: &'static str, body: &str) -> Self {
+        Self {
+            status,
+            status_text,
+            content_type: "text/plain; charset=utf-8",
+            headers: Vec::new(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+}
+
+fn serve_request<W: Write>(stream: &mut W, request: &Request, root: &Path) -> Result<()> {
+    let head_only = request.method == "HEAD";
+    let response = if request.method != "GET" && !head_only {
+        Response::plain(405, "Method Not Allowed", "Method not allowed")
+    } else {
+        resolve_file(request, root)?
+    };
+    write_response(stream, &response, head_only, request.keep_alive)
+}
+
+fn resolve_file(request: &Request, root: &Path) -> Result<Response> {
+    let mut relative = request.path.trim_start_matches('/').to_string();
+    if relative.is_empty() {
+        relative.push_str("index.html");
+    }
+    if relative.ends_with('/') {
+        relative.push_str("index.html");
+    }
+
+    let mut requested = root.join(&relative);
+    if requested.is_dir() {
+        requested = requested.join("index.html");
+    }
+
+    if !requested.exists() {
+        return Ok(Response::plain(404, "Not Found", "Not found"));
+    }
+
+    let canonical = fs::canonicalize(&requested)?;
+    if !canonical.starts_with(root) {
+        return Ok(Response::plain(403, "Forbidden", "Forbidden"));
+    }
+
+    let body = fs::read(&canonical)?;
+    let content_type = content_type_for(&canonical);
+    let etag = format!("\"{}\"", blob_hash(&body)?);
+    let last_modified = fs::metadata(&canonical)?
+        .modified()
+        .ok()
+        .and_then(|modified| jiff::Timestamp::try_from(modified).ok());
+
+    let mut headers = vec![
+        ("ETag", etag.clone()),
+        ("Cache-Control", "no-cache".to_string()),
+        ("Vary", "Accept-Encoding".to_string()),
+    ];
+    if let Some(modified) = last_modified
+        && let Ok(value) = DateTimePrinter::new().timestamp_to_rfc9110_string(&modified)
+    {
+        headers.push(("Last-Modified", value));
+    }
+
+    if is_not_modified(request, &etag, last_modified) {
+        return Ok(Response {
+            status: 304,
+            status_text: "Not Modified",
+            content_type,
+            headers,
+            body: Vec::new(),
+        });
+    }
+
+    let mut body = body;
+    if encoding::is_compressible(content_type)
+        && let Some(encoding) = encoding::negotiate(request.header("accept-encoding"))
+        && let Some(encoded) = encoding::encode(&body, encoding)?
+    {
+        headers.push(("Content-Encoding", encoding.as_str().to_string()));
+        body = encoded;
+    }
+
+    Ok(Response {
+        status: 200,
+        status_text: "OK",
+        content_type,
+        headers,
+        body,
+    })
+}
+
+/// Evaluates conditional request headers; `If-None-Match` takes precedence.
+fn is_not_modified(request: &Request, etag: &str, last_modified: Option<jiff::Timestamp>) -> bool {
+    if let Some(if_none_match) = request.header("if-none-match") {
+        return if_none_match
+            .split(',')
+            .map(|tag| tag.trim().trim_start_matches("W/"))
+            .any(|tag| tag == etag || tag == "*");
+    }
+
+    let (Some(since), Some(modified)) = (request.header("if-modified-since"), last_modified) else {
+        return false;
+    };
+    let Ok(since) = DateTimeParser::new().parse_timestamp(since) else {
+        return false;
+    };
+    modified.as_second() <= since.as_second()
+}
+
+fn content_type_for(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()).unwrap_or("") {
+        "html" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" => "application/javascript; charset=utf-8",
+        "json" => "application/json; charset=utf-8",
+        "md" => "text/markdown; charset=utf-8",
+        "txt" => "text/plain; charset=utf-8",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        _ => "application/octet-stream",
+    }
+}
+
+fn write_response<W: Write>(
+    stream: &mut W,
+    response: &Response,
+    head_only: bool,
+    keep_alive: bool,
+) -> Result<()> {
+    let connection = if keep_alive { "keep-alive" } else { "close" };
+    let mut header = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: {connection}\r\n",
+        response.status,
+        response.status_text,
+        response.content_type,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        header.push_str(&format!("{name}: {value}\r\n"));
+    }
+    header.push_str("\r\n");
+
+    stream.write_all(header.as_bytes())?;
+    if !head_only {
+        stream.write_all(&response.body)?;
+    }
+    stream.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn read_request_parses_pipelined_requests_and_discards_bodies() {
+        let raw = "POST /a HTTP/1.1\r\nContent-Length: 5\r\n\r\nhelloGET /b%20c?x=1 HTTP/1.1\r\nConnection: close\r\n\r\n";
+        let mut reader = Cursor::new(raw.as_bytes());
+
+        let first = read_request(&mut reader).expect("read").expect("request");
+        assert_eq!(first.method, "POST");
+        assert_eq!(first.path, "/a");
+        assert!(first.keep_alive);
+        assert_eq!(first.header("content-length"), Some("5"));
+
+        let second = read_request(&mut reader).expect("read").expect("request");
+        assert_eq!(second.method, "GET");
+        assert_eq!(second.path, "/b c");
+        assert!(!second.keep_alive);
+
+        assert!(read_request(&mut reader).expect("read").is_none());
+    }
+
+    #[test]
+    fn read_request_defaults_http10_to_close() {
+        let mut reader = Cursor::new(b"GET / HTTP/1.0\r\n\r\n".as_slice());
+        let request = read_request(&mut reader).expect("read").expect("request");
+        assert!(!request.keep_alive);
+    }
+
+    #[test]
+    fn read_request_rejects_malformed_request_line() {
+        let mut reader = Cursor::new(b"GARBAGE\r\n\r\n".as_slice());
+        let err = read_request(&mut reader).expect_err("malformed");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn serve_request_keeps_connection_open_when_requested() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = fs::canonicalize(dir.path()).expect("canonical");
+        fs::write(root.join("index.html"), "<h1>hi</h1>").expect("write");
+
+        let request = Request {
+            method: "GET".to_string(),
+            path: "/".to_string(),
+            keep_alive: true,
+            headers: Vec::new(),
+        };
+        let mut out = Vec::new();
+        serve_request(&mut out, &request, &root).expect("serve");
+        let response = String::from_utf8(out).expect("utf8");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Connection: keep-alive\r\n"));
+        assert!(response.ends_with("<h1>hi</h1>"));
+    }
+
+    fn get(path: &str, headers: &[(&str, &str)]) -> Request {
+        Request {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            keep_alive: true,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn serve_request_compresses_and_revalidates() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = fs::canonicalize(dir.path()).expect("canonical");
+        fs::write(root.join("styles.css"), "body { color: red; }\n".repeat(50)).expect("write");
+
+        let mut out = Vec::new();
+        let request = get("/styles.css", &[("Accept-Encoding", "gzip")]);
+        serve_request(&mut out, &request, &root).expect("serve");
+        let response = String::from_utf8_lossy(&out).to_string();
+        assert!(response.contains("Content-Encoding: gzip\r\n"));
+        assert!(response.contains("Last-Modified: "));
+        let etag = response
+            .lines()
+            .find_map(|line| line.strip_prefix("ETag: "))
+            .expect("etag")
+            .to_string();
+
+        let mut out = Vec::new();
+        let request = get("/styles.css", &[("If-None-Match", &etag)]);
+        serve_request(&mut out, &request, &root).expect("serve");
+        let response = String::from_utf8_lossy(&out).to_string();
+        assert!(response.starts_with("HTTP/1.1 304 Not Modified\r\n"));
+        assert!(response.contains("Content-Length: 0\r\n"));
+    }
+
+    #[test]
+    fn serve_request_sends_identity_for_unsupported_encodings() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = fs::canonicalize(dir.path()).expect("canonical");
+        let css = "body { color: red; }\n".repeat(50);
+        fs::write(root.join("styles.css"), &css).expect("write");
+
+        let mut out = Vec::new();
+        let request = get("/styles.css", &[("Accept-Encoding", "br")]);
+        serve_request(&mut out, &request, &root).expect("serve");
+        let response = String::from_utf8(out).expect("utf8");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Vary: Accept-Encoding\r\n"));
+        assert!(!response.contains("Content-Encoding"));
+        assert!(response.ends_with(&css));
+    }
+
+    #[test]
+    fn bind_falls_back_to_next_port_unless_strict() {
+        let taken = TcpListener::bind(("127.0.0.1", 0)).expect("bind");
+        let port = taken.local_addr().expect("addr").port();
+
+        let err = bind("127.0.0.1", port, true).expect_err("strict port should fail");
+        assert!(err.to_string().contains("already in use"));
+
+        let listener = bind("127.0.0.1", port, false).expect("fallback");
+        assert_ne!(listener.local_addr().expect("addr").port(), port);
+    }
+}
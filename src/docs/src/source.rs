@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use skil_core::download::download_source;
+use skil_core::git::{clone_repo, init_submodules};
+use skil_core::paths::scratch_dir;
+use skil_core::skills::{DiscoverOptions, Skill, discover_skills};
+use skil_core::source::{Source, parse_source};
+use skil_core::{Result, SkilError};
+
+use crate::history::RepoHistory;
+use crate::{install_source_for, slugify};
+
+/// One `--source` root with the skills discovered in it.
+pub(crate) struct DocSource {
+    pub label: String,
+    pub root: PathBuf,
+    pub install_source: String,
+    pub skills: Vec<Skill>,
+    pub history: Option<RepoHistory>,
+    _temp: Option<tempfile::TempDir>,
+}
+
+/// A skill page in the built site, tied to the source it came from.
+pub(crate) struct DocPage<'a> {
+    pub skill: &'a Skill,
+    pub slug: String,
+    pub source: &'a DocSource,
+}
+
+impl DocPage<'_> {
+    /// Path of the skill folder relative to its source root.
+    pub fn location(&self) -> String {
+        self.skill
+            .path
+            .strip_prefix(&self.source.root)
+            .unwrap_or(&self.skill.path)
+            .display()
+            .to_string()
+    }
+}
+
+/// Resolves a `--source` value (local path or git source) and discovers its skills.
+pub(crate) fn load_source(source: &str, full_depth: bool) -> Result<DocSource> {
+    let (root, install_source, label, temp) = match parse_source(source)? {
+        Source::Local { path } => {
+            let install_source = install_source_for(&path);
+            let label = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| install_source.clone());
+            (path, install_source, label, None)
+        }
+        Source::Download { url, kind } => {
+            let temp_dir = scratch_dir("download-")?;
+            println!("Downloading {}...", url);
+            download_source(&url, &kind, temp_dir.path())?;
+            let root = fs::canonicalize(temp_dir.path())?;
+            (root, source.to_string(), url, Some(temp_dir))
+        }
+        Source::Git { url, subpath, info } => {
+            let temp_dir = scratch_dir("clone-")?;
+            println!("Cloning {}...", url);
+            clone_repo(&url, temp_dir.path())?;
+            let base = fs::canonicalize(temp_dir.path())?;
+            init_submodules(&base, subpath.as_slice())?;
+            let root = subpath.map(|p| base.join(p)).unwrap_or(base);
+            (root, source.to_string(), info.source_id, Some(temp_dir))
+        }
+    };
+
+    let mut skills = discover_skills(
+        &root,
+        None,
+        &DiscoverOptions {
+            full_depth,
+            ..Default::default()
+        },
+    )?
+    .skills;
+    skills.sort_by_key(|a| a.name.to_lowercase());
+
+    let history = RepoHistory::discover(&root);
+
+    Ok(DocSource {
+        label,
+        root,
+        install_source,
+        skills,
+        history,
+        _temp: temp,
+    })
+}
+
+/// Loads every source, failing when none of them contain skills.
+pub(crate) fn load_sources(sources: &[String], full_depth: bool) -> Result<Vec<DocSource>> {
+    let mut loaded = Vec::new();
+    for source in sources {
+        let doc_source = load_source(source, full_depth)?;
+        if doc_source.skills.is_empty() {
+            eprintln!("No skills found in {}", doc_source.root.display());
+        }
+        loaded.push(doc_source);
+    }
+
+    if loaded.iter().all(|source| source.skills.is_empty()) {
+        return Err(SkilError::Message(format!(
+            "No skills found in {}",
+            sources.join(", ")
+        )));
+    }
+    Ok(loaded)
+}
+
+/// Flattens sources into pages, disambiguating slugs that collide across sources.
+pub(crate) fn collect_pages(sources: &[DocSource]) -> Vec<DocPage<'_>> {
+    let mut used = HashSet::new();
+    let mut pages = Vec::new();
+    for source in sources {
+        for skill in &source.skills {
+            let mut slug = slugify(&skill.name);
+            if !used.insert(slug.clone()) {
+                slug = format!("{}-{}", slugify(&source.label), slug);
+                used.insert(slug.clone());
+            }
+            pages.push(DocPage {
+                skill,
+                slug,
+                source,
+            });
+        }
+    }
+    pages.sort_by_key(|page| page.skill.name.to_lowercase());
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_skill(root: &std::path::Path, dir: &str, name: &str) {
+        let skill_dir = root.join("skills").join(dir);
+        fs::create_dir_all(&skill_dir).expect("create skill dir");
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: Desc\n---\n# {name}"),
+        )
+        .expect("write skill");
+    }
+
+    #[test]
+    fn collect_pages_disambiguates_colliding_slugs() {
+        let first = tempfile::tempdir().expect("tempdir");
+        let second = tempfile::tempdir().expect("tempdir");
+        write_skill(first.path(), "review", "review");
+        write_skill(second.path(), "review", "review");
+        write_skill(second.path(), "deploy", "deploy");
+
+        let sources = load_sources(
+            &[
+                first.path().display().to_string(),
+                second.path().display().to_string(),
+            ],
+            false,
+        )
+        .expect("load sources");
+        let pages = collect_pages(&sources);
+
+        let slugs: Vec<&str> = pages.iter().map(|page| page.slug.as_str()).collect();
+        let second_label = slugify(&sources[1].label);
+        assert_eq!(
+            slugs,
+            vec!["deploy", "review", &format!("{second_label}-review")]
+        );
+    }
+}
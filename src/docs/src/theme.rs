@@ -0,0 +1,161 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use skil_core::{Result, SkilError};
+
+use crate::cache::cache_key;
+
+const HEADER_FILE: &str = "header.html";
+const FOOTER_FILE: &str = "footer.html";
+const INDEX_FILE: &str = "index.html";
+const SKILL_FILE: &str = "skill.html";
+const STYLES_FILE: &str = "styles.css";
+const LOGO_CANDIDATES: &[&str] = &["logo.svg", "logo.png", "logo.jpg", "logo.jpeg"];
+
+/// Optional overrides loaded from a `--theme` directory.
+///
+/// Every piece is optional; anything missing falls back to the built-in templates.
+#[derive(Debug, Clone, Default)]
+pub struct Theme {
+    pub header: Option<String>,
+    pub footer: Option<String>,
+    pub index_template: Option<String>,
+    pub skill_template: Option<String>,
+    pub styles: Option<String>,
+    pub logo: Option<PathBuf>,
+}
+
+impl Theme {
+    /// Loads a theme from a directory, or the built-in theme when `dir` is `None`.
+    pub fn load(dir: Option<&Path>) -> Result<Self> {
+        let Some(dir) = dir else {
+            return Ok(Self::default());
+        };
+        if !dir.is_dir() {
+            return Err(SkilError::Message(format!(
+                "Theme directory does not exist: {}",
+                dir.display()
+            )));
+        }
+
+        Ok(Self {
+            header: read_optional(&dir.join(HEADER_FILE))?,
+            footer: read_optional(&dir.join(FOOTER_FILE))?,
+            index_template: read_optional(&dir.join(INDEX_FILE))?,
+            skill_template: read_optional(&dir.join(SKILL_FILE))?,
+            styles: read_optional(&dir.join(STYLES_FILE))?,
+            logo: LOGO_CANDIDATES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|path| path.is_file()),
+        })
+    }
+
+    /// Returns the site-relative URL of the logo, if the theme ships one.
+    pub fn logo_href(&self) -> Option<String> {
+        let name = self.logo.as_ref()?.file_name()?.to_str()?;
+        Some(format!("/{name}"))
+    }
+
+    /// Returns a key that changes whenever any part of the theme changes.
+    pub fn fingerprint(&self) -> Result<String> {
+        let logo = match &self.logo {
+            Some(path) => fs::read(path)?,
+            None => Vec::new(),
+        };
+        let part = |value: &Option<String>| value.as_deref().unwrap_or("").as_bytes().to_vec();
+        cache_key(&[
+            &part(&self.header),
+            &part(&self.footer),
+            &part(&self.index_template),
+            &part(&self.skill_template),
+            &part(&self.styles),
+            &logo,
+        ])
+    }
+
+    /// Copies theme assets (custom CSS and logo) into the output directory.
+    pub fn write_assets(&self, output: &Path) -> Result<()> {
+        if let Some(styles) = &self.styles {
+            fs::write(output.join("theme.css"), styles)?;
+        }
+        if let Some(logo) = &self.logo
+            && let Some(name) = logo.file_name()
+        {
+            fs::copy(logo, output.join(name))?;
+        }
+        Ok(())
+    }
+}
+
+/// Replaces `{{ key }}` placeholders in a template with the given values.
+///
+/// Unknown placeholders are left untouched so typos stay visible in the output.
+pub fn render_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let key = after[..end].trim();
+        match values.iter().find(|(name, _)| *name == key) {
+            Some((_, value)) => out.push_str(value),
+            None => out.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    Ok(Some(fs::read_to_string(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_template_replaces_known_placeholders() {
+        let out = render_template(
+            "<h1>{{ title }}</h1>{{content}}",
+            &[("title", "Docs"), ("content", "<p>hi</p>")],
+        );
+        assert_eq!(out, "<h1>Docs</h1><p>hi</p>");
+    }
+
+    #[test]
+    fn render_template_keeps_unknown_placeholders() {
+        let out = render_template("{{ missing }} {{ title", &[("title", "Docs")]);
+        assert_eq!(out, "{{ missing }} {{ title");
+    }
+
+    #[test]
+    fn load_reads_overrides_and_logo() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(dir.path().join("header.html"), "<nav>Acme</nav>").expect("write header");
+        fs::write(dir.path().join("styles.css"), "body{}").expect("write css");
+        fs::write(dir.path().join("logo.svg"), "<svg/>").expect("write logo");
+
+        let theme = Theme::load(Some(dir.path())).expect("load");
+        assert_eq!(theme.header.as_deref(), Some("<nav>Acme</nav>"));
+        assert!(theme.footer.is_none());
+        assert_eq!(theme.styles.as_deref(), Some("body{}"));
+        assert_eq!(theme.logo_href().as_deref(), Some("/logo.svg"));
+    }
+
+    #[test]
+    fn load_rejects_missing_directory() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let err = Theme::load(Some(&dir.path().join("nope"))).expect_err("missing theme");
+        assert!(err.to_string().contains("Theme directory does not exist"));
+    }
+}
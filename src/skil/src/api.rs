@@ -0,0 +1,540 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+use clap::Args;
+use serde_json::{Value, json};
+use skil_core::config::{config_location, find_source, read_config};
+use skil_core::discovery::source_key;
+use skil_core::install::canonical_skills_dir;
+use skil_core::source::parse_source;
+use skil_core::updater::Updater;
+use skil_core::{Result, SkilError};
+
+use crate::cli::{self, AddArgs, RemoveArgs};
+use crate::ui;
+
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Arguments for `skills serve-api`.
+#[derive(Args, Clone)]
+#[command(about = "Serve a local JSON API for editor integrations")]
+pub struct ServeApiArgs {
+    #[arg(long = "host", default_value = "127.0.0.1")]
+    pub host: String,
+    #[arg(long = "port", default_value_t = 4174)]
+    pub port: u16,
+}
+
+/// Who may call the API: clients that send the token printed at startup,
+/// address the server by a loopback name or the bound host, and are not web
+/// pages, which always send an `Origin`.
+struct Access {
+    token: String,
+    hosts: Vec<String>,
+}
+
+impl Access {
+    fn new(token: String, bound_host: &str) -> Self {
+        let mut hosts: Vec<String> = ["127.0.0.1", "localhost", "[::1]"]
+            .iter()
+            .map(|host| host.to_string())
+            .collect();
+        if !hosts.iter().any(|host| host == bound_host) {
+            hosts.push(bound_host.to_string());
+        }
+        Self { token, hosts }
+    }
+
+    /// Rejects a request by its headers, before its body is read.
+    fn check(
+        &self,
+        method: &str,
+        headers: &[(String, String)],
+    ) -> std::result::Result<(), ApiError> {
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        };
+        if header("origin").is_some() {
+            return Err(ApiError::new(
+                403,
+                "Requests from web pages are not allowed",
+            ));
+        }
+        let host = header("host").unwrap_or_default();
+        let host = match host.rsplit_once(':') {
+            Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name,
+            _ => host,
+        };
+        if !self
+            .hosts
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(host))
+        {
+            return Err(ApiError::new(403, format!("Host {host} is not allowed")));
+        }
+        let token = header("authorization").and_then(|value| value.strip_prefix("Bearer "));
+        if !token.is_some_and(|token| constant_time_eq(token.trim(), &self.token)) {
+            return Err(ApiError::new(401, "Missing or wrong API token"));
+        }
+        let has_body = header("content-length").is_some_and(|length| length.trim() != "0")
+            || header("transfer-encoding").is_some();
+        let is_json = header("content-type").is_some_and(|value| {
+            value
+                .split(';')
+                .next()
+                .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"))
+        });
+        if (method == "POST" || has_body) && !is_json {
+            return Err(ApiError::new(415, "Content-Type must be application/json"));
+        }
+        Ok(())
+    }
+}
+
+/// Compares tokens without returning early on the first differing byte.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// A random token for [`Access`], as 64 hex digits.
+fn generate_token() -> Result<String> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes)
+        .map_err(|err| SkilError::Message(format!("Could not generate an API token: {err}")))?;
+    Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+/// Parsed API request.
+#[derive(Debug)]
+struct ApiRequest {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    body: Value,
+}
+
+impl ApiRequest {
+    fn query_flag(&self, key: &str) -> bool {
+        self.query
+            .iter()
+            .any(|(name, value)| name == key && matches!(value.as_str(), "" | "1" | "true"))
+    }
+
+    fn query_values(&self, key: &str) -> Vec<String> {
+        self.query
+            .iter()
+            .filter(|(name, _)| name == key)
+            .map(|(_, value)| value.clone())
+            .collect()
+    }
+}
+
+/// Error returned to API clients as `{"error": "..."}`.
+#[derive(Debug)]
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<SkilError> for ApiError {
+    fn from(err: SkilError) -> Self {
+        Self::new(500, err.to_string())
+    }
+}
+
+pub fn run_serve_api(args: ServeApiArgs) -> Result<()> {
+    ui::use_stderr_only();
+    let listener = TcpListener::bind((args.host.as_str(), args.port)).map_err(|err| {
+        SkilError::Message(format!("Failed to bind {}:{}: {err}", args.host, args.port))
+    })?;
+    let addr = listener.local_addr()?;
+    if !addr.ip().is_loopback() {
+        ui::warn("API is reachable from other machines; it can install and remove skills");
+    }
+    let access = Access::new(generate_token()?, &args.host);
+    ui::info(&format!("Serving skil API at http://{addr}"));
+    ui::info(&format!(
+        "Send the header: Authorization: Bearer {}",
+        access.token
+    ));
+    println!(
+        "{}",
+        json!({ "url": format!("http://{addr}"), "token": access.token })
+    );
+
+    // Requests are handled one at a time so installs and removals never race.
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(err) = handle_connection(stream, &access) {
+                    ui::warn(&format!("Request failed: {err}"));
+                }
+            }
+            Err(err) => ui::warn(&format!("Failed to accept connection: {err}")),
+        }
+    }
+    Ok(())
+}
+
+fn handle_connection(mut stream: TcpStream, access: &Access) -> Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    let (status, body) = match read_request(&stream, access) {
+        Ok(request) => match route(&request) {
+            Ok(body) => (200, body),
+            Err(err) => (err.status, json!({ "error": err.message })),
+        },
+        Err(err) => (err.status, json!({ "error": err.message })),
+    };
+    let payload = serde_json::to_vec(&body)?;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        reason(status),
+        payload.len()
+    )?;
+    stream.write_all(&payload)?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn read_request(stream: &TcpStream, access: &Access) -> std::result::Result<ApiRequest, ApiError> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader
+        .read_line(&mut line)
+        .map_err(|err| ApiError::new(400, err.to_string()))?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(ApiError::new(400, "Malformed request line"));
+    };
+
+    let mut headers = Vec::new();
+    loop {
+        let mut header = String::new();
+        let read = reader
+            .read_line(&mut header)
+            .map_err(|err| ApiError::new(400, err.to_string()))?;
+        let header = header.trim_end();
+        if read == 0 || header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    access.check(method, &headers)?;
+    if headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case("transfer-encoding"))
+    {
+        return Err(ApiError::new(411, "Content-Length required"));
+    }
+    let content_length: usize = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .map(|(_, value)| value.parse())
+        .transpose()
+        .map_err(|_| ApiError::new(400, "Invalid Content-Length"))?
+        .unwrap_or(0);
+    if content_length > MAX_BODY_BYTES {
+        return Err(ApiError::new(413, "Request body too large"));
+    }
+
+    let mut body = vec![0u8; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|err| ApiError::new(400, err.to_string()))?;
+    let body = if body.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&body)
+            .map_err(|err| ApiError::new(400, format!("Invalid JSON body: {err}")))?
+    };
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    Ok(ApiRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        query: parse_query(query),
+        body,
+    })
+}
+
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let decode = |value: &str| {
+                urlencoding::decode(&value.replace('+', " "))
+                    .map(|value| value.into_owned())
+                    .unwrap_or_else(|_| value.to_string())
+            };
+            (decode(name), decode(value))
+        })
+        .collect()
+}
+
+fn route(request: &ApiRequest) -> std::result::Result<Value, ApiError> {
+    let segments: Vec<&str> = request
+        .path
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["v1", "health"]) => Ok(json!({ "version": env!("CARGO_PKG_VERSION") })),
+        ("GET", ["v1", "skills"]) => list_skills(request),
+        ("POST", ["v1", "skills"]) => add_skills(request),
+        ("DELETE", ["v1", "skills", name]) => remove_skill(request, name),
+        ("GET", ["v1", "updates"]) => check_updates(),
+        (_, ["v1", "health" | "skills" | "updates", ..]) => {
+            Err(ApiError::new(405, "Method not allowed"))
+        }
+        _ => Err(ApiError::new(404, "Not found")),
+    }
+}
+
+fn list_skills(request: &ApiRequest) -> std::result::Result<Value, ApiError> {
+    let dir = canonical_skills_dir(request.query_flag("global"))?;
+    let skills: Vec<Value> = cli::installed_skills(&dir)?
+        .into_iter()
+        .map(|skill| {
+            json!({
+                "name": skill.name,
+                "description": skill.description,
+                "path": skill.path,
+            })
+        })
+        .collect();
+    Ok(json!({ "skills": skills }))
+}
+
+fn add_skills(request: &ApiRequest) -> std::result::Result<Value, ApiError> {
+    let body = &request.body;
+    let source = body
+        .get("source")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ApiError::new(400, "Missing required field: source"))?;
+    let strings = |key: &str| -> Vec<String> {
+        body.get(key)
+            .and_then(Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut skills = strings("skills");
+    if skills.is_empty() {
+        skills.push("*".to_string());
+    }
+    let (local, git) = cli::source_type_flags(body.get("source_type").and_then(Value::as_str))
+        .map_err(|err| ApiError::new(400, err.to_string()))?;
+    let global = body.get("global").and_then(Value::as_bool).unwrap_or(false);
+    let trusted = body.get("trust").and_then(Value::as_bool).unwrap_or(false);
+    if !trusted && !is_tracked(source, global)? {
+        return Err(ApiError::new(
+            403,
+            format!("{source} is not tracked in config; send \"trust\": true to install from it"),
+        ));
+    }
+    cli::run_add(AddArgs {
+        source: source.to_string(),
+        global,
+        copy: body.get("copy").and_then(Value::as_bool).unwrap_or(false),
+        agent: strings("agents"),
+        skill: skills.clone(),
+        list: false,
+        long: false,
+        ask: false,
+        overwrite: false,
+        yes: true,
+        all: false,
+        full_depth: false,
+        no_ignore: false,
+        max_depth: None,
+        file_skills: false,
+        adopt: false,
+        paths: Vec::new(),
+        branch: None,
+        local,
+        git,
+        allow_large: body
+            .get("allow_large")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+    })?;
+    Ok(json!({ "installed": skills, "source": source }))
+}
+
+/// Whether config already tracks `source`, so installing from it again needs
+/// no confirmation from the client.
+fn is_tracked(source: &str, global: bool) -> Result<bool> {
+    let config = read_config(&config_location(global)?.path)?;
+    Ok(find_source(&config, source).is_some()
+        || parse_source(source)
+            .is_ok_and(|parsed| find_source(&config, &source_key(&parsed)).is_some()))
+}
+
+fn remove_skill(request: &ApiRequest, name: &str) -> std::result::Result<Value, ApiError> {
+    let name = urlencoding::decode(name)
+        .map(|name| name.into_owned())
+        .unwrap_or_else(|_| name.to_string());
+    cli::run_remove(RemoveArgs {
+        skills: vec![name.clone()],
+        global: request.query_flag("global"),
+        project_only: false,
+        both: false,
+        agent: request.query_values("agent"),
+        skill: Vec::new(),
+        yes: true,
+        all: false,
+        source: None,
+    })?;
+    Ok(json!({ "removed": name }))
+}
+
+fn check_updates() -> std::result::Result<Value, ApiError> {
+    let updates: Vec<Value> = Updater::auto()?
+        .check()?
+        .into_iter()
+        .map(|update| {
+            json!({
+                "source": update.source_key,
+                "skills": update.source.skills,
+                "latest_version": update.latest_version,
+                "latest_checksum": update.latest_checksum,
+            })
+        })
+        .collect();
+    Ok(json!({ "updates": updates }))
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, path: &str) -> ApiRequest {
+        ApiRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            query: Vec::new(),
+            body: Value::Null,
+        }
+    }
+
+    #[test]
+    fn parse_query_decodes_pairs() {
+        let query = parse_query("global&agent=claude-code&agent=cursor&name=a%20b+c");
+        assert_eq!(
+            query,
+            vec![
+                ("global".to_string(), String::new()),
+                ("agent".to_string(), "claude-code".to_string()),
+                ("agent".to_string(), "cursor".to_string()),
+                ("name".to_string(), "a b c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_requests_without_token_local_host_or_json() {
+        let access = Access::new("secret".to_string(), "127.0.0.1");
+        let headers = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        };
+        let ok = [
+            ("Host", "127.0.0.1:4174"),
+            ("Authorization", "Bearer secret"),
+            ("Content-Type", "application/json; charset=utf-8"),
+            ("Content-Length", "2"),
+        ];
+        assert!(access.check("POST", &headers(&ok)).is_ok());
+        assert!(
+            access
+                .check(
+                    "GET",
+                    &headers(&[("host", "localhost"), ("authorization", "Bearer secret")])
+                )
+                .is_ok()
+        );
+
+        let status = |method: &str, pairs: &[(&str, &str)]| {
+            access
+                .check(method, &headers(pairs))
+                .expect_err("rejected")
+                .status
+        };
+        assert_eq!(status("GET", &ok[..1]), 401);
+        assert_eq!(
+            status(
+                "GET",
+                &[("Host", "127.0.0.1"), ("Authorization", "Bearer wrong")]
+            ),
+            401
+        );
+        let mut page = ok.to_vec();
+        page.push(("Origin", "https://example.com"));
+        assert_eq!(status("POST", &page), 403);
+        let mut rebound = ok.to_vec();
+        rebound[0] = ("Host", "evil.example:4174");
+        assert_eq!(status("POST", &rebound), 403);
+        let mut form = ok.to_vec();
+        form[2] = ("Content-Type", "text/plain");
+        assert_eq!(status("POST", &form), 415);
+    }
+
+    #[test]
+    fn route_reports_unknown_paths_and_methods() {
+        let health = route(&request("GET", "/v1/health")).expect("health");
+        assert_eq!(health["version"], env!("CARGO_PKG_VERSION"));
+
+        let err = route(&request("PUT", "/v1/skills")).expect_err("405");
+        assert_eq!(err.status, 405);
+        let err = route(&request("GET", "/v2/skills")).expect_err("404");
+        assert_eq!(err.status, 404);
+        let err = route(&request("POST", "/v1/skills")).expect_err("400");
+        assert_eq!(err.status, 400);
+    }
+}
@@ -1,24 +1,72 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::io;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use clap_complete::{Shell, generate};
 use dialoguer::theme::ColorfulTheme;
-use skil_core::agent::{AgentConfig, agent_configs, resolve_agents};
+use indicatif::HumanBytes;
+use skil_core::adopt::{scan_markdown, write_skill};
+use skil_core::agent::{AgentConfig, agent_configs, default_agent_names, resolve_agents};
+use skil_core::annotation::{Annotation, AnnotationLevel, OutputFormat, format_annotations};
+use skil_core::audit::{AdvisoryKind, Finding, audit, load_advisories};
+use skil_core::budget::{
+    LARGE_SKILL_BYTES, SkillSize, check_budgets, disk_usage, measure_dir, skill_sizes,
+};
+use skil_core::clean::{Leftover, LeftoverKind, find_leftovers, remove_leftovers};
 use skil_core::config::{
-    SkilConfig, SkilSource, config_location, config_location_auto, read_config, update_config,
+    PromptAnswers, SkilConfig, SkilSource, agent_rules, config_location, config_location_auto,
+    find_source, local_overrides_path, mark_modified, max_skill_bytes, parse_toggle,
+    preferred_agents, preferred_copy, read_config, remember_answers, remembered_answers,
+    rules_allow, telemetry_enabled, tracking_source, untrack_skills, write_config,
 };
-use skil_core::git::{checkout_revision, clone_repo, head_revision, latest_tag, remote_revision};
+use skil_core::discovery::{DiscoveredSource, Discovery, Revision, source_key};
+use skil_core::git;
+use skil_core::history::{HistoryEntry, read_history, record};
+use skil_core::hook::{HookOutcome, hook_path, install_hook, uninstall_hook};
+use skil_core::import::{ImportFormat, import_skills};
 use skil_core::install::{
-    InstallMode, agent_skills_base, canonical_skills_dir, install_skill, sanitize_name,
+    IGNORE_FILE, IgnorePatterns, InstallMode, InstallState, agent_skills_base,
+    canonical_skills_dir, install_size, install_skill, install_state, link_skill, remove_managed,
+    sanitize_name,
+};
+use skil_core::installer::{InstallReport, Installer, check_skil_version};
+use skil_core::lock::{lock_config, lock_scope};
+use skil_core::migrate::migrate_legacy;
+use skil_core::observer::{
+    Answer, CloneStage, ConflictResolution, Drift, DriftResolution, Homonym, LargeSkill, Observer,
+    Prompt,
+};
+use skil_core::overrides::{overrides_dir, skill_overrides};
+use skil_core::paths::{configured_store_dir, global_store_dir, scratch_dir};
+use skil_core::policy::{Violation, check_drift, check_policy, load_policy};
+use skil_core::profile::{span, timings};
+use skil_core::render::{Provenance, render_section, replace_section};
+use skil_core::schema::validate_config;
+use skil_core::search_cache::{
+    cache_response, cache_skill_names, cached_response, cached_skill_names,
+};
+use skil_core::skills::{
+    DiscoverOptions, DuplicateSkill, Frontmatter, RESOURCE_DIRS, Skill, group_skills,
+    missing_skills, parse_frontmatter, parse_skill_md, select_skills, split_frontmatter,
 };
-use skil_core::skills::{Skill, discover_skills, parse_skill_md, select_skills};
-use skil_core::source::{Source, parse_source};
-use skil_core::{Result, SkilError};
+use skil_core::source::{Source, SourceHint, parse_source, parse_source_as};
+use skil_core::template::{DEFAULT_TEMPLATE_SOURCE, fetch_templates, parse_template_ref, scaffold};
+use skil_core::trash::{TRASH_EXPIRY_DAYS, restore_skill, trash_entries, trash_skill};
+use skil_core::updater::{DeprecatedSkill, Updater};
+use skil_core::{ErrorCode, Result, SkilError};
 use skil_docs::DocsArgs;
+use skil_docs::manifest::{MANIFEST_FILE, SkillsManifest};
 
+use crate::api::ServeApiArgs;
+use crate::markdown;
+use crate::picker::{Picker, PickerItem};
+use crate::telemetry;
+use crate::template::Template;
+use crate::tui::TuiArgs;
 use crate::ui;
+use crate::upgrade::UpgradeSelfArgs;
 
 /// CLI argument parser definition.
 #[derive(Parser)]
@@ -29,6 +77,15 @@ use crate::ui;
     arg_required_else_help = true
 )]
 pub struct Cli {
+    /// Print results and errors as JSON on stdout and send other output to stderr.
+    #[arg(long = "json", global = true)]
+    pub json: bool,
+    /// Clone repositories with the system git instead of the built-in client.
+    #[arg(long = "use-system-git", global = true)]
+    pub use_system_git: bool,
+    /// Report where the time went after the command finishes, on stderr.
+    #[arg(long = "profile", global = true)]
+    pub profile: bool,
     #[command(subcommand)]
     pub command: Command,
 }
@@ -42,20 +99,60 @@ pub enum Command {
     Install(InstallArgs),
     #[command(aliases = ["rm", "r"], about = "Remove installed skills")]
     Remove(RemoveArgs),
+    #[command(about = "Put back a skill removed in the last few days")]
+    Restore(RestoreArgs),
     #[command(aliases = ["ls"], about = "List installed skills")]
     List(ListArgs),
     #[command(aliases = ["search", "f", "s"], about = "Search for skills by keyword")]
     Find(FindArgs),
     #[command(about = "Check for available skill updates")]
-    Check,
+    Check(CheckArgs),
     #[command(aliases = ["upgrade"], about = "Update all skills to latest versions")]
-    Update,
+    Update(UpdateArgs),
+    #[command(about = "Reinstall the previous revision of a skill's source")]
+    Rollback(RollbackArgs),
+    #[command(about = "Show sources, their skills, and the agents they are installed into")]
+    Tree,
+    #[command(about = "Convert other agents' configuration into skills")]
+    Import(ImportArgs),
+    #[command(about = "Render installed skills into a single instruction file")]
+    Render(RenderArgs),
+    #[command(about = "Estimate the size and token count of installed skills")]
+    Stats(StatsArgs),
     #[command(about = "Create a new SKILL.md template")]
     Init(InitArgs),
     #[command(aliases = ["completion"], about = "Generate shell completion scripts")]
     Completions(CompletionsArgs),
+    #[command(about = "Manage skills in an interactive terminal UI")]
+    Tui(TuiArgs),
+    #[command(about = "Run an MCP server over stdio exposing skill management tools")]
+    Mcp,
+    #[command(about = "Serve a local JSON API for editor integrations")]
+    ServeApi(ServeApiArgs),
+    #[command(about = "Read or change skil settings")]
+    Config(ConfigArgs),
     #[command(about = "Build and serve static docs for discovered skills")]
     Docs(DocsArgs),
+    #[command(about = "Upgrade the skil binary to the latest release")]
+    UpgradeSelf(UpgradeSelfArgs),
+    #[command(about = "Remove leftover clones and orphaned skill folders")]
+    Clean(CleanArgs),
+    #[command(about = "Move skills and config from earlier releases into the current layout")]
+    Migrate(MigrateArgs),
+    #[command(about = "Flag installed skills with known security or deprecation advisories")]
+    Audit(AuditArgs),
+    #[command(about = "Show details and bundled files of an installed skill")]
+    Info(InfoArgs),
+    #[command(about = "Print a skill's SKILL.md from a source without installing it")]
+    Show(ShowArgs),
+    #[command(about = "Show past add, remove, update, and rollback operations")]
+    History(HistoryArgs),
+    #[command(about = "Open an installed skill in your editor, keeping edits across updates")]
+    Edit(EditArgs),
+    #[command(about = "Run skil checks from a git hook of the current repository")]
+    Hook(HookArgs),
+    #[command(name = "complete-skills", hide = true)]
+    CompleteSkills(CompleteSkillsArgs),
 }
 
 /// Arguments for `skills add`.
@@ -63,6 +160,12 @@ pub enum Command {
 #[command(about = "Install skills from a repository or archive")]
 pub struct AddArgs {
     pub source: String,
+    /// Folder of the source to search for skills; repeat to search several in one clone.
+    #[arg(long = "path")]
+    pub paths: Vec<PathBuf>,
+    /// Branch to install from and follow on update, instead of the latest tag.
+    #[arg(short = 'b', long = "branch")]
+    pub branch: Option<String>,
     #[arg(short = 'g', long = "global")]
     pub global: bool,
     #[arg(long = "copy")]
@@ -73,12 +176,47 @@ pub struct AddArgs {
     pub skill: Vec<String>,
     #[arg(short = 'l', long = "list")]
     pub list: bool,
+    /// With --list, also show each skill's path in the source, the pattern
+    /// that found it, tags, and size.
+    #[arg(long = "long", requires = "list")]
+    pub long: bool,
     #[arg(short = 'y', long = "yes")]
     pub yes: bool,
     #[arg(long = "all")]
     pub all: bool,
     #[arg(long = "full-depth")]
     pub full_depth: bool,
+    /// Also search folders that `.gitignore` and `.ignore` files leave out.
+    #[arg(long = "no-ignore")]
+    pub no_ignore: bool,
+    /// How many levels below the source to look for SKILL.md files when the
+    /// usual places have none (default 5).
+    #[arg(long = "max-depth", value_name = "N")]
+    pub max_depth: Option<usize>,
+    /// Also take each markdown file with a `name` and `description` in its
+    /// frontmatter as a skill by itself.
+    #[arg(long = "file-skills")]
+    pub file_skills: bool,
+    /// Convert plain markdown files in a local directory into skills before installing.
+    #[arg(long = "adopt")]
+    pub adopt: bool,
+    /// Read the source as a local path, also searched for in parent directories.
+    #[arg(long = "local", conflicts_with = "git")]
+    pub local: bool,
+    /// Read the source as a git URL or GitHub owner/repo.
+    #[arg(long = "git")]
+    pub git: bool,
+    /// Install skills over the `max_skill_bytes` limit without asking.
+    #[arg(long = "allow-large")]
+    pub allow_large: bool,
+    /// Ask for agents, scope, and method again instead of reusing the answers
+    /// given for this source before.
+    #[arg(long = "ask")]
+    pub ask: bool,
+    /// Replace skills of the same name installed from another source without
+    /// asking; otherwise they are kept, or you are asked on a terminal.
+    #[arg(long = "overwrite")]
+    pub overwrite: bool,
 }
 
 /// Arguments for `skills install`.
@@ -95,6 +233,17 @@ pub struct InstallArgs {
     pub yes: bool,
     #[arg(long = "full-depth")]
     pub full_depth: bool,
+    /// Also search folders that `.gitignore` and `.ignore` files leave out.
+    #[arg(long = "no-ignore")]
+    pub no_ignore: bool,
+    /// How many levels below the source to look for SKILL.md files when the
+    /// usual places have none (default 5).
+    #[arg(long = "max-depth", value_name = "N")]
+    pub max_depth: Option<usize>,
+    /// Also take each markdown file with a `name` and `description` in its
+    /// frontmatter as a skill by itself.
+    #[arg(long = "file-skills")]
+    pub file_skills: bool,
 }
 
 /// Arguments for `skills remove`.
@@ -102,8 +251,19 @@ pub struct InstallArgs {
 #[command(about = "Remove installed skills")]
 pub struct RemoveArgs {
     pub skills: Vec<String>,
-    #[arg(short = 'g', long = "global")]
+    #[arg(
+        short = 'g',
+        long = "global",
+        visible_alias = "global-only",
+        conflicts_with_all = ["project_only", "both"]
+    )]
     pub global: bool,
+    /// Only remove from the project scope.
+    #[arg(long = "project-only", conflicts_with = "both")]
+    pub project_only: bool,
+    /// Remove from both the project and the global scope.
+    #[arg(long = "both")]
+    pub both: bool,
     #[arg(short = 'a', long = "agent", num_args = 1..)]
     pub agent: Vec<String>,
     #[arg(short = 's', long = "skill", num_args = 1..)]
@@ -112,16 +272,111 @@ pub struct RemoveArgs {
     pub yes: bool,
     #[arg(long = "all")]
     pub all: bool,
+    /// Remove every skill installed from a source, in all agents and, unless a
+    /// scope is given, both scopes.
+    #[arg(long = "source", conflicts_with_all = ["skills", "skill", "agent", "all"])]
+    pub source: Option<String>,
+}
+
+/// Arguments for `skills restore`.
+#[derive(Args, Clone)]
+#[command(about = "Put back a skill removed in the last few days")]
+pub struct RestoreArgs {
+    /// Skill to restore; lists the trash when omitted.
+    pub skill: Option<String>,
+    /// List removed skills that can be restored.
+    #[arg(long = "list", conflicts_with = "skill")]
+    pub list: bool,
+}
+
+/// Arguments for `skills check`.
+#[derive(Args, Clone)]
+pub struct CheckArgs {
+    /// Verify installed skills against a policy of required skills instead of
+    /// looking for updates; defaults to `.skil-policy.toml`.
+    #[arg(long = "policy", num_args = 0..=1, default_missing_value = ".skil-policy.toml")]
+    pub policy: Option<PathBuf>,
+    /// Fail when installed skills no longer match the content recorded at
+    /// install instead of looking for updates.
+    #[arg(long = "drift", conflicts_with = "policy")]
+    pub drift: bool,
+    /// `text`, `github` for GitHub Actions annotations, or `sarif` for code scanning.
+    #[arg(long = "output", default_value = "text")]
+    pub output: OutputFormat,
+    /// Print a line per update from a template such as '{{source}}\t{{latest}}'.
+    #[arg(long = "format", conflicts_with_all = ["policy", "drift", "output"])]
+    pub format: Option<String>,
+}
+
+/// Arguments for `skills update`.
+#[derive(Args, Clone, Default)]
+#[command(about = "Update all skills to latest versions")]
+pub struct UpdateArgs {
+    #[arg(long = "prune")]
+    pub prune: bool,
+    #[arg(long = "keep-going")]
+    pub keep_going: bool,
+    /// Replace deprecated skills with their declared successors without asking.
+    #[arg(long = "replace-deprecated")]
+    pub replace_deprecated: bool,
+    /// What to do with skills edited since they were installed, without asking.
+    #[arg(long = "strategy", value_enum)]
+    pub strategy: Option<DriftStrategy>,
+}
+
+/// Ways `skills update --strategy` resolves local edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DriftStrategy {
+    KeepLocal,
+    TakeUpstream,
+    Merge,
+}
+
+impl From<DriftStrategy> for DriftResolution {
+    fn from(strategy: DriftStrategy) -> Self {
+        match strategy {
+            DriftStrategy::KeepLocal => DriftResolution::KeepLocal,
+            DriftStrategy::TakeUpstream => DriftResolution::TakeUpstream,
+            DriftStrategy::Merge => DriftResolution::Merge,
+        }
+    }
+}
+
+/// Arguments for `skills rollback`.
+#[derive(Args, Clone)]
+#[command(about = "Reinstall the previous revision of a skill's source")]
+pub struct RollbackArgs {
+    pub skill: String,
+    #[arg(short = 'g', long = "global")]
+    pub global: bool,
 }
 
 /// Arguments for `skills list`.
 #[derive(Args, Clone)]
 #[command(about = "List installed skills")]
 pub struct ListArgs {
-    #[arg(short = 'g', long = "global")]
+    #[arg(
+        short = 'g',
+        long = "global",
+        visible_alias = "global-only",
+        conflicts_with_all = ["project_only", "all_scopes"]
+    )]
     pub global: bool,
     #[arg(short = 'a', long = "agent", num_args = 1..)]
     pub agent: Vec<String>,
+    /// Only list the project scope, without falling back to global skills.
+    #[arg(long = "project-only", conflicts_with = "all_scopes")]
+    pub project_only: bool,
+    #[arg(long = "all-scopes", visible_alias = "both")]
+    pub all_scopes: bool,
+    #[arg(short = 'v', long = "verbose")]
+    pub verbose: bool,
+    /// Show the disk space each skill and agent takes.
+    #[arg(long = "size", conflicts_with = "verbose")]
+    pub size: bool,
+    /// Print a line per skill from a template such as '{{name}}\t{{source}}'.
+    #[arg(long = "format", conflicts_with_all = ["size", "verbose"])]
+    pub format: Option<String>,
 }
 
 /// Arguments for `skills find`.
@@ -129,6 +384,106 @@ pub struct ListArgs {
 #[command(about = "Search for skills by keyword")]
 pub struct FindArgs {
     pub query: Option<String>,
+    #[arg(long = "registry")]
+    pub registry: Option<String>,
+    /// Narrow results: `agent:<name>`, `tag:<tag>`, `license:<id>`, or
+    /// `installs:<min>`; repeat for several.
+    #[arg(long = "filter", value_name = "KEY:VALUE")]
+    pub filters: Vec<SearchFilter>,
+    /// Ask the registry even when results for the same search are cached.
+    #[arg(long = "refresh")]
+    pub refresh: bool,
+}
+
+/// Arguments for `skills clean`.
+#[derive(Args, Clone)]
+#[command(about = "Remove leftover clones and orphaned skill folders")]
+pub struct CleanArgs {
+    #[arg(short = 'g', long = "global")]
+    pub global: bool,
+    /// List what would be removed without removing it.
+    #[arg(short = 'n', long = "dry-run")]
+    pub dry_run: bool,
+    #[arg(short = 'y', long = "yes")]
+    pub yes: bool,
+}
+
+/// Arguments for `skills migrate`.
+#[derive(Args, Clone)]
+#[command(about = "Move skills and config from earlier releases into the current layout")]
+pub struct MigrateArgs {
+    /// List what would be moved without moving it.
+    #[arg(short = 'n', long = "dry-run")]
+    pub dry_run: bool,
+}
+
+/// Arguments for `skills audit`.
+#[derive(Args, Clone)]
+#[command(about = "Flag installed skills with known security or deprecation advisories")]
+pub struct AuditArgs {
+    /// Advisory database to check against: a JSON file or URL. Defaults to the registry.
+    #[arg(long = "db")]
+    pub db: Option<String>,
+}
+
+/// Arguments for `skills history`.
+#[derive(Args, Clone)]
+#[command(about = "Show past add, remove, update, and rollback operations")]
+pub struct HistoryArgs {
+    /// Only show operations on or after this day (UTC), as YYYY-MM-DD.
+    #[arg(long = "since")]
+    pub since: Option<jiff::civil::Date>,
+    /// Only show operations on or before this day (UTC), as YYYY-MM-DD.
+    #[arg(long = "until")]
+    pub until: Option<jiff::civil::Date>,
+    /// Only show operations that touched this skill.
+    #[arg(short = 's', long = "skill")]
+    pub skill: Option<String>,
+    /// Show at most this many operations, newest first.
+    #[arg(short = 'n', long = "limit", default_value_t = 50)]
+    pub limit: usize,
+}
+
+/// Arguments for `skills info`.
+#[derive(Args, Clone)]
+#[command(about = "Show details and bundled files of an installed skill")]
+pub struct InfoArgs {
+    pub skill: String,
+    #[arg(short = 'g', long = "global")]
+    pub global: bool,
+    /// Also print the skill's SKILL.md body.
+    #[arg(long = "content")]
+    pub content: bool,
+    /// Print the details from a template such as '{{source}}\t{{path}}'.
+    #[arg(long = "format", conflicts_with = "content")]
+    pub format: Option<String>,
+}
+
+/// Arguments for `skills show`.
+#[derive(Args, Clone)]
+#[command(about = "Print a skill's SKILL.md from a source without installing it")]
+pub struct ShowArgs {
+    pub source: String,
+    /// Skill to show; required when the source has several and there is no terminal to pick one.
+    #[arg(short = 's', long = "skill")]
+    pub skill: Option<String>,
+    /// Folder of the source to search for skills.
+    #[arg(long = "path")]
+    pub paths: Vec<PathBuf>,
+    #[arg(short = 'b', long = "branch")]
+    pub branch: Option<String>,
+}
+
+/// Arguments for `skills edit`.
+#[derive(Args, Clone)]
+#[command(about = "Open an installed skill in your editor, keeping edits across updates")]
+pub struct EditArgs {
+    pub skill: String,
+    #[arg(short = 'g', long = "global")]
+    pub global: bool,
+    /// Stop tracking the skill's source instead of marking it locally modified.
+    #[arg(long = "detach")]
+    pub detach: bool,
 }
 
 /// Arguments for `skills init`.
@@ -136,6 +491,124 @@ pub struct FindArgs {
 #[command(about = "Initialize a new skill template")]
 pub struct InitArgs {
     pub name: Option<String>,
+    /// Scaffold from a template, given as `<name>` or `<name>@<source>`.
+    #[arg(short = 't', long = "template")]
+    pub template: Option<String>,
+    /// List the templates of a source and exit.
+    #[arg(
+        long = "list-templates",
+        value_name = "SOURCE",
+        num_args = 0..=1,
+        default_missing_value = DEFAULT_TEMPLATE_SOURCE
+    )]
+    pub list_templates: Option<String>,
+    /// Install the new skill into these agents without asking.
+    #[arg(short = 'a', long = "agent", num_args = 1..)]
+    pub agent: Vec<String>,
+    /// Do not offer to install the new skill.
+    #[arg(long = "no-install", conflicts_with = "agent")]
+    pub no_install: bool,
+}
+
+/// Arguments for `skills import`.
+#[derive(Args, Clone)]
+#[command(about = "Convert other agents' configuration into skills")]
+pub struct ImportArgs {
+    #[arg(long = "from", value_enum)]
+    pub from: ImportFrom,
+    pub path: PathBuf,
+    #[arg(short = 'g', long = "global")]
+    pub global: bool,
+    /// Also install the imported skills into these agents.
+    #[arg(short = 'a', long = "agent", num_args = 1..)]
+    pub agent: Vec<String>,
+    #[arg(long = "copy")]
+    pub copy: bool,
+    /// Replace skills that already exist in the canonical store.
+    #[arg(long = "force")]
+    pub force: bool,
+}
+
+/// Configuration formats accepted by `skills import --from`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ImportFrom {
+    ClaudePlugins,
+    CursorRules,
+    AgentsMd,
+}
+
+impl From<ImportFrom> for ImportFormat {
+    fn from(from: ImportFrom) -> Self {
+        match from {
+            ImportFrom::ClaudePlugins => ImportFormat::ClaudePlugins,
+            ImportFrom::CursorRules => ImportFormat::CursorRules,
+            ImportFrom::AgentsMd => ImportFormat::AgentsMd,
+        }
+    }
+}
+
+/// Arguments for `skills render`.
+#[derive(Args, Clone)]
+#[command(about = "Render installed skills into a single instruction file")]
+pub struct RenderArgs {
+    /// Skills to include; all installed skills when empty.
+    pub skills: Vec<String>,
+    #[arg(long = "format", value_enum, default_value_t = RenderFormat::AgentsMd)]
+    pub format: RenderFormat,
+    #[arg(short = 'g', long = "global")]
+    pub global: bool,
+    /// Update the skil section of AGENTS.md or CLAUDE.md instead of printing it.
+    #[arg(short = 'w', long = "write")]
+    pub write: bool,
+    /// File to update instead of the format's default; implies --write.
+    #[arg(short = 'o', long = "output")]
+    pub output: Option<PathBuf>,
+}
+
+/// Instruction files `skills render` can produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RenderFormat {
+    AgentsMd,
+    ClaudeMd,
+}
+
+impl RenderFormat {
+    fn file_name(self) -> &'static str {
+        match self {
+            RenderFormat::AgentsMd => "AGENTS.md",
+            RenderFormat::ClaudeMd => "CLAUDE.md",
+        }
+    }
+}
+
+/// Arguments for `skills stats`.
+#[derive(Args, Clone)]
+#[command(about = "Estimate the size and token count of installed skills")]
+pub struct StatsArgs {
+    #[arg(short = 'g', long = "global")]
+    pub global: bool,
+    #[arg(short = 'a', long = "agent", num_args = 1..)]
+    pub agent: Vec<String>,
+    #[arg(long = "sort", value_enum, default_value_t = StatsSort::Tokens)]
+    pub sort: StatsSort,
+}
+
+/// Orderings for `skills stats`; sizes sort largest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StatsSort {
+    Tokens,
+    Bytes,
+    Name,
+}
+
+impl StatsSort {
+    fn sort<T>(self, items: &mut [T], size: impl Fn(&T) -> &SkillSize) {
+        match self {
+            StatsSort::Tokens => items.sort_by_key(|item| std::cmp::Reverse(size(item).tokens)),
+            StatsSort::Bytes => items.sort_by_key(|item| std::cmp::Reverse(size(item).bytes)),
+            StatsSort::Name => items.sort_by(|a, b| size(a).name.cmp(&size(b).name)),
+        }
+    }
 }
 
 /// Arguments for `skills completions`.
@@ -146,143 +619,388 @@ pub struct CompletionsArgs {
     pub shell: Shell,
 }
 
+/// Arguments for the hidden `skills complete-skills`, which completion
+/// scripts call for `--skill` values.
+#[derive(Args, Clone)]
+pub struct CompleteSkillsArgs {
+    pub source: String,
+}
+
+/// Arguments for `skills config`.
+#[derive(Args, Clone)]
+#[command(about = "Read or change skil settings")]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+/// Subcommands for `skills config`.
+#[derive(Subcommand, Clone)]
+pub enum ConfigCommand {
+    #[command(about = "Set a setting in the global config")]
+    Set { key: String, value: String },
+    #[command(about = "Print the effective value of a setting")]
+    Get { key: String },
+    #[command(about = "Check .skil.toml and the global config for mistakes")]
+    Validate {
+        /// Only check the global config.
+        #[arg(short = 'g', long = "global")]
+        global: bool,
+        /// `text`, `github` for GitHub Actions annotations, or `sarif` for code scanning.
+        #[arg(long = "output", default_value = "text")]
+        output: OutputFormat,
+    },
+}
+
+/// Arguments for `skills hook`.
+#[derive(Args, Clone)]
+pub struct HookArgs {
+    #[command(subcommand)]
+    pub command: HookCommand,
+}
+
+/// Subcommands for `skills hook`.
+#[derive(Subcommand, Clone)]
+pub enum HookCommand {
+    #[command(about = "Add config and policy checks to a git hook")]
+    Install {
+        /// Hook to add the checks to.
+        #[arg(long = "hook", default_value = "pre-commit")]
+        hook: String,
+    },
+    #[command(about = "Remove the checks added by `hook install`")]
+    Uninstall {
+        #[arg(long = "hook", default_value = "pre-commit")]
+        hook: String,
+    },
+}
+
+/// Lines of a skill's body the skill picker previews.
+const PREVIEW_LINES: usize = 8;
+
 const SEARCH_API_BASE: &str = "https://skills.sh";
 
 /// Response payload returned by the registry search endpoint.
 #[derive(Debug, serde::Deserialize)]
-struct SearchApiResponse {
-    skills: Vec<SearchApiSkill>,
+pub(crate) struct SearchApiResponse {
+    pub skills: Vec<SearchApiSkill>,
 }
 
 /// One search result item returned by the registry API.
 #[derive(Debug, serde::Deserialize)]
-struct SearchApiSkill {
-    name: String,
-    installs: Option<u64>,
-    source: Option<String>,
+pub(crate) struct SearchApiSkill {
+    pub name: String,
+    pub installs: Option<u64>,
+    pub source: Option<String>,
+    #[serde(default)]
+    pub agents: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub license: Option<String>,
 }
 
-/// Represents one source with an available newer checksum or version.
-#[derive(Debug)]
-struct UpdateEntry {
-    source_key: String,
-    source: SkilSource,
-    latest_checksum: Option<String>,
-    latest_version: Option<String>,
+impl SearchApiSkill {
+    /// Whether the result satisfies `filter`; fields the registry left out
+    /// are trusted to have been filtered server-side.
+    fn matches(&self, filter: &SearchFilter) -> bool {
+        let listed = |values: &[String], wanted: &str| {
+            values.is_empty()
+                || values
+                    .iter()
+                    .any(|value| value.eq_ignore_ascii_case(wanted))
+        };
+        match filter {
+            SearchFilter::Agent(agent) => listed(&self.agents, agent),
+            SearchFilter::Tag(tag) => listed(&self.tags, tag),
+            SearchFilter::License(license) => self
+                .license
+                .as_deref()
+                .is_none_or(|value| value.eq_ignore_ascii_case(license)),
+            SearchFilter::MinInstalls(min) => self.installs.is_none_or(|n| n >= *min),
+        }
+    }
 }
 
-/// Returns true when a source key looks like a remote git reference.
-fn is_remote_source_key(source_key: &str) -> bool {
-    source_key.contains("://") || source_key.starts_with("git@")
+/// A structured `find --filter` condition, passed to the registry as a query
+/// parameter.
+#[derive(Debug, Clone)]
+pub enum SearchFilter {
+    Agent(String),
+    Tag(String),
+    License(String),
+    MinInstalls(u64),
 }
 
-/// Collects all updatable sources from config.
-/// For tagged repositories, compares by latest tag name.
-/// For non-tagged repositories, compares by latest remote revision checksum.
-fn collect_available_updates(config: &SkilConfig) -> Result<Vec<UpdateEntry>> {
-    let mut updates = Vec::new();
-    for (source_key, source) in &config.sources {
-        if !is_remote_source_key(source_key) {
-            continue;
+impl SearchFilter {
+    fn query_param(&self) -> (&'static str, String) {
+        match self {
+            SearchFilter::Agent(agent) => ("agent", agent.clone()),
+            SearchFilter::Tag(tag) => ("tag", tag.clone()),
+            SearchFilter::License(license) => ("license", license.clone()),
+            SearchFilter::MinInstalls(min) => ("min_installs", min.to_string()),
         }
+    }
+}
 
-        if let Some(tag) = latest_tag(source_key)? {
-            let current = source.version.clone().unwrap_or_default();
-            if current != tag {
-                updates.push(UpdateEntry {
-                    source_key: source_key.clone(),
-                    source: source.clone(),
-                    latest_checksum: None,
-                    latest_version: Some(tag),
-                });
-            }
-            continue;
-        }
+impl std::str::FromStr for SearchFilter {
+    type Err = String;
 
-        let latest = remote_revision(source_key, source.branch.as_deref())?;
-        let current = source.checksum.clone().unwrap_or_default();
-        if current.is_empty() || current != latest {
-            updates.push(UpdateEntry {
-                source_key: source_key.clone(),
-                source: source.clone(),
-                latest_checksum: Some(latest),
-                latest_version: None,
-            });
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let Some((key, arg)) = value.split_once(':').filter(|(_, arg)| !arg.is_empty()) else {
+            return Err(format!("expected KEY:VALUE, got `{value}`"));
+        };
+        match key {
+            "agent" => Ok(SearchFilter::Agent(arg.to_string())),
+            "tag" => Ok(SearchFilter::Tag(arg.to_string())),
+            "license" => Ok(SearchFilter::License(arg.to_string())),
+            "installs" => arg
+                .parse()
+                .map(SearchFilter::MinInstalls)
+                .map_err(|_| format!("installs takes a number, got `{arg}`")),
+            _ => Err(format!(
+                "unknown filter `{key}`; use agent, tag, license, or installs"
+            )),
         }
     }
-    Ok(updates)
 }
 
-/// Presents an interactive skill picker and returns selected skill names.
-fn prompt_for_skills(skills: &[Skill]) -> Result<Vec<String>> {
-    let max_width = console::Term::stdout().size().1 as usize;
-    let items: Vec<String> = skills
-        .iter()
-        .map(|s| format_skill_line(&s.name, &s.description, max_width))
-        .collect();
-    if items.is_empty() {
-        return Ok(vec![]);
+/// Observer rendering core progress as spinners and answering prompts interactively.
+struct CliObserver {
+    spinner: ui::ProgressSpinner,
+    interactive: bool,
+    /// Ask on a terminal before replacing a skill installed from another
+    /// source; such skills are kept otherwise.
+    confirm_homonyms: bool,
+    /// Replace skills installed from another source without asking.
+    overwrite_homonyms: bool,
+    large_skills: LargeSkills,
+    /// Resolution for locally edited skills; asks on a terminal when unset.
+    drift: Option<DriftResolution>,
+}
+
+/// What [`CliObserver`] does with skills over the size limit.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LargeSkills {
+    Install,
+    /// Ask on a terminal, refuse elsewhere.
+    Confirm,
+    Refuse,
+}
+
+impl CliObserver {
+    fn new(interactive: bool) -> Self {
+        Self {
+            spinner: ui::ProgressSpinner::default(),
+            interactive,
+            confirm_homonyms: false,
+            overwrite_homonyms: false,
+            large_skills: LargeSkills::Install,
+            drift: None,
+        }
     }
 
-    let selection = dialoguer::MultiSelect::with_theme(&ColorfulTheme::default())
-        .with_prompt("Select skills to install")
-        .items(&items)
-        .max_length(12)
-        .interact()
-        .map_err(|err| SkilError::Message(err.to_string()))?;
-    let selected = selection
-        .into_iter()
-        .map(|idx| skills[idx].name.clone())
-        .collect();
-    Ok(selected)
+    fn confirm_homonyms(mut self, confirm: bool) -> Self {
+        self.confirm_homonyms = confirm;
+        self
+    }
+
+    fn overwrite_homonyms(mut self, overwrite: bool) -> Self {
+        self.overwrite_homonyms = overwrite;
+        self
+    }
+
+    fn large_skills(mut self, large_skills: LargeSkills) -> Self {
+        self.large_skills = large_skills;
+        self
+    }
+
+    fn drift(mut self, drift: Option<DriftResolution>) -> Self {
+        self.drift = drift;
+        self
+    }
 }
 
-/// Formats one skill item line for interactive selection.
-fn format_skill_line(name: &str, description: &str, max_width: usize) -> String {
-    let sep = " — ";
-    if max_width == 0 {
-        return format!(
-            "{}{sep}{}",
-            console::style(name).bold(),
-            console::style(description).dim()
+impl Observer for CliObserver {
+    fn on_clone_progress(&self, url: &str, stage: CloneStage) {
+        match stage {
+            CloneStage::Started => self.spinner.start(&format!("Cloning {url}...")),
+            CloneStage::Finished => self.spinner.finish("Repository cloned"),
+            CloneStage::Failed => self.spinner.finish("Repository clone failed"),
+        }
+    }
+
+    fn on_duplicate_skill(&self, duplicate: &DuplicateSkill) {
+        let paths: Vec<String> = duplicate
+            .paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        ui::warn(&format!(
+            "{} is declared by {} folders: {}; using {}",
+            duplicate.name,
+            paths.len(),
+            paths.join(", "),
+            paths[0]
+        ));
+    }
+
+    fn on_install_started(&self, _skills: usize, _agents: usize) {
+        self.spinner.start("Installing skills...");
+    }
+
+    fn on_install_finished(&self, _skills: usize, _agents: usize) {
+        self.spinner.finish("Installation complete");
+    }
+
+    fn on_homonym(&self, homonym: &Homonym<'_>) -> Result<ConflictResolution> {
+        let name = &homonym.skill.name;
+        ui::warn(&format!(
+            "{name} is already installed from {}",
+            homonym.source
+        ));
+        if let Some(description) = &homonym.description {
+            ui::info(&format!("  Installed: {description}"));
+        }
+        ui::info(&format!("  New:       {}", homonym.skill.description));
+        if self.overwrite_homonyms {
+            return Ok(ConflictResolution::Overwrite);
+        }
+        if !self.confirm_homonyms || !console::Term::stdout().is_term() {
+            ui::info(&format!(
+                "Kept {name} from {}; pass --overwrite to replace it",
+                homonym.source
+            ));
+            return Ok(ConflictResolution::Skip);
+        }
+        let replace = dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Replace {name}?"))
+            .default(false)
+            .interact()
+            .map_err(|err| SkilError::Message(err.to_string()))?;
+        Ok(if replace {
+            ConflictResolution::Overwrite
+        } else {
+            ConflictResolution::Skip
+        })
+    }
+
+    fn on_large_skill(&self, large: &LargeSkill<'_>) -> Result<ConflictResolution> {
+        if self.large_skills == LargeSkills::Install {
+            return Ok(ConflictResolution::Overwrite);
+        }
+        let name = &large.skill.name;
+        let size = format!(
+            "{name} is {}, over the max_skill_bytes limit of {}",
+            HumanBytes(large.bytes),
+            HumanBytes(large.limit)
         );
+        if self.large_skills == LargeSkills::Refuse || !console::Term::stdout().is_term() {
+            return Err(SkilError::Message(format!(
+                "{size}; pass --allow-large to install it, or leave out files it does not need with {IGNORE_FILE}"
+            )));
+        }
+        ui::warn(&size);
+        let install = dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Install {name} anyway?"))
+            .default(false)
+            .interact()
+            .map_err(|err| SkilError::Message(err.to_string()))?;
+        if install {
+            return Ok(ConflictResolution::Overwrite);
+        }
+        ui::info(&format!(
+            "  Skipped {name}; leave out files it does not need with {IGNORE_FILE}"
+        ));
+        Ok(ConflictResolution::Skip)
     }
 
-    let max_len = max_width.saturating_sub(4).max(20);
-    let name_len = name.chars().count();
-    let sep_len = sep.chars().count();
-    let available = max_len.saturating_sub(name_len + sep_len);
-    if available == 0 {
-        return format!("{}", console::style(name).bold());
+    fn on_drift(&self, drift: &Drift<'_>) -> Result<DriftResolution> {
+        let name = &drift.skill.name;
+        if let Some(resolution) = self.drift {
+            return Ok(resolution);
+        }
+        if !console::Term::stdout().is_term() {
+            ui::warn(&format!(
+                "  {name} was edited locally; keeping the local copy (pass --strategy to choose)"
+            ));
+            return Ok(DriftResolution::KeepLocal);
+        }
+        ui::warn(&format!("{name} was edited locally since it was installed"));
+        let diff = git::diff_dirs(drift.local, drift.upstream)?;
+        print!("{diff}");
+        let choice = dialoguer::Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Update {name}?"))
+            .items([
+                "Keep local edits",
+                "Take upstream and drop local edits",
+                "Merge local edits into upstream",
+            ])
+            .default(0)
+            .interact()
+            .map_err(|err| SkilError::Message(err.to_string()))?;
+        Ok(match choice {
+            1 => DriftResolution::TakeUpstream,
+            2 => DriftResolution::Merge,
+            _ => DriftResolution::KeepLocal,
+        })
     }
-    let mut desc = description.to_string();
-    if desc.chars().count() > available {
-        let take = available.saturating_sub(3);
-        desc = desc.chars().take(take).collect();
-        desc.push_str("...");
+
+    fn on_prompt(&self, prompt: &Prompt<'_>) -> Result<Answer> {
+        match prompt {
+            Prompt::SelectSkills { skills } if self.interactive => {
+                Ok(Answer::Skills(prompt_for_skills(skills)?))
+            }
+            _ => Ok(Answer::Default),
+        }
     }
+}
 
-    format!(
-        "{}{}{}",
-        console::style(name).bold(),
-        console::style(sep).dim(),
-        console::style(desc).dim()
-    )
+/// Presents an interactive skill picker and returns selected skill names.
+fn prompt_for_skills(skills: &[Skill]) -> Result<Vec<String>> {
+    if skills.is_empty() {
+        return Ok(vec![]);
+    }
+    // Listed by folder, so a repository's categories show as headings.
+    let ordered: Vec<(String, &Skill)> = group_skills(skills)
+        .into_iter()
+        .flat_map(|(group, skills)| skills.into_iter().map(move |skill| (group.clone(), skill)))
+        .collect();
+    let items = ordered
+        .iter()
+        .map(|(group, skill)| PickerItem {
+            label: skill.name.clone(),
+            detail: skill.description.clone(),
+            preview: markdown::render(split_frontmatter(&skill.raw_content).1)
+                .lines()
+                .take(PREVIEW_LINES)
+                .map(str::to_string)
+                .collect(),
+            group: group.clone(),
+        })
+        .collect();
+    let selection = Picker::new("Select skills to install", items).interact()?;
+    Ok(selection
+        .into_iter()
+        .map(|idx| ordered[idx].1.name.clone())
+        .collect())
 }
 
 /// Presents an interactive agent picker and returns selected agent names.
 fn prompt_for_agents() -> Result<Vec<String>> {
     let agents = agent_configs();
-    let items: Vec<String> = agents.iter().map(|a| a.display_name.to_string()).collect();
-    if items.is_empty() {
+    if agents.is_empty() {
         return Ok(vec![]);
     }
+    let items = agents
+        .iter()
+        .map(|agent| PickerItem {
+            label: agent.display_name.to_string(),
+            detail: format!("{}, {}", agent.name, agent.skills_dir),
+            ..PickerItem::default()
+        })
+        .collect();
 
-    let selection = dialoguer::MultiSelect::with_theme(&ColorfulTheme::default())
-        .with_prompt("Select agents to install to")
-        .items(&items)
-        .interact()
-        .map_err(|err| SkilError::Message(err.to_string()))?;
+    let selection = Picker::new("Select agents to install to", items).interact()?;
     let selected = selection
         .into_iter()
         .map(|idx| agents[idx].name.to_string())
@@ -292,6 +1010,17 @@ fn prompt_for_agents() -> Result<Vec<String>> {
 
 /// Initializes a new SKILL.md file in the current or named directory.
 pub fn run_init(args: InitArgs) -> Result<()> {
+    if let Some(source) = &args.list_templates {
+        let observer = CliObserver::new(false);
+        let set = fetch_templates(source, &observer)?;
+        let rows: Vec<Vec<String>> = set
+            .templates
+            .iter()
+            .map(|template| vec![template.name.clone(), template.description.clone()])
+            .collect();
+        ui::table(&["TEMPLATE", "DESCRIPTION"], &rows);
+        return Ok(());
+    }
     let cwd = std::env::current_dir()?;
     let has_name = args.name.is_some();
     let skill_name = args.name.clone().unwrap_or_else(|| {
@@ -307,6 +1036,12 @@ pub fn run_init(args: InitArgs) -> Result<()> {
         cwd.clone()
     };
     let skill_file = skill_dir.join("SKILL.md");
+    if !args.agent.is_empty() && !has_name {
+        return Err(SkilError::Message(
+            "Pass a name to install the new skill; a skill at the project root cannot be installed into it"
+                .to_string(),
+        ));
+    }
 
     if skill_file.exists() {
         ui::warn(&format!(
@@ -316,6 +1051,20 @@ pub fn run_init(args: InitArgs) -> Result<()> {
         return Ok(());
     }
 
+    if let Some(template) = &args.template {
+        let (template, source) = parse_template_ref(template);
+        let observer = CliObserver::new(false);
+        let set = fetch_templates(source, &observer)?;
+        let files = scaffold(set.get(template)?, &skill_dir, &skill_name)?;
+        ui::success(&format!(
+            "Initialized skill {skill_name} from template {template}"
+        ));
+        for file in files {
+            ui::list_item(&display_path(&skill_dir.join(file)));
+        }
+        return install_initialized(&skill_dir, &skill_name, &args);
+    }
+
     if has_name {
         std::fs::create_dir_all(&skill_dir)?;
     }
@@ -329,48 +1078,285 @@ pub fn run_init(args: InitArgs) -> Result<()> {
 
     ui::success(&format!("Initialized skill: {}", skill_name));
     ui::info(&format!("Created: {}", display_path(&skill_file)));
+    install_initialized(&skill_dir, &skill_name, &args)
+}
+
+/// Symlinks a new skill into project agents when `--agent` is set or the
+/// user accepts, so it can be tried right away.
+fn install_initialized(skill_dir: &Path, skill_name: &str, args: &InitArgs) -> Result<()> {
+    // A skill at the project root would be copied into its own store.
+    if args.no_install || args.name.is_none() {
+        return Ok(());
+    }
+    if args.agent.is_empty() {
+        let confirmed = console::Term::stdout().is_term()
+            && dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Install {skill_name} into your agents now?"))
+                .default(true)
+                .interact()
+                .map_err(|err| SkilError::Message(err.to_string()))?;
+        if !confirmed {
+            return Ok(());
+        }
+    }
+    let agents = resolve_install_agents(&args.agent, false)?;
+    let observer = CliObserver::new(false);
+    let report = Installer::new(skill_dir.to_string_lossy().to_string())
+        .agents(agents)
+        .mode(InstallMode::Symlink)
+        .observer(&observer)
+        .install()?;
+    ui::success(&format!(
+        "Installed {skill_name} to {}",
+        report.agents.join(", ")
+    ));
+    ui::info(&format!(
+        "Run `skil add {}` after editing to refresh the installed copy",
+        display_path(skill_dir)
+    ));
     Ok(())
 }
 
 /// Prints shell completion scripts to stdout.
 pub fn run_completions(args: CompletionsArgs) -> Result<()> {
     use clap::CommandFactory;
-    let mut cmd = crate::cli::Cli::command();
-    let bin_name = cmd.get_name().to_string();
-    generate(args.shell, &mut cmd, bin_name, &mut io::stdout());
+    let mut cmd = crate::cli::Cli::command().name("skil");
+    let mut script = Vec::new();
+    generate(args.shell, &mut cmd, "skil", &mut script);
+    let script = String::from_utf8_lossy(&script);
+    print!("{}", complete_source_skills(args.shell, &script));
     Ok(())
 }
 
-/// Resolves and validates target agents for install flows.
-fn resolve_install_agents(agent_args: &[String], yes: bool) -> Result<Vec<AgentConfig>> {
-    let mut selected = agent_args.to_vec();
-    if selected.is_empty() && !yes {
-        selected = prompt_for_agents()?;
-    }
-    if !(selected.is_empty() || (selected.len() == 1 && selected[0] == "*")) {
-        let valid: HashSet<&str> = agent_configs().iter().map(|a| a.name).collect();
-        let invalid: Vec<String> = selected
-            .iter()
-            .filter(|name| !valid.contains(name.as_str()))
-            .cloned()
-            .collect();
-        if !invalid.is_empty() {
-            let valid_list = agent_configs()
-                .iter()
-                .map(|a| a.name)
-                .collect::<Vec<_>>()
-                .join(", ");
-            return Err(SkilError::Message(format!(
-                "Invalid agents: {}. Valid agents: {}",
-                invalid.join(", "),
-                valid_list
-            )));
-        }
-    }
+/// Bash function printing the skills of the source typed after `add` or `show`.
+const BASH_SOURCE_SKILLS: &str = r#"_skil_source_skills() {
+    local i word source="" skip=""
+    for ((i = 2; i < COMP_CWORD; i++)); do
+        word="${COMP_WORDS[i]}"
+        case "$word" in
+            -a|--agent|-b|--branch|--path|-s|--skill) skip=1 ;;
+            -*) ;;
+            *)
+                if [[ -z $skip ]]; then
+                    source="$word"
+                    break
+                fi
+                skip=""
+                ;;
+        esac
+    done
+    [[ -n $source ]] && skil complete-skills "$source" 2>/dev/null
+}
 
-    let agents = resolve_agents(&selected);
+"#;
+
+/// Zsh function completing the skills of the source argument of `add` or `show`.
+const ZSH_SOURCE_SKILLS: &str = r#"(( $+functions[_skil_source_skills] )) ||
+_skil_source_skills() {
+    local source=${line[1]}
+    [[ -n $source ]] || return 1
+    local -a skills
+    skills=(${(f)"$(skil complete-skills "$source" 2>/dev/null)"})
+    compadd -a skills
+}
+
+"#;
+
+/// Fish completions for `--skill` of `add` and `show`, on top of the generated ones.
+const FISH_SOURCE_SKILLS: &str = r#"
+function __fish_skil_source_skills
+	set -l skip 0
+	for token in (commandline -opc)[3..-1]
+		if test $skip -eq 1
+			set skip 0
+		else if string match -qr -- '^(-a|--agent|-b|--branch|--path|-s|--skill)$' $token
+			set skip 1
+		else if not string match -q -- '-*' $token
+			skil complete-skills $token 2>/dev/null
+			return
+		end
+	end
+end
+
+complete -c skil -n "__fish_skil_using_subcommand add show" -s s -l skill -f -a "(__fish_skil_source_skills)"
+"#;
+
+/// Makes a generated completion script complete `--skill` of `add` and `show`
+/// with the skills of the source typed before it. Other shells keep plain
+/// completion.
+fn complete_source_skills(shell: Shell, script: &str) -> String {
+    match shell {
+        Shell::Bash => {
+            let mut script = script.to_string();
+            for command in ["skil__add)", "skil__show)"] {
+                script = replace_in_block(
+                    &script,
+                    command,
+                    "\n        skil__",
+                    "COMPREPLY=($(compgen -f \"${cur}\"))\n                    return 0\n                    ;;\n                -s)",
+                    "COMPREPLY=($(compgen -W \"$(_skil_source_skills)\" -- \"${cur}\"))\n                    return 0\n                    ;;\n                -s)",
+                );
+                script = replace_in_block(
+                    &script,
+                    command,
+                    "\n        skil__",
+                    "-s)\n                    COMPREPLY=($(compgen -f \"${cur}\"))",
+                    "-s)\n                    COMPREPLY=($(compgen -W \"$(_skil_source_skills)\" -- \"${cur}\"))",
+                );
+            }
+            format!("{BASH_SOURCE_SKILLS}{script}")
+        }
+        Shell::Zsh => {
+            let mut script = script.to_string();
+            for command in ["(add)\n", "(show)\n"] {
+                script = replace_in_block(
+                    &script,
+                    command,
+                    "\n;;\n",
+                    ":SKILL:_default'",
+                    ":SKILL:_skil_source_skills'",
+                );
+            }
+            // The script ends by running or registering `_skil`, which needs
+            // the helper defined.
+            match script.rfind("\nif [ \"$funcstack[1]\"") {
+                Some(end) => format!(
+                    "{}\n{ZSH_SOURCE_SKILLS}{}",
+                    &script[..end],
+                    &script[end + 1..]
+                ),
+                None => script,
+            }
+        }
+        Shell::Fish => format!("{script}{FISH_SOURCE_SKILLS}"),
+        _ => script.to_string(),
+    }
+}
+
+/// Replaces every `from` with `to` between the first `start` in `script` and
+/// the next `end`.
+fn replace_in_block(script: &str, start: &str, end: &str, from: &str, to: &str) -> String {
+    let Some(begin) = script.find(start) else {
+        return script.to_string();
+    };
+    let block_end = script[begin + start.len()..]
+        .find(end)
+        .map_or(script.len(), |offset| begin + start.len() + offset);
+    format!(
+        "{}{}{}",
+        &script[..begin],
+        script[begin..block_end].replace(from, to),
+        &script[block_end..]
+    )
+}
+
+/// Prints the skill names of a source, one per line, for shell completion.
+/// Names of remote sources are cached for a day.
+pub fn run_complete_skills(args: CompleteSkillsArgs) -> Result<()> {
+    let source = parse_source(&args.source)?;
+    let key = source_key(&source);
+    let names = match cached_skill_names(&key) {
+        Some(names) if !matches!(source, Source::Local { .. }) => names,
+        _ => {
+            let discovered = Discovery::new(args.source)
+                .revision(Revision::Head)
+                .options(DiscoverOptions::default().with_config(false)?)
+                .run()?;
+            cache_source_skills(&discovered);
+            discovered
+                .skills
+                .into_iter()
+                .map(|skill| skill.name)
+                .collect()
+        }
+    };
+    for name in names.iter().collect::<BTreeSet<_>>() {
+        println!("{name}");
+    }
+    Ok(())
+}
+
+/// Saves the skill names of a remote source for `--skill` completion.
+fn cache_source_skills(discovered: &DiscoveredSource) {
+    if matches!(discovered.source, Source::Local { .. }) {
+        return;
+    }
+    let names: Vec<String> = discovered
+        .skills
+        .iter()
+        .map(|skill| skill.name.clone())
+        .collect();
+    let _ = cache_skill_names(&discovered.key(), &names);
+}
+
+/// Prints the time spent in each profiled phase of a command that took
+/// `elapsed` in all, on stderr.
+pub fn print_profile(elapsed: Duration) {
+    ui::use_stderr_only();
+    let timings = timings();
+    let share = |time: Duration| {
+        format!(
+            "{:.0}%",
+            100.0 * time.as_secs_f64() / elapsed.as_secs_f64().max(f64::EPSILON)
+        )
+    };
+    let mut rows: Vec<Vec<String>> = timings
+        .iter()
+        .map(|timing| {
+            vec![
+                timing.phase.to_string(),
+                timing.calls.to_string(),
+                format!("{:.1?}", timing.total),
+                share(timing.total),
+            ]
+        })
+        .collect();
+    // Phases do not overlap, so the rest is skil's own work and waiting on prompts.
+    let other = elapsed.saturating_sub(timings.iter().map(|timing| timing.total).sum());
+    rows.push(vec![
+        "other".to_string(),
+        "-".to_string(),
+        format!("{other:.1?}"),
+        share(other),
+    ]);
+    ui::heading(&format!("Finished in {elapsed:.1?}"));
+    ui::table(&["PHASE", "CALLS", "TIME", "SHARE"], &rows);
+}
+
+/// Resolves and validates target agents for install flows.
+fn resolve_install_agents(agent_args: &[String], yes: bool) -> Result<Vec<AgentConfig>> {
+    let mut selected = agent_args.to_vec();
+    if selected.is_empty() && !yes && default_agent_names().is_none() {
+        selected = prompt_for_agents()?;
+    }
+    if !(selected.is_empty() || (selected.len() == 1 && selected[0] == "*")) {
+        let valid: HashSet<&str> = agent_configs().iter().map(|a| a.name).collect();
+        let invalid: Vec<String> = selected
+            .iter()
+            .filter(|name| !valid.contains(name.as_str()))
+            .cloned()
+            .collect();
+        if !invalid.is_empty() {
+            let valid_list = agent_configs()
+                .iter()
+                .map(|a| a.name)
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(SkilError::Message(format!(
+                "Invalid agents: {}. Valid agents: {}",
+                invalid.join(", "),
+                valid_list
+            )));
+        }
+    }
+
+    require_agents(resolve_agents(&selected))
+}
+
+/// Fails with `E_NO_AGENTS` when no agent was selected or detected.
+fn require_agents(agents: Vec<AgentConfig>) -> Result<Vec<AgentConfig>> {
     if agents.is_empty() {
-        return Err(SkilError::Message("No agents selected".to_string()));
+        return Err(SkilError::new(ErrorCode::NoAgents, "No agents selected"));
     }
     Ok(agents)
 }
@@ -383,151 +1369,470 @@ pub fn run_add(mut args: AddArgs) -> Result<()> {
         args.yes = true;
     }
 
-    let source = parse_source(&args.source)?;
-
-    let should_prompt_agents = !args.list;
-    let agents = if should_prompt_agents {
-        resolve_install_agents(&args.agent, args.yes)?
+    let hint = if args.local {
+        SourceHint::Local
+    } else if args.git {
+        SourceHint::Git
     } else {
-        Vec::new()
+        SourceHint::Auto
+    };
+    match parse_source_as(&args.source, hint)? {
+        Source::Local { path } if hint == SourceHint::Local => {
+            args.source = path.to_string_lossy().to_string();
+        }
+        Source::Git { url, subpath, info } if hint == SourceHint::Git => {
+            if args.paths.is_empty() {
+                args.paths = subpath.into_iter().collect();
+            }
+            args.branch = args.branch.take().or(info.github_branch);
+            args.source = url;
+        }
+        _ => {}
+    }
+    let adopted = if args.adopt {
+        Some(adopt_markdown(&args.source, args.yes || args.list)?)
+    } else {
+        None
+    };
+    if let Some(dir) = &adopted {
+        args.source = dir.path().to_string_lossy().to_string();
+        args.full_depth = true;
+    }
+    let large_skills = if args.allow_large {
+        LargeSkills::Install
+    } else if args.yes {
+        LargeSkills::Refuse
+    } else {
+        LargeSkills::Confirm
+    };
+    let observer = CliObserver::new(args.skill.is_empty() && !args.yes)
+        .confirm_homonyms(!args.yes)
+        .overwrite_homonyms(args.overwrite)
+        .large_skills(large_skills);
+
+    if args.list {
+        let discovered = Discovery::new(args.source.clone())
+            .subpaths(args.paths.clone())
+            .branch(args.branch.clone())
+            .options(
+                DiscoverOptions {
+                    full_depth: args.full_depth,
+                    no_ignore: args.no_ignore,
+                    max_depth: args.max_depth,
+                    file_skills: args.file_skills,
+                    ..Default::default()
+                }
+                .with_config(args.global)?,
+            )
+            .observer(&observer)
+            .run()?;
+        if args.paths.is_empty() {
+            cache_source_skills(&discovered);
+        }
+        if discovered.skills.is_empty() {
+            return Err(SkilError::new(
+                ErrorCode::NoSkills,
+                "No skills found in source",
+            ));
+        }
+        if ui::json_output() || args.long {
+            return list_skills_long(&discovered, args.global);
+        }
+        ui::heading("Available skills");
+        for (group, skills) in group_skills(&discovered.skills) {
+            if !group.is_empty() {
+                ui::info(&format!("{group}/"));
+            }
+            for skill in skills {
+                ui::list_item(&format!("{}: {}", skill.name, skill.description));
+            }
+        }
+        return Ok(());
+    }
+
+    // Answers are remembered per source; adopted sources live in a temp dir.
+    let answers_key = match &adopted {
+        Some(_) => None,
+        None => Some(source_key(&parse_source_as(&args.source, hint)?)),
     };
+    let answers_path = config_location(true)?.path;
+    let remembered = match &answers_key {
+        Some(key) if !args.yes && !args.ask => {
+            remembered_answers(&read_config(&answers_path)?, key).cloned()
+        }
+        _ => None,
+    }
+    .unwrap_or_default();
+    let mut asked = PromptAnswers::default();
+    let mut reused = Vec::new();
+
+    let mut agent_names = args.agent.clone();
+    if agent_names.is_empty() {
+        agent_names = preferred_agents(args.global)?;
+    }
+    if agent_names.is_empty() && !args.yes && default_agent_names().is_none() {
+        if remembered.agents.is_empty() {
+            agent_names = prompt_for_agents()?;
+            asked.agents = agent_names.clone();
+        } else {
+            agent_names = remembered.agents.clone();
+            reused.push(format!("agents {}", agent_names.join(", ")));
+        }
+    }
+    // Already asked above when needed.
+    let agents = resolve_install_agents(&agent_names, true)?;
 
     let supports_global = agents
         .iter()
         .any(|agent| !agent.global_skills_dir.is_empty());
     let mut install_global = args.global;
-    if should_prompt_agents && supports_global && !args.global && !args.yes {
-        let selection = dialoguer::Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Installation scope")
-            .items(["Project (current directory)", "Global (home directory)"])
-            .default(0)
-            .interact()
-            .map_err(|err| SkilError::Message(err.to_string()))?;
-        install_global = selection == 1;
+    if supports_global && !args.global && !args.yes {
+        if let Some(global) = remembered.global {
+            install_global = global;
+            reused.push(
+                if global {
+                    "global scope"
+                } else {
+                    "project scope"
+                }
+                .to_string(),
+            );
+        } else {
+            let selection = dialoguer::Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Installation scope")
+                .items(["Project (current directory)", "Global (home directory)"])
+                .default(0)
+                .interact()
+                .map_err(|err| SkilError::Message(err.to_string()))?;
+            install_global = selection == 1;
+            asked.global = Some(install_global);
+        }
     }
 
-    let mut install_mode = if args.copy {
+    let preferred_copy = preferred_copy(args.global)?;
+    let mut install_mode = if args.copy || preferred_copy == Some(true) {
         InstallMode::Copy
     } else {
         InstallMode::Symlink
     };
-    if should_prompt_agents && !args.yes && !args.copy {
-        let selection = dialoguer::Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Installation method")
-            .items(["Symlink (recommended)", "Copy to each agent"])
-            .default(0)
-            .interact()
-            .map_err(|err| SkilError::Message(err.to_string()))?;
-        if selection == 1 {
+    if !args.yes && !args.copy && preferred_copy.is_none() {
+        let copy = match remembered.copy {
+            Some(copy) => {
+                reused.push(
+                    if copy {
+                        "copy method"
+                    } else {
+                        "symlink method"
+                    }
+                    .to_string(),
+                );
+                copy
+            }
+            None => {
+                let selection = dialoguer::Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Installation method")
+                    .items(["Symlink (recommended)", "Copy to each agent"])
+                    .default(0)
+                    .interact()
+                    .map_err(|err| SkilError::Message(err.to_string()))?;
+                asked.copy = Some(selection == 1);
+                selection == 1
+            }
+        };
+        if copy {
             install_mode = InstallMode::Copy;
         }
     }
+    if !reused.is_empty() {
+        ui::info(&format!(
+            "Using your earlier answers for this source: {}; pass --ask to choose again",
+            reused.join("; ")
+        ));
+    }
 
-    let (base_path, _temp): (PathBuf, Option<tempfile::TempDir>) = match &source {
-        Source::Local { path } => (path.clone(), None),
-        Source::Git { url, .. } => {
-            let temp_dir = tempfile::tempdir()?;
-            let spinner = ui::spinner("Cloning repository...");
-            let result = clone_repo(url, temp_dir.path());
-            match result {
-                Ok(()) => spinner.finish_with_message("Repository cloned"),
-                Err(err) => {
-                    spinner.finish_with_message("Repository clone failed");
-                    return Err(err);
-                }
-            }
-            (temp_dir.path().to_path_buf(), Some(temp_dir))
-        }
-    };
+    let report = Installer::new(args.source.clone())
+        .subpaths(args.paths.clone())
+        .branch(args.branch.clone())
+        .skills(args.skill.clone())
+        .agents(agents.clone())
+        .global(install_global)
+        .mode(install_mode)
+        .full_depth(args.full_depth)
+        .no_ignore(args.no_ignore)
+        .max_depth(args.max_depth)
+        .file_skills(args.file_skills)
+        .track(adopted.is_none())
+        .observer(&observer)
+        .install()?;
+    if let Some(key) = &answers_key
+        && let Err(err) = remember_answers(&answers_path, key, asked)
+    {
+        ui::warn(&format!("Could not remember your answers: {err}"));
+    }
+    record_history(
+        HistoryEntry::new("add", install_global)?
+            .source(report.source_key.clone())
+            .skills(report.skills.clone())
+            .revisions(None, short_report_revision(&report)),
+    );
 
-    let subpath = match &source {
-        Source::Local { .. } => None,
-        Source::Git { subpath, .. } => subpath.clone(),
-    };
+    if let Source::Git { info, .. } = &report.source
+        && let Some(owner_repo) = info.github_owner_repo.as_deref()
+        && telemetry_enabled().unwrap_or(false)
+    {
+        let agent_names: Vec<&str> = report.agents.iter().map(String::as_str).collect();
+        telemetry::ping_install(owner_repo, &report.skills, &agent_names);
+    }
 
-    let (checksum, version) = match &source {
-        Source::Local { .. } => (None, None),
-        Source::Git { url, .. } => {
-            let tag = latest_tag(url)?;
-            if let Some(version) = tag.as_deref() {
-                checkout_revision(&base_path, version)?;
-            }
-            (head_revision(&base_path).ok(), tag)
+    if ui::json_output() {
+        println!("{}", install_summary_json(&report));
+        warn_lfs_pointers(&report.lfs_pointers);
+        return warn_budgets(&agents, install_global);
+    }
+    ui::success(&format!(
+        "Installed {} skill(s) to {} agent(s)",
+        report.skills.len(),
+        report.agents.len()
+    ));
+    for entry in &report.excluded {
+        ui::info(&format!(
+            "Skipped {} for {}: excluded by config rules",
+            entry.skill, entry.agent
+        ));
+    }
+    warn_lfs_pointers(&report.lfs_pointers);
+    if args.yes {
+        for line in install_summary_lines(&report) {
+            ui::info(&line);
         }
-    };
+    }
+    warn_budgets(&agents, install_global)
+}
 
-    let skills = discover_skills(&base_path, subpath.as_deref(), args.full_depth)?;
+/// Maps a `source_type` of the MCP and HTTP APIs to the `--local` and `--git` flags.
+pub(crate) fn source_type_flags(source_type: Option<&str>) -> Result<(bool, bool)> {
+    match source_type {
+        None => Ok((false, false)),
+        Some("local") => Ok((true, false)),
+        Some("git") => Ok((false, true)),
+        Some(other) => Err(SkilError::new(
+            ErrorCode::InvalidSource,
+            format!("Unknown source_type {other}: expected local or git"),
+        )),
+    }
+}
 
-    if skills.is_empty() {
-        return Err(SkilError::Message("No skills found in source".to_string()));
+/// Converts markdown files without frontmatter into skills in a temporary directory.
+///
+/// Names and descriptions are inferred from each file and confirmed unless `yes` is set.
+fn adopt_markdown(source: &str, yes: bool) -> Result<tempfile::TempDir> {
+    let Source::Local { path } = parse_source(source)? else {
+        return Err(SkilError::new(
+            ErrorCode::InvalidSource,
+            "--adopt requires a local directory",
+        ));
+    };
+    let docs = scan_markdown(&path)?;
+    if docs.is_empty() {
+        return Err(SkilError::new(
+            ErrorCode::NoSkills,
+            format!("No markdown files to adopt in {}", display_path(&path)),
+        ));
     }
 
-    if args.list {
-        ui::heading("Available skills");
-        for skill in &skills {
-            ui::list_item(&format!("{}: {}", skill.name, skill.description));
+    let out = scratch_dir("adopt-")?;
+    let mut seen = HashSet::new();
+    for doc in docs {
+        let (name, description) = if yes {
+            (doc.name, doc.description)
+        } else {
+            let file = doc.path.strip_prefix(&path).unwrap_or(&doc.path);
+            ui::heading(&display_path(file));
+            let theme = ColorfulTheme::default();
+            let name: String = dialoguer::Input::with_theme(&theme)
+                .with_prompt("Name")
+                .default(doc.name)
+                .interact_text()
+                .map_err(|err| SkilError::Message(err.to_string()))?;
+            let description: String = dialoguer::Input::with_theme(&theme)
+                .with_prompt("Description")
+                .default(doc.description)
+                .interact_text()
+                .map_err(|err| SkilError::Message(err.to_string()))?;
+            (name, description)
+        };
+        if !seen.insert(sanitize_name(&name)) {
+            ui::warn(&format!(
+                "Skipping {}: a skill named {name} was already adopted",
+                display_path(&doc.path)
+            ));
+            continue;
         }
-        return Ok(());
+        write_skill(out.path(), &name, &description, &doc.content)?;
     }
+    Ok(out)
+}
 
-    if args.skill.is_empty() && !args.yes {
-        args.skill = prompt_for_skills(&skills)?;
-    }
+/// Agents and paths each reported skill was installed to.
+fn installed_by_skill(report: &InstallReport) -> Vec<(&str, Vec<&str>, Vec<String>)> {
+    report
+        .skills
+        .iter()
+        .map(|skill| {
+            let entries = report
+                .installed
+                .iter()
+                .filter(|entry| &entry.skill == skill);
+            let agents = entries.clone().map(|entry| entry.agent.as_str()).collect();
+            let paths = entries.map(|entry| display_path(&entry.path)).collect();
+            (skill.as_str(), agents, paths)
+        })
+        .collect()
+}
 
-    let selected_skills = select_skills(&skills, &args.skill);
-    if selected_skills.is_empty() {
-        return Err(SkilError::Message(
-            "No matching skills selected".to_string(),
-        ));
-    }
+fn report_revision(report: &InstallReport) -> &str {
+    report
+        .version
+        .as_deref()
+        .or(report.checksum.as_deref())
+        .unwrap_or("local")
+}
 
-    let install_spinner = ui::spinner("Installing skills...");
-    for skill in &selected_skills {
-        for agent in &agents {
-            install_skill(skill, agent, install_global, install_mode)?;
+/// Version or short checksum a report installed, as [`source_revision`] shows it.
+fn short_report_revision(report: &InstallReport) -> Option<String> {
+    report
+        .version
+        .clone()
+        .or_else(|| report.checksum.as_deref().map(short_revision))
+}
+
+/// One `installed skill=... revision=... agents=... paths=...` line per skill.
+fn install_summary_lines(report: &InstallReport) -> Vec<String> {
+    installed_by_skill(report)
+        .into_iter()
+        .map(|(skill, agents, paths)| {
+            format!(
+                "installed skill={} revision={} agents={} paths={}",
+                skill,
+                report_revision(report),
+                agents.join(","),
+                paths.join(",")
+            )
+        })
+        .collect()
+}
+
+/// Machine-readable summary of an install for `--json`.
+/// Prints each skill of a source with its path, tags, and install size, as a
+/// table or as JSON.
+fn list_skills_long(discovered: &DiscoveredSource, global: bool) -> Result<()> {
+    let ignore = IgnorePatterns::load(&discovered.root, global)?;
+    let mut skills = Vec::new();
+    for (group, members) in group_skills(&discovered.skills) {
+        for skill in members {
+            let frontmatter = parse_frontmatter(&skill.raw_content)?;
+            let tags = frontmatter
+                .as_ref()
+                .map(|frontmatter| frontmatter.tags.clone())
+                .unwrap_or_default();
+            let deprecated = frontmatter
+                .as_ref()
+                .and_then(Frontmatter::deprecation)
+                .is_some();
+            let path = skill
+                .path
+                .strip_prefix(&discovered.root)
+                .unwrap_or(&skill.path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let bytes = install_size(skill, &ignore)?;
+            skills.push((group.clone(), skill, path, tags, bytes, deprecated));
         }
     }
-    install_spinner.finish_with_message("Installation complete");
 
-    let config_location = config_location(install_global)?;
-    let source_key = match &source {
-        Source::Local { path } => path.to_string_lossy().to_string(),
-        Source::Git { url, .. } => url.clone(),
-    };
-    let source_entry = match &source {
-        Source::Local { .. } => SkilSource {
-            branch: None,
-            subpath: None,
-            checksum: None,
-            version: None,
-            skills: vec![],
-        },
-        Source::Git { subpath, info, .. } => SkilSource {
-            branch: info.github_branch.clone(),
-            subpath: subpath.as_ref().map(|p| p.to_string_lossy().to_string()),
-            checksum: None,
-            version: None,
-            skills: vec![],
-        },
-    };
-    let skill_names: Vec<String> = selected_skills.iter().map(|s| s.name.clone()).collect();
-    update_config(
-        &config_location.path,
-        &source_key,
-        source_entry,
-        &skill_names,
-        checksum,
-        version,
-    )?;
+    if ui::json_output() {
+        let skills: Vec<serde_json::Value> = skills
+            .iter()
+            .map(|(group, skill, path, tags, bytes, deprecated)| {
+                serde_json::json!({
+                    "name": skill.name,
+                    "description": skill.description,
+                    "path": path,
+                    "found_by": skill.found_by,
+                    "group": group,
+                    "tags": tags,
+                    "bytes": bytes,
+                    "deprecated": deprecated,
+                })
+            })
+            .collect();
+        let duplicates: Vec<serde_json::Value> = discovered
+            .duplicates
+            .iter()
+            .map(|duplicate| {
+                serde_json::json!({
+                    "name": duplicate.name,
+                    "paths": duplicate.paths,
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "source": discovered.key(),
+                "version": discovered.version,
+                "checksum": discovered.checksum,
+                "skills": skills,
+                "duplicates": duplicates,
+            })
+        );
+        return Ok(());
+    }
 
-    ui::success(&format!(
-        "Installed {} skill(s) to {} agent(s)",
-        selected_skills.len(),
-        agents.len()
-    ));
+    let rows: Vec<Vec<String>> = skills
+        .iter()
+        .map(|(_, skill, path, tags, bytes, _)| {
+            vec![
+                skill.name.clone(),
+                path.clone(),
+                skill.found_by.clone(),
+                tags.join(", "),
+                HumanBytes(*bytes).to_string(),
+                skill.description.clone(),
+            ]
+        })
+        .collect();
+    ui::table(
+        &["NAME", "PATH", "FOUND BY", "TAGS", "SIZE", "DESCRIPTION"],
+        &rows,
+    );
     Ok(())
 }
 
+fn install_summary_json(report: &InstallReport) -> serde_json::Value {
+    let skills: Vec<serde_json::Value> = installed_by_skill(report)
+        .into_iter()
+        .map(|(skill, agents, paths)| {
+            serde_json::json!({ "name": skill, "agents": agents, "paths": paths })
+        })
+        .collect();
+    let excluded: Vec<serde_json::Value> = report
+        .excluded
+        .iter()
+        .map(|entry| serde_json::json!({ "name": entry.skill, "agent": entry.agent }))
+        .collect();
+    serde_json::json!({
+        "source": report.source_key,
+        "revision": report_revision(report),
+        "version": report.version,
+        "checksum": report.checksum,
+        "skills": skills,
+        "missing": report.missing,
+        "excluded": excluded,
+        "lfs_pointers": report.lfs_pointers,
+    })
+}
+
 /// Installs all tracked skills from config, respecting pinned checksums/versions.
 pub fn run_install(mut args: InstallArgs) -> Result<()> {
     let location = config_location(args.global)?;
@@ -540,64 +1845,97 @@ pub fn run_install(mut args: InstallArgs) -> Result<()> {
         return Ok(());
     }
 
+    if args.agent.is_empty() {
+        args.agent = preferred_agents(args.global)?;
+    }
     if args.agent.is_empty() && !args.yes {
         args.agent = prompt_for_agents()?;
     }
     let agents = resolve_install_agents(&args.agent, true)?;
 
-    let install_mode = if args.copy {
+    let install_mode = if args.copy || preferred_copy(args.global)? == Some(true) {
         InstallMode::Copy
     } else {
         InstallMode::Symlink
     };
 
+    let _lock = lock_config(&location.path)?;
+    let rules = agent_rules(args.global)?;
+    let canonical = canonical_skills_dir(args.global)?;
+    let observer = CliObserver::new(false);
     let mut installed = 0usize;
     for (source_key, source_entry) in &config.sources {
         if source_entry.skills.is_empty() {
             continue;
         }
-        let source = parse_source(source_key)?;
-
-        let (base_path, _temp): (PathBuf, Option<tempfile::TempDir>) = match &source {
-            Source::Local { path } => (path.clone(), None),
-            Source::Git { url, .. } => {
-                let temp_dir = tempfile::tempdir()?;
-                let spinner = ui::spinner(&format!("Cloning {}...", source_key));
-                let result = clone_repo(url, temp_dir.path());
-                match result {
-                    Ok(()) => spinner.finish_with_message("Repository cloned"),
-                    Err(err) => {
-                        spinner.finish_with_message("Repository clone failed");
-                        return Err(err);
-                    }
-                }
-                if let Some(checksum) = source_entry.checksum.as_deref() {
-                    checkout_revision(temp_dir.path(), checksum)?;
-                } else if let Some(version) = source_entry.version.as_deref() {
-                    checkout_revision(temp_dir.path(), version)?;
-                }
-                (temp_dir.path().to_path_buf(), Some(temp_dir))
-            }
-        };
 
-        let parsed_subpath = match &source {
-            Source::Git { subpath, .. } => subpath.clone(),
-            Source::Local { .. } => None,
+        let revision = match (&source_entry.checksum, &source_entry.version) {
+            (Some(checksum), _) => Revision::Pinned(checksum.clone()),
+            (None, Some(version)) => Revision::Pinned(version.clone()),
+            (None, None) => Revision::Head,
         };
-        let subpath = source_entry
-            .subpath
-            .as_deref()
-            .map(PathBuf::from)
-            .or(parsed_subpath);
-        let skills = discover_skills(&base_path, subpath.as_deref(), args.full_depth)?;
-        if skills.is_empty() {
+        let discovered = Discovery::new(source_key.clone())
+            .subpaths(source_entry.search_paths())
+            .branch(source_entry.branch.clone())
+            .revision(revision)
+            .options(
+                DiscoverOptions {
+                    full_depth: args.full_depth,
+                    no_ignore: args.no_ignore,
+                    max_depth: args.max_depth,
+                    file_skills: args.file_skills,
+                    ..Default::default()
+                }
+                .with_config(args.global)?,
+            )
+            .observer(&observer)
+            .run()?;
+        if discovered.skills.is_empty() {
             continue;
         }
 
-        let selected_skills = select_skills(&skills, &source_entry.skills);
+        let selected_skills = select_skills(&discovered.skills, &source_entry.skills);
+        check_skil_version(&selected_skills)?;
+        let ignore = IgnorePatterns::load(&discovered.root, args.global)?;
+        let pointers: Vec<PathBuf> = discovered
+            .lfs_pointers
+            .iter()
+            .filter(|path| {
+                let path = discovered.root.join(path);
+                selected_skills
+                    .iter()
+                    .any(|skill| path.starts_with(&skill.path))
+            })
+            .cloned()
+            .collect();
+        warn_lfs_pointers(&pointers);
         for skill in &selected_skills {
+            // Locally modified skills keep their store copy and are only linked.
+            let keep = source_entry.is_modified(&skill.name)
+                && canonical.join(sanitize_name(&skill.name)).is_dir();
             for agent in &agents {
-                install_skill(skill, agent, args.global, install_mode)?;
+                if rules_allow(&rules, skill, agent.name) {
+                    if keep {
+                        link_skill(
+                            &sanitize_name(&skill.name),
+                            agent,
+                            args.global,
+                            install_mode,
+                        )?;
+                    } else {
+                        install_skill(skill, agent, args.global, install_mode, &ignore)?;
+                    }
+                    continue;
+                }
+                let dir_name = sanitize_name(&skill.name);
+                remove_managed(
+                    &agent_skills_base(agent, args.global)?.join(&dir_name),
+                    &canonical.join(&dir_name),
+                )?;
+                ui::info(&format!(
+                    "Skipped {} for {}: excluded by config rules",
+                    skill.name, agent.display_name
+                ));
             }
         }
         installed += selected_skills.len();
@@ -613,18 +1951,24 @@ pub fn run_install(mut args: InstallArgs) -> Result<()> {
 
 /// Removes installed skills from agent directories.
 pub fn run_remove(mut args: RemoveArgs) -> Result<()> {
-    if args.all {
-        args.skill = vec!["*".to_string()];
+    let scopes = if args.source.is_some() && !args.global && !args.project_only {
+        vec![false, true]
+    } else {
+        selected_scopes(args.global, args.both)
+    };
+    if let Some(source) = &args.source {
+        return remove_source(source, &scopes, args.yes);
+    }
+    if args.all && args.agent.is_empty() {
         args.agent = vec!["*".to_string()];
-        args.yes = true;
     }
 
     let mut requested_skills = args.skills.clone();
     requested_skills.extend(args.skill.clone());
 
-    let agents = resolve_agents(&args.agent);
-    if agents.is_empty() {
-        return Err(SkilError::Message("No agents selected".to_string()));
+    let agents = require_agents(resolve_agents(&args.agent))?;
+    if args.all || requested_skills == ["*"] {
+        return remove_all(&agents, &scopes, args.yes);
     }
 
     let skill_names = if requested_skills.is_empty() {
@@ -635,8 +1979,11 @@ pub fn run_remove(mut args: RemoveArgs) -> Result<()> {
         }
 
         let mut names = std::collections::BTreeSet::new();
-        for agent in &agents {
-            let base = agent_skills_base(agent, args.global)?;
+        for (agent, &global) in agents
+            .iter()
+            .flat_map(|a| scopes.iter().map(move |s| (a, s)))
+        {
+            let base = agent_skills_base(agent, global)?;
             if !base.exists() {
                 continue;
             }
@@ -659,12 +2006,14 @@ pub fn run_remove(mut args: RemoveArgs) -> Result<()> {
         }
 
         let items: Vec<String> = names.into_iter().collect();
-        let selection = dialoguer::MultiSelect::with_theme(&ColorfulTheme::default())
-            .with_prompt("Select skills to remove")
-            .items(&items)
-            .max_length(12)
-            .interact()
-            .map_err(|err| SkilError::Message(err.to_string()))?;
+        let choices = items
+            .iter()
+            .map(|name| PickerItem {
+                label: name.clone(),
+                ..PickerItem::default()
+            })
+            .collect();
+        let selection = Picker::new("Select skills to remove", choices).interact()?;
         if selection.is_empty() {
             return Err(SkilError::Message("No skills selected".to_string()));
         }
@@ -677,31 +2026,18 @@ pub fn run_remove(mut args: RemoveArgs) -> Result<()> {
     };
 
     let mut removed = 0usize;
-
-    for agent in &agents {
-        let base = agent_skills_base(agent, args.global)?;
-        if !base.exists() {
-            continue;
-        }
-
-        if skill_names.len() == 1 && skill_names[0] == "*" {
-            for entry in std::fs::read_dir(&base)? {
-                let entry = entry?;
-                if entry.path().is_dir() {
-                    std::fs::remove_dir_all(entry.path())?;
-                    removed += 1;
-                }
-            }
-            continue;
-        }
-
+    for &global in &scopes {
+        let _lock = lock_scope(global)?;
+        let mut names = Vec::new();
         for name in &skill_names {
-            let sanitized = sanitize_name(name);
-            let target = base.join(&sanitized);
-            if target.exists() {
-                std::fs::remove_dir_all(&target)?;
-                removed += 1;
+            let count = remove_from_agents(&agents, global, name)?;
+            if count > 0 {
+                names.push(name.clone());
             }
+            removed += count;
+        }
+        if !names.is_empty() {
+            record_history(HistoryEntry::new("remove", global)?.skills(names));
         }
     }
 
@@ -709,153 +2045,1552 @@ pub fn run_remove(mut args: RemoveArgs) -> Result<()> {
     Ok(())
 }
 
-/// Lists installed skills for agents or the canonical store.
-pub fn run_list(args: ListArgs) -> Result<()> {
-    if args.agent.is_empty() {
-        if !args.global {
-            let local_config = config_location(false)?;
-            if local_config.path.exists() {
-                let config = read_config(&local_config.path)?;
-                let mut names: Vec<String> = config
-                    .sources
-                    .values()
-                    .flat_map(|source| source.skills.iter().cloned())
-                    .collect::<std::collections::BTreeSet<_>>()
-                    .into_iter()
-                    .collect();
-                if !names.is_empty() {
-                    ui::heading("Skills");
-                    names.sort();
-                    for name in names {
-                        ui::list_item(&name);
-                    }
-                    return Ok(());
-                }
-            }
+/// Moves a skill's folders in the agents' skills directories to the trash.
+///
+/// Returns how many folders were removed.
+fn remove_from_agents(agents: &[AgentConfig], global: bool, name: &str) -> Result<usize> {
+    let mut paths = Vec::new();
+    for agent in agents {
+        let target = agent_skills_base(agent, global)?.join(sanitize_name(name));
+        if target.exists() && !paths.contains(&target) {
+            paths.push(target);
         }
+    }
+    trash_skill(name, &paths)?;
+    Ok(paths.len())
+}
 
-        let canonical = canonical_skills_dir(args.global)?;
-        if canonical.exists() {
-            let mut names = Vec::new();
-            for entry in std::fs::read_dir(&canonical)? {
-                let entry = entry?;
-                if entry.path().is_dir() {
-                    if let Some(skill) = parse_skill_md(&entry.path().join("SKILL.md"))? {
-                        names.push(skill.name);
-                    } else if let Some(name) = entry.file_name().to_str() {
-                        names.push(name.to_string());
+/// Removes every folder in the agents' skills directories after listing them,
+/// marking the ones no tracked source installed.
+fn remove_all(agents: &[AgentConfig], scopes: &[bool], yes: bool) -> Result<()> {
+    let mut targets: Vec<(bool, PathBuf, bool, String)> = Vec::new();
+    for &global in scopes {
+        let config = read_config(&config_location(global)?.path)?;
+        for agent in agents {
+            let base = agent_skills_base(agent, global)?;
+            if !base.exists() {
+                continue;
+            }
+            for (name, path) in installed_skill_dirs(&base)? {
+                let tracked = tracked_source(&config, &name).is_some();
+                targets.push((global, path, tracked, name));
+            }
+        }
+    }
+    // Agents sharing a skills directory list its folders once.
+    targets.sort();
+    targets.dedup();
+    if targets.is_empty() {
+        ui::info("No skills installed.");
+        return Ok(());
+    }
+
+    ui::heading(&format!("Folders to remove ({})", targets.len()));
+    for (_, path, tracked, _) in &targets {
+        let label = if *tracked {
+            console::style("tracked").dim()
+        } else {
+            console::style("untracked").yellow()
+        };
+        ui::list_item(&format!("{} {label}", path.display()));
+    }
+    let untracked = targets.iter().filter(|(_, _, tracked, _)| !tracked).count();
+    if untracked > 0 {
+        ui::warn(&format!(
+            "{untracked} folder(s) were not installed by skil and cannot be restored with `skil install`"
+        ));
+    }
+    if !yes {
+        if !console::Term::stdout().is_term() {
+            return Err(SkilError::Message(format!(
+                "Refusing to remove {} folder(s) without confirmation; pass --yes to remove them",
+                targets.len()
+            )));
+        }
+        let confirmed = dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Remove {} folder(s)?", targets.len()))
+            .default(false)
+            .interact()
+            .map_err(|err| SkilError::Message(err.to_string()))?;
+        if !confirmed {
+            ui::info("Nothing removed");
+            return Ok(());
+        }
+    }
+
+    let mut by_skill: BTreeMap<(bool, &str), Vec<PathBuf>> = BTreeMap::new();
+    for (global, path, _, name) in &targets {
+        by_skill
+            .entry((*global, name))
+            .or_default()
+            .push(path.clone());
+    }
+    for ((global, name), paths) in &by_skill {
+        let _lock = lock_scope(*global)?;
+        trash_skill(name, paths)?;
+    }
+    for &global in scopes {
+        let names: Vec<String> = by_skill
+            .keys()
+            .filter(|(scope, _)| *scope == global)
+            .map(|(_, name)| name.to_string())
+            .collect();
+        if !names.is_empty() {
+            record_history(HistoryEntry::new("remove", global)?.skills(names));
+        }
+    }
+    ui::success(&format!("Removed {} folder(s)", targets.len()));
+    Ok(())
+}
+
+/// Removes the skills of a source from each of `scopes` that tracks it.
+fn remove_source(source: &str, scopes: &[bool], yes: bool) -> Result<()> {
+    let mut found = false;
+    for &global in scopes {
+        let scope = if global { "global" } else { "project" };
+        let updater = Updater::new(global)?;
+        let config = updater.config()?;
+        let Some(key) = find_source(&config, source) else {
+            continue;
+        };
+        found = true;
+        let skills = &config.sources[key].skills;
+        if !yes && console::Term::stdout().is_term() {
+            ui::heading(&format!("{key} ({scope})"));
+            for skill in skills {
+                ui::list_item(skill);
+            }
+            let confirmed = dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Remove {} skill(s)?", skills.len()))
+                .default(false)
+                .interact()
+                .map_err(|err| SkilError::Message(err.to_string()))?;
+            if !confirmed {
+                ui::info(&format!("Kept {key} in the {scope} scope"));
+                continue;
+            }
+        }
+        let _lock = lock_scope(global)?;
+        let from = source_revision(&config.sources[key]);
+        let (key, removed) = updater.remove_source(key)?;
+        record_history(
+            HistoryEntry::new("remove", global)?
+                .source(key.clone())
+                .skills(removed.clone())
+                .revisions(from, None),
+        );
+        ui::success(&format!(
+            "Removed {} skill(s) of {key} from the {scope} scope",
+            removed.len()
+        ));
+    }
+    if !found {
+        return Err(SkilError::Message(format!("Source not tracked: {source}")));
+    }
+    Ok(())
+}
+
+/// Lists installed skills for agents or the canonical store.
+/// Returns sorted names of skills installed in a skills directory.
+///
+/// Folders without a valid SKILL.md are listed by folder name.
+pub(crate) fn installed_skill_names(dir: &Path) -> Result<Vec<String>> {
+    Ok(installed_skill_dirs(dir)?
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect())
+}
+
+/// Returns skill names with their directories in a skills directory, sorted by name.
+fn installed_skill_dirs(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let mut dirs = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.path().is_dir() {
+            let skill_md = entry.path().join("SKILL.md");
+            let skill = if skill_md.is_file() {
+                parse_skill_md(&skill_md)?
+            } else {
+                None
+            };
+            if let Some(skill) = skill {
+                dirs.push((skill.name, entry.path()));
+            } else if let Some(name) = entry.file_name().to_str() {
+                dirs.push((name.to_string(), entry.path()));
+            }
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Returns skills with a valid SKILL.md in a skills directory, sorted by name.
+pub(crate) fn installed_skills(dir: &Path) -> Result<Vec<Skill>> {
+    let mut skills = Vec::new();
+    if !dir.exists() {
+        return Ok(skills);
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if let Some(skill) = parse_skill_md(&path.join("SKILL.md"))? {
+            skills.push(skill);
+        }
+    }
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(skills)
+}
+
+pub fn run_list(args: ListArgs) -> Result<()> {
+    let agents = if args.agent.is_empty() {
+        agent_configs()
+    } else {
+        resolve_agents(&args.agent)
+    };
+    let scopes = selected_scopes(args.global, args.all_scopes);
+    if let Some(format) = &args.format {
+        ui::use_stderr_only();
+        list_formatted(&Template::parse(format, LIST_FIELDS)?, &scopes)?;
+    } else if args.size {
+        for &global in &scopes {
+            list_sizes(&agents, global, args.all_scopes)?;
+        }
+    } else {
+        list_skills(&args)?;
+    }
+    for global in scopes {
+        warn_budgets(&agents, global)?;
+        warn_deprecated(&Updater::new(global)?.deprecated()?);
+    }
+    Ok(())
+}
+
+/// Scopes picked by `--global`, `--project-only`, and `--both`, project first.
+fn selected_scopes(global: bool, both: bool) -> Vec<bool> {
+    if both {
+        vec![false, true]
+    } else {
+        vec![global]
+    }
+}
+
+/// Prints the disk space of each skill in a scope, counting the store folder
+/// and agent copies, then what each agent holds.
+fn list_sizes(agents: &[AgentConfig], global: bool, show_scope: bool) -> Result<()> {
+    let scope = if global { "Global" } else { "Project" };
+    let store = canonical_skills_dir(global)?;
+    let mut bases = Vec::new();
+    for agent in agents {
+        bases.push((agent.name.to_string(), agent_skills_base(agent, global)?));
+    }
+    let usage = disk_usage(&store, &bases)?;
+    if show_scope {
+        ui::heading(&format!("{scope} skills"));
+    }
+    if usage.is_empty() {
+        ui::info("No skills installed.");
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = usage
+        .iter()
+        .map(|skill| {
+            let copies = match skill.copies.len() {
+                0 => "-".to_string(),
+                count => format!(
+                    "{} ({count})",
+                    HumanBytes(skill.total() - skill.store_bytes)
+                ),
+            };
+            vec![
+                skill.name.clone(),
+                HumanBytes(skill.store_bytes).to_string(),
+                copies,
+                HumanBytes(skill.total()).to_string(),
+            ]
+        })
+        .collect();
+    ui::table(&["SKILL", "STORE", "COPIES", "TOTAL"], &rows);
+
+    let mut rows = Vec::new();
+    for (agent, (name, base)) in agents.iter().zip(&bases) {
+        let installed = skill_sizes(base)?;
+        if installed.is_empty() {
+            continue;
+        }
+        let copied: u64 = usage
+            .iter()
+            .flat_map(|skill| &skill.copies)
+            .filter(|(copy, _)| copy == name)
+            .map(|(_, bytes)| bytes)
+            .sum();
+        rows.push(vec![
+            agent.display_name.to_string(),
+            installed.len().to_string(),
+            HumanBytes(installed.iter().map(|size| size.bytes).sum()).to_string(),
+            HumanBytes(copied).to_string(),
+        ]);
+    }
+    if !rows.is_empty() {
+        ui::table(&["AGENT", "SKILLS", "SIZE", "COPIED"], &rows);
+    }
+    for skill in usage
+        .iter()
+        .filter(|skill| skill.total() > LARGE_SKILL_BYTES)
+    {
+        ui::warn(&format!(
+            "{} takes {} on disk; exclude bundled files it does not need with {}",
+            skill.name,
+            HumanBytes(skill.total()),
+            IGNORE_FILE
+        ));
+    }
+    Ok(())
+}
+
+/// Prints installed skills for `skills list`.
+fn list_skills(args: &ListArgs) -> Result<()> {
+    if args.agent.is_empty() && (args.all_scopes || args.verbose) {
+        return list_table(args);
+    }
+    if args.agent.is_empty() {
+        if !args.global {
+            let local_config = config_location(false)?;
+            if local_config.path.exists() {
+                let config = read_config(&local_config.path)?;
+                let mut names: Vec<String> = config
+                    .sources
+                    .values()
+                    .flat_map(|source| source.skills.iter().cloned())
+                    .collect::<std::collections::BTreeSet<_>>()
+                    .into_iter()
+                    .collect();
+                if !names.is_empty() {
+                    ui::heading("Skills");
+                    names.sort();
+                    for name in names {
+                        ui::list_item(&name);
                     }
+                    return Ok(());
                 }
             }
+        }
 
+        let canonical = canonical_skills_dir(args.global)?;
+        if canonical.exists() {
+            let names = installed_skill_names(&canonical)?;
             if !names.is_empty() {
                 ui::heading("Skills");
-                names.sort();
                 for name in names {
                     ui::list_item(&name);
                 }
-                return Ok(());
-            }
+                return Ok(());
+            }
+        }
+
+        if !args.global && !args.project_only {
+            let global_canonical = canonical_skills_dir(true)?;
+            if global_canonical.exists() {
+                let names = installed_skill_names(&global_canonical)?;
+                if !names.is_empty() {
+                    ui::heading("Global skills (use -g to list directly)");
+                    for name in names {
+                        ui::list_item(&name);
+                    }
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    let agents = require_agents(resolve_agents(&args.agent))?;
+
+    let scopes = selected_scopes(args.global, args.all_scopes);
+    for (agent, &global) in agents
+        .iter()
+        .flat_map(|a| scopes.iter().map(move |s| (a, s)))
+    {
+        let base = agent_skills_base(agent, global)?;
+        if args.all_scopes {
+            let scope = if global { "global" } else { "project" };
+            ui::heading(&format!("{} ({scope}):", agent.display_name));
+        } else {
+            ui::heading(&format!("{}:", agent.display_name));
+        }
+        if !base.exists() {
+            ui::info("  (no skills installed)");
+            continue;
+        }
+
+        let dirs = installed_skill_dirs(&base)?;
+        if dirs.is_empty() {
+            ui::info("  (no skills installed)");
+            continue;
+        }
+        let canonical = canonical_skills_dir(global)?;
+        for (name, path) in dirs {
+            let Some(file_name) = path.file_name() else {
+                continue;
+            };
+            let state = install_state(&path, &canonical.join(file_name))?
+                .unwrap_or(InstallState::Unmanaged);
+            let label = match state {
+                InstallState::Drifted => console::style(state.as_str()).yellow(),
+                _ => console::style(state.as_str()).dim(),
+            };
+            ui::list_item(&format!("{name} {label}"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints installed skills as a table, with source and update details in verbose mode.
+fn list_table(args: &ListArgs) -> Result<()> {
+    let scopes = selected_scopes(args.global, args.all_scopes);
+
+    let mut rows = Vec::new();
+    for global in scopes {
+        let scope = if global { "global" } else { "project" };
+        let location = config_location(global)?;
+        let config = read_config(&location.path)?;
+        let updates: HashSet<String> = if args.verbose && !config.sources.is_empty() {
+            match Updater::from_location(location).check() {
+                Ok(updates) => updates.into_iter().map(|u| u.source_key).collect(),
+                Err(err) => {
+                    ui::warn(&format!("Could not check {scope} updates: {err}"));
+                    HashSet::new()
+                }
+            }
+        } else {
+            HashSet::new()
+        };
+
+        for skill in installed_skills(&canonical_skills_dir(global)?)? {
+            let mut row = vec![skill.name.clone(), scope.to_string()];
+            if args.verbose {
+                let (source, revision, updated, update) = match tracked_source(&config, &skill.name)
+                {
+                    Some((key, source)) => (
+                        key.clone(),
+                        source_revision(source).unwrap_or_else(|| "-".to_string()),
+                        source
+                            .updated_at
+                            .as_deref()
+                            .and_then(|at| at.parse::<jiff::Timestamp>().ok())
+                            .map(|at| at.strftime("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_else(|| "-".to_string()),
+                        if updates.contains(key) {
+                            console::style("available").yellow().to_string()
+                        } else {
+                            "-".to_string()
+                        },
+                    ),
+                    None => (
+                        "(untracked)".to_string(),
+                        "-".into(),
+                        "-".into(),
+                        "-".into(),
+                    ),
+                };
+                row.extend([
+                    source,
+                    revision,
+                    install_modes(&skill.name, global)?,
+                    updated,
+                    update,
+                ]);
+            }
+            rows.push(row);
+        }
+    }
+
+    if rows.is_empty() {
+        ui::info("No skills installed.");
+        return Ok(());
+    }
+    if args.verbose {
+        ui::table(
+            &[
+                "SKILL", "SCOPE", "SOURCE", "REVISION", "MODE", "UPDATED", "UPDATE",
+            ],
+            &rows,
+        );
+    } else {
+        ui::table(&["SKILL", "SCOPE"], &rows);
+    }
+    Ok(())
+}
+
+/// Fields of `skil list --format`.
+const LIST_FIELDS: &[&str] = &[
+    "name",
+    "description",
+    "scope",
+    "source",
+    "version",
+    "checksum",
+    "revision",
+    "mode",
+    "updated",
+    "path",
+];
+
+/// Prints a line per skill installed in `scopes` from `template`.
+fn list_formatted(template: &Template, scopes: &[bool]) -> Result<()> {
+    for &global in scopes {
+        let config = read_config(&config_location(global)?.path)?;
+        for skill in installed_skills(&canonical_skills_dir(global)?)? {
+            let source = tracked_source(&config, &skill.name);
+            let field = |value: Option<&String>| value.cloned().unwrap_or_default();
+            println!(
+                "{}",
+                template.render(&[
+                    ("name", skill.name.clone()),
+                    ("description", skill.description.clone()),
+                    (
+                        "scope",
+                        if global { "global" } else { "project" }.to_string()
+                    ),
+                    ("source", field(source.map(|(key, _)| key))),
+                    (
+                        "version",
+                        field(source.and_then(|(_, source)| source.version.as_ref()))
+                    ),
+                    (
+                        "checksum",
+                        field(source.and_then(|(_, source)| source.checksum.as_ref()))
+                    ),
+                    (
+                        "revision",
+                        source
+                            .and_then(|(_, source)| source_revision(source))
+                            .unwrap_or_default()
+                    ),
+                    ("mode", install_modes(&skill.name, global)?),
+                    (
+                        "updated",
+                        field(source.and_then(|(_, source)| source.updated_at.as_ref()))
+                    ),
+                    ("path", skill.path.display().to_string()),
+                ])
+            );
+        }
+    }
+    Ok(())
+}
+
+/// The config source that tracks a skill, with its key.
+fn tracked_source<'a>(config: &'a SkilConfig, skill: &str) -> Option<(&'a String, &'a SkilSource)> {
+    config.sources.iter().find(|(_, source)| {
+        source
+            .skills
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(skill))
+    })
+}
+
+/// Installed tag of a source, or its short commit checksum.
+fn source_revision(source: &SkilSource) -> Option<String> {
+    source
+        .version
+        .clone()
+        .or_else(|| source.checksum.as_deref().map(short_revision))
+}
+
+/// Warns about agents whose installed skills exceed their configured budget.
+fn warn_budgets(agents: &[AgentConfig], global: bool) -> Result<()> {
+    for overrun in check_budgets(agents, global)? {
+        let mut limits = Vec::new();
+        if let Some(max) = overrun.budget.max_bytes {
+            limits.push(format!("{}", HumanBytes(max)));
+        }
+        if let Some(max) = overrun.budget.max_tokens {
+            limits.push(format!("{max} tokens"));
+        }
+        ui::warn(&format!(
+            "{} skills use {} (~{} tokens), over the budget of {}",
+            overrun.agent.display_name,
+            HumanBytes(overrun.bytes),
+            overrun.tokens,
+            limits.join(" / ")
+        ));
+        let largest: Vec<String> = overrun
+            .skills
+            .iter()
+            .take(3)
+            .map(|skill| format!("{} ({})", skill.name, HumanBytes(skill.bytes)))
+            .collect();
+        ui::warn(&format!("  Largest: {}", largest.join(", ")));
+    }
+    Ok(())
+}
+
+/// First 12 characters of a commit checksum.
+/// Warns about skill files installed as Git LFS pointers instead of content.
+fn warn_lfs_pointers(files: &[PathBuf]) {
+    if files.is_empty() {
+        return;
+    }
+    ui::warn(&format!(
+        "{} file(s) are Git LFS pointers, not their content:",
+        files.len()
+    ));
+    for file in files {
+        ui::warn(&format!("  {}", file.display()));
+    }
+    ui::warn(
+        "Install git-lfs (https://git-lfs.com), then run `skil update` or add the source again",
+    );
+}
+
+fn short_revision(checksum: &str) -> String {
+    checksum.chars().take(12).collect()
+}
+
+/// How a skill is placed into agent directories: `symlink`, `copy`, or both.
+fn install_modes(name: &str, global: bool) -> Result<String> {
+    let dir_name = sanitize_name(name);
+    let mut modes = std::collections::BTreeSet::new();
+    for agent in agent_configs() {
+        let path = agent_skills_base(&agent, global)?.join(&dir_name);
+        match std::fs::symlink_metadata(&path) {
+            Ok(meta) if meta.file_type().is_symlink() => modes.insert("symlink"),
+            Ok(meta) if meta.is_dir() => modes.insert("copy"),
+            _ => false,
+        };
+    }
+    if modes.is_empty() {
+        return Ok("-".to_string());
+    }
+    Ok(modes.into_iter().collect::<Vec<_>>().join(", "))
+}
+
+/// Prints config sources as a tree of skills and the agents they are installed into.
+pub fn run_tree() -> Result<()> {
+    let agents = agent_configs();
+    let mut printed = false;
+    for global in [false, true] {
+        let scope = if global { "global" } else { "project" };
+        let config = read_config(&config_location(global)?.path)?;
+        for (key, source) in &config.sources {
+            ui::info(&format!(
+                "{key} {}",
+                console::style(format!("({scope})")).dim()
+            ));
+            for (i, skill) in source.skills.iter().enumerate() {
+                let last_skill = i + 1 == source.skills.len();
+                ui::info(&format!("{}{skill}", tree_branch(last_skill)));
+                let installs = skill_installs(skill, &agents)?;
+                let indent = if last_skill { "    " } else { "│   " };
+                if installs.is_empty() {
+                    ui::info(&format!(
+                        "{indent}{}{}",
+                        tree_branch(true),
+                        console::style("(not installed)").dim()
+                    ));
+                }
+                for (j, install) in installs.iter().enumerate() {
+                    let branch = tree_branch(j + 1 == installs.len());
+                    ui::info(&format!("{indent}{branch}{install}"));
+                }
+            }
+            printed = true;
+        }
+    }
+    if !printed {
+        ui::info("No sources tracked.");
+    }
+    Ok(())
+}
+
+/// Converts agent-specific configuration into skills in the canonical store.
+pub fn run_import(args: ImportArgs) -> Result<()> {
+    let agents = if args.agent.is_empty() {
+        Vec::new()
+    } else {
+        resolve_install_agents(&args.agent, true)?
+    };
+    let _lock = lock_scope(args.global)?;
+    let store = canonical_skills_dir(args.global)?;
+    let report = import_skills(args.from.into(), &args.path, &store, args.force)?;
+
+    let mode = if args.copy {
+        InstallMode::Copy
+    } else {
+        InstallMode::Symlink
+    };
+    let rules = agent_rules(args.global)?;
+    for imported in &report.imported {
+        let Some(skill) = parse_skill_md(&store.join(&imported.dir).join("SKILL.md"))? else {
+            continue;
+        };
+        for agent in &agents {
+            if rules_allow(&rules, &skill, agent.name) {
+                link_skill(&imported.dir, agent, args.global, mode)?;
+            } else {
+                ui::info(&format!(
+                    "Skipped {} for {}: excluded by config rules",
+                    skill.name, agent.display_name
+                ));
+            }
+        }
+    }
+
+    for skill in &report.skipped {
+        ui::warn(&format!(
+            "Skipped {} from {}: already in {} (use --force to replace)",
+            skill.name,
+            display_path(&skill.origin),
+            display_path(&store)
+        ));
+    }
+    if report.imported.is_empty() {
+        ui::info("No skills imported.");
+        return Ok(());
+    }
+    ui::success(&format!(
+        "Imported {} skill(s) into {}",
+        report.imported.len(),
+        display_path(&store)
+    ));
+    for skill in &report.imported {
+        ui::list_item(&format!(
+            "{} {}",
+            skill.name,
+            console::style(format!("({})", display_path(&skill.origin))).dim()
+        ));
+    }
+    if agents.is_empty() {
+        ui::info("Use --agent to also install them into agent directories.");
+    }
+    Ok(())
+}
+
+/// Concatenates installed skills into the skil section of AGENTS.md or CLAUDE.md.
+pub fn run_render(args: RenderArgs) -> Result<()> {
+    let installed = installed_skills(&canonical_skills_dir(args.global)?)?;
+    let skills = if args.skills.is_empty() {
+        installed
+    } else {
+        let missing = missing_skills(&installed, &args.skills);
+        if !missing.is_empty() {
+            return Err(SkilError::new(
+                ErrorCode::NoMatchingSkills,
+                format!("Skills not installed: {}", missing.join(", ")),
+            ));
+        }
+        select_skills(&installed, &args.skills)
+    };
+    if skills.is_empty() {
+        return Err(SkilError::new(
+            ErrorCode::NoSkills,
+            "No skills installed to render",
+        ));
+    }
+
+    let config = read_config(&config_location(args.global)?.path)?;
+    let rendered: Vec<(Skill, Provenance)> = skills
+        .into_iter()
+        .map(|skill| {
+            let provenance = tracked_source(&config, &skill.name)
+                .map(|(key, source)| Provenance {
+                    source: Some(key.clone()),
+                    revision: source_revision(source),
+                })
+                .unwrap_or_default();
+            (skill, provenance)
+        })
+        .collect();
+    let section = render_section(&rendered);
+
+    let output = match args.output {
+        Some(path) => path,
+        None if args.write => std::env::current_dir()?.join(args.format.file_name()),
+        None => {
+            print!("{section}");
+            return Ok(());
+        }
+    };
+    let existing = match std::fs::read_to_string(&output) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+    std::fs::write(&output, replace_section(&existing, &section))?;
+    ui::success(&format!(
+        "Rendered {} skill(s) into {}",
+        rendered.len(),
+        display_path(&output)
+    ));
+    Ok(())
+}
+
+/// Removes scratch clones left by interrupted commands and canonical skill
+/// folders that no source records and no agent uses.
+pub fn run_clean(args: CleanArgs) -> Result<()> {
+    let leftovers = find_leftovers(&agent_configs(), args.global)?;
+    if leftovers.is_empty() {
+        ui::info("Nothing to clean.");
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = leftovers
+        .iter()
+        .map(|leftover| {
+            let kind = match leftover.kind {
+                LeftoverKind::Scratch => "temporary",
+                LeftoverKind::OrphanedSkill => "orphaned skill",
+            };
+            vec![
+                kind.to_string(),
+                display_path(&leftover.path),
+                HumanBytes(leftover.bytes).to_string(),
+            ]
+        })
+        .collect();
+    ui::table(&["KIND", "PATH", "BYTES"], &rows);
+    if args.dry_run {
+        return Ok(());
+    }
+    let confirmed = args.yes
+        || (console::Term::stdout().is_term()
+            && dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Remove {} item(s)?", leftovers.len()))
+                .default(false)
+                .interact()
+                .map_err(|err| SkilError::Message(err.to_string()))?);
+    if !confirmed {
+        ui::info("Run `skil clean --yes` to remove them");
+        return Ok(());
+    }
+    let _lock = lock_scope(args.global)?;
+    let current = find_leftovers(&agent_configs(), args.global)?;
+    let leftovers: Vec<Leftover> = leftovers
+        .into_iter()
+        .filter(|leftover| current.iter().any(|now| now.path == leftover.path))
+        .collect();
+    let freed = remove_leftovers(&leftovers)?;
+    ui::success(&format!(
+        "Removed {} item(s), freeing {}",
+        leftovers.len(),
+        HumanBytes(freed)
+    ));
+    Ok(())
+}
+
+/// Prints an installed skill's description, source, and resource files,
+/// looking in the project store before the global one.
+pub fn run_info(args: InfoArgs) -> Result<()> {
+    let template = args
+        .format
+        .as_deref()
+        .map(|format| Template::parse(format, INFO_FIELDS))
+        .transpose()?;
+    let scopes = if args.global {
+        vec![true]
+    } else {
+        vec![false, true]
+    };
+    for global in scopes {
+        let file = canonical_skills_dir(global)?
+            .join(sanitize_name(&args.skill))
+            .join("SKILL.md");
+        if !file.is_file() {
+            continue;
+        }
+        let Some(skill) = parse_skill_md(&file)? else {
+            continue;
+        };
+        let config = read_config(&config_location(global)?.path)?;
+        let source = tracking_source(&config, &skill.name, "");
+        if let Some(template) = &template {
+            let tracked = source.map(|key| &config.sources[key]);
+            println!(
+                "{}",
+                template.render(&[
+                    ("name", skill.name.clone()),
+                    ("description", skill.description.clone()),
+                    (
+                        "scope",
+                        if global { "global" } else { "project" }.to_string()
+                    ),
+                    ("source", source.unwrap_or_default().to_string()),
+                    (
+                        "version",
+                        tracked
+                            .and_then(|source| source.version.clone())
+                            .unwrap_or_default()
+                    ),
+                    (
+                        "checksum",
+                        tracked
+                            .and_then(|source| source.checksum.clone())
+                            .unwrap_or_default()
+                    ),
+                    (
+                        "modified",
+                        tracked
+                            .is_some_and(|source| source.is_modified(&skill.name))
+                            .to_string()
+                    ),
+                    ("path", skill.path.display().to_string()),
+                    ("files", skill.resources.len().to_string()),
+                ])
+            );
+            return Ok(());
+        }
+        ui::heading(&skill.name);
+        ui::info(&skill.description);
+        ui::info(&format!(
+            "Scope: {}",
+            if global { "global" } else { "project" }
+        ));
+        let modified = source.is_some_and(|key| config.sources[key].is_modified(&skill.name));
+        ui::info(&format!(
+            "Source: {}{}",
+            source.unwrap_or("untracked"),
+            if modified { " (locally modified)" } else { "" }
+        ));
+        ui::info(&format!("Path: {}", display_path(&skill.path)));
+        let overrides = overrides_dir(global)?;
+        let applied = skill_overrides(&overrides, &sanitize_name(&skill.name))?;
+        if !applied.is_empty() {
+            let files: Vec<String> = applied.iter().map(|file| display_path(file)).collect();
+            ui::info(&format!(
+                "Overrides: {} (from {})",
+                files.join(", "),
+                display_path(&overrides.join(sanitize_name(&skill.name)))
+            ));
+        }
+        if let Some(deprecation) =
+            parse_frontmatter(&skill.raw_content)?.and_then(|frontmatter| frontmatter.deprecation())
+        {
+            warn_deprecated(&[DeprecatedSkill {
+                source_key: source.unwrap_or_default().to_string(),
+                skill: skill.name.clone(),
+                deprecation,
+            }]);
+        }
+        for dir in RESOURCE_DIRS {
+            let files: Vec<&PathBuf> = skill
+                .resources
+                .iter()
+                .filter(|file| file.starts_with(dir))
+                .collect();
+            if files.is_empty() {
+                continue;
+            }
+            ui::heading(&format!("{dir}/"));
+            for file in files {
+                ui::list_item(&display_path(file));
+            }
+        }
+        if args.content {
+            let (_, body) = split_frontmatter(&skill.raw_content);
+            println!();
+            println!("{}", markdown::render(body));
+        }
+        return Ok(());
+    }
+    Err(SkilError::new(
+        ErrorCode::NoMatchingSkills,
+        format!("Skill not installed: {}", args.skill),
+    ))
+}
+
+/// Fields of `skil info --format`.
+const INFO_FIELDS: &[&str] = &[
+    "name",
+    "description",
+    "scope",
+    "source",
+    "version",
+    "checksum",
+    "modified",
+    "path",
+    "files",
+];
+
+/// Prints a skill's details and SKILL.md body from a source, without
+/// installing anything.
+pub fn run_show(args: ShowArgs) -> Result<()> {
+    let observer = CliObserver::new(false);
+    let discovered = Discovery::new(args.source.clone())
+        .subpaths(args.paths.clone())
+        .branch(args.branch.clone())
+        .options(DiscoverOptions::default().with_config(false)?)
+        .observer(&observer)
+        .run()?;
+    if args.paths.is_empty() {
+        cache_source_skills(&discovered);
+    }
+    let skill = match (&args.skill, discovered.skills.as_slice()) {
+        (_, []) => {
+            return Err(SkilError::new(
+                ErrorCode::NoSkills,
+                "No skills found in source",
+            ));
+        }
+        (Some(name), skills) => skills
+            .iter()
+            .find(|skill| skill.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                SkilError::new(
+                    ErrorCode::NoMatchingSkills,
+                    format!("No skill named {name} in {}", args.source),
+                )
+            })?,
+        (None, [skill]) => skill,
+        (None, skills) if console::Term::stdout().is_term() => {
+            let names: Vec<&str> = skills.iter().map(|skill| skill.name.as_str()).collect();
+            let choice = dialoguer::Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select a skill to show")
+                .items(&names)
+                .max_length(12)
+                .interact()
+                .map_err(|err| SkilError::Message(err.to_string()))?;
+            &skills[choice]
+        }
+        (None, skills) => {
+            let names: Vec<&str> = skills.iter().map(|skill| skill.name.as_str()).collect();
+            return Err(SkilError::new(
+                ErrorCode::NoMatchingSkills,
+                format!(
+                    "{} has several skills ({}); pick one with --skill",
+                    args.source,
+                    names.join(", ")
+                ),
+            ));
+        }
+    };
+
+    ui::heading(&skill.name);
+    ui::info(&skill.description);
+    ui::info(&format!("Source: {}", discovered.key()));
+    if !skill.resources.is_empty() {
+        let files: Vec<String> = skill
+            .resources
+            .iter()
+            .map(|file| display_path(file))
+            .collect();
+        ui::info(&format!("Files: {}", files.join(", ")));
+    }
+    let frontmatter = parse_frontmatter(&skill.raw_content)?;
+    if let Some(deprecation) = frontmatter.as_ref().and_then(Frontmatter::deprecation) {
+        warn_deprecated(&[DeprecatedSkill {
+            source_key: discovered.key(),
+            skill: skill.name.clone(),
+            deprecation,
+        }]);
+    }
+    if let Some(required) = frontmatter
+        .as_ref()
+        .and_then(Frontmatter::unsupported_version)
+    {
+        ui::warn(&format!(
+            "Requires skil {required}; this is skil {}",
+            env!("CARGO_PKG_VERSION")
+        ));
+    }
+    let (_, body) = split_frontmatter(&skill.raw_content);
+    println!();
+    println!("{}", markdown::render(body));
+    println!();
+    ui::info(&format!(
+        "Install with: skil add {} --skill {}",
+        args.source, skill.name
+    ));
+    Ok(())
+}
+
+/// Opens an installed skill's SKILL.md in `$VISUAL` or `$EDITOR`.
+///
+/// A skill tracked from a source is first marked as locally modified, or
+/// detached from the source, so the next update does not overwrite the edits.
+pub fn run_edit(args: EditArgs) -> Result<()> {
+    let scopes = if args.global {
+        vec![true]
+    } else {
+        vec![false, true]
+    };
+    let Some((global, dir)) = scopes.into_iter().find_map(|global| {
+        let dir = canonical_skills_dir(global)
+            .ok()?
+            .join(sanitize_name(&args.skill));
+        dir.join("SKILL.md").is_file().then_some((global, dir))
+    }) else {
+        return Err(SkilError::new(
+            ErrorCode::NoMatchingSkills,
+            format!("Skill not installed: {}", args.skill),
+        ));
+    };
+
+    let location = config_location(global)?;
+    let config = read_config(&location.path)?;
+    if let Some(source_key) = tracking_source(&config, &args.skill, "") {
+        let modified = config.sources[source_key].is_modified(&args.skill);
+        if args.detach {
+            untrack_skills(
+                &location.path,
+                source_key,
+                std::slice::from_ref(&args.skill),
+            )?;
+            ui::info(&format!(
+                "Detached {} from {source_key}; update no longer changes it",
+                args.skill
+            ));
+        } else if !modified && confirm_local_override(&args.skill, source_key)? {
+            mark_modified(&location.path, source_key, &args.skill)?;
+            ui::info(&format!(
+                "Marked {} as locally modified; update keeps your edits",
+                args.skill
+            ));
+        }
+    }
+
+    // Agents holding a copy get the edited files once the editor exits.
+    let mut copies = Vec::new();
+    for agent in agent_configs() {
+        let target = agent_skills_base(&agent, global)?.join(sanitize_name(&args.skill));
+        if install_state(&target, &dir)? == Some(InstallState::Copy) {
+            copies.push(agent);
+        }
+    }
+    open_in_editor(&dir.join("SKILL.md"))?;
+    for agent in &copies {
+        link_skill(
+            &sanitize_name(&args.skill),
+            agent,
+            global,
+            InstallMode::Copy,
+        )?;
+    }
+    Ok(())
+}
+
+/// Asks whether update should keep local edits to `skill`; without a
+/// terminal, it does.
+fn confirm_local_override(skill: &str, source_key: &str) -> Result<bool> {
+    if !console::Term::stdout().is_term() {
+        return Ok(true);
+    }
+    ui::info(&format!("{skill} is installed from {source_key}"));
+    dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Keep your edits when updating? (otherwise the next update overwrites them)")
+        .default(true)
+        .interact()
+        .map_err(|err| SkilError::Message(err.to_string()))
+}
+
+/// Runs `$VISUAL` or `$EDITOR`, falling back to `vi`, on `path` and waits for it.
+fn open_in_editor(path: &Path) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let status = std::process::Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .map_err(|err| SkilError::Message(format!("Failed to start {program}: {err}")))?;
+    if !status.success() {
+        return Err(SkilError::Message(format!(
+            "{program} exited with {status}"
+        )));
+    }
+    Ok(())
+}
+
+/// Checks project and global sources against the advisory database.
+pub fn run_audit(args: AuditArgs) -> Result<()> {
+    let location = args
+        .db
+        .unwrap_or_else(|| format!("{SEARCH_API_BASE}/api/advisories"));
+    let db = load_advisories(&location)?;
+    let mut findings: Vec<(&str, Finding)> = Vec::new();
+    for (scope, global) in [("project", false), ("global", true)] {
+        let config = read_config(&config_location(global)?.path)?;
+        findings.extend(
+            audit(&config, &db)
+                .into_iter()
+                .map(|finding| (scope, finding)),
+        );
+    }
+    if findings.is_empty() {
+        ui::success(&format!(
+            "No advisories match installed skills ({} checked)",
+            db.advisories.len()
+        ));
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = findings
+        .iter()
+        .map(|(scope, finding)| {
+            vec![
+                finding.advisory.id.clone(),
+                finding.advisory.kind.as_str().to_string(),
+                scope.to_string(),
+                finding.source_key.clone(),
+                finding.skills.join(", "),
+                finding.advisory.title.clone(),
+            ]
+        })
+        .collect();
+    ui::table(
+        &["ID", "KIND", "SCOPE", "SOURCE", "SKILLS", "ADVISORY"],
+        &rows,
+    );
+    for (_, finding) in &findings {
+        if let Some(url) = &finding.advisory.url {
+            ui::list_item(&format!("{}: {url}", finding.advisory.id));
+        }
+    }
+    let malicious = findings
+        .iter()
+        .filter(|(_, finding)| finding.advisory.kind == AdvisoryKind::Malicious)
+        .count();
+    if malicious > 0 {
+        return Err(SkilError::Message(format!(
+            "{malicious} advisory(ies) flag installed skills as malicious; remove them with `skil remove`"
+        )));
+    }
+    ui::warn(&format!(
+        "{} advisory(ies) flag installed skills as deprecated",
+        findings.len()
+    ));
+    Ok(())
+}
+
+/// Moves the legacy global store and project config into the current layout.
+pub fn run_migrate(args: MigrateArgs) -> Result<()> {
+    let report = migrate_legacy(&agent_configs(), args.dry_run)?;
+    if report.is_empty() {
+        ui::info("Nothing to migrate.");
+        return Ok(());
+    }
+    let verb = if args.dry_run { "Would move" } else { "Moved" };
+    if let Some(legacy) = &report.legacy_store {
+        ui::info(&format!(
+            "{verb} {} skill(s) from {} to {}",
+            report.moved.len(),
+            display_path(legacy),
+            display_path(&configured_store_dir()?)
+        ));
+        for name in &report.moved {
+            ui::list_item(name);
+        }
+        if !report.kept.is_empty() {
+            ui::warn(&format!(
+                "Left in {}, already in the store: {}",
+                display_path(legacy),
+                report.kept.join(", ")
+            ));
+        }
+    }
+    if let Some(legacy) = &report.legacy_config {
+        ui::info(&format!(
+            "{verb} {} source(s) from {} to .skil.toml",
+            report.sources.len(),
+            display_path(legacy)
+        ));
+        for key in &report.sources {
+            ui::list_item(key);
+        }
+    }
+    Ok(())
+}
+
+/// Prints estimated tokens per installed skill and totals per agent.
+pub fn run_stats(args: StatsArgs) -> Result<()> {
+    let mut skills: Vec<SkillSize> = installed_skills(&canonical_skills_dir(args.global)?)?
+        .into_iter()
+        .map(|skill| measure_dir(skill.name, &skill.path))
+        .collect();
+    if skills.is_empty() {
+        ui::info("No skills installed.");
+        return Ok(());
+    }
+    args.sort.sort(&mut skills, |size| size);
+    let rows: Vec<Vec<String>> = skills
+        .iter()
+        .map(|size| {
+            vec![
+                size.name.clone(),
+                HumanBytes(size.bytes).to_string(),
+                size.tokens.to_string(),
+            ]
+        })
+        .collect();
+    ui::heading("Skills");
+    ui::table(&["SKILL", "BYTES", "TOKENS"], &rows);
+
+    let agents = if args.agent.is_empty() {
+        agent_configs()
+    } else {
+        resolve_agents(&args.agent)
+    };
+    let mut totals = Vec::new();
+    for agent in agents {
+        let installed = skill_sizes(&agent_skills_base(&agent, args.global)?)?;
+        if installed.is_empty() {
+            continue;
+        }
+        totals.push((
+            installed.len(),
+            SkillSize {
+                name: agent.display_name.to_string(),
+                bytes: installed.iter().map(|size| size.bytes).sum(),
+                tokens: installed.iter().map(|size| size.tokens).sum(),
+            },
+        ));
+    }
+    if totals.is_empty() {
+        return Ok(());
+    }
+    args.sort.sort(&mut totals, |(_, size)| size);
+    let rows: Vec<Vec<String>> = totals
+        .iter()
+        .map(|(count, size)| {
+            vec![
+                size.name.clone(),
+                count.to_string(),
+                HumanBytes(size.bytes).to_string(),
+                size.tokens.to_string(),
+            ]
+        })
+        .collect();
+    ui::heading("Agents");
+    ui::table(&["AGENT", "SKILLS", "BYTES", "TOKENS"], &rows);
+    Ok(())
+}
+
+/// Tree connector for an entry, depending on whether it is the last sibling.
+fn tree_branch(last: bool) -> &'static str {
+    if last { "└── " } else { "├── " }
+}
+
+/// Agents and scopes a skill is installed into, as `Agent (scope)` labels.
+fn skill_installs(skill: &str, agents: &[AgentConfig]) -> Result<Vec<String>> {
+    let dir_name = sanitize_name(skill);
+    let mut installs = Vec::new();
+    for global in [false, true] {
+        let scope = if global { "global" } else { "project" };
+        for agent in agents {
+            let path = agent_skills_base(agent, global)?.join(&dir_name);
+            if std::fs::symlink_metadata(&path).is_ok() {
+                installs.push(format!("{} ({scope})", agent.display_name));
+            }
+        }
+    }
+    Ok(installs)
+}
+
+/// Reads or writes global settings.
+pub fn run_config(args: ConfigArgs) -> Result<()> {
+    match args.command {
+        ConfigCommand::Set { key, value } => {
+            let location = config_location(true)?;
+            let _lock = lock_config(&location.path)?;
+            let mut config = read_config(&location.path)?;
+            let shown = match key.as_str() {
+                "telemetry" => {
+                    let enabled = parse_toggle(&value).ok_or_else(|| {
+                        SkilError::Message(format!(
+                            "Invalid value for telemetry: {value} (use on/off)"
+                        ))
+                    })?;
+                    config.telemetry = Some(enabled);
+                    if enabled { "on" } else { "off" }.to_string()
+                }
+                "store-dir" => {
+                    let dir = std::path::absolute(&value)?;
+                    config.store_dir = Some(dir.clone());
+                    display_path(&dir)
+                }
+                "max-skill-bytes" => {
+                    let bytes: u64 = value.trim().parse().map_err(|_| {
+                        SkilError::Message(format!(
+                            "Invalid value for max-skill-bytes: {value} (use a number of bytes, 0 for no limit)"
+                        ))
+                    })?;
+                    config.max_skill_bytes = Some(bytes);
+                    bytes.to_string()
+                }
+                _ => return Err(unknown_setting(&key)),
+            };
+            write_config(&location.path, &config)?;
+            ui::success(&format!(
+                "Set {key} = {shown} in {}",
+                display_path(&location.path)
+            ));
         }
+        ConfigCommand::Get { key } => match key.as_str() {
+            "telemetry" => ui::info(if telemetry_enabled()? { "on" } else { "off" }),
+            "store-dir" => ui::info(&display_path(&global_store_dir()?)),
+            "max-skill-bytes" => ui::info(&max_skill_bytes(false)?.to_string()),
+            _ => return Err(unknown_setting(&key)),
+        },
+        ConfigCommand::Validate { global, output } => return validate_configs(global, output),
+    }
+    Ok(())
+}
 
-        if !args.global {
-            let global_canonical = canonical_skills_dir(true)?;
-            if global_canonical.exists() {
-                let mut names = Vec::new();
-                for entry in std::fs::read_dir(&global_canonical)? {
-                    let entry = entry?;
-                    if entry.path().is_dir() {
-                        if let Some(skill) = parse_skill_md(&entry.path().join("SKILL.md"))? {
-                            names.push(skill.name);
-                        } else if let Some(name) = entry.file_name().to_str() {
-                            names.push(name.to_string());
-                        }
-                    }
+/// Adds skil checks to, or removes them from, a hook of the current repository.
+pub fn run_hook(args: HookArgs) -> Result<()> {
+    let hooks = git::hooks_dir(&std::env::current_dir()?)?;
+    match args.command {
+        HookCommand::Install { hook } => {
+            let path = hook_path(&hooks, &hook);
+            match install_hook(&path)? {
+                HookOutcome::Created => {
+                    ui::success(&format!("Created {} hook", display_path(&path)));
                 }
-
-                if !names.is_empty() {
-                    ui::heading("Global skills (use -g to list directly)");
-                    names.sort();
-                    for name in names {
-                        ui::list_item(&name);
-                    }
-                    return Ok(());
+                HookOutcome::Appended => {
+                    ui::success(&format!("Added skil checks to {}", display_path(&path)));
+                }
+                HookOutcome::AlreadyInstalled => {
+                    ui::info(&format!("{} already runs skil checks", display_path(&path)));
                 }
             }
         }
+        HookCommand::Uninstall { hook } => {
+            let path = hook_path(&hooks, &hook);
+            if uninstall_hook(&path)? {
+                ui::success(&format!("Removed skil checks from {}", display_path(&path)));
+            } else {
+                ui::info(&format!("{} does not run skil checks", display_path(&path)));
+            }
+        }
     }
+    Ok(())
+}
 
-    let agents = resolve_agents(&args.agent);
-    if agents.is_empty() {
-        return Err(SkilError::Message("No agents selected".to_string()));
+/// Reports every problem in the project and global config files.
+fn validate_configs(global_only: bool, output: OutputFormat) -> Result<()> {
+    if output == OutputFormat::Sarif {
+        ui::use_stderr_only();
     }
-
-    for agent in agents {
-        let base = agent_skills_base(&agent, args.global)?;
-        ui::heading(&format!("{}:", agent.display_name));
-        if !base.exists() {
-            ui::info("  (no skills installed)");
+    let mut paths = vec![config_location(true)?.path];
+    if !global_only {
+        paths.insert(0, local_overrides_path()?);
+        paths.insert(0, config_location(false)?.path);
+    }
+    let mut checked = 0usize;
+    let mut problems = 0usize;
+    let mut annotations = Vec::new();
+    for path in paths {
+        if !path.exists() {
             continue;
         }
-
-        let mut names = Vec::new();
-        for entry in std::fs::read_dir(base)? {
-            let entry = entry?;
-            if entry.path().is_dir() {
-                if let Some(skill) = parse_skill_md(&entry.path().join("SKILL.md"))? {
-                    names.push(skill.name);
-                } else if let Some(name) = entry.file_name().to_str() {
-                    names.push(name.to_string());
-                }
-            }
+        checked += 1;
+        let issues = validate_config(&std::fs::read_to_string(&path)?);
+        if issues.is_empty() {
+            ui::success(&format!("{} is valid", display_path(&path)));
         }
-
-        if names.is_empty() {
-            ui::info("  (no skills installed)");
-        } else {
-            names.sort();
-            for name in names {
-                ui::list_item(&name);
+        for issue in &issues {
+            if output == OutputFormat::Text {
+                ui::error(&format!("{}:{issue}", display_path(&path)));
+                continue;
+            }
+            let mut message = issue.message.clone();
+            if let Some(hint) = &issue.hint {
+                message.push_str(&format!(" ({hint})"));
             }
+            annotations.push(
+                Annotation::new(AnnotationLevel::Error, message)
+                    .file(annotation_path(&path))
+                    .line(issue.line)
+                    .column(issue.column)
+                    .title("skil config validate")
+                    .rule("invalid-config"),
+            );
         }
+        problems += issues.len();
+    }
+    print!("{}", format_annotations(output, &annotations));
+    if checked == 0 {
+        ui::info("No config files found");
+    }
+    if problems > 0 {
+        return Err(SkilError::new(
+            ErrorCode::Config,
+            format!("Found {problems} problem(s) in config"),
+        ));
     }
-
     Ok(())
 }
 
-/// Searches for skills using the remote registry API.
-pub fn run_find(args: FindArgs) -> Result<()> {
-    let Some(query) = args.query else {
-        ui::info("Usage: skills find <query>");
-        ui::info("Tip: use `skills find typescript`");
-        return Ok(());
-    };
+fn unknown_setting(key: &str) -> SkilError {
+    SkilError::Message(format!(
+        "Unknown setting: {key} (available: telemetry, store-dir, max-skill-bytes)"
+    ))
+}
 
-    let url = format!(
+/// Returns the skills.sh search URL for a query and filters.
+pub(crate) fn registry_search_url(query: &str, filters: &[SearchFilter]) -> String {
+    let mut url = format!(
         "{}/api/search?q={}&limit=10",
         SEARCH_API_BASE,
-        urlencoding::encode(&query)
+        urlencoding::encode(query)
     );
-    let res = reqwest::blocking::get(url)?;
-    if !res.status().is_success() {
-        ui::warn(&format!("Search failed: {}", res.status()));
-        return Ok(());
+    for filter in filters {
+        let (key, value) = filter.query_param();
+        url.push_str(&format!("&{key}={}", urlencoding::encode(&value)));
+    }
+    url
+}
+
+/// Searches for skills using the remote registry API.
+pub fn run_find(args: FindArgs) -> Result<()> {
+    let query = match args.query {
+        Some(query) => query,
+        None if !args.filters.is_empty() => String::new(),
+        None => {
+            ui::info("Usage: skills find <query>");
+            ui::info("Tip: use `skills find typescript`");
+            return Ok(());
+        }
+    };
+
+    if let Some(registry) = args.registry.as_deref() {
+        return find_in_docs_registry(registry, &query, &args.filters, args.refresh);
     }
 
-    let data: SearchApiResponse = res.json()?;
-    if data.skills.is_empty() {
+    let Some(body) = fetch_search(&registry_search_url(&query, &args.filters), args.refresh)?
+    else {
+        return Ok(());
+    };
+    let data: SearchApiResponse = serde_json::from_str(&body)?;
+    let results: Vec<SearchApiSkill> = data
+        .skills
+        .into_iter()
+        .filter(|skill| args.filters.iter().all(|filter| skill.matches(filter)))
+        .collect();
+    if results.is_empty() {
         ui::info("No skills found");
         return Ok(());
     }
 
     ui::heading("Results");
-    for skill in data.skills {
+    for skill in results {
         let source = skill.source.clone().unwrap_or_default();
         let installs = skill.installs.unwrap_or(0);
         ui::list_item(&format!(
             "{} ({}) - {} installs",
             skill.name, source, installs
         ));
+        let mut details = Vec::new();
+        if !skill.agents.is_empty() {
+            details.push(format!("agents: {}", skill.agents.join(", ")));
+        }
+        if !skill.tags.is_empty() {
+            details.push(format!("tags: {}", skill.tags.join(", ")));
+        }
+        if let Some(license) = &skill.license {
+            details.push(format!("license: {license}"));
+        }
+        if !details.is_empty() {
+            ui::info(&format!("  {}", details.join("; ")));
+        }
         if !source.is_empty() {
             ui::info(&format!(
                 "  add: skills add {} --skill {}",
@@ -867,93 +3602,745 @@ pub fn run_find(args: FindArgs) -> Result<()> {
     Ok(())
 }
 
+/// Fetches a search response, reusing one cached within the last hour
+/// unless `refresh` is set.
+///
+/// When the registry cannot be reached, an older cached response is used
+/// with a warning. Returns `None` after warning about a failed request with
+/// nothing cached.
+fn fetch_search(url: &str, refresh: bool) -> Result<Option<String>> {
+    let cached = cached_response(url);
+    if !refresh && let Some(cached) = cached.as_ref().filter(|cached| cached.is_fresh()) {
+        ui::info(&format!(
+            "Showing results cached {} ago; pass --refresh to search again",
+            format_age(cached.age)
+        ));
+        return Ok(Some(cached.body.clone()));
+    }
+    let request = {
+        let _span = span("http");
+        reqwest::blocking::get(url)
+    };
+    let failure = match request {
+        Ok(res) if res.status().is_success() => {
+            let body = res.text()?;
+            // A cache that cannot be written only costs the next search a request.
+            let _ = cache_response(url, &body);
+            return Ok(Some(body));
+        }
+        Ok(res) => format!("Search failed: {}", res.status()),
+        Err(err) if cached.is_none() => return Err(err.into()),
+        Err(err) => format!("Search failed: {err}"),
+    };
+    match cached {
+        Some(cached) => {
+            ui::warn(&format!(
+                "{failure}; showing results cached {} ago",
+                format_age(cached.age)
+            ));
+            Ok(Some(cached.body))
+        }
+        None => {
+            ui::warn(&failure);
+            Ok(None)
+        }
+    }
+}
+
+/// Formats a duration as whole seconds, minutes, hours, or days.
+fn format_age(age: Duration) -> String {
+    match age.as_secs() {
+        secs if secs < 60 => format!("{secs}s"),
+        secs if secs < 60 * 60 => format!("{}m", secs / 60),
+        secs if secs < 24 * 60 * 60 => format!("{}h", secs / (60 * 60)),
+        secs => format!("{}d", secs / (24 * 60 * 60)),
+    }
+}
+
+/// Searches the `skills.json` manifest of a built docs site.
+///
+/// Manifests list tags only, so other filters are ignored with a warning.
+fn find_in_docs_registry(
+    registry: &str,
+    query: &str,
+    filters: &[SearchFilter],
+    refresh: bool,
+) -> Result<()> {
+    let url = format!("{}/{}", registry.trim_end_matches('/'), MANIFEST_FILE);
+    let Some(body) = fetch_search(&url, refresh)? else {
+        return Ok(());
+    };
+    let manifest: SkillsManifest = serde_json::from_str(&body)?;
+    let mut tags = Vec::new();
+    for filter in filters {
+        match filter {
+            SearchFilter::Tag(tag) => tags.push(tag),
+            other => ui::warn(&format!(
+                "Ignoring --filter {}: docs registries only support tag filters",
+                other.query_param().0
+            )),
+        }
+    }
+    let results: Vec<_> = manifest
+        .search(query)
+        .into_iter()
+        .filter(|skill| {
+            tags.iter()
+                .all(|tag| skill.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+        })
+        .collect();
+    if results.is_empty() {
+        ui::info("No skills found");
+        return Ok(());
+    }
+
+    ui::heading("Results");
+    for skill in results {
+        ui::list_item(&format!("{} - {}", skill.name, skill.description));
+        if !skill.tags.is_empty() {
+            ui::info(&format!("  tags: {}", skill.tags.join(", ")));
+        }
+        ui::info(&format!("  add: {}", skill.install));
+    }
+
+    Ok(())
+}
+
 /// Checks for updates for skills tracked in config.
-pub fn run_check() -> Result<()> {
+pub fn run_check(args: CheckArgs) -> Result<()> {
+    if args.output == OutputFormat::Sarif {
+        ui::use_stderr_only();
+    }
+    if let Some(path) = &args.policy {
+        return check_against_policy(path, args.output);
+    }
+    if args.drift {
+        return check_installed_drift(args.output);
+    }
+    let template = args
+        .format
+        .as_deref()
+        .map(|format| Template::parse(format, CHECK_FIELDS))
+        .transpose()?;
+    if template.is_some() {
+        ui::use_stderr_only();
+    }
     ui::info("Checking for skill updates...");
-    let location = config_location_auto()?;
-    let config = read_config(&location.path)?;
-    if config.sources.is_empty() {
+    let updater = Updater::auto()?;
+    let updates = if updater.config()?.sources.is_empty() {
         ui::info("No skills tracked in config.");
+        Vec::new()
+    } else {
+        warn_deprecated(&updater.deprecated()?);
+        updater.check()?
+    };
+
+    if let Some(template) = &template {
+        for update in &updates {
+            println!(
+                "{}",
+                template.render(&[
+                    ("source", update.source_key.clone()),
+                    (
+                        "current",
+                        update
+                            .source
+                            .version
+                            .clone()
+                            .or_else(|| update.source.checksum.clone())
+                            .unwrap_or_default()
+                    ),
+                    ("latest", update.latest().to_string()),
+                    ("skills", update.source.skills.join(",")),
+                ])
+            );
+        }
+        return Ok(());
+    }
+    if args.output != OutputFormat::Text {
+        let annotations: Vec<Annotation> = updates
+            .iter()
+            .map(|update| {
+                Annotation::new(
+                    AnnotationLevel::Notice,
+                    format!("{} has an update: {}", update.source_key, update.latest()),
+                )
+                .title("skil check")
+                .rule("update-available")
+            })
+            .collect();
+        print!("{}", format_annotations(args.output, &annotations));
         return Ok(());
     }
-
-    let updates = collect_available_updates(&config)?;
-
     if updates.is_empty() {
         ui::success("All skills are up to date");
         return Ok(());
     }
-
     ui::heading(&format!("{} update(s) available", updates.len()));
     for update in updates {
-        let latest = update
-            .latest_version
-            .as_deref()
-            .or(update.latest_checksum.as_deref())
-            .unwrap_or("unknown");
-        ui::list_item(&format!("{} ({})", update.source_key, latest));
+        ui::list_item(&format!("{} ({})", update.source_key, update.latest()));
     }
 
     Ok(())
 }
 
-/// Updates all skills that have updates available.
-pub fn run_update() -> Result<()> {
-    ui::info("Checking for skill updates...");
+/// Fields of `skil check --format`.
+const CHECK_FIELDS: &[&str] = &["source", "current", "latest", "skills"];
+
+/// Fails unless the installed skills meet the policy at `path`.
+fn check_against_policy(path: &Path, output: OutputFormat) -> Result<()> {
+    let policy = load_policy(path)?;
     let location = config_location_auto()?;
     let config = read_config(&location.path)?;
-    if config.sources.is_empty() {
+    let violations = check_policy(&policy, &config, &canonical_skills_dir(location.is_global)?);
+    report_violations(
+        &violations,
+        output,
+        path,
+        "skil check --policy",
+        "policy-violation",
+    );
+    if violations.is_empty() {
+        ui::success(&format!(
+            "Installed skills meet {} ({} requirement(s))",
+            display_path(path),
+            policy.require.len()
+        ));
+        return Ok(());
+    }
+    Err(SkilError::Message(format!(
+        "{} policy violation(s); run `skil install` or `skil add` to fix them",
+        violations.len()
+    )))
+}
+
+/// Fails when store copies of tracked skills differ from what was installed.
+fn check_installed_drift(output: OutputFormat) -> Result<()> {
+    let location = config_location_auto()?;
+    let config = read_config(&location.path)?;
+    let violations = check_drift(&config, &canonical_skills_dir(location.is_global)?)?;
+    report_violations(
+        &violations,
+        output,
+        &location.path,
+        "skil check --drift",
+        "installed-drift",
+    );
+    if violations.is_empty() {
+        ui::success(&format!(
+            "Installed skills match {}",
+            display_path(&location.path)
+        ));
+        return Ok(());
+    }
+    Err(SkilError::Message(format!(
+        "{} skill(s) differ from what was installed; run `skil edit <skill>` to keep \
+         the edits, or `skil install` in a terminal to review them",
+        violations.len()
+    )))
+}
+
+/// Prints violations as JSON, annotations against `file`, or a table.
+fn report_violations(
+    violations: &[Violation],
+    output: OutputFormat,
+    file: &Path,
+    title: &str,
+    rule: &str,
+) {
+    if ui::json_output() {
+        println!("{}", serde_json::json!({ "violations": violations }));
+    } else if output != OutputFormat::Text {
+        let content = std::fs::read_to_string(file).unwrap_or_default();
+        let annotations: Vec<Annotation> = violations
+            .iter()
+            .map(|violation| {
+                let message = match &violation.skill {
+                    Some(skill) => format!("{} {skill}: {}", violation.source, violation.problem),
+                    None => format!("{}: {}", violation.source, violation.problem),
+                };
+                let annotation = Annotation::new(AnnotationLevel::Error, message)
+                    .file(annotation_path(file))
+                    .title(title)
+                    .rule(rule);
+                // Point at the entry naming the source.
+                let quoted = format!("\"{}\"", violation.source);
+                match content.lines().position(|line| line.contains(&quoted)) {
+                    Some(index) => annotation.line(index + 1),
+                    None => annotation,
+                }
+            })
+            .collect();
+        print!("{}", format_annotations(output, &annotations));
+    } else if !violations.is_empty() {
+        let rows: Vec<Vec<String>> = violations
+            .iter()
+            .map(|violation| {
+                vec![
+                    violation.source.clone(),
+                    violation.skill.clone().unwrap_or_default(),
+                    violation.problem.clone(),
+                ]
+            })
+            .collect();
+        ui::table(&["SOURCE", "SKILL", "PROBLEM"], &rows);
+    }
+}
+
+/// Updates all skills that have updates available.
+pub fn run_update(args: UpdateArgs) -> Result<()> {
+    ui::info("Checking for skill updates...");
+    let observer = CliObserver::new(false).drift(args.strategy.map(DriftResolution::from));
+    let updater = Updater::auto()?.observer(&observer);
+    if updater.config()?.sources.is_empty() {
         ui::info("No skills tracked in config.");
         return Ok(());
     }
 
-    let updates = collect_available_updates(&config)?;
+    let updates = updater.check()?;
 
     if updates.is_empty() {
         ui::success("All skills are up to date");
-        return Ok(());
+        return replace_deprecated(&updater, args.replace_deprecated);
     }
 
     ui::heading(&format!("Found {} update(s)", updates.len()));
 
+    // Without --keep-going the batch is all or nothing.
+    let snapshot = if args.keep_going {
+        None
+    } else {
+        Some(updater.snapshot(&updates)?)
+    };
+
     let mut success = 0usize;
     let mut failed = 0usize;
+    // Recorded once the batch can no longer be rolled back.
+    let mut history = Vec::new();
 
-    for update in updates {
+    for update in &updates {
         ui::info(&format!("Updating {}...", update.source_key));
 
-        let args = AddArgs {
-            source: update.source_key.clone(),
-            global: location.is_global,
-            copy: false,
-            agent: vec![],
-            skill: update.source.skills.clone(),
-            list: false,
-            yes: true,
-            all: false,
-            full_depth: false,
-        };
-
-        match run_add(args) {
-            Ok(_) => {
-                success += 1;
-                ui::info(&format!("  Updated {}", update.source_key));
+        let result = updater.update(update).and_then(|report| {
+            ui::info(&format!("  Updated {}", update.source_key));
+            history.push(
+                HistoryEntry::new("update", updater.is_global())?
+                    .source(update.source_key.clone())
+                    .skills(report.skills.clone())
+                    .revisions(
+                        source_revision(&update.source),
+                        short_report_revision(&report),
+                    ),
+            );
+            print_changes(&report);
+            if !report.new_skills.is_empty() {
+                ui::info(&format!(
+                    "  New in source: {}; add with `skil add {} --skill <name>`",
+                    report.new_skills.join(", "),
+                    update.source_key
+                ));
+            }
+            if !update.source.modified.is_empty() {
+                ui::info(&format!(
+                    "  Kept local edits to {}",
+                    update.source.modified.join(", ")
+                ));
             }
+            if !report.kept_local.is_empty() {
+                ui::info(&format!(
+                    "  Kept local edits to {}; marked as locally modified",
+                    report.kept_local.join(", ")
+                ));
+            }
+            for conflict in &report.conflicts {
+                ui::warn(&format!("  Merge conflict in {}", conflict.display()));
+            }
+            warn_lfs_pointers(&report.lfs_pointers);
+            if report.missing.is_empty() {
+                return Ok(());
+            }
+            prune_missing(&updater, &update.source_key, &report.missing, args.prune)
+        });
+        match result {
+            Ok(()) => success += 1,
             Err(err) => {
                 failed += 1;
                 ui::warn(&format!(
                     "  Failed to update {}: {}",
                     update.source_key, err
                 ));
+                if let Some(snapshot) = &snapshot {
+                    snapshot.restore()?;
+                    return Err(SkilError::new(
+                        err.code(),
+                        format!(
+                            "Update failed; restored {} source(s) to their previous state (use --keep-going to keep partial updates)",
+                            updates.len()
+                        ),
+                    ));
+                }
             }
         }
     }
 
+    for entry in history {
+        record_history(entry);
+    }
     ui::success(&format!("Updated {} source(s), {} failed", success, failed));
+    replace_deprecated(&updater, args.replace_deprecated)
+}
+
+/// Warns about each deprecated skill and its successor.
+fn warn_deprecated(deprecated: &[DeprecatedSkill]) {
+    for skill in deprecated {
+        let mut message = format!("{} is deprecated", skill.skill);
+        if let Some(reason) = &skill.deprecation.reason {
+            message.push_str(&format!(": {reason}"));
+        }
+        if let Some(successor) = successor_label(skill) {
+            message.push_str(&format!(" (superseded by {successor})"));
+        }
+        ui::warn(&message);
+    }
+}
+
+fn successor_label(skill: &DeprecatedSkill) -> Option<String> {
+    let successor = skill.deprecation.successor.as_ref()?;
+    Some(match &successor.source {
+        Some(source) => format!("{} from {source}", successor.skill),
+        None => successor.skill.clone(),
+    })
+}
+
+/// Offers to replace deprecated skills with their successors, without asking
+/// when `replace` is set.
+fn replace_deprecated(updater: &Updater, replace: bool) -> Result<()> {
+    let deprecated = updater.deprecated()?;
+    warn_deprecated(&deprecated);
+    for skill in &deprecated {
+        let Some(successor) = successor_label(skill) else {
+            continue;
+        };
+        let confirmed = replace
+            || (console::Term::stdout().is_term()
+                && dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+                    .with_prompt(format!("Replace {} with {successor}?", skill.skill))
+                    .default(true)
+                    .interact()
+                    .map_err(|err| SkilError::Message(err.to_string()))?);
+        if !confirmed {
+            ui::info("  Run `skil update --replace-deprecated` to replace it");
+            continue;
+        }
+        updater.replace_deprecated(skill)?;
+        ui::success(&format!("Replaced {} with {successor}", skill.skill));
+    }
+    Ok(())
+}
+
+/// Restores the most recently removed copy of a skill from the trash.
+pub fn run_restore(args: RestoreArgs) -> Result<()> {
+    let Some(skill) = args.skill else {
+        let entries = trash_entries()?;
+        if entries.is_empty() {
+            ui::info("No removed skills to restore.");
+            return Ok(());
+        }
+        let rows: Vec<Vec<String>> = entries
+            .iter()
+            .map(|entry| {
+                vec![
+                    entry.skill.clone(),
+                    entry.removed_time().strftime("%Y-%m-%d %H:%M").to_string(),
+                    entry.paths.len().to_string(),
+                ]
+            })
+            .collect();
+        ui::table(&["SKILL", "REMOVED", "FOLDERS"], &rows);
+        ui::info(&format!(
+            "Removed skills are kept for {TRASH_EXPIRY_DAYS} days; restore one with `skil restore <skill>`"
+        ));
+        return Ok(());
+    };
+    let entry = restore_skill(&skill)?;
+    ui::success(&format!("Restored {}", entry.skill));
+    for path in &entry.paths {
+        ui::list_item(&path.original.display().to_string());
+    }
+    Ok(())
+}
+
+/// Reverts the source of a skill to its previously installed revision.
+pub fn run_rollback(args: RollbackArgs) -> Result<()> {
+    let observer = CliObserver::new(false);
+    let updater = if args.global {
+        Updater::new(true)?
+    } else {
+        Updater::auto()?
+    }
+    .observer(&observer);
+    let config = updater.config()?;
+    let from = tracked_source(&config, &args.skill).and_then(|(_, source)| source_revision(source));
+    let report = updater.rollback(&args.skill)?;
+    record_history(
+        HistoryEntry::new("rollback", updater.is_global())?
+            .source(report.source_key.clone())
+            .skills(report.skills.clone())
+            .revisions(from, short_report_revision(&report)),
+    );
+    let revision = report
+        .checksum
+        .as_deref()
+        .map(|checksum| &checksum[..checksum.len().min(12)])
+        .unwrap_or("unknown");
+    ui::success(&format!(
+        "Rolled back {} to {}",
+        report.source_key, revision
+    ));
+    for skill in &report.skills {
+        ui::list_item(skill);
+    }
+    print_changes(&report);
+    Ok(())
+}
+
+/// Prints recorded operations, newest first.
+pub fn run_history(args: HistoryArgs) -> Result<()> {
+    let day_start = |date: jiff::civil::Date| -> Result<jiff::Timestamp> {
+        date.to_zoned(jiff::tz::TimeZone::UTC)
+            .map(|zoned| zoned.timestamp())
+            .map_err(|err| SkilError::Message(err.to_string()))
+    };
+    let since = args.since.map(day_start).transpose()?;
+    let until = match args.until {
+        Some(date) => Some(day_start(
+            date.tomorrow()
+                .map_err(|err| SkilError::Message(err.to_string()))?,
+        )?),
+        None => None,
+    };
+    let entries: Vec<HistoryEntry> = read_history()?
+        .into_iter()
+        .rev()
+        .filter(|entry| since.is_none_or(|since| entry.time() >= since))
+        .filter(|entry| until.is_none_or(|until| entry.time() < until))
+        .filter(|entry| {
+            args.skill.as_ref().is_none_or(|skill| {
+                entry
+                    .skills
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(skill))
+            })
+        })
+        .take(args.limit)
+        .collect();
+    if ui::json_output() {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+    if entries.is_empty() {
+        ui::info("No recorded operations.");
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = entries
+        .iter()
+        .map(|entry| {
+            let revision = match (&entry.from, &entry.to) {
+                (Some(from), Some(to)) => format!("{from} -> {to}"),
+                (Some(from), None) => format!("{from} -> -"),
+                (None, Some(to)) => to.clone(),
+                (None, None) => "-".to_string(),
+            };
+            vec![
+                entry.time().strftime("%Y-%m-%d %H:%M").to_string(),
+                entry.command.clone(),
+                entry.scope.clone(),
+                entry.source.clone().unwrap_or_else(|| "-".to_string()),
+                entry.skills.join(", "),
+                revision,
+            ]
+        })
+        .collect();
+    ui::table(
+        &["TIME", "COMMAND", "SCOPE", "SOURCE", "SKILLS", "REVISION"],
+        &rows,
+    );
+    Ok(())
+}
+
+/// Prints a diffstat of each skill an install changed.
+fn print_changes(report: &InstallReport) {
+    if report.changes.is_empty() {
+        ui::info("  No installed files changed");
+        return;
+    }
+    for change in &report.changes {
+        let (added, removed) = change.lines();
+        ui::info(&format!(
+            "  {}: {} file(s) changed, +{added} -{removed}",
+            change.skill,
+            change.files.len()
+        ));
+        for file in &change.files {
+            let lines = match file.lines {
+                Some((added, removed)) => format!(" (+{added} -{removed})"),
+                None => " (binary)".to_string(),
+            };
+            ui::info(&format!(
+                "    {} {}{lines}",
+                file.kind.marker(),
+                file.path.display()
+            ));
+        }
+    }
+}
+
+/// Appends an operation to the history file, warning instead of failing the
+/// command when it cannot be written.
+fn record_history(entry: HistoryEntry) {
+    if let Err(err) = record(&entry) {
+        ui::warn(&format!("Could not record history: {err}"));
+    }
+}
+
+/// Reports skills removed upstream and prunes them when confirmed or `--prune` is set.
+fn prune_missing(
+    updater: &Updater,
+    source_key: &str,
+    missing: &[String],
+    prune: bool,
+) -> Result<()> {
+    ui::warn(&format!("  No longer in source: {}", missing.join(", ")));
+    let confirmed = prune
+        || (console::Term::stdout().is_term()
+            && dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Remove {} stale skill(s)?", missing.len()))
+                .default(false)
+                .interact()
+                .map_err(|err| SkilError::Message(err.to_string()))?);
+    if !confirmed {
+        ui::info("  Run `skil update --prune` to remove them");
+        return Ok(());
+    }
+    updater.prune(source_key, missing)?;
+    record_history(
+        HistoryEntry::new("remove", updater.is_global())?
+            .source(source_key)
+            .skills(missing.to_vec()),
+    );
+    ui::info(&format!("  Removed {}", missing.join(", ")));
     Ok(())
 }
 
 /// Converts a filesystem path to a display-friendly string.
+/// `path` relative to the current directory, where CI runs from the
+/// repository root, for annotations to attach to.
+fn annotation_path(path: &Path) -> String {
+    let relative = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf));
+    relative
+        .unwrap_or_else(|| path.to_path_buf())
+        .to_string_lossy()
+        .to_string()
+}
+
 fn display_path(path: &Path) -> String {
     path.to_string_lossy().to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_agents_and_skills_have_error_codes() {
+        let err = require_agents(Vec::new()).expect_err("no agents");
+        assert_eq!(err.code(), ErrorCode::NoAgents);
+        assert_eq!(
+            require_agents(resolve_agents(&["codex".to_string()]))
+                .expect("agents")
+                .len(),
+            1
+        );
+
+        let empty = tempfile::tempdir().expect("tempdir");
+        let err = run_add(AddArgs {
+            source: empty.path().display().to_string(),
+            global: false,
+            copy: false,
+            agent: Vec::new(),
+            skill: Vec::new(),
+            list: true,
+            long: false,
+            ask: false,
+            overwrite: false,
+            yes: true,
+            all: false,
+            full_depth: false,
+            no_ignore: false,
+            max_depth: None,
+            file_skills: false,
+            adopt: false,
+            paths: Vec::new(),
+            branch: None,
+            local: true,
+            git: false,
+            allow_large: false,
+        })
+        .expect_err("no skills");
+        assert_eq!(err.code(), ErrorCode::NoSkills);
+    }
+
+    #[test]
+    fn search_filters_parse_key_value_pairs() {
+        assert!(matches!(
+            "agent:codex".parse(),
+            Ok(SearchFilter::Agent(agent)) if agent == "codex"
+        ));
+        assert!(matches!(
+            "installs:100".parse(),
+            Ok(SearchFilter::MinInstalls(100))
+        ));
+
+        let error = |value: &str| value.parse::<SearchFilter>().expect_err(value);
+        assert_eq!(error("tag"), "expected KEY:VALUE, got `tag`");
+        assert_eq!(error("tag:"), "expected KEY:VALUE, got `tag:`");
+        assert_eq!(
+            error("stars:5"),
+            "unknown filter `stars`; use agent, tag, license, or installs"
+        );
+        assert_eq!(
+            error("installs:many"),
+            "installs takes a number, got `many`"
+        );
+    }
+
+    #[test]
+    fn search_results_are_filtered_on_known_fields() {
+        let skill: SearchApiSkill = serde_json::from_value(serde_json::json!({
+            "name": "pdf",
+            "installs": 50,
+            "agents": ["codex", "Cursor"],
+            "tags": ["docs"],
+            "license": "MIT",
+        }))
+        .expect("skill");
+        assert!(skill.matches(&SearchFilter::Agent("cursor".to_string())));
+        assert!(!skill.matches(&SearchFilter::Agent("claude-code".to_string())));
+        assert!(skill.matches(&SearchFilter::Tag("DOCS".to_string())));
+        assert!(!skill.matches(&SearchFilter::Tag("pdf".to_string())));
+        assert!(skill.matches(&SearchFilter::License("mit".to_string())));
+        assert!(!skill.matches(&SearchFilter::License("Apache-2.0".to_string())));
+        assert!(skill.matches(&SearchFilter::MinInstalls(50)));
+        assert!(!skill.matches(&SearchFilter::MinInstalls(51)));
+
+        // Fields the registry left out were filtered server-side.
+        let bare: SearchApiSkill =
+            serde_json::from_value(serde_json::json!({ "name": "pdf" })).expect("skill");
+        assert!(bare.matches(&SearchFilter::Agent("claude-code".to_string())));
+        assert!(bare.matches(&SearchFilter::Tag("pdf".to_string())));
+        assert!(bare.matches(&SearchFilter::License("MIT".to_string())));
+        assert!(bare.matches(&SearchFilter::MinInstalls(1)));
+    }
+}
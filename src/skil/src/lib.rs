@@ -1,7 +1,15 @@
 #![allow(clippy::result_large_err)]
 
+mod api;
 mod cli;
+mod markdown;
+mod mcp;
+mod picker;
+mod telemetry;
+mod template;
+mod tui;
 pub mod ui;
+mod upgrade;
 
 pub use skil_core::{Result, SkilError};
 
@@ -9,17 +17,51 @@ pub use skil_core::{Result, SkilError};
 pub fn run() -> Result<()> {
     use clap::Parser;
     let cli = cli::Cli::parse();
+    if cli.json {
+        ui::use_json_output();
+    }
+    if cli.use_system_git {
+        skil_core::git::use_system_git();
+    }
+    let started = cli.profile.then(|| {
+        skil_core::profile::enable_profiling();
+        std::time::Instant::now()
+    });
 
-    match cli.command {
+    let result = match cli.command {
         cli::Command::Add(args) => cli::run_add(args),
         cli::Command::Install(args) => cli::run_install(args),
         cli::Command::Remove(args) => cli::run_remove(args),
+        cli::Command::Restore(args) => cli::run_restore(args),
         cli::Command::List(args) => cli::run_list(args),
         cli::Command::Find(args) => cli::run_find(args),
-        cli::Command::Check => cli::run_check(),
-        cli::Command::Update => cli::run_update(),
+        cli::Command::Check(args) => cli::run_check(args),
+        cli::Command::Update(args) => cli::run_update(args),
+        cli::Command::Rollback(args) => cli::run_rollback(args),
+        cli::Command::Tree => cli::run_tree(),
+        cli::Command::Import(args) => cli::run_import(args),
+        cli::Command::Render(args) => cli::run_render(args),
+        cli::Command::Stats(args) => cli::run_stats(args),
         cli::Command::Init(args) => cli::run_init(args),
         cli::Command::Completions(args) => cli::run_completions(args),
+        cli::Command::CompleteSkills(args) => cli::run_complete_skills(args),
+        cli::Command::Mcp => mcp::run_mcp(),
+        cli::Command::ServeApi(args) => api::run_serve_api(args),
+        cli::Command::Tui(args) => tui::run_tui(args),
+        cli::Command::Config(args) => cli::run_config(args),
         cli::Command::Docs(args) => skil_docs::run_docs(args),
+        cli::Command::UpgradeSelf(args) => upgrade::run_upgrade_self(args),
+        cli::Command::Clean(args) => cli::run_clean(args),
+        cli::Command::Migrate(args) => cli::run_migrate(args),
+        cli::Command::Audit(args) => cli::run_audit(args),
+        cli::Command::Info(args) => cli::run_info(args),
+        cli::Command::Show(args) => cli::run_show(args),
+        cli::Command::History(args) => cli::run_history(args),
+        cli::Command::Edit(args) => cli::run_edit(args),
+        cli::Command::Hook(args) => cli::run_hook(args),
+    };
+    if let Some(started) = started {
+        cli::print_profile(started.elapsed());
     }
+    result
 }
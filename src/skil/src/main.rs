@@ -1,6 +1,6 @@
 fn main() {
     if let Err(err) = skil::run() {
-        skil::ui::error(&err.to_string());
-        std::process::exit(1);
+        skil::ui::report_error(&err);
+        std::process::exit(err.code().exit_code());
     }
 }
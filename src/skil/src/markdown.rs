@@ -0,0 +1,245 @@
+use console::Style;
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+/// Renders markdown as terminal text.
+///
+/// Headings and strong text are bold, emphasis italic, code yellow, and
+/// links underlined with their target after them. Lists get bullets or
+/// numbers, code blocks are indented, and quotes get a bar. Source line breaks
+/// are kept, since there is no width to wrap to. Styles are dropped when
+/// colors are disabled.
+pub fn render(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+    let mut renderer = Renderer::default();
+    for event in Parser::new_ext(markdown, options) {
+        renderer.event(event);
+    }
+    renderer.out.trim_end().to_string()
+}
+
+#[derive(Default)]
+struct Renderer {
+    out: String,
+    /// A block ended and the next one should start after a blank line.
+    gap: bool,
+    heading: Option<HeadingLevel>,
+    strong: usize,
+    emphasis: usize,
+    strikethrough: usize,
+    code_block: bool,
+    quotes: usize,
+    /// Open lists, with the next number of ordered ones.
+    lists: Vec<Option<u64>>,
+    /// Targets of open links, with the text rendered for them so far.
+    links: Vec<(String, String)>,
+}
+
+impl Renderer {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => {
+                let style = if self.code_block {
+                    Style::new().yellow()
+                } else {
+                    self.style()
+                };
+                self.text(&text, &style);
+            }
+            Event::Code(code) => self.text(&code, &Style::new().yellow()),
+            Event::Html(html) | Event::InlineHtml(html) => self.text(&html, &Style::new().dim()),
+            Event::SoftBreak | Event::HardBreak => self.newline(),
+            Event::Rule => {
+                self.block();
+                self.text(&"─".repeat(40), &Style::new().dim());
+                self.gap = true;
+            }
+            Event::TaskListMarker(done) => {
+                self.text(if done { "[x] " } else { "[ ] " }, &Style::new());
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.block(),
+            Tag::Heading { level, .. } => {
+                self.block();
+                self.heading = Some(level);
+            }
+            Tag::BlockQuote(_) => {
+                self.block();
+                self.quotes += 1;
+            }
+            Tag::CodeBlock(_) => {
+                self.block();
+                self.code_block = true;
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.block();
+                } else {
+                    self.newline();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                if self.gap {
+                    self.block();
+                }
+                self.newline();
+                let depth = self.lists.len().saturating_sub(1);
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.prefix(depth);
+                self.out.push_str(&marker);
+            }
+            Tag::Emphasis => self.emphasis += 1,
+            Tag::Strong => self.strong += 1,
+            Tag::Strikethrough => self.strikethrough += 1,
+            Tag::Link { dest_url, .. } => self.links.push((dest_url.to_string(), String::new())),
+            Tag::Image { .. } => self.text("[image: ", &Style::new().dim()),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => self.gap = true,
+            TagEnd::Heading(_) => {
+                self.heading = None;
+                self.gap = true;
+            }
+            TagEnd::BlockQuote(_) => {
+                self.quotes = self.quotes.saturating_sub(1);
+                self.gap = true;
+            }
+            TagEnd::CodeBlock => {
+                self.code_block = false;
+                // Code block text ends with its own newline.
+                self.gap = true;
+            }
+            TagEnd::List(_) => {
+                self.lists.pop();
+                self.gap = self.lists.is_empty();
+            }
+            TagEnd::Item => self.newline(),
+            TagEnd::Emphasis => self.emphasis = self.emphasis.saturating_sub(1),
+            TagEnd::Strong => self.strong = self.strong.saturating_sub(1),
+            TagEnd::Strikethrough => self.strikethrough = self.strikethrough.saturating_sub(1),
+            TagEnd::Link => {
+                if let Some((dest, text)) = self.links.pop()
+                    && dest != text
+                    && !dest.starts_with('#')
+                {
+                    self.text(&format!(" ({dest})"), &Style::new().dim());
+                }
+            }
+            TagEnd::Image => self.text("]", &Style::new().dim()),
+            _ => {}
+        }
+    }
+
+    /// Style of inline text in the current context.
+    fn style(&self) -> Style {
+        let mut style = Style::new();
+        match self.heading {
+            Some(HeadingLevel::H1) => style = style.bold().underlined(),
+            Some(_) => style = style.bold(),
+            None => {}
+        }
+        if self.strong > 0 {
+            style = style.bold();
+        }
+        if self.emphasis > 0 {
+            style = style.italic();
+        }
+        if self.strikethrough > 0 {
+            style = style.strikethrough();
+        }
+        if !self.links.is_empty() {
+            style = style.underlined();
+        }
+        style
+    }
+
+    /// Writes `text`, starting each new line with the quote and list indent.
+    fn text(&mut self, text: &str, style: &Style) {
+        if let Some((_, link_text)) = self.links.last_mut() {
+            link_text.push_str(text);
+        }
+        let indent = if self.code_block { 4 } else { 0 };
+        for (index, line) in text.split('\n').enumerate() {
+            if index > 0 {
+                self.out.push('\n');
+            }
+            if line.is_empty() {
+                continue;
+            }
+            if self.at_line_start() {
+                self.prefix(self.lists.len());
+                self.out.push_str(&" ".repeat(indent));
+            }
+            self.out.push_str(&style.apply_to(line).to_string());
+        }
+    }
+
+    /// Writes quote bars and `depth` levels of list indent.
+    fn prefix(&mut self, depth: usize) {
+        for _ in 0..self.quotes {
+            self.out
+                .push_str(&Style::new().dim().apply_to("│ ").to_string());
+        }
+        self.out.push_str(&"  ".repeat(depth));
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.out.is_empty() || self.out.ends_with('\n')
+    }
+
+    fn newline(&mut self) {
+        if !self.at_line_start() {
+            self.out.push('\n');
+        }
+    }
+
+    /// Starts a block on a new line, after a blank one if a block just ended.
+    fn block(&mut self) {
+        self.newline();
+        if self.gap && !self.out.is_empty() {
+            self.prefix(0);
+            self.out.push('\n');
+        }
+        self.gap = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(markdown: &str) -> String {
+        console::strip_ansi_codes(&render(markdown)).to_string()
+    }
+
+    #[test]
+    fn renders_blocks_lists_and_inline_markup() {
+        let markdown = "# Title\n\nSome **bold** and `code`, see [docs](https://x.dev).\n\n\
+                        - one\n- two\n  1. nested\n  2. again\n\n```sh\nrun it\n```\n\n> quoted\n";
+        assert_eq!(
+            plain(markdown),
+            "Title\n\n\
+             Some bold and code, see docs (https://x.dev).\n\n\
+             - one\n- two\n  1. nested\n  2. again\n\n    run it\n\n│ quoted"
+        );
+    }
+}
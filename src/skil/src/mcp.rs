@@ -0,0 +1,377 @@
+use std::io::{BufRead, Write};
+
+use serde_json::{Value, json};
+use skil_core::install::canonical_skills_dir;
+use skil_core::{Result, SkilError};
+
+use crate::cli::{self, AddArgs, SearchApiResponse};
+use crate::ui;
+
+const PROTOCOL_VERSION: &str = "2025-06-18";
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Runs skil as an MCP server over stdio (newline-delimited JSON-RPC).
+///
+/// Human-oriented output from reused commands goes to stderr so stdout only
+/// carries protocol messages.
+pub fn run_mcp() -> Result<()> {
+    ui::use_stderr_only();
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout().lock();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_message(&line) {
+            writeln!(stdout, "{response}")?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Handles one JSON-RPC message and returns the response, if any.
+fn handle_message(line: &str) -> Option<Value> {
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(err) => return Some(error_response(Value::Null, PARSE_ERROR, &err.to_string())),
+    };
+    let id = message.get("id").cloned();
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        return id.map(|id| error_response(id, INVALID_REQUEST, "Missing method"));
+    };
+    // Notifications carry no id and never get a response.
+    let id = id?;
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match method {
+        "initialize" => Ok(initialize(&params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => call_tool(&params),
+        _ => Err((METHOD_NOT_FOUND, format!("Unknown method: {method}"))),
+    };
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, &message),
+    })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn initialize(params: &Value) -> Value {
+    let version = params
+        .get("protocolVersion")
+        .and_then(Value::as_str)
+        .unwrap_or(PROTOCOL_VERSION);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {} },
+        "serverInfo": { "name": "skil", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "search_skills",
+            "description": "Search the skills.sh registry for skills matching a keyword.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "query": { "type": "string" } },
+                "required": ["query"],
+            },
+        },
+        {
+            "name": "install_skill",
+            "description": "Install skills from a source (owner/repo, git URL, or local path) for the given agents.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "source": { "type": "string" },
+                    "skills": { "type": "array", "items": { "type": "string" } },
+                    "agents": { "type": "array", "items": { "type": "string" } },
+                    "global": { "type": "boolean" },
+                    "source_type": { "type": "string", "enum": ["local", "git"] },
+                    "allow_large": { "type": "boolean" },
+                },
+                "required": ["source"],
+            },
+        },
+        {
+            "name": "list_installed",
+            "description": "List skills installed in the project (or globally).",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "global": { "type": "boolean" } },
+            },
+        },
+        {
+            "name": "read_skill",
+            "description": "Return the SKILL.md content of an installed skill.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "global": { "type": "boolean" },
+                },
+                "required": ["name"],
+            },
+        },
+    ])
+}
+
+/// Dispatches `tools/call`. Tool failures are reported as `isError` results so
+/// the calling agent can read them; only malformed calls are protocol errors.
+fn call_tool(params: &Value) -> std::result::Result<Value, (i64, String)> {
+    let name = params
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or((INVALID_PARAMS, "Missing tool name".to_string()))?;
+    let args = params.get("arguments").cloned().unwrap_or(json!({}));
+
+    let output = match name {
+        "search_skills" => search_skills(&args),
+        "install_skill" => install_skill(&args),
+        "list_installed" => list_installed(&args),
+        "read_skill" => read_skill(&args),
+        _ => return Err((INVALID_PARAMS, format!("Unknown tool: {name}"))),
+    };
+    let (text, is_error) = match output {
+        Ok(text) => (text, false),
+        Err(err) => (err.to_string(), true),
+    };
+    Ok(json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    }))
+}
+
+fn required_str<'a>(args: &'a Value, key: &str) -> Result<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| SkilError::Message(format!("Missing required argument: {key}")))
+}
+
+fn string_list(args: &Value, key: &str) -> Vec<String> {
+    args.get(key)
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn bool_arg(args: &Value, key: &str) -> bool {
+    args.get(key).and_then(Value::as_bool).unwrap_or(false)
+}
+
+fn search_skills(args: &Value) -> Result<String> {
+    let query = required_str(args, "query")?;
+    let res = reqwest::blocking::get(cli::registry_search_url(query, &[]))?;
+    if !res.status().is_success() {
+        return Err(SkilError::Message(format!(
+            "Search failed: {}",
+            res.status()
+        )));
+    }
+    let data: SearchApiResponse = res.json()?;
+    if data.skills.is_empty() {
+        return Ok("No skills found".to_string());
+    }
+    let lines: Vec<String> = data
+        .skills
+        .iter()
+        .map(|skill| {
+            let source = skill.source.as_deref().unwrap_or("");
+            format!(
+                "{} (source: {}, {} installs)",
+                skill.name,
+                source,
+                skill.installs.unwrap_or(0)
+            )
+        })
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+fn install_skill(args: &Value) -> Result<String> {
+    let source = required_str(args, "source")?.to_string();
+    let mut skill = string_list(args, "skills");
+    if skill.is_empty() {
+        skill.push("*".to_string());
+    }
+    let global = bool_arg(args, "global");
+    let (local, git) = cli::source_type_flags(args.get("source_type").and_then(Value::as_str))?;
+    cli::run_add(AddArgs {
+        source: source.clone(),
+        global,
+        copy: false,
+        agent: string_list(args, "agents"),
+        skill: skill.clone(),
+        list: false,
+        long: false,
+        ask: false,
+        overwrite: false,
+        yes: true,
+        all: false,
+        full_depth: false,
+        no_ignore: false,
+        max_depth: None,
+        file_skills: false,
+        adopt: false,
+        paths: Vec::new(),
+        branch: None,
+        local,
+        git,
+        allow_large: bool_arg(args, "allow_large"),
+    })?;
+    Ok(format!(
+        "Installed {} from {source}{}",
+        skill.join(", "),
+        if global { " globally" } else { "" }
+    ))
+}
+
+fn list_installed(args: &Value) -> Result<String> {
+    let dir = canonical_skills_dir(bool_arg(args, "global"))?;
+    let skills = cli::installed_skills(&dir)?;
+    if skills.is_empty() {
+        return Ok("No skills installed".to_string());
+    }
+    let lines: Vec<String> = skills
+        .iter()
+        .map(|skill| format!("{}: {}", skill.name, skill.description))
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+fn read_skill(args: &Value) -> Result<String> {
+    let name = required_str(args, "name")?;
+    let dir = canonical_skills_dir(bool_arg(args, "global"))?;
+    cli::installed_skills(&dir)?
+        .into_iter()
+        .find(|skill| {
+            skill.name.eq_ignore_ascii_case(name)
+                || skill
+                    .path
+                    .file_name()
+                    .is_some_and(|dir_name| dir_name == name)
+        })
+        .map(|skill| skill.raw_content)
+        .ok_or_else(|| SkilError::Message(format!("Skill not installed: {name}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_initialize_and_tool_listing() {
+        let response = handle_message(
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26"}}"#,
+        )
+        .expect("response");
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["protocolVersion"], "2025-03-26");
+        assert_eq!(response["result"]["serverInfo"]["name"], "skil");
+
+        let response =
+            handle_message(r#"{"jsonrpc":"2.0","id":"a","method":"tools/list"}"#).expect("list");
+        let names: Vec<&str> = response["result"]["tools"]
+            .as_array()
+            .expect("tools")
+            .iter()
+            .filter_map(|tool| tool["name"].as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "search_skills",
+                "install_skill",
+                "list_installed",
+                "read_skill"
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_notifications_and_reports_errors() {
+        assert!(
+            handle_message(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#).is_none()
+        );
+
+        let response =
+            handle_message(r#"{"jsonrpc":"2.0","id":2,"method":"nope"}"#).expect("response");
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = handle_message("not json").expect("response");
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+
+        let response = handle_message(
+            r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"read_skill","arguments":{}}}"#,
+        )
+        .expect("response");
+        assert_eq!(response["result"]["isError"], true);
+        assert_eq!(
+            response["result"]["content"][0]["text"],
+            "Missing required argument: name"
+        );
+    }
+
+    #[test]
+    fn install_skill_keeps_a_homonym_from_another_source() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let home = temp.path().join("home");
+        // SAFETY: no other test in this crate reads these variables.
+        unsafe {
+            std::env::set_var("HOME", &home);
+            std::env::set_var("XDG_CONFIG_HOME", home.join(".config"));
+            std::env::set_var("XDG_DATA_HOME", home.join(".local/share"));
+            std::env::set_var("SKIL_CONFIG", temp.path().join("config.toml"));
+            std::env::set_var("SKIL_STORE_DIR", temp.path().join("store"));
+            std::env::set_var("SKIL_CACHE_DIR", temp.path().join("cache"));
+            std::env::set_var("SKIL_HISTORY_FILE", temp.path().join("history.jsonl"));
+            std::env::set_var("SKIL_TELEMETRY", "0");
+            std::env::remove_var("CLAUDE_CONFIG_DIR");
+        }
+        for (source, description) in [("a", "From a"), ("b", "From b")] {
+            let dir = temp.path().join(source).join("pdf");
+            std::fs::create_dir_all(&dir).expect("source");
+            std::fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: pdf\ndescription: {description}\n---\n"),
+            )
+            .expect("skill");
+        }
+
+        let install = |source: &str| {
+            install_skill(&json!({
+                "source": temp.path().join(source).display().to_string(),
+                "global": true,
+                "agents": ["claude-code"],
+            }))
+        };
+        install("a").expect("install");
+        assert!(install("b").is_err());
+
+        let installed = std::fs::read_to_string(temp.path().join("store/pdf/SKILL.md"))
+            .expect("installed skill");
+        assert!(installed.contains("From a"), "{installed}");
+    }
+}
@@ -0,0 +1,341 @@
+use console::{Key, Term, style, truncate_str};
+use skil_core::{Result, SkilError};
+
+/// Rows of choices shown at once.
+const VISIBLE_ROWS: usize = 12;
+
+/// One choice of a [`Picker`].
+#[derive(Debug, Clone, Default)]
+pub struct PickerItem {
+    pub label: String,
+    /// Dimmed text after the label; searched along with it.
+    pub detail: String,
+    /// Lines shown under the list while the item is highlighted.
+    pub preview: Vec<String>,
+    /// Heading shown above the item when the previous one has another; items
+    /// of a group should be next to each other.
+    pub group: String,
+}
+
+/// What a key press did to the prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Confirm,
+    Cancel,
+}
+
+/// Multi-select prompt that narrows its choices by fuzzy search as you type
+/// and previews the highlighted one.
+pub struct Picker {
+    prompt: String,
+    items: Vec<PickerItem>,
+    query: String,
+    /// Indices into `items` matching `query`, best match first.
+    matches: Vec<usize>,
+    /// Position of the highlighted item in `matches`.
+    cursor: usize,
+    checked: Vec<bool>,
+}
+
+impl Picker {
+    pub fn new(prompt: impl Into<String>, items: Vec<PickerItem>) -> Self {
+        let checked = vec![false; items.len()];
+        let matches = (0..items.len()).collect();
+        Self {
+            prompt: prompt.into(),
+            items,
+            query: String::new(),
+            matches,
+            cursor: 0,
+            checked,
+        }
+    }
+
+    /// Runs the prompt on stderr and returns the indices of the chosen items.
+    ///
+    /// Space toggles the highlighted item and Enter confirms; with nothing
+    /// toggled, Enter chooses the highlighted item. Esc clears the search, or
+    /// cancels when it is empty.
+    pub fn interact(mut self) -> Result<Vec<usize>> {
+        let term = Term::stderr();
+        term.hide_cursor()?;
+        let result = self.run(&term);
+        term.show_cursor()?;
+        result
+    }
+
+    fn run(&mut self, term: &Term) -> Result<Vec<usize>> {
+        let mut drawn = 0;
+        loop {
+            let lines = self.render(term.size().1 as usize);
+            term.clear_last_lines(drawn)?;
+            term.write_line(&lines.join("\n"))?;
+            drawn = lines.len();
+            match self.handle(term.read_key()?) {
+                Some(Outcome::Confirm) => {
+                    term.clear_last_lines(drawn)?;
+                    let chosen = self.chosen();
+                    let labels: Vec<&str> = chosen
+                        .iter()
+                        .map(|&index| self.items[index].label.as_str())
+                        .collect();
+                    term.write_line(&format!(
+                        "{} {} {} {}",
+                        style("✔").green(),
+                        self.prompt,
+                        style("·").dim(),
+                        labels.join(", ")
+                    ))?;
+                    return Ok(chosen);
+                }
+                Some(Outcome::Cancel) => {
+                    term.clear_last_lines(drawn)?;
+                    return Err(SkilError::Message("Selection cancelled".to_string()));
+                }
+                None => {}
+            }
+        }
+    }
+
+    fn handle(&mut self, key: Key) -> Option<Outcome> {
+        match key {
+            Key::Enter => {
+                if !self.checked.contains(&true)
+                    && let Some(&index) = self.matches.get(self.cursor)
+                {
+                    self.checked[index] = true;
+                }
+                return Some(Outcome::Confirm);
+            }
+            Key::Escape if self.query.is_empty() => return Some(Outcome::Cancel),
+            Key::CtrlC => return Some(Outcome::Cancel),
+            Key::Escape => {
+                self.query.clear();
+                self.filter();
+            }
+            Key::ArrowUp => self.cursor = self.cursor.saturating_sub(1),
+            Key::ArrowDown => {
+                self.cursor = (self.cursor + 1).min(self.matches.len().saturating_sub(1));
+            }
+            Key::PageUp => self.cursor = self.cursor.saturating_sub(VISIBLE_ROWS),
+            Key::PageDown => {
+                self.cursor =
+                    (self.cursor + VISIBLE_ROWS).min(self.matches.len().saturating_sub(1));
+            }
+            Key::Char(' ') => {
+                if let Some(&index) = self.matches.get(self.cursor) {
+                    self.checked[index] = !self.checked[index];
+                }
+            }
+            Key::Backspace => {
+                self.query.pop();
+                self.filter();
+            }
+            Key::Char(c) if !c.is_control() => {
+                self.query.push(c);
+                self.filter();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Recomputes `matches` for the current query.
+    fn filter(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                // A hit in the label outranks one in the detail.
+                let score = fuzzy_score(&self.query, &item.label)
+                    .map(|score| score * 2)
+                    .or_else(|| fuzzy_score(&self.query, &item.detail))
+                    .or_else(|| fuzzy_score(&self.query, &item.group))?;
+                Some((score, index))
+            })
+            .collect();
+        scored.sort_by_key(|&(score, index)| (std::cmp::Reverse(score), index));
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.cursor = 0;
+    }
+
+    /// Indices of the toggled items, in their original order.
+    fn chosen(&self) -> Vec<usize> {
+        (0..self.items.len())
+            .filter(|&index| self.checked[index])
+            .collect()
+    }
+
+    /// Lines of the prompt, each cut to `width` columns.
+    fn render(&self, width: usize) -> Vec<String> {
+        let mut lines = vec![if self.query.is_empty() {
+            format!(
+                "{} {} {}",
+                style("?").yellow(),
+                self.prompt,
+                style("(type to filter, space to select, enter to confirm)").dim()
+            )
+        } else {
+            format!("{} {} {}", style("?").yellow(), self.prompt, self.query)
+        }];
+
+        let offset = self.cursor.saturating_sub(VISIBLE_ROWS - 1);
+        // Matches are ranked rather than grouped while searching.
+        let mut group = self.query.is_empty().then_some("");
+        for (row, &index) in self
+            .matches
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(VISIBLE_ROWS)
+        {
+            let item = &self.items[index];
+            if let Some(previous) = group.as_mut()
+                && *previous != item.group
+            {
+                *previous = &item.group;
+                if !item.group.is_empty() {
+                    lines.push(style(format!("  {}/", item.group)).bold().to_string());
+                }
+            }
+            let pointer = if row == self.cursor {
+                style(">").cyan().to_string()
+            } else {
+                " ".to_string()
+            };
+            let check = if self.checked[index] {
+                style("[x]").green().to_string()
+            } else {
+                "[ ]".to_string()
+            };
+            let mut line = format!("{pointer} {check} {}", style(&item.label).bold());
+            if !item.detail.is_empty() {
+                line.push_str(&format!(" {}", style(format!("— {}", item.detail)).dim()));
+            }
+            lines.push(line);
+        }
+        if self.matches.is_empty() {
+            lines.push(style("  No matches").dim().to_string());
+        }
+        let selected = self.checked.iter().filter(|checked| **checked).count();
+        lines.push(
+            style(format!(
+                "  {} of {} shown, {selected} selected",
+                self.matches.len(),
+                self.items.len()
+            ))
+            .dim()
+            .to_string(),
+        );
+
+        if let Some(item) = self
+            .matches
+            .get(self.cursor)
+            .map(|&index| &self.items[index])
+            && !item.preview.is_empty()
+        {
+            lines.push(style(format!("  {}", "─".repeat(40))).dim().to_string());
+            lines.extend(item.preview.iter().map(|line| format!("  {line}")));
+        }
+
+        lines
+            .into_iter()
+            .map(|line| truncate_str(&line, width, "…").to_string())
+            .collect()
+    }
+}
+
+/// Scores how well `query` matches `text` as a case-insensitive subsequence;
+/// `None` when it does not. Consecutive characters and word starts score
+/// higher.
+fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for wanted in query.to_lowercase().chars() {
+        let found = next + text.get(next..)?.iter().position(|&c| c == wanted)?;
+        score += if previous.is_some_and(|previous| found == previous + 1) {
+            4
+        } else {
+            1
+        };
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 2;
+        }
+        previous = Some(found);
+        next = found + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picker() -> Picker {
+        let item = |label: &str, detail: &str, group: &str| PickerItem {
+            label: label.to_string(),
+            detail: detail.to_string(),
+            preview: vec![format!("# {label}")],
+            group: group.to_string(),
+        };
+        Picker::new(
+            "Select skills",
+            vec![
+                item("docx", "Word documents", "documents"),
+                item("pdf", "Fill and merge PDF files", "documents"),
+                item("frontend-design", "Pages and components", "web"),
+            ],
+        )
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_consecutive_and_word_start_matches() {
+        assert_eq!(fuzzy_score("xyz", "pdf"), None);
+        assert!(fuzzy_score("fd", "frontend-design") > fuzzy_score("fd", "pdf"));
+        assert!(fuzzy_score("pdf", "pdf") > fuzzy_score("pdf", "a-pd-f"));
+        assert_eq!(fuzzy_score("", "pdf"), Some(0));
+    }
+
+    #[test]
+    fn typing_filters_and_space_toggles_the_highlighted_item() {
+        console::set_colors_enabled(false);
+        let mut picker = picker();
+        for c in "fd".chars() {
+            assert_eq!(picker.handle(Key::Char(c)), None);
+        }
+        assert_eq!(picker.matches, vec![2, 1]);
+        picker.handle(Key::Char(' '));
+        let lines = picker.render(80);
+        assert!(lines[1].starts_with("> [x] frontend-design"));
+        assert!(!lines.iter().any(|line| line.ends_with('/')));
+        assert!(lines.iter().any(|line| line == "  # frontend-design"));
+
+        picker.handle(Key::Escape);
+        assert_eq!(picker.matches.len(), 3);
+        let lines = picker.render(80);
+        assert_eq!(lines[1], "  documents/");
+        assert_eq!(lines[4], "  web/");
+        picker.handle(Key::ArrowDown);
+        picker.handle(Key::Char(' '));
+        assert_eq!(picker.handle(Key::Enter), Some(Outcome::Confirm));
+        assert_eq!(picker.chosen(), vec![1, 2]);
+        assert!(
+            picker
+                .render(20)
+                .iter()
+                .all(|line| console::measure_text_width(line) <= 20)
+        );
+    }
+
+    #[test]
+    fn enter_without_toggles_chooses_the_highlighted_item() {
+        assert_eq!(picker().handle(Key::Escape), Some(Outcome::Cancel));
+        let mut picker = picker();
+        picker.handle(Key::ArrowDown);
+        assert_eq!(picker.handle(Key::Enter), Some(Outcome::Confirm));
+        assert_eq!(picker.chosen(), vec![1]);
+    }
+}
@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Endpoint receiving the opt-in install ping.
+const INSTALL_PING_URL: &str = "https://skills.sh/api/install";
+
+/// Anonymous payload sent after installing skills from a registry source.
+///
+/// It contains no user, machine, or path information.
+#[derive(Debug, Serialize)]
+struct InstallPing<'a> {
+    source: &'a str,
+    skills: &'a [String],
+    agents: Vec<&'a str>,
+    version: &'static str,
+}
+
+/// Reports an install to the registry. Failures are ignored so installs never
+/// depend on the registry being reachable.
+pub fn ping_install(source: &str, skills: &[String], agents: &[&str]) {
+    let payload = InstallPing {
+        source,
+        skills,
+        agents: agents.to_vec(),
+        version: env!("CARGO_PKG_VERSION"),
+    };
+    let Ok(client) = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(2))
+        .user_agent(concat!("skil/", env!("CARGO_PKG_VERSION")))
+        .build()
+    else {
+        return;
+    };
+    let _ = client.post(INSTALL_PING_URL).json(&payload).send();
+}
@@ -0,0 +1,104 @@
+use skil_core::{Result, SkilError};
+
+/// Part of a parsed [`Template`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Field(String),
+}
+
+/// A `--format` template, such as `{{name}}\t{{source}}`, printing one line
+/// per item.
+///
+/// `{{field}}` is replaced by the field's value, and `\t`, `\n`, and `\\` are
+/// read as escapes, since shells pass them through single quotes as typed.
+#[derive(Debug, Clone)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Parses `format`, failing on a placeholder not in `fields`.
+    pub fn parse(format: &str, fields: &[&str]) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut rest = format;
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix("{{") {
+                let Some(end) = after.find("}}") else {
+                    return Err(SkilError::Message(format!(
+                        "Unclosed {{{{ in --format: {format}"
+                    )));
+                };
+                let field = after[..end].trim();
+                if !fields.contains(&field) {
+                    return Err(SkilError::Message(format!(
+                        "Unknown field {{{{{field}}}}} in --format; available: {}",
+                        fields.join(", ")
+                    )));
+                }
+                if !text.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut text)));
+                }
+                segments.push(Segment::Field(field.to_string()));
+                rest = &after[end + 2..];
+                continue;
+            }
+            let mut chars = rest.chars();
+            match (chars.next(), chars.clone().next()) {
+                (Some('\\'), Some(escaped @ ('t' | 'n' | '\\'))) => {
+                    text.push(match escaped {
+                        't' => '\t',
+                        'n' => '\n',
+                        _ => '\\',
+                    });
+                    chars.next();
+                }
+                (Some(c), _) => text.push(c),
+                (None, _) => {}
+            }
+            rest = chars.as_str();
+        }
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+        Ok(Self { segments })
+    }
+
+    /// Fills the template from `values`; fields without a value are empty.
+    pub fn render(&self, values: &[(&str, String)]) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.as_str(),
+                Segment::Field(field) => values
+                    .iter()
+                    .find(|(name, _)| name == field)
+                    .map(|(_, value)| value.as_str())
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_fields_and_reads_escapes() {
+        let template =
+            Template::parse(r"{{name}}\t{{ source }}\\n", &["name", "source"]).expect("template");
+        assert_eq!(template.render(&[("name", "pdf".to_string())]), "pdf\t\\n");
+        assert_eq!(
+            template.render(&[
+                ("source", "anthropics/skills".to_string()),
+                ("name", "pdf".to_string())
+            ]),
+            "pdf\tanthropics/skills\\n"
+        );
+        let err = Template::parse("{{nmae}}", &["name", "source"]).expect_err("unknown field");
+        assert!(err.to_string().contains("available: name, source"));
+        assert!(Template::parse("{{name", &["name"]).is_err());
+    }
+}
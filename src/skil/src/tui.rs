@@ -0,0 +1,482 @@
+use std::io::Write;
+
+use clap::Args;
+use console::{Alignment, Key, Term, pad_str, style};
+use skil_core::install::canonical_skills_dir;
+use skil_core::skills::{Skill, split_frontmatter};
+use skil_core::{Result, SkilError};
+
+use crate::cli::{self, AddArgs, RemoveArgs, SearchApiResponse, SearchApiSkill, UpdateArgs};
+use crate::markdown;
+
+const ENTER_ALT_SCREEN: &str = "\x1b[?1049h";
+const LEAVE_ALT_SCREEN: &str = "\x1b[?1049l";
+
+/// Arguments for `skills tui`.
+#[derive(Args, Clone)]
+#[command(about = "Manage skills in an interactive terminal UI")]
+pub struct TuiArgs {
+    #[arg(short = 'g', long = "global")]
+    pub global: bool,
+}
+
+/// Which list the left pane shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    Installed,
+    Search,
+}
+
+/// State of the terminal UI.
+struct App {
+    pane: Pane,
+    global: bool,
+    installed: Vec<Skill>,
+    results: Vec<SearchApiSkill>,
+    query: String,
+    selected: usize,
+    /// First line of the detail pane shown, for skills longer than the screen.
+    detail_offset: usize,
+    status: String,
+}
+
+impl App {
+    fn len(&self) -> usize {
+        match self.pane {
+            Pane::Installed => self.installed.len(),
+            Pane::Search => self.results.len(),
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.len();
+        if len == 0 {
+            self.selected = 0;
+            return;
+        }
+        let next = self.selected as isize + delta;
+        self.selected = next.clamp(0, len as isize - 1) as usize;
+        self.detail_offset = 0;
+    }
+
+    fn scroll_detail(&mut self, delta: isize) {
+        let last = self.detail_lines().len().saturating_sub(1);
+        let next = self.detail_offset as isize + delta;
+        self.detail_offset = next.clamp(0, last as isize) as usize;
+    }
+
+    fn switch_pane(&mut self) {
+        self.pane = match self.pane {
+            Pane::Installed => Pane::Search,
+            Pane::Search => Pane::Installed,
+        };
+        self.selected = 0;
+        self.detail_offset = 0;
+    }
+
+    fn reload_installed(&mut self) -> Result<()> {
+        self.installed = cli::installed_skills(&canonical_skills_dir(self.global)?)?;
+        self.move_selection(0);
+        Ok(())
+    }
+
+    fn list_items(&self) -> Vec<String> {
+        match self.pane {
+            Pane::Installed => self.installed.iter().map(|s| s.name.clone()).collect(),
+            Pane::Search => self
+                .results
+                .iter()
+                .map(|s| format!("{} ({})", s.name, s.installs.unwrap_or(0)))
+                .collect(),
+        }
+    }
+
+    fn detail_lines(&self) -> Vec<String> {
+        match self.pane {
+            Pane::Installed => match self.installed.get(self.selected) {
+                Some(skill) => {
+                    let mut lines = vec![
+                        style(&skill.name).bold().to_string(),
+                        skill.description.clone(),
+                        style(skill.path.display()).dim().to_string(),
+                        String::new(),
+                    ];
+                    lines.extend(body_lines(&skill.raw_content));
+                    lines
+                }
+                None => vec!["No skills installed. Press / to search the registry.".to_string()],
+            },
+            Pane::Search => match self.results.get(self.selected) {
+                Some(result) => {
+                    let source = result.source.as_deref().unwrap_or("unknown");
+                    vec![
+                        style(&result.name).bold().to_string(),
+                        format!("Source: {source}"),
+                        format!("Installs: {}", result.installs.unwrap_or(0)),
+                        String::new(),
+                        format!(
+                            "Press Enter to run `skil add {source} --skill {}`",
+                            result.name
+                        ),
+                    ]
+                }
+                None if self.query.is_empty() => {
+                    vec!["Press / to search the registry.".to_string()]
+                }
+                None => vec![format!("No results for \"{}\"", self.query)],
+            },
+        }
+    }
+
+    /// Renders the whole screen as exactly `height` lines of `width` columns.
+    fn render(&self, width: usize, height: usize) -> Vec<String> {
+        let scope = if self.global { "global" } else { "project" };
+        let tab = |pane: Pane, label: &str| {
+            if self.pane == pane {
+                style(format!(" {label} ")).reverse().to_string()
+            } else {
+                format!(" {label} ")
+            }
+        };
+        let header = format!(
+            "{}{}  {}",
+            tab(Pane::Installed, &format!("Installed ({scope})")),
+            tab(Pane::Search, "Search"),
+            style(&self.query).dim()
+        );
+        let footer = "↑/↓ move  J/K scroll  tab switch  / search  enter install  d remove  \
+                      u update  g scope  q quit";
+
+        let body_height = height.saturating_sub(3);
+        let left_width = (width / 3).clamp(12, 40).min(width);
+        let right_width = width.saturating_sub(left_width + 3);
+
+        let items = self.list_items();
+        let offset = self.selected.saturating_sub(body_height.saturating_sub(1));
+        let detail = self.detail_lines();
+
+        let mut lines = Vec::with_capacity(height);
+        lines.push(pad_str(&header, width, Alignment::Left, Some("…")).to_string());
+        for row in 0..body_height {
+            let index = offset + row;
+            let left = match items.get(index) {
+                Some(item) if index == self.selected => {
+                    let cell = pad_str(item, left_width, Alignment::Left, Some("…"));
+                    style(cell).reverse().to_string()
+                }
+                Some(item) => pad_str(item, left_width, Alignment::Left, Some("…")).to_string(),
+                None => " ".repeat(left_width),
+            };
+            let right = detail
+                .get(self.detail_offset + row)
+                .map(String::as_str)
+                .unwrap_or("");
+            lines.push(format!(
+                "{left} │ {}",
+                pad_str(right, right_width, Alignment::Left, Some("…"))
+            ));
+        }
+        lines.push(pad_str(&self.status, width, Alignment::Left, Some("…")).to_string());
+        lines.push(
+            style(pad_str(footer, width, Alignment::Left, Some("…")))
+                .dim()
+                .to_string(),
+        );
+        lines.truncate(height);
+        lines
+    }
+}
+
+/// Returns the SKILL.md body without frontmatter, rendered, one entry per line.
+fn body_lines(content: &str) -> Vec<String> {
+    let (_, body) = split_frontmatter(content);
+    markdown::render(body)
+        .lines()
+        .map(|line| line.replace('\t', "    "))
+        .collect()
+}
+
+pub fn run_tui(args: TuiArgs) -> Result<()> {
+    let term = Term::stdout();
+    if !term.is_term() {
+        return Err(SkilError::Message(
+            "skil tui requires an interactive terminal".to_string(),
+        ));
+    }
+
+    let mut app = App {
+        pane: Pane::Installed,
+        global: args.global,
+        installed: Vec::new(),
+        results: Vec::new(),
+        query: String::new(),
+        selected: 0,
+        detail_offset: 0,
+        status: String::new(),
+    };
+    app.reload_installed()?;
+
+    let _screen = Screen::enter(&term)?;
+    event_loop(&term, &mut app)
+}
+
+/// Keeps the alternate screen while alive and restores the terminal when
+/// dropped, including when the UI returns an error or panics.
+struct Screen<'a> {
+    term: &'a Term,
+}
+
+impl<'a> Screen<'a> {
+    fn enter(term: &'a Term) -> Result<Self> {
+        enter_screen(term)?;
+        // Leave first so the panic message is not lost on the alternate screen.
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let _ = leave_screen(&Term::stdout());
+            previous(info);
+        }));
+        Ok(Self { term })
+    }
+}
+
+impl Drop for Screen<'_> {
+    fn drop(&mut self) {
+        let _ = leave_screen(self.term);
+        if !std::thread::panicking() {
+            // Puts back the default hook, which skil does not replace elsewhere.
+            drop(std::panic::take_hook());
+        }
+    }
+}
+
+fn event_loop(term: &Term, app: &mut App) -> Result<()> {
+    loop {
+        draw(term, app)?;
+        match term.read_key()? {
+            Key::Char('q') | Key::Escape | Key::CtrlC => return Ok(()),
+            Key::ArrowUp | Key::Char('k') => app.move_selection(-1),
+            Key::ArrowDown | Key::Char('j') => app.move_selection(1),
+            Key::PageUp => app.move_selection(-10),
+            Key::PageDown => app.move_selection(10),
+            Key::Char('J') => app.scroll_detail(1),
+            Key::Char('K') => app.scroll_detail(-1),
+            Key::Tab | Key::BackTab => app.switch_pane(),
+            Key::Char('g') => {
+                app.global = !app.global;
+                app.reload_installed()?;
+            }
+            Key::Char('/') => search(term, app)?,
+            Key::Enter if app.pane == Pane::Search => install_selected(term, app)?,
+            Key::Char('d') if app.pane == Pane::Installed => remove_selected(term, app)?,
+            Key::Char('u') => {
+                run_outside(term, || cli::run_update(UpdateArgs::default()))?;
+                app.reload_installed()?;
+                app.status = "Update finished".to_string();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn search(term: &Term, app: &mut App) -> Result<()> {
+    let (rows, _) = term.size();
+    term.move_cursor_to(0, rows as usize - 1)?;
+    term.clear_line()?;
+    term.write_str("Search: ")?;
+    term.show_cursor()?;
+    let query = term.read_line_initial_text(&app.query)?;
+    term.hide_cursor()?;
+    if query.trim().is_empty() {
+        return Ok(());
+    }
+
+    app.query = query.trim().to_string();
+    app.pane = Pane::Search;
+    app.selected = 0;
+    app.detail_offset = 0;
+    match fetch_results(&app.query) {
+        Ok(skills) => {
+            app.status = format!("{} result(s)", skills.len());
+            app.results = skills;
+        }
+        Err(err) => {
+            app.results.clear();
+            app.status = format!("Search failed: {err}");
+        }
+    }
+    Ok(())
+}
+
+/// Queries the registry; failures are shown in the status line rather than
+/// closing the UI.
+fn fetch_results(query: &str) -> Result<Vec<SearchApiSkill>> {
+    let res = reqwest::blocking::get(cli::registry_search_url(query, &[]))?;
+    if !res.status().is_success() {
+        return Err(SkilError::Message(res.status().to_string()));
+    }
+    Ok(res.json::<SearchApiResponse>()?.skills)
+}
+
+fn install_selected(term: &Term, app: &mut App) -> Result<()> {
+    let Some(result) = app.results.get(app.selected) else {
+        return Ok(());
+    };
+    let Some(source) = result.source.clone() else {
+        app.status = format!("{} has no installable source", result.name);
+        return Ok(());
+    };
+    let args = AddArgs {
+        source,
+        global: app.global,
+        copy: false,
+        agent: Vec::new(),
+        skill: vec![result.name.clone()],
+        list: false,
+        long: false,
+        ask: false,
+        overwrite: false,
+        yes: true,
+        all: false,
+        full_depth: false,
+        no_ignore: false,
+        max_depth: None,
+        file_skills: false,
+        adopt: false,
+        paths: Vec::new(),
+        branch: None,
+        local: false,
+        git: false,
+        allow_large: false,
+    };
+    let name = result.name.clone();
+    run_outside(term, || cli::run_add(args))?;
+    app.reload_installed()?;
+    app.status = format!("Installed {name}");
+    Ok(())
+}
+
+fn remove_selected(term: &Term, app: &mut App) -> Result<()> {
+    let Some(skill) = app.installed.get(app.selected) else {
+        return Ok(());
+    };
+    let name = skill.name.clone();
+    let args = RemoveArgs {
+        skills: vec![name.clone()],
+        global: app.global,
+        project_only: false,
+        both: false,
+        agent: Vec::new(),
+        skill: Vec::new(),
+        yes: true,
+        all: false,
+        source: None,
+    };
+    run_outside(term, || cli::run_remove(args))?;
+    app.reload_installed()?;
+    app.status = format!("Removed {name}");
+    Ok(())
+}
+
+/// Runs a CLI action on the normal screen so its output stays readable, then
+/// waits for a key before returning to the UI.
+fn run_outside(term: &Term, action: impl FnOnce() -> Result<()>) -> Result<()> {
+    leave_screen(term)?;
+    if let Err(err) = action() {
+        crate::ui::error(&err.to_string());
+    }
+    term.write_line(&style("Press any key to return").dim().to_string())?;
+    term.read_key()?;
+    enter_screen(term)
+}
+
+fn enter_screen(term: &Term) -> Result<()> {
+    let mut out = term.clone();
+    out.write_all(ENTER_ALT_SCREEN.as_bytes())?;
+    term.hide_cursor()?;
+    Ok(())
+}
+
+fn leave_screen(term: &Term) -> Result<()> {
+    let mut out = term.clone();
+    term.show_cursor()?;
+    out.write_all(LEAVE_ALT_SCREEN.as_bytes())?;
+    out.flush()?;
+    Ok(())
+}
+
+fn draw(term: &Term, app: &App) -> Result<()> {
+    let (rows, cols) = term.size();
+    let frame = app.render(cols as usize, rows as usize).join("\r\n");
+    term.move_cursor_to(0, 0)?;
+    term.write_str(&frame)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app() -> App {
+        App {
+            pane: Pane::Installed,
+            global: false,
+            installed: vec![Skill {
+                name: "alpha".to_string(),
+                description: "First skill".to_string(),
+                path: "skills/alpha".into(),
+                raw_content: "---\nname: alpha\n---\n# Alpha\nDo things.\n".to_string(),
+                resources: Vec::new(),
+                found_by: String::new(),
+                file: None,
+            }],
+            results: Vec::new(),
+            query: String::new(),
+            selected: 0,
+            detail_offset: 0,
+            status: String::new(),
+        }
+    }
+
+    #[test]
+    fn render_fills_the_screen_with_panes() {
+        console::set_colors_enabled(false);
+        let lines = app().render(60, 10);
+        assert_eq!(lines.len(), 10);
+        assert!(lines[1].starts_with("alpha"));
+        assert!(lines[1].contains("│ alpha"));
+        assert!(lines.iter().any(|line| line.contains("│ Alpha")));
+        assert!(
+            lines
+                .iter()
+                .all(|line| console::measure_text_width(line) <= 60)
+        );
+    }
+
+    #[test]
+    fn selection_stays_in_bounds_across_panes() {
+        let mut app = app();
+        app.move_selection(5);
+        assert_eq!(app.selected, 0);
+        app.switch_pane();
+        assert_eq!(app.pane, Pane::Search);
+        app.move_selection(1);
+        assert_eq!(app.selected, 0);
+        assert!(app.detail_lines()[0].contains("Press / to search"));
+    }
+
+    #[test]
+    fn detail_pane_scrolls_within_the_skill() {
+        console::set_colors_enabled(false);
+        let mut app = app();
+        let lines = app.detail_lines().len();
+        app.scroll_detail(1);
+        assert!(app.render(60, 10)[1].contains("│ First skill"));
+        app.scroll_detail(100);
+        assert_eq!(app.detail_offset, lines - 1);
+        app.scroll_detail(-100);
+        assert_eq!(app.detail_offset, 0);
+        app.scroll_detail(2);
+        app.move_selection(0);
+        assert_eq!(app.detail_offset, 0);
+    }
+}
@@ -1,19 +1,50 @@
-use console::{Term, style};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use console::{Alignment, Term, measure_text_width, pad_str, style};
 use indicatif::{ProgressBar, ProgressStyle};
+use skil_core::SkilError;
+
+static STDERR_ONLY: AtomicBool = AtomicBool::new(false);
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Sends all human-oriented output to stderr, keeping stdout free for a protocol.
+pub fn use_stderr_only() {
+    STDERR_ONLY.store(true, Ordering::Relaxed);
+}
+
+/// Reports results and failures as JSON on stdout and moves all other output to stderr.
+pub fn use_json_output() {
+    JSON_OUTPUT.store(true, Ordering::Relaxed);
+    use_stderr_only();
+}
+
+/// Returns true when `--json` was passed.
+pub fn json_output() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+fn emit(line: String) {
+    if STDERR_ONLY.load(Ordering::Relaxed) {
+        eprintln!("{line}");
+    } else {
+        println!("{line}");
+    }
+}
 
 /// Prints a styled heading line.
 pub fn heading(text: &str) {
-    println!("{}", style(text).bold().cyan());
+    emit(style(text).bold().cyan().to_string());
 }
 
 /// Prints a standard info line.
 pub fn info(text: &str) {
-    println!("{}", text);
+    emit(text.to_string());
 }
 
 /// Prints a success line.
 pub fn success(text: &str) {
-    println!("{}", style(text).green());
+    emit(style(text).green().to_string());
 }
 
 /// Prints a warning line to stderr.
@@ -26,14 +57,58 @@ pub fn error(text: &str) {
     eprintln!("{}", style(text).red());
 }
 
+/// Reports a fatal error, as `{"error": {"code", "message"}}` in JSON mode.
+pub fn report_error(err: &SkilError) {
+    if json_output() {
+        let payload = serde_json::json!({
+            "error": {
+                "code": err.code().as_str(),
+                "exit_code": err.code().exit_code(),
+                "message": err.to_string(),
+            }
+        });
+        println!("{payload}");
+    } else {
+        error(&err.to_string());
+    }
+}
+
+/// Prints rows as left-aligned columns under a bold header.
+pub fn table(headers: &[&str], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = headers.iter().map(|h| measure_text_width(h)).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(measure_text_width(cell));
+        }
+    }
+    let line = |cells: Vec<String>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| pad_str(cell, *width, Alignment::Left, None).to_string())
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    emit(
+        style(line(headers.iter().map(|h| h.to_string()).collect()))
+            .bold()
+            .to_string(),
+    );
+    for row in rows {
+        emit(line(row.clone()));
+    }
+}
+
 /// Prints a list item with a dimmed bullet.
 pub fn list_item(text: &str) {
-    println!("  {} {}", style("-").dim(), text);
+    emit(format!("  {} {}", style("-").dim(), text));
 }
 
 /// Creates a spinner that is hidden when not running in a TTY.
 pub fn spinner(message: &str) -> ProgressBar {
-    let pb = if Term::stdout().is_term() {
+    let pb = if Term::stdout().is_term() && !STDERR_ONLY.load(Ordering::Relaxed) {
         ProgressBar::new_spinner()
     } else {
         ProgressBar::hidden()
@@ -46,3 +121,23 @@ pub fn spinner(message: &str) -> ProgressBar {
     pb.enable_steady_tick(std::time::Duration::from_millis(100));
     pb
 }
+
+/// A spinner that can be started and finished from shared references.
+#[derive(Default)]
+pub struct ProgressSpinner {
+    current: RefCell<Option<ProgressBar>>,
+}
+
+impl ProgressSpinner {
+    /// Starts a new spinner, replacing any running one.
+    pub fn start(&self, message: &str) {
+        self.current.replace(Some(spinner(message)));
+    }
+
+    /// Finishes the running spinner with a final message.
+    pub fn finish(&self, message: &str) {
+        if let Some(pb) = self.current.take() {
+            pb.finish_with_message(message.to_string());
+        }
+    }
+}
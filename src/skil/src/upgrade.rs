@@ -0,0 +1,266 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use clap::Args;
+use dialoguer::theme::ColorfulTheme;
+use skil_core::skills::is_newer_version;
+use skil_core::{Result, SkilError};
+
+use crate::ui;
+
+const RELEASES_API: &str = "https://api.github.com/repos/matoous/skil/releases/latest";
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+/// Arguments for `skills upgrade-self`.
+#[derive(Args, Clone)]
+#[command(about = "Upgrade the skil binary to the latest release")]
+pub struct UpgradeSelfArgs {
+    #[arg(long = "check")]
+    pub check: bool,
+    #[arg(short = 'y', long = "yes")]
+    pub yes: bool,
+}
+
+/// Release payload returned by the GitHub releases API.
+#[derive(Debug, serde::Deserialize)]
+struct Release {
+    tag_name: String,
+    assets: Vec<ReleaseAsset>,
+}
+
+/// One downloadable file attached to a release.
+#[derive(Debug, serde::Deserialize)]
+struct ReleaseAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+impl Release {
+    fn asset(&self, name: &str) -> Result<&ReleaseAsset> {
+        self.assets
+            .iter()
+            .find(|asset| asset.name == name)
+            .ok_or_else(|| {
+                SkilError::Message(format!(
+                    "Release {} has no asset named {name}",
+                    self.tag_name
+                ))
+            })
+    }
+}
+
+pub fn run_upgrade_self(args: UpgradeSelfArgs) -> Result<()> {
+    let current = env!("CARGO_PKG_VERSION");
+    let client = http_client()?;
+
+    let spinner = ui::spinner("Checking latest release...");
+    let release: Release = client
+        .get(RELEASES_API)
+        .send()?
+        .error_for_status()?
+        .json()?;
+    spinner.finish_and_clear();
+
+    let latest = release.tag_name.trim_start_matches('v');
+    if !is_newer_version(latest, current) {
+        ui::success(&format!("skil {current} is up to date"));
+        return Ok(());
+    }
+    ui::info(&format!("New release available: {current} -> {latest}"));
+    if args.check {
+        return Ok(());
+    }
+
+    if !args.yes
+        && !dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Upgrade skil to {latest}?"))
+            .default(true)
+            .interact()
+            .map_err(|err| SkilError::Message(err.to_string()))?
+    {
+        ui::info("Upgrade cancelled");
+        return Ok(());
+    }
+
+    let target = current_target()?;
+    let archive_name = archive_name(latest, target);
+    let asset = release.asset(&archive_name)?;
+    let checksums = release.asset(CHECKSUMS_ASSET)?;
+
+    let spinner = ui::spinner(&format!("Downloading {archive_name}..."));
+    let archive = download(&client, &asset.browser_download_url)?;
+    let sums =
+        String::from_utf8_lossy(&download(&client, &checksums.browser_download_url)?).to_string();
+    spinner.finish_and_clear();
+
+    let expected = expected_checksum(&sums, &archive_name).ok_or_else(|| {
+        SkilError::Message(format!("{CHECKSUMS_ASSET} has no entry for {archive_name}"))
+    })?;
+    let actual = sha256_hex(&archive);
+    if !actual.eq_ignore_ascii_case(&expected) {
+        return Err(SkilError::Message(format!(
+            "Checksum mismatch for {archive_name}: expected {expected}, got {actual}"
+        )));
+    }
+
+    let temp = tempfile::tempdir()?;
+    let archive_path = temp.path().join(&archive_name);
+    fs::write(&archive_path, &archive)?;
+    let binary = extract_binary(&archive_path, temp.path())?;
+    replace_current_exe(&binary)?;
+
+    ui::success(&format!("Upgraded skil {current} -> {latest}"));
+    Ok(())
+}
+
+fn http_client() -> Result<reqwest::blocking::Client> {
+    Ok(reqwest::blocking::Client::builder()
+        .user_agent(concat!("skil/", env!("CARGO_PKG_VERSION")))
+        .build()?)
+}
+
+fn download(client: &reqwest::blocking::Client, url: &str) -> Result<Vec<u8>> {
+    Ok(client
+        .get(url)
+        .send()?
+        .error_for_status()?
+        .bytes()?
+        .to_vec())
+}
+
+/// Returns the release target triple for the running platform.
+fn current_target() -> Result<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
+        ("windows", "x86_64") => Ok("x86_64-pc-windows-msvc"),
+        ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
+        ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
+        (os, arch) => Err(SkilError::Message(format!(
+            "No prebuilt skil release for {arch}-{os}; reinstall with `cargo install skil`"
+        ))),
+    }
+}
+
+fn archive_name(version: &str, target: &str) -> String {
+    let extension = if target.contains("windows") {
+        "zip"
+    } else {
+        "tar.gz"
+    };
+    format!("skil-{version}-{target}.{extension}")
+}
+
+/// Finds the checksum of `file` in `shasum`-style output.
+fn expected_checksum(sums: &str, file: &str) -> Option<String> {
+    sums.lines().find_map(|line| {
+        let (hash, name) = line.split_once(char::is_whitespace)?;
+        let name = name.trim().trim_start_matches('*').trim_start_matches("./");
+        (name == file).then(|| hash.to_string())
+    })
+}
+
+/// Unpacks the release archive with the system `tar` and returns the binary path.
+///
+/// `tar` ships with macOS, Linux, and Windows 10+, and the Windows build reads zip files too.
+fn extract_binary(archive: &Path, dir: &Path) -> Result<PathBuf> {
+    let out = dir.join("unpacked");
+    fs::create_dir_all(&out)?;
+    let status = Command::new("tar")
+        .arg("-xf")
+        .arg(archive)
+        .arg("-C")
+        .arg(&out)
+        .status()
+        .map_err(|err| SkilError::Message(format!("Failed to run tar: {err}")))?;
+    if !status.success() {
+        return Err(SkilError::Message(format!(
+            "Failed to extract {}",
+            archive.display()
+        )));
+    }
+
+    let binary = out.join(format!("skil{}", std::env::consts::EXE_SUFFIX));
+    if !binary.is_file() {
+        return Err(SkilError::Message(
+            "Release archive does not contain a skil binary".to_string(),
+        ));
+    }
+    Ok(binary)
+}
+
+/// Swaps the running executable for `binary`.
+///
+/// The new binary is first copied next to the current one so the final rename
+/// stays on one filesystem. Windows cannot overwrite a running executable, so
+/// the old one is moved aside first.
+fn replace_current_exe(binary: &Path) -> Result<()> {
+    let current = std::env::current_exe()?;
+    let current = fs::canonicalize(&current).unwrap_or(current);
+    let staged = current.with_extension("new");
+    fs::copy(binary, &staged)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    }
+
+    if cfg!(windows) {
+        let old = current.with_extension("old");
+        let _ = fs::remove_file(&old);
+        fs::rename(&current, &old)?;
+    }
+    fs::rename(&staged, &current).map_err(|err| {
+        SkilError::Message(format!(
+            "Failed to replace {}: {err} (try again with sufficient permissions)",
+            current.display()
+        ))
+    })?;
+    Ok(())
+}
+
+/// Computes a hex-encoded SHA-256 digest.
+fn sha256_hex(data: &[u8]) -> String {
+    ring::digest::digest(&ring::digest::SHA256, data)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_matches_known_vectors() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            sha256_hex(&[b'a'; 1000]),
+            "41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3"
+        );
+    }
+
+    #[test]
+    fn expected_checksum_reads_shasum_output() {
+        let sums = "abc123  skil-0.2.0-x86_64-apple-darwin.tar.gz\n\
+                    def456 *skil-0.2.0-x86_64-pc-windows-msvc.zip\n";
+        assert_eq!(
+            expected_checksum(sums, "skil-0.2.0-x86_64-pc-windows-msvc.zip").as_deref(),
+            Some("def456")
+        );
+        assert_eq!(expected_checksum(sums, "skil-0.2.0-other.tar.gz"), None);
+        assert_eq!(
+            archive_name("0.2.0", "x86_64-apple-darwin"),
+            "skil-0.2.0-x86_64-apple-darwin.tar.gz"
+        );
+    }
+}